@@ -74,6 +74,18 @@ program
 
       await startRESTServer(config, port);
 
+      // One JSON line for the process supervising us (the desktop app), which
+      // reads it instead of assuming where we listen and what we opened
+      if (process.env.ENGRAM_READY_EVENT) {
+        console.log(JSON.stringify({
+          event: 'ready',
+          port,
+          dbPath: getDatabasePath(config),
+          version,
+          pid: process.pid
+        }));
+      }
+
       // Keep process alive
       process.on('SIGINT', () => {
         console.log('');
@@ -585,7 +597,7 @@ program
 program
   .command('import')
   .description('Import memories from developer artifacts (smart import wizard)')
-  .option('-s, --source <type>', 'Single source: cursorrules, claude, package, git, ssh, shell, obsidian, env')
+  .option('-s, --source <type>', 'Single source: cursorrules, claude, package, git, ssh, shell, obsidian, env, chatgpt, claude-export')
   .option('--dry-run', 'Preview without committing')
   .option('-n, --namespace <name>', 'Override namespace for imported memories')
   .option('--config <path>', 'Path to config file')
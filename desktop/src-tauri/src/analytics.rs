@@ -0,0 +1,228 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+use duckdb::types::{TimeUnit, Value};
+use duckdb::{params, AccessMode, Config, Connection};
+use rusqlite::OpenFlags;
+use serde::Serialize;
+use tauri::AppHandle;
+use tokio::time::sleep;
+
+/// How often the analytics copy is rebuilt while developer mode is on.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const ANALYTICS_FILE: &str = "analytics.duckdb";
+/// Rows returned by `run_query`; the rest are dropped and `truncated` set.
+const MAX_ROWS: usize = 1000;
+
+/// Result of an analytics query, for a table view.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyticsResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+    /// More than `MAX_ROWS` rows matched.
+    pub truncated: bool,
+    /// When the copy queried was built, Unix milliseconds.
+    pub refreshed_at: Option<i64>,
+}
+
+/// The analytics copy beside the database at `db_path`.
+pub fn analytics_path(db_path: &Path) -> PathBuf {
+    db_path.with_file_name(ANALYTICS_FILE)
+}
+
+/// Rebuild the analytics copy once an hour while developer mode is on, so
+/// queries never touch the live database.
+pub fn setup_refresh_schedule(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if crate::commands::read_preferences().unwrap_or_default().developer_mode {
+                if let Err(e) = refresh(&app).await {
+                    engram_log!("Analytics refresh failed: {}", e);
+                }
+            }
+            sleep(REFRESH_INTERVAL).await;
+        }
+    });
+}
+
+/// Copy the memories into a fresh DuckDB file beside the database.
+pub async fn refresh(app: &AppHandle) -> Result<(), String> {
+    let db_path = crate::database::active_db_path(app).await?;
+    tokio::task::spawn_blocking(move || build_copy(&db_path))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Write `memories` (tags as a JSON string) and `memory_tags` (one row per
+/// tag) to a new DuckDB file, then swap it in so running queries keep the
+/// old copy.
+fn build_copy(db_path: &Path) -> Result<(), String> {
+    let source = rusqlite::Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let dest = analytics_path(db_path);
+    let partial = dest.with_extension("duckdb.partial");
+    let _ = fs::remove_file(&partial);
+
+    let conn = Connection::open(&partial).map_err(|e| format!("Failed to create analytics copy: {}", e))?;
+    conn.execute_batch(
+        "CREATE TABLE memories (
+            id VARCHAR PRIMARY KEY,
+            content VARCHAR NOT NULL,
+            entity VARCHAR,
+            category VARCHAR,
+            confidence DOUBLE,
+            source VARCHAR,
+            namespace VARCHAR,
+            tags VARCHAR,
+            created_at TIMESTAMP,
+            updated_at TIMESTAMP,
+            last_accessed TIMESTAMP,
+            access_count BIGINT
+        );
+        CREATE TABLE memory_tags (memory_id VARCHAR NOT NULL, tag VARCHAR NOT NULL);",
+    )
+    .map_err(|e| format!("Failed to create analytics tables: {}", e))?;
+
+    let mut stmt = source
+        .prepare(
+            "SELECT id, content, entity, category, confidence, source, namespace, tags,
+                    created_at, updated_at, last_accessed, access_count
+             FROM memories",
+        )
+        .map_err(|e| format!("Failed to read memories: {}", e))?;
+    let mut rows = stmt.query([]).map_err(|e| format!("Failed to read memories: {}", e))?;
+    {
+        let mut memories = conn.appender("memories").map_err(|e| e.to_string())?;
+        let mut memory_tags = conn.appender("memory_tags").map_err(|e| e.to_string())?;
+        let timestamp = |ms: Option<i64>| match ms {
+            Some(ms) => Value::Timestamp(TimeUnit::Millisecond, ms),
+            None => Value::Null,
+        };
+        while let Some(row) = rows.next().map_err(|e| format!("Failed to read memories: {}", e))? {
+            let read = |e: rusqlite::Error| format!("Failed to read memories: {}", e);
+            let id: String = row.get(0).map_err(read)?;
+            let tags: Option<String> = row.get(7).map_err(read)?;
+            memories
+                .append_row(params![
+                    id,
+                    row.get::<_, String>(1).map_err(read)?,
+                    row.get::<_, Option<String>>(2).map_err(read)?,
+                    row.get::<_, Option<String>>(3).map_err(read)?,
+                    row.get::<_, Option<f64>>(4).map_err(read)?,
+                    row.get::<_, Option<String>>(5).map_err(read)?,
+                    row.get::<_, Option<String>>(6).map_err(read)?,
+                    tags,
+                    timestamp(row.get(8).map_err(read)?),
+                    timestamp(row.get(9).map_err(read)?),
+                    timestamp(row.get(10).map_err(read)?),
+                    row.get::<_, Option<i64>>(11).map_err(read)?,
+                ])
+                .map_err(|e| format!("Failed to copy memory {}: {}", id, e))?;
+            let tags: Vec<String> = tags
+                .and_then(|tags| serde_json::from_str(&tags).ok())
+                .unwrap_or_default();
+            for tag in tags {
+                memory_tags
+                    .append_row(params![id, tag])
+                    .map_err(|e| format!("Failed to copy memory {}: {}", id, e))?;
+            }
+        }
+        memories.flush().map_err(|e| e.to_string())?;
+        memory_tags.flush().map_err(|e| e.to_string())?;
+    }
+    drop(conn);
+
+    fs::rename(&partial, &dest).map_err(|e| format!("Failed to save {}: {}", dest.display(), e))
+}
+
+/// Run `sql` against the analytics copy, read-only, building the copy
+/// first if there isn't one yet.
+pub async fn run_query(app: &AppHandle, sql: String) -> Result<AnalyticsResult, String> {
+    let db_path = crate::database::active_db_path(app).await?;
+    let path = analytics_path(&db_path);
+    if !path.exists() {
+        refresh(app).await?;
+    }
+    tokio::task::spawn_blocking(move || query(&path, &sql))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn query(path: &Path, sql: &str) -> Result<AnalyticsResult, String> {
+    let config = Config::default()
+        .access_mode(AccessMode::ReadOnly)
+        .map_err(|e| e.to_string())?;
+    let conn = Connection::open_with_flags(path, config)
+        .map_err(|e| format!("Failed to open analytics copy: {}", e))?;
+    let mut stmt = conn.prepare(sql).map_err(|e| e.to_string())?;
+    let mut rows = stmt.query([]).map_err(|e| e.to_string())?;
+    let columns = rows
+        .as_ref()
+        .map(|stmt| stmt.column_names())
+        .unwrap_or_default();
+
+    let mut result = Vec::new();
+    let mut truncated = false;
+    while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+        if result.len() == MAX_ROWS {
+            truncated = true;
+            break;
+        }
+        let values = (0..columns.len())
+            .map(|i| row.get::<_, Value>(i).map(to_json))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        result.push(values);
+    }
+
+    let refreshed_at = fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as i64);
+    Ok(AnalyticsResult {
+        columns,
+        rows: result,
+        truncated,
+        refreshed_at,
+    })
+}
+
+/// A DuckDB value as JSON; timestamps become RFC 3339 strings and types
+/// without a JSON counterpart their debug form.
+fn to_json(value: Value) -> serde_json::Value {
+    match value {
+        Value::Null => serde_json::Value::Null,
+        Value::Boolean(b) => b.into(),
+        Value::TinyInt(n) => n.into(),
+        Value::SmallInt(n) => n.into(),
+        Value::Int(n) => n.into(),
+        Value::BigInt(n) => n.into(),
+        Value::UTinyInt(n) => n.into(),
+        Value::USmallInt(n) => n.into(),
+        Value::UInt(n) => n.into(),
+        Value::UBigInt(n) => n.into(),
+        // Counts over large tables come back as HUGEINT
+        Value::HugeInt(n) => i64::try_from(n).map_or_else(|_| n.to_string().into(), Into::into),
+        Value::Float(n) => n.into(),
+        Value::Double(n) => n.into(),
+        Value::Text(s) => s.into(),
+        Value::Timestamp(unit, n) => {
+            let micros = match unit {
+                TimeUnit::Second => n * 1_000_000,
+                TimeUnit::Millisecond => n * 1_000,
+                TimeUnit::Microsecond => n,
+                TimeUnit::Nanosecond => n / 1_000,
+            };
+            chrono::DateTime::from_timestamp_micros(micros)
+                .map_or(serde_json::Value::Null, |t| t.to_rfc3339().into())
+        }
+        Value::Date32(days) => chrono::NaiveDate::from_num_days_from_ce_opt(days + 719_163)
+            .map_or(serde_json::Value::Null, |d| d.to_string().into()),
+        Value::List(items) => items.into_iter().map(to_json).collect(),
+        other => format!("{:?}", other).into(),
+    }
+}
@@ -0,0 +1,168 @@
+/// An exported note from the Notes app.
+#[derive(Debug, Clone)]
+pub struct AppleNote {
+    pub title: String,
+    /// Note body converted from Notes' HTML to markdown.
+    pub markdown: String,
+    pub attachments: Vec<String>,
+    /// Last modification, as reported by Notes (`YYYY-MM-DDTHH:MM:SS`, local time).
+    pub modified: String,
+}
+
+// ASCII unit/record separators; neither can appear in note titles or HTML bodies
+const FIELD_SEP: char = '\u{1f}';
+const RECORD_SEP: char = '\u{1e}';
+
+/// Read every note in a Notes folder by driving the Notes app with AppleScript.
+/// The first run triggers the macOS automation permission prompt.
+pub fn read_folder(folder: &str) -> Result<Vec<AppleNote>, String> {
+    if !cfg!(target_os = "macos") {
+        return Err("Apple Notes import is only available on macOS".to_string());
+    }
+
+    let escaped = folder.replace('\\', "\\\\").replace('"', "\\\"");
+    let script = format!(
+        r#"set fs to ASCII character 31
+set rs to ASCII character 30
+set output to ""
+tell application "Notes"
+    repeat with n in notes of folder "{}"
+        set attachmentNames to ""
+        repeat with a in attachments of n
+            set attachmentNames to attachmentNames & (name of a) & linefeed
+        end repeat
+        set modified to (modification date of n) as «class isot» as string
+        set output to output & (name of n) & fs & (body of n) & fs & attachmentNames & fs & modified & rs
+    end repeat
+end tell
+return output"#,
+        escaped
+    );
+
+    let output = std::process::Command::new("osascript")
+        .args(["-e", &script])
+        .output()
+        .map_err(|e| format!("Failed to run osascript: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("-1743") {
+            return Err(
+                "Engram is not allowed to control Notes. Enable it in System Settings > Privacy & Security > Automation."
+                    .to_string(),
+            );
+        }
+        return Err(format!("Failed to read Notes folder \"{}\": {}", folder, stderr.trim()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let notes = stdout
+        .trim_end_matches('\n')
+        .split(RECORD_SEP)
+        .filter(|record| !record.trim().is_empty())
+        .filter_map(|record| {
+            let fields: Vec<&str> = record.split(FIELD_SEP).collect();
+            if fields.len() < 4 {
+                return None;
+            }
+            Some(AppleNote {
+                title: fields[0].trim().to_string(),
+                markdown: html_to_markdown(fields[1]),
+                attachments: fields[2]
+                    .lines()
+                    .map(|n| n.trim().to_string())
+                    .filter(|n| !n.is_empty())
+                    .collect(),
+                modified: fields[3].trim().to_string(),
+            })
+        })
+        .collect();
+
+    Ok(notes)
+}
+
+/// Convert the limited HTML that Notes produces into markdown.
+/// Handles headings, bold/italic, lists, links, and line breaks; other tags are dropped.
+pub fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    let mut pending_href: Option<String> = None;
+
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("")
+            .to_lowercase();
+
+        match (name.as_str(), closing) {
+            ("h1", false) => out.push_str("\n# "),
+            ("h2", false) => out.push_str("\n## "),
+            ("h3", false) | ("h4", false) | ("h5", false) | ("h6", false) => out.push_str("\n### "),
+            ("b", _) | ("strong", _) => out.push_str("**"),
+            ("i", _) | ("em", _) => out.push('*'),
+            ("li", false) => out.push_str("\n- "),
+            ("br", _) => out.push('\n'),
+            ("div", true) | ("p", true) | ("h1", true) | ("h2", true) | ("h3", true)
+            | ("h4", true) | ("h5", true) | ("h6", true) | ("ul", true) | ("ol", true) => {
+                out.push('\n')
+            }
+            ("a", false) => {
+                pending_href = attribute(tag, "href");
+                out.push('[');
+            }
+            ("a", true) => match pending_href.take() {
+                Some(href) => out.push_str(&format!("]({})", href)),
+                None => out.push(']'),
+            },
+            _ => {}
+        }
+    }
+    out.push_str(rest);
+
+    let decoded = decode_entities(&out);
+
+    // Collapse the blank lines left behind by nested block tags
+    let mut result = String::new();
+    let mut blank = false;
+    for line in decoded.lines().map(|l| l.trim_end()) {
+        if line.trim().is_empty() || line.trim() == "**" {
+            if !blank && !result.is_empty() {
+                result.push('\n');
+            }
+            blank = true;
+        } else {
+            result.push_str(line);
+            result.push('\n');
+            blank = false;
+        }
+    }
+    result.trim().to_string()
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let key = format!("{}=\"", name);
+    let start = tag.find(&key)? + key.len();
+    let end = tag[start..].find('"')?;
+    Some(tag[start..start + end].to_string())
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
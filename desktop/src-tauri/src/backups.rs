@@ -0,0 +1,152 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use rusqlite::{Connection, DatabaseName, OpenFlags};
+use serde::Serialize;
+
+const BACKUP_EXTENSION: &str = "db";
+/// Appended to snapshot names, e.g. `before-upgrade-20260314-091500.db`.
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// A database snapshot in the backups directory.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupInfo {
+    /// File name, which identifies the snapshot to `restore`.
+    pub file_name: String,
+    pub name: String,
+    pub path: String,
+    pub size_bytes: u64,
+    /// RFC 3339.
+    pub created_at: String,
+}
+
+/// ~/.engram/backups for the active profile.
+pub fn backups_dir() -> Result<PathBuf, String> {
+    Ok(crate::commands::get_engram_data_dir()?.join("backups"))
+}
+
+/// `name` reduced to characters that are safe in a file name.
+fn sanitize_name(name: &str) -> String {
+    let cleaned: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+        .collect();
+    let cleaned = cleaned.trim_matches('-');
+    if cleaned.is_empty() {
+        "snapshot".to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+fn info(path: &Path) -> Option<BackupInfo> {
+    let file_name = path.file_name()?.to_str()?.to_string();
+    let stem = file_name.strip_suffix(&format!(".{}", BACKUP_EXTENSION))?;
+    // The timestamp itself contains one dash
+    let mut parts = stem.rsplitn(3, '-');
+    let (time, date) = (parts.next()?, parts.next()?);
+    let name = parts.next().unwrap_or_default().to_string();
+    let timestamp = format!("{}-{}", date, time);
+    let created = chrono::NaiveDateTime::parse_from_str(&timestamp, TIMESTAMP_FORMAT)
+        .ok()?
+        .and_local_timezone(Local)
+        .single()?;
+    let size_bytes = fs::metadata(path).ok()?.len();
+    Some(BackupInfo {
+        file_name,
+        name,
+        path: path.to_string_lossy().to_string(),
+        size_bytes,
+        created_at: created.to_rfc3339(),
+    })
+}
+
+/// Copy the database into `<name>-<timestamp>.db` with SQLite's online
+/// backup, which is consistent even while the sidecar is writing.
+pub fn create(db_path: &Path, name: &str) -> Result<BackupInfo, String> {
+    if !db_path.exists() {
+        return Err(format!("Database not found at: {}", db_path.display()));
+    }
+    let dir = backups_dir()?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create backups directory: {}", e))?;
+
+    let now: DateTime<Local> = Local::now();
+    let file_name = format!(
+        "{}-{}.{}",
+        sanitize_name(name),
+        now.format(TIMESTAMP_FORMAT),
+        BACKUP_EXTENSION
+    );
+    let path = dir.join(&file_name);
+    if path.exists() {
+        return Err(format!("A snapshot named {} already exists", file_name));
+    }
+
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    if let Err(e) = conn.backup(DatabaseName::Main, &path, None) {
+        let _ = fs::remove_file(&path);
+        return Err(format!("Failed to back up database: {}", e));
+    }
+
+    info(&path).ok_or_else(|| format!("Failed to read snapshot {}", file_name))
+}
+
+/// Snapshots in the backups directory, newest first.
+pub fn list() -> Result<Vec<BackupInfo>, String> {
+    let dir = backups_dir()?;
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read backups directory: {}", e)),
+    };
+    let mut backups: Vec<BackupInfo> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| info(&entry.path()))
+        .collect();
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(backups)
+}
+
+/// The snapshot called `file_name`, refusing anything outside the backups
+/// directory.
+pub fn find(file_name: &str) -> Result<PathBuf, String> {
+    if file_name.contains(['/', '\\']) || file_name.starts_with('.') {
+        return Err(format!("Invalid snapshot name: {}", file_name));
+    }
+    let path = backups_dir()?.join(file_name);
+    if !path.is_file() {
+        return Err(format!("Snapshot not found: {}", file_name));
+    }
+    Ok(path)
+}
+
+/// Replace the database with the snapshot at `backup`. The sidecar must be
+/// stopped first. The snapshot is checked before anything is touched, and
+/// the stale WAL and shared-memory files are removed so SQLite doesn't
+/// replay them over the restored file.
+pub fn restore(backup: &Path, db_path: &Path) -> Result<(), String> {
+    let conn = Connection::open_with_flags(backup, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Failed to open snapshot: {}", e))?;
+    let check: String = conn
+        .query_row("PRAGMA quick_check", [], |row| row.get(0))
+        .map_err(|e| format!("Snapshot is not a valid database: {}", e))?;
+    if check != "ok" {
+        return Err(format!("Snapshot failed its integrity check: {}", check));
+    }
+    drop(conn);
+
+    let partial = db_path.with_extension("db.restoring");
+    fs::copy(backup, &partial).map_err(|e| format!("Failed to copy snapshot: {}", e))?;
+    for suffix in ["db-wal", "db-shm"] {
+        let path = db_path.with_extension(suffix);
+        if path.exists() {
+            fs::remove_file(&path)
+                .map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+        }
+    }
+    fs::rename(&partial, db_path).map_err(|e| format!("Failed to replace database: {}", e))
+}
@@ -0,0 +1,360 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::AppHandle;
+
+/// Published with every release next to the app installers.
+const MANIFEST_URL: &str =
+    "https://github.com/HBarefoot/engram/releases/latest/download/sidecar-manifest.json";
+/// Hex Ed25519 key that release bundles are signed with, set by the release
+/// build. Builds without it can check for updates but not install them.
+const UPDATE_PUBLIC_KEY: Option<&str> = option_env!("ENGRAM_UPDATE_PUBLIC_KEY");
+/// The sidecar's npm package; its latest version is the newest release.
+const NPM_LATEST_URL: &str = "https://registry.npmjs.org/@hbarefoot/engram/latest";
+const RELEASES_URL: &str = "https://api.github.com/repos/HBarefoot/engram/releases?per_page=30";
+/// Changelog lines kept per release in the summary.
+const RELEASE_HIGHLIGHTS_MAX: usize = 5;
+const BUNDLE_FILE: &str = "engram-bundle.cjs";
+const METADATA_FILE: &str = "engram-bundle.json";
+
+/// What a release says about its sidecar bundle.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateManifest {
+    version: String,
+    url: String,
+    /// Hex SHA-256 of the bundle.
+    sha256: String,
+    /// Hex Ed25519 signature of the bundle bytes.
+    signature: String,
+    /// Oldest app whose bundled runtime and native modules can run it.
+    #[serde(default)]
+    min_app_version: Option<String>,
+}
+
+/// Written next to a downloaded bundle so it can be re-verified at startup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InstalledBundle {
+    version: String,
+    sha256: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidecarUpdateInfo {
+    pub current_version: String,
+    pub latest_version: String,
+    pub available: bool,
+    /// Set when a newer bundle exists but needs a newer app.
+    pub requires_app_version: Option<String>,
+}
+
+/// The changes in one release, summarized from its release notes.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseNotes {
+    pub version: String,
+    /// RFC 3339.
+    pub published_at: Option<String>,
+    pub highlights: Vec<String>,
+    /// Changes left out of `highlights`.
+    pub more: usize,
+}
+
+/// How the running sidecar compares with the latest npm release.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidecarReleaseInfo {
+    pub current_version: String,
+    pub latest_version: String,
+    pub newer: bool,
+    /// Releases after the current version, newest first.
+    pub changelog: Vec<ReleaseNotes>,
+    /// The bundle update for this app, when the release manifest could be read.
+    pub update: Option<SidecarUpdateInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NpmRelease {
+    version: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubRelease {
+    tag_name: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    published_at: Option<String>,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+}
+
+fn runtime_dir() -> Result<PathBuf, String> {
+    Ok(crate::profiles::root_dir()?.join("runtime"))
+}
+
+fn read_installed() -> Option<InstalledBundle> {
+    let content = fs::read_to_string(runtime_dir().ok()?.join(METADATA_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+pub fn decode_hex(hex: &str) -> Result<Vec<u8>, String> {
+    let hex = hex.trim();
+    if !hex.is_ascii() || hex.len() % 2 != 0 {
+        return Err("Invalid hex string".to_string());
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|e| e.to_string()))
+        .collect()
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn verify_signature(bytes: &[u8], signature_hex: &str) -> Result<(), String> {
+    let key = UPDATE_PUBLIC_KEY.ok_or("This build has no update signing key")?;
+    let key: [u8; 32] = decode_hex(key)?
+        .try_into()
+        .map_err(|_| "Invalid update public key".to_string())?;
+    let key = VerifyingKey::from_bytes(&key)
+        .map_err(|e| format!("Invalid update public key: {}", e))?;
+    let signature: [u8; 64] = decode_hex(signature_hex)?
+        .try_into()
+        .map_err(|_| "Invalid bundle signature".to_string())?;
+    key.verify(bytes, &Signature::from_bytes(&signature))
+        .map_err(|_| "Bundle signature does not match the release key".to_string())
+}
+
+/// A downloaded bundle newer than the app's own that still matches its
+/// recorded checksum, with its version. Otherwise the sidecar runs the
+/// bundle shipped with the app.
+pub fn installed_bundle(app: &AppHandle) -> Option<(PathBuf, String)> {
+    let installed = read_installed()?;
+    if !newer(&installed.version, &app.package_info().version.to_string()) {
+        return None;
+    }
+    let path = runtime_dir().ok()?.join(BUNDLE_FILE);
+    let bytes = fs::read(&path).ok()?;
+    if sha256_hex(&bytes) != installed.sha256 {
+        engram_log!("Ignoring {}: checksum mismatch", path.display());
+        return None;
+    }
+    Some((path, installed.version))
+}
+
+fn current_version(app: &AppHandle) -> String {
+    installed_bundle(app)
+        .map(|(_, version)| version)
+        .unwrap_or_else(|| app.package_info().version.to_string())
+}
+
+fn newer(candidate: &str, than: &str) -> bool {
+    match (
+        crate::sidecar::parse_version(candidate),
+        crate::sidecar::parse_version(than),
+    ) {
+        (Some(candidate), Some(than)) => candidate > than,
+        _ => false,
+    }
+}
+
+fn client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(120))
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+async fn fetch_manifest(client: &reqwest::Client) -> Result<UpdateManifest, String> {
+    let resp = client
+        .get(MANIFEST_URL)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to check for sidecar updates: {}", e))?;
+    if !resp.status().is_success() {
+        return Err(format!("Failed to check for sidecar updates: HTTP {}", resp.status()));
+    }
+    resp.json()
+        .await
+        .map_err(|e| format!("Invalid sidecar update manifest: {}", e))
+}
+
+pub async fn check(app: &AppHandle) -> Result<SidecarUpdateInfo, String> {
+    let manifest = fetch_manifest(&client()?).await?;
+    let current = current_version(app);
+    let app_version = app.package_info().version.to_string();
+    let requires_app_version = manifest
+        .min_app_version
+        .clone()
+        .filter(|min| newer(min, &app_version));
+    Ok(SidecarUpdateInfo {
+        available: newer(&manifest.version, &current) && requires_app_version.is_none(),
+        current_version: current,
+        latest_version: manifest.version,
+        requires_app_version,
+    })
+}
+
+/// List items from release notes, without their bullet markers.
+fn highlights(body: &str) -> Vec<String> {
+    body.lines()
+        .map(str::trim)
+        .filter_map(|line| line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")))
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Notes for the GitHub releases after `current` up to `latest`. Failing to
+/// reach GitHub leaves the changelog empty rather than failing the check.
+async fn changelog(client: &reqwest::Client, current: &str, latest: &str) -> Vec<ReleaseNotes> {
+    let releases: Vec<GitHubRelease> = match client
+        .get(RELEASES_URL)
+        .header("User-Agent", "Engram")
+        .send()
+        .await
+    {
+        Ok(resp) if resp.status().is_success() => resp.json().await.unwrap_or_default(),
+        Ok(resp) => {
+            engram_log!("Failed to fetch release notes: HTTP {}", resp.status());
+            Vec::new()
+        }
+        Err(e) => {
+            engram_log!("Failed to fetch release notes: {}", e);
+            Vec::new()
+        }
+    };
+
+    let mut notes: Vec<ReleaseNotes> = releases
+        .into_iter()
+        .filter(|r| !r.draft && !r.prerelease)
+        .filter(|r| newer(&r.tag_name, current) && !newer(&r.tag_name, latest))
+        .map(|r| {
+            let mut items = highlights(r.body.as_deref().unwrap_or_default());
+            let more = items.len().saturating_sub(RELEASE_HIGHLIGHTS_MAX);
+            items.truncate(RELEASE_HIGHLIGHTS_MAX);
+            ReleaseNotes {
+                version: r.tag_name.trim_start_matches('v').to_string(),
+                published_at: r.published_at,
+                highlights: items,
+                more,
+            }
+        })
+        .collect();
+    notes.sort_by(|a, b| {
+        crate::sidecar::parse_version(&b.version).cmp(&crate::sidecar::parse_version(&a.version))
+    });
+    notes
+}
+
+/// Compare the running sidecar with the latest `@hbarefoot/engram` on npm,
+/// with release notes for the versions in between.
+pub async fn check_release(app: &AppHandle) -> Result<SidecarReleaseInfo, String> {
+    let client = client()?;
+    let resp = client
+        .get(NPM_LATEST_URL)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to check npm for sidecar releases: {}", e))?;
+    if !resp.status().is_success() {
+        return Err(format!(
+            "Failed to check npm for sidecar releases: HTTP {}",
+            resp.status()
+        ));
+    }
+    let latest: NpmRelease = resp
+        .json()
+        .await
+        .map_err(|e| format!("Invalid npm release info: {}", e))?;
+
+    let current = current_version(app);
+    let is_newer = newer(&latest.version, &current);
+    let (changelog, update) = if is_newer {
+        let update = match check(app).await {
+            Ok(update) => Some(update),
+            Err(e) => {
+                engram_log!("{}", e);
+                None
+            }
+        };
+        (changelog(&client, &current, &latest.version).await, update)
+    } else {
+        (Vec::new(), None)
+    };
+    Ok(SidecarReleaseInfo {
+        current_version: current,
+        latest_version: latest.version,
+        newer: is_newer,
+        changelog,
+        update,
+    })
+}
+
+/// Download, verify, and install the latest bundle, then restart the
+/// sidecar on it. Returns the installed version.
+pub async fn apply(app: &AppHandle) -> Result<String, String> {
+    let client = client()?;
+    let manifest = fetch_manifest(&client).await?;
+    let app_version = app.package_info().version.to_string();
+    if let Some(min) = manifest.min_app_version.as_deref() {
+        if newer(min, &app_version) {
+            return Err(format!(
+                "Sidecar v{} needs Engram v{} or newer; update the app instead",
+                manifest.version, min
+            ));
+        }
+    }
+    if !newer(&manifest.version, &current_version(app)) {
+        return Err("The sidecar is already up to date".to_string());
+    }
+
+    let resp = client
+        .get(&manifest.url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download sidecar update: {}", e))?;
+    if !resp.status().is_success() {
+        return Err(format!("Failed to download sidecar update: HTTP {}", resp.status()));
+    }
+    let bytes = resp
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to download sidecar update: {}", e))?;
+
+    let checksum = sha256_hex(&bytes);
+    if checksum != manifest.sha256.trim().to_lowercase() {
+        return Err("Downloaded sidecar bundle failed its checksum".to_string());
+    }
+    verify_signature(&bytes, &manifest.signature)?;
+
+    let dir = runtime_dir()?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let tmp = dir.join(format!("{}.tmp", BUNDLE_FILE));
+    fs::write(&tmp, &bytes).map_err(|e| format!("Failed to write sidecar update: {}", e))?;
+    fs::rename(&tmp, dir.join(BUNDLE_FILE))
+        .map_err(|e| format!("Failed to install sidecar update: {}", e))?;
+    let installed = InstalledBundle {
+        version: manifest.version.clone(),
+        sha256: checksum,
+    };
+    let metadata = serde_json::to_string_pretty(&installed).map_err(|e| e.to_string())?;
+    fs::write(dir.join(METADATA_FILE), metadata)
+        .map_err(|e| format!("Failed to write sidecar update metadata: {}", e))?;
+
+    engram_log!("Installed sidecar bundle v{}", manifest.version);
+    crate::sidecar::restart(app).await?;
+    Ok(manifest.version)
+}
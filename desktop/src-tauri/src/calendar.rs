@@ -0,0 +1,177 @@
+use std::sync::RwLock;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::time::sleep;
+
+use crate::commands;
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+/// Events overlapping this window from now are cached, so one refresh
+/// covers meetings that start before the next.
+const LOOKAHEAD_SECS: u64 = 120;
+
+/// Meetings around now, as (start, end, title) in Unix seconds. Refreshed in
+/// the background so quick add never waits on EventKit.
+static UPCOMING: RwLock<Vec<(i64, i64, String)>> = RwLock::new(Vec::new());
+
+/// Reads events through EventKit from JavaScript for Automation. `status`
+/// prints the authorization status, `request` prompts for access and prints
+/// `granted` or `denied`, and `events <secs>` prints timed (not all-day)
+/// events overlapping the next `secs` seconds as `start\tend\ttitle` lines.
+const EVENTKIT_SCRIPT: &str = r#"
+ObjC.import('EventKit');
+function run(argv) {
+  const EVENTS = 0;
+  if (argv[0] === 'status') {
+    return String($.EKEventStore.authorizationStatusForEntityType(EVENTS));
+  }
+  const store = $.EKEventStore.alloc.init;
+  if (argv[0] === 'request') {
+    let done = false;
+    let granted = false;
+    store.requestAccessToEntityTypeCompletion(EVENTS, (ok, error) => {
+      granted = ok;
+      done = true;
+    });
+    const deadline = Date.now() + 120000;
+    while (!done && Date.now() < deadline) {
+      $.NSRunLoop.currentRunLoop.runUntilDate($.NSDate.dateWithTimeIntervalSinceNow(0.1));
+    }
+    return granted ? 'granted' : 'denied';
+  }
+  const start = $.NSDate.date;
+  const end = $.NSDate.dateWithTimeIntervalSinceNow(Number(argv[1]));
+  const events = store.eventsMatchingPredicate(
+    store.predicateForEventsWithStartDateEndDateCalendars(start, end, $())
+  );
+  const lines = [];
+  for (let i = 0; i < events.count; i++) {
+    const event = events.objectAtIndex(i);
+    if (event.allDay) continue;
+    const title = event.title.js.replace(/[\t\n]/g, ' ');
+    lines.push([
+      Math.floor(event.startDate.timeIntervalSince1970),
+      Math.floor(event.endDate.timeIntervalSince1970),
+      title
+    ].join('\t'));
+  }
+  return lines.join('\n');
+}
+"#;
+
+/// Calendar permission as EventKit reports it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CalendarAccess {
+    NotDetermined,
+    Denied,
+    Granted,
+    /// Not macOS.
+    Unavailable,
+}
+
+fn run_script(args: &[&str]) -> Result<String, String> {
+    let output = std::process::Command::new("osascript")
+        .args(["-l", "JavaScript", "-e", EVENTKIT_SCRIPT])
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run osascript: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to read the calendar: {}", stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+pub fn access() -> Result<CalendarAccess, String> {
+    if !cfg!(target_os = "macos") {
+        return Ok(CalendarAccess::Unavailable);
+    }
+    // EKAuthorizationStatus: 0 not determined, 1 restricted, 2 denied,
+    // 3 full access, 4 write-only (which can't read events)
+    Ok(match run_script(&["status"])?.as_str() {
+        "0" => CalendarAccess::NotDetermined,
+        "3" => CalendarAccess::Granted,
+        _ => CalendarAccess::Denied,
+    })
+}
+
+/// Show the system calendar permission prompt if it hasn't been answered.
+/// Once denied, it can only be changed in System Settings.
+pub fn request_access() -> Result<CalendarAccess, String> {
+    match access()? {
+        CalendarAccess::NotDetermined => {}
+        other => return Ok(other),
+    }
+    Ok(match run_script(&["request"])?.as_str() {
+        "granted" => CalendarAccess::Granted,
+        _ => CalendarAccess::Denied,
+    })
+}
+
+fn read_upcoming() -> Result<Vec<(i64, i64, String)>, String> {
+    let output = run_script(&["events", &LOOKAHEAD_SECS.to_string()])?;
+    Ok(output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let start = fields.next()?.parse().ok()?;
+            let end = fields.next()?.parse().ok()?;
+            let title = fields.next()?.trim().to_string();
+            (!title.is_empty()).then_some((start, end, title))
+        })
+        .collect())
+}
+
+/// Title of the meeting in progress, from the last refresh. When meetings
+/// overlap, the one that started last wins.
+pub fn current_meeting() -> Option<String> {
+    let now = chrono::Utc::now().timestamp();
+    let upcoming = UPCOMING.read().ok()?;
+    upcoming
+        .iter()
+        .filter(|(start, end, _)| *start <= now && now < *end)
+        .max_by_key(|(start, _, _)| *start)
+        .map(|(_, _, title)| title.clone())
+}
+
+fn set_upcoming(events: Vec<(i64, i64, String)>) {
+    if let Ok(mut upcoming) = UPCOMING.write() {
+        *upcoming = events;
+    }
+}
+
+/// Re-read meetings around now, or forget them if calendar context is off or
+/// access hasn't been granted. Blocks on osascript.
+pub fn refresh() -> Result<(), String> {
+    let enabled = commands::read_preferences().unwrap_or_default().calendar_context;
+    if !enabled || access()? != CalendarAccess::Granted {
+        set_upcoming(Vec::new());
+        return Ok(());
+    }
+    let events = read_upcoming();
+    set_upcoming(events.clone().unwrap_or_default());
+    events.map(|_| ())
+}
+
+/// Spawn the loop that keeps the meeting cache fresh.
+pub fn setup_calendar_refresh() {
+    tauri::async_runtime::spawn(async move {
+        let mut logged_failure = false;
+        loop {
+            let result = tokio::task::spawn_blocking(refresh)
+                .await
+                .unwrap_or_else(|e| Err(e.to_string()));
+            match result {
+                Ok(()) => logged_failure = false,
+                Err(e) if !logged_failure => {
+                    engram_log!("Calendar refresh failed: {}", e);
+                    logged_failure = true;
+                }
+                Err(_) => {}
+            }
+            sleep(REFRESH_INTERVAL).await;
+        }
+    });
+}
@@ -0,0 +1,187 @@
+use std::sync::Mutex;
+
+use axum::body::Bytes;
+use axum::extract::{DefaultBodyLimit, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::AppHandle;
+use tokio::sync::oneshot;
+
+use crate::commands::{self, CaptureEndpointSettings};
+
+/// Keychain account holding the bearer token callers must send.
+const TOKEN_ACCOUNT: &str = "capture-token";
+/// Source of captured memories; a caller's own `source` is appended to it.
+const CAPTURE_SOURCE: &str = "capture";
+/// Larger requests are rejected; memories are meant to be recalled into a prompt.
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// Stops the running endpoint when sent to or dropped.
+static SHUTDOWN: Mutex<Option<oneshot::Sender<()>>> = Mutex::new(None);
+
+/// Where and how to call the endpoint, for the preferences UI.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureEndpointInfo {
+    pub enabled: bool,
+    pub url: String,
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CaptureRequest {
+    content: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    source: Option<String>,
+}
+
+#[derive(Clone)]
+struct EndpointState {
+    app: AppHandle,
+    token_hash: Vec<u8>,
+}
+
+pub fn info(settings: &CaptureEndpointSettings) -> Result<CaptureEndpointInfo, String> {
+    Ok(CaptureEndpointInfo {
+        enabled: settings.enabled,
+        url: format!("http://127.0.0.1:{}/capture", settings.port),
+        token: crate::keychain::get(TOKEN_ACCOUNT)?,
+    })
+}
+
+/// Store a new random token, replacing the previous one.
+pub fn rotate_token() -> Result<String, String> {
+    let bytes: [u8; 32] = rand::random();
+    let token: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    crate::keychain::set(TOKEN_ACCOUNT, &token)?;
+    Ok(token)
+}
+
+/// Start the endpoint at launch if it is enabled.
+pub fn setup_capture_endpoint(app: &AppHandle) {
+    let settings = commands::read_preferences().unwrap_or_default().capture_endpoint;
+    if !settings.enabled {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = start(&app, settings.port).await {
+            engram_log!("Failed to start capture endpoint: {}", e);
+        }
+    });
+}
+
+/// Stop the endpoint, then start it again with `settings` if enabled.
+pub async fn restart(app: &AppHandle, settings: &CaptureEndpointSettings) -> Result<(), String> {
+    stop();
+    if settings.enabled {
+        start(app, settings.port).await?;
+    }
+    Ok(())
+}
+
+fn stop() {
+    if let Some(shutdown) = SHUTDOWN.lock().ok().and_then(|mut s| s.take()) {
+        let _ = shutdown.send(());
+        engram_log!("Capture endpoint stopped");
+    }
+}
+
+/// Listen on 127.0.0.1 only, so the endpoint is never reachable from the network.
+async fn start(app: &AppHandle, port: u16) -> Result<(), String> {
+    let token = crate::keychain::get(TOKEN_ACCOUNT)?
+        .ok_or("No capture token; turn the endpoint off and on again to create one")?;
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+        .await
+        .map_err(|e| format!("Failed to listen on port {}: {}", port, e))?;
+
+    let state = EndpointState {
+        app: app.clone(),
+        token_hash: Sha256::digest(token.as_bytes()).to_vec(),
+    };
+    let router = Router::new()
+        .route("/capture", post(capture))
+        .layer(DefaultBodyLimit::max(MAX_BODY_BYTES))
+        .with_state(state);
+
+    let (shutdown, shutdown_rx) = oneshot::channel();
+    if let Ok(mut current) = SHUTDOWN.lock() {
+        *current = Some(shutdown);
+    }
+    tauri::async_runtime::spawn(async move {
+        let server = axum::serve(listener, router).with_graceful_shutdown(async {
+            let _ = shutdown_rx.await;
+        });
+        if let Err(e) = server.await {
+            engram_log!("Capture endpoint failed: {}", e);
+        }
+    });
+    engram_log!("Capture endpoint listening on 127.0.0.1:{}", port);
+    Ok(())
+}
+
+/// `POST /capture` with `Authorization: Bearer <token>` and either a JSON
+/// body (`content`, optional `tags` and `source`) or plain text. The memory
+/// goes through the same tagging as quick add; the sidecar then redacts
+/// secrets and skips duplicates.
+async fn capture(
+    State(state): State<EndpointState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let error = |status: StatusCode, message: &str| {
+        (status, Json(serde_json::json!({ "error": message })))
+    };
+
+    let presented = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .unwrap_or_default();
+    // Comparing digests keeps the comparison time independent of the token
+    if Sha256::digest(presented.trim().as_bytes()).as_slice() != state.token_hash.as_slice() {
+        return error(StatusCode::UNAUTHORIZED, "Missing or invalid token");
+    }
+
+    let is_json = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    let request = if is_json {
+        match serde_json::from_slice::<CaptureRequest>(&body) {
+            Ok(request) => request,
+            Err(e) => return error(StatusCode::BAD_REQUEST, &format!("Invalid JSON: {}", e)),
+        }
+    } else {
+        CaptureRequest {
+            content: String::from_utf8_lossy(&body).to_string(),
+            tags: Vec::new(),
+            source: None,
+        }
+    };
+
+    if request.content.trim().is_empty() {
+        return error(StatusCode::BAD_REQUEST, "Memory content is empty");
+    }
+    let source = match request.source.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        Some(source) => format!("{}:{}", CAPTURE_SOURCE, source),
+        None => CAPTURE_SOURCE.to_string(),
+    };
+    let saved = commands::ingest_memory(
+        &state.app,
+        &request.content,
+        request.tags,
+        Some(&source),
+        None,
+        None,
+    )
+    .await;
+    match saved {
+        Ok(id) => (StatusCode::CREATED, Json(serde_json::json!({ "id": id }))),
+        Err(e) => error(StatusCode::BAD_GATEWAY, &e),
+    }
+}
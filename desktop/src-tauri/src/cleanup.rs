@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Memories shorter than this (in characters) rarely carry enough context to be useful.
+const SHORT_CONTENT_CHARS: usize = 20;
+/// Never-recalled memories younger than this haven't had a chance to be recalled yet.
+const NEVER_RECALLED_MIN_AGE_DAYS: u64 = 30;
+const PREVIEW_CHARS: usize = 120;
+
+/// A reason a memory may be low-value, with how much it lowers the quality score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CleanupReason {
+    Short,
+    Stale,
+    NeverRecalled,
+    Duplicate,
+}
+
+impl CleanupReason {
+    fn penalty(self) -> f64 {
+        match self {
+            CleanupReason::Short => 0.3,
+            CleanupReason::Stale => 0.25,
+            CleanupReason::NeverRecalled => 0.2,
+            CleanupReason::Duplicate => 0.35,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            CleanupReason::Short => "Very short memories",
+            CleanupReason::Stale => "Not recalled in a long time",
+            CleanupReason::NeverRecalled => "Never recalled",
+            CleanupReason::Duplicate => "Near-duplicates of another memory",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupCandidate {
+    pub id: String,
+    pub preview: String,
+    pub category: Option<String>,
+    /// 0 (almost certainly low-value) to 1; lower for each reason that applies.
+    pub quality: f64,
+    pub reasons: Vec<CleanupReason>,
+    /// For duplicates, the memory that is kept instead.
+    pub duplicate_of: Option<String>,
+}
+
+/// Candidates sharing one reason, to be archived together.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupBatch {
+    pub reason: CleanupReason,
+    pub label: String,
+    pub memories: Vec<CleanupCandidate>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupSuggestions {
+    pub batches: Vec<CleanupBatch>,
+    /// Distinct memories across all batches.
+    pub total: usize,
+}
+
+/// Responses from the sidecar's memory list and analytics endpoints.
+pub struct CleanupInputs {
+    /// `/api/memories` → `memories`
+    pub memories: Vec<Value>,
+    /// `/api/analytics/stale` → `items`
+    pub stale: Vec<Value>,
+    /// `/api/analytics/never-recalled` → `items`
+    pub never_recalled: Vec<Value>,
+    /// `/api/analytics/duplicates` → `clusters`
+    pub duplicate_clusters: Vec<Value>,
+}
+
+fn str_field(value: &Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+fn preview(content: &str) -> String {
+    let mut chars = content.chars();
+    let mut preview: String = chars.by_ref().take(PREVIEW_CHARS).collect();
+    if chars.next().is_some() {
+        preview.push('…');
+    }
+    preview
+}
+
+/// Flag likely low-value memories and group them into batches by reason.
+/// In each duplicate cluster the most recalled (then most confident) memory
+/// is kept and the rest are suggested.
+pub fn suggest(inputs: &CleanupInputs) -> CleanupSuggestions {
+    let mut found: Vec<(String, CleanupReason, Option<String>)> = Vec::new();
+
+    for memory in &inputs.memories {
+        let content = memory.get("content").and_then(|v| v.as_str()).unwrap_or("");
+        if content.trim().chars().count() < SHORT_CONTENT_CHARS {
+            if let Some(id) = str_field(memory, "id") {
+                found.push((id, CleanupReason::Short, None));
+            }
+        }
+    }
+    for item in &inputs.stale {
+        if let Some(id) = str_field(item, "id") {
+            found.push((id, CleanupReason::Stale, None));
+        }
+    }
+    for item in &inputs.never_recalled {
+        let age = item
+            .get("daysSinceCreation")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        if age >= NEVER_RECALLED_MIN_AGE_DAYS {
+            if let Some(id) = str_field(item, "id") {
+                found.push((id, CleanupReason::NeverRecalled, None));
+            }
+        }
+    }
+    for cluster in &inputs.duplicate_clusters {
+        let mut members: Vec<&Value> = cluster
+            .get("memories")
+            .and_then(|m| m.as_array())
+            .map(|m| m.iter().collect())
+            .unwrap_or_default();
+        members.sort_by(|a, b| {
+            let rank = |m: &Value| {
+                (
+                    m.get("accessCount").and_then(|v| v.as_u64()).unwrap_or(0),
+                    m.get("confidence").and_then(|v| v.as_f64()).unwrap_or(0.0),
+                )
+            };
+            rank(b)
+                .partial_cmp(&rank(a))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let Some(keep) = members.first().and_then(|m| str_field(m, "id")) else {
+            continue;
+        };
+        for member in members.iter().skip(1) {
+            if let Some(id) = str_field(member, "id") {
+                found.push((id, CleanupReason::Duplicate, Some(keep.clone())));
+            }
+        }
+    }
+
+    // Details come from whichever response has the memory; the full list has them all
+    let mut details: HashMap<String, &Value> = HashMap::new();
+    let clustered = inputs
+        .duplicate_clusters
+        .iter()
+        .filter_map(|c| c.get("memories").and_then(|m| m.as_array()))
+        .flatten();
+    for value in inputs
+        .stale
+        .iter()
+        .chain(&inputs.never_recalled)
+        .chain(clustered)
+        .chain(&inputs.memories)
+    {
+        if let Some(id) = str_field(value, "id") {
+            details.insert(id, value);
+        }
+    }
+
+    let mut candidates: HashMap<String, CleanupCandidate> = HashMap::new();
+    for (id, reason, duplicate_of) in &found {
+        let candidate = candidates.entry(id.clone()).or_insert_with(|| {
+            let detail = details.get(id);
+            CleanupCandidate {
+                id: id.clone(),
+                preview: preview(
+                    detail
+                        .and_then(|d| d.get("content"))
+                        .and_then(|v| v.as_str())
+                        .unwrap_or(""),
+                ),
+                category: detail.and_then(|d| str_field(d, "category")),
+                quality: 1.0,
+                reasons: Vec::new(),
+                duplicate_of: None,
+            }
+        });
+        if !candidate.reasons.contains(reason) {
+            candidate.reasons.push(*reason);
+            candidate.quality = (candidate.quality - reason.penalty()).max(0.0);
+        }
+        if duplicate_of.is_some() {
+            candidate.duplicate_of = duplicate_of.clone();
+        }
+    }
+
+    let batches = [
+        CleanupReason::Duplicate,
+        CleanupReason::Short,
+        CleanupReason::Stale,
+        CleanupReason::NeverRecalled,
+    ]
+    .into_iter()
+    .filter_map(|reason| {
+        let mut memories: Vec<CleanupCandidate> = candidates
+            .values()
+            .filter(|c| c.reasons.contains(&reason))
+            .cloned()
+            .collect();
+        if memories.is_empty() {
+            return None;
+        }
+        memories.sort_by(|a, b| {
+            a.quality
+                .total_cmp(&b.quality)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        Some(CleanupBatch {
+            reason,
+            label: reason.label().to_string(),
+            memories,
+        })
+    })
+    .collect();
+
+    CleanupSuggestions {
+        batches,
+        total: candidates.len(),
+    }
+}
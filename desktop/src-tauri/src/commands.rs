@@ -1,11 +1,46 @@
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use sha2::{Digest, Sha256};
+use tauri::{Emitter, Manager, State};
+use tokio::sync::Mutex;
 
-use crate::sidecar::{health_check, SidecarState, SidecarStatus};
+use crate::database;
+use crate::export::{
+    ExportFilter, ExportFormat, ExportOutput, ExportProgress, ExportWriter, ParquetWriter,
+    VaultWriter,
+};
+use crate::mcp::McpSimulationResult;
+use crate::notifications::{notify, NotificationEvent};
+use crate::timestamps;
+use crate::sidecar::{health_check, LogLine, ProcessUsage, SidecarState, SidecarStatus};
+use crate::tag_rules::{CompiledRules, TagContext, TagRule, TagRuleScope};
+
+/// Longer notes are truncated; memories are meant to be recalled into a prompt.
+const APPLE_NOTE_MAX_CHARS: usize = 4000;
+const NOTION_PAGE_MAX_CHARS: usize = 4000;
+const NOTION_SOURCE: &str = "import:notion";
+const GITHUB_ACTIVITY_MAX_CHARS: usize = 4000;
+const GITHUB_SOURCE: &str = "import:github";
+const PINNED_TAG: &str = "pinned";
+/// Source that tag rules see for quick-add captures.
+const QUICK_ADD_SOURCE: &str = "quick-add";
+/// Memories sent to the sidecar per request when restoring an export.
+const IMPORT_BATCH_SIZE: usize = 100;
+/// Memories fetched per request when exporting or listing.
+const EXPORT_PAGE_SIZE: u64 = 500;
+/// How long a token from `request_reset` stays valid.
+const RESET_TOKEN_TTL: Duration = Duration::from_secs(120);
+/// What the user types to confirm `factory_reset`.
+const FACTORY_RESET_CONFIRMATION: &str = "DELETE ENGRAM";
+/// Frontend error reports kept in the diagnostics directory.
+const FRONTEND_ERROR_REPORTS_MAX: usize = 20;
+/// Times an agent config is re-read and merged again when something else
+/// saves it while Engram is updating it.
+const CONFIG_WRITE_ATTEMPTS: usize = 3;
 
 // --- Response types ---
 
@@ -17,6 +52,30 @@ pub struct AppStatus {
     pub memory_count: u64,
     pub uptime: Option<u64>,
     pub version: String,
+    /// When this status was taken, RFC 3339 in the preferred timezone.
+    pub checked_at: String,
+    pub timezone: String,
+    /// Embeddings are running on the WASM fallback because onnxruntime failed to load.
+    pub reduced_performance: bool,
+    /// CPU and memory of the sidecar process, when one is running.
+    pub process: Option<ProcessUsage>,
+    /// Crash restarts since the sidecar was last stable; non-zero means it is flapping.
+    pub restart_count: u32,
+    /// When the sidecar last crashed, RFC 3339 in the preferred timezone.
+    pub last_crash_at: Option<String>,
+    pub last_crash_reason: Option<String>,
+    /// Profile the sidecar was started for.
+    pub profile: String,
+    /// Database the sidecar reported opening, once it is ready.
+    pub db_path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidecarLogs {
+    pub lines: Vec<LogLine>,
+    /// Offset to pass on the next call to receive only newer lines.
+    pub next_offset: u64,
 }
 
 #[derive(Debug, Serialize)]
@@ -26,7 +85,134 @@ pub struct DetectedAgent {
     pub name: String,
     pub config_path: String,
     pub connected: bool,
+    /// Whether the agent can be connected: the same as `installed`.
     pub available: bool,
+    /// The app (or CLI) itself is present, not just its config directory.
+    pub installed: bool,
+    /// The config directory exists, which it may long after an uninstall.
+    pub config_dir_exists: bool,
+    /// An Engram entry is left in the config of an agent that isn't installed.
+    pub orphaned: bool,
+    /// `data:` URI of the agent's bundled icon.
+    pub icon: String,
+}
+
+/// A JavaScript error from a webview, as sent by `report_frontend_error`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrontendError {
+    /// "error", "unhandledrejection", or "render"
+    pub kind: String,
+    pub message: String,
+    pub stack: Option<String>,
+    /// React component stack for render errors.
+    pub component_stack: Option<String>,
+    /// Route (hash) the window was showing.
+    pub route: Option<String>,
+}
+
+/// A sidecar response relayed by `api_request`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiResponse {
+    pub status: u16,
+    pub duration_ms: u64,
+    /// Parsed JSON, or the raw text for other responses.
+    pub body: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigFinding {
+    /// "error", "warning", or "info"
+    pub severity: String,
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentConfigReport {
+    pub agent_id: String,
+    pub config_path: String,
+    /// True when no error-level findings were reported.
+    pub valid: bool,
+    pub findings: Vec<ConfigFinding>,
+}
+
+/// Outcome of committing imported memories through the sidecar's import pipeline.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ImportSummary {
+    /// Items read from the source before deduplication.
+    pub found: u64,
+    pub created: u64,
+    pub duplicates: u64,
+    pub merged: u64,
+    /// Rejected by the sidecar's secret detection.
+    pub rejected: u64,
+    pub errors: Vec<serde_json::Value>,
+}
+
+/// How `import_data` treats memories already in the database.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportMode {
+    /// Keep them; imported duplicates are skipped by the sidecar.
+    Merge,
+    /// Delete them once the export has been read.
+    Replace,
+}
+
+/// Emitted as `import-progress` after each batch of an import.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportProgress {
+    pub processed: u64,
+    pub total: u64,
+}
+
+/// Outcome of applying tag rules to stored memories.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagRulesReport {
+    /// Memories in scope that the rules were evaluated against.
+    pub scanned: u64,
+    pub updated: u64,
+    pub failed: u64,
+}
+
+/// Outcome of renaming, merging, or deleting a tag.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagEditReport {
+    /// Memories whose tags were rewritten.
+    pub updated: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubsystemHealth {
+    /// "rest", "database", "embeddings", or "mcp"
+    pub name: String,
+    /// "ok", "degraded", or "down"
+    pub status: String,
+    pub latency_ms: Option<u64>,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeepHealthReport {
+    /// True when every subsystem is "ok".
+    pub healthy: bool,
+    pub checked_at: String,
+    pub subsystems: Vec<SubsystemHealth>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImportCommitResponse {
+    results: ImportSummary,
 }
 
 #[derive(Debug, Deserialize)]
@@ -37,6 +223,105 @@ struct EngramStatusResponse {
     version: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct MemoryRecord {
+    id: String,
+    content: String,
+    category: Option<String>,
+    entity: Option<String>,
+    confidence: Option<f64>,
+    namespace: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    source: Option<String>,
+    #[serde(rename = "createdAt")]
+    created_at: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MemoryResponse {
+    memory: MemoryRecord,
+}
+
+#[derive(Debug, Deserialize)]
+struct MemoryListResponse {
+    memories: Vec<MemoryRecord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagListResponse {
+    tags: Vec<database::CountEntry>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ScoreBreakdown {
+    similarity: f64,
+    recency: f64,
+    confidence: f64,
+    access: f64,
+    feedback: f64,
+    fts_boost: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchHit {
+    id: String,
+    content: String,
+    score: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    memories: Vec<SearchHit>,
+}
+
+/// A search result as returned by `search_memories`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemorySearchResult {
+    pub id: String,
+    pub content: String,
+    pub category: String,
+    pub entity: Option<String>,
+    pub confidence: f64,
+    pub namespace: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub score: f64,
+    #[serde(default)]
+    pub score_breakdown: ScoreBreakdown,
+    #[serde(default)]
+    pub access_count: u64,
+    /// Unix milliseconds.
+    pub created_at: i64,
+    pub last_accessed: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MemorySearchResponse {
+    #[serde(default)]
+    memories: Vec<MemorySearchResult>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankedMemory {
+    pub id: String,
+    pub content: String,
+    pub score: f64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchPreview {
+    pub query: String,
+    pub current: Vec<RankedMemory>,
+    pub proposed: Vec<RankedMemory>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SeedOptions {
@@ -46,27 +331,527 @@ pub struct SeedOptions {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct DesktopPreferences {
     pub start_at_login: bool,
-    pub sound_on_save: bool,
+    /// Replaced by `notifications`; read from older config files and migrated.
+    #[serde(skip_serializing)]
+    pub sound_on_save: Option<bool>,
+    pub notifications: NotificationSettings,
     pub rest_port: String,
     pub enable_rest_api: bool,
     pub log_level: String,
+    /// Quick captures made within this many minutes of the previous one are
+    /// appended to it instead of creating a new memory.
+    pub append_window_minutes: u64,
+    /// Days to keep files in ~/.engram/logs before deleting them.
+    pub log_retention_days: u32,
+    /// Size at which the current log file is rotated.
+    pub log_max_file_mb: u64,
+    /// Engram turns read-only while the database volume has less free space
+    /// than this; 0 turns the check off.
+    pub low_disk_threshold_mb: u64,
+    /// IANA timezone for exported and displayed timestamps; empty uses the system timezone.
+    pub timezone: String,
+    pub search: SearchSettings,
+    pub maintenance: MaintenanceSettings,
+    /// How long to wait for a newly spawned sidecar to answer /api/status.
+    pub startup_timeout_secs: u64,
+    pub health_check: HealthCheckSettings,
+    pub restart_backoff: RestartBackoffSettings,
+    pub shortcuts: ShortcutSettings,
+    pub tray: TrayLayout,
+    /// Extra environment variables for the sidecar (e.g. `ENGRAM_EMBEDDING_MODEL`).
+    /// They are applied after the app's own variables and take precedence.
+    pub env_overrides: HashMap<String, String>,
+    /// Extra arguments appended to `engram start`, split shell-style (e.g. "--verbose").
+    pub sidecar_args: String,
+    pub developer: DeveloperSettings,
+    /// Applied to quick-add and import captures, and retroactively by `apply_tag_rules`.
+    pub tag_rules: Vec<TagRule>,
+    /// Strip ANSI escapes and terminal noise from captures, NFC-normalize
+    /// them, and tag non-English content with its language.
+    pub normalize_ingest: bool,
+    /// Keep the sidecar running under launchd when the app isn't; the app
+    /// then only attaches to it and never spawns or kills it (macOS).
+    pub run_as_service: bool,
+    pub notion: NotionSyncSettings,
+    pub issue_tracker: IssueTrackerSettings,
+    pub github: GitHubSettings,
+    /// Tag quick-add captures with the calendar meeting in progress (macOS,
+    /// needs calendar access).
+    pub calendar_context: bool,
+    /// Tag quick-add captures with the app that was in front when the panel
+    /// opened (macOS).
+    pub capture_frontmost_app: bool,
+    pub resurfacing: ResurfacingSettings,
+    /// Enables the API explorer window and the `api_request` proxy it uses.
+    pub developer_mode: bool,
+    /// Folder the last export was saved to, where save dialogs start. Empty
+    /// uses the Desktop.
+    pub last_save_dir: String,
+    pub capture_endpoint: CaptureEndpointSettings,
+}
+
+/// Explicit sidecar paths for working on Engram itself. When enabled, they
+/// are used ahead of the bundled runtime; empty fields are auto-detected.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DeveloperSettings {
+    pub enabled: bool,
+    pub node_path: String,
+    /// Script run as `<node> <entry_script> start`, e.g. a checkout's bin/engram.js.
+    pub entry_script: String,
+}
+
+/// Global shortcuts, as accelerator strings (e.g. "CmdOrCtrl+Shift+M").
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ShortcutSettings {
+    pub quick_add: String,
+}
+
+impl Default for ShortcutSettings {
+    fn default() -> Self {
+        Self {
+            quick_add: "CmdOrCtrl+Shift+M".to_string(),
+        }
+    }
+}
+
+/// Optional sections of the tray menu.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TrayLayout {
+    pub show_memory_count: bool,
+    pub show_agents: bool,
+    pub show_copy_connection_info: bool,
+}
+
+impl Default for TrayLayout {
+    fn default() -> Self {
+        Self {
+            show_memory_count: true,
+            show_agents: true,
+            show_copy_connection_info: true,
+        }
+    }
+}
+
+/// Identifies a file written by `export_preferences`.
+const PREFERENCES_FILE_KIND: &str = "engram-preferences";
+/// Bumped when the preferences file format changes incompatibly.
+const PREFERENCES_FILE_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PreferencesFile {
+    kind: String,
+    version: u32,
+    preferences: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreferencesImport {
+    pub applied: crate::config_reload::ConfigApplyReport,
+    /// Settings that were ignored or adjusted to work on this machine.
+    pub warnings: Vec<String>,
+}
+
+/// Periodic liveness checks of a running sidecar.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HealthCheckSettings {
+    pub interval_secs: u64,
+    pub timeout_secs: u64,
+    /// Consecutive failed checks before the sidecar is treated as crashed.
+    pub failure_threshold: u32,
+}
+
+impl Default for HealthCheckSettings {
+    fn default() -> Self {
+        Self {
+            interval_secs: 30,
+            timeout_secs: 5,
+            failure_threshold: 3,
+        }
+    }
+}
+
+/// Crash restart schedule: an exponential burst, then slow periodic retries
+/// so issues that outlast the burst (full disk, sleep) still recover.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RestartBackoffSettings {
+    /// Delay before the first restart; doubles for each further attempt.
+    pub base_delay_secs: u64,
+    pub max_delay_secs: u64,
+    /// Restarts made on the exponential schedule.
+    pub burst_attempts: u32,
+    /// Interval between retries after the burst; 0 gives up instead.
+    pub slow_retry_secs: u64,
+    /// Random spread applied to each delay, as a fraction (0.2 = ±20%).
+    pub jitter: f64,
+}
+
+impl Default for RestartBackoffSettings {
+    fn default() -> Self {
+        Self {
+            base_delay_secs: 2,
+            max_delay_secs: 60,
+            burst_attempts: 3,
+            slow_retry_secs: 600,
+            jitter: 0.2,
+        }
+    }
+}
+
+/// How one kind of event is announced.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EventNotification {
+    pub enabled: bool,
+    /// "banner" for a system notification, "silent" for in-app only.
+    pub style: String,
+    /// System sound name, "default", or "none".
+    pub sound: String,
+}
+
+impl Default for EventNotification {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            style: "banner".to_string(),
+            sound: "default".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationSettings {
+    pub memory_saved: EventNotification,
+    pub sidecar_crashed: EventNotification,
+    pub maintenance_complete: EventNotification,
+    pub import_complete: EventNotification,
+    pub resurfaced: EventNotification,
+    pub low_disk: EventNotification,
+    pub quiet_hours: QuietHours,
+}
+
+/// Times when non-critical notifications are held back and delivered once
+/// the window ends.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QuietHours {
+    pub enabled: bool,
+    pub ranges: Vec<QuietRange>,
+    /// Quiet all day on Saturday and Sunday.
+    pub weekends: bool,
+}
+
+impl Default for QuietHours {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ranges: vec![QuietRange {
+                start: "22:00".to_string(),
+                end: "08:00".to_string(),
+            }],
+            weekends: false,
+        }
+    }
+}
+
+/// `HH:MM` local times; a range whose end is before its start runs past
+/// midnight.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QuietRange {
+    pub start: String,
+    pub end: String,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        let silent = EventNotification {
+            style: "silent".to_string(),
+            sound: "none".to_string(),
+            ..EventNotification::default()
+        };
+        Self {
+            // Quick captures are frequent; a sound confirms them without a banner each time
+            memory_saved: EventNotification {
+                style: "silent".to_string(),
+                ..EventNotification::default()
+            },
+            sidecar_crashed: EventNotification::default(),
+            maintenance_complete: silent,
+            import_complete: EventNotification::default(),
+            // A gentle prompt, no sound
+            resurfaced: EventNotification {
+                sound: "none".to_string(),
+                ..EventNotification::default()
+            },
+            low_disk: EventNotification::default(),
+            quiet_hours: QuietHours::default(),
+        }
+    }
+}
+
+impl DesktopPreferences {
+    /// Carry settings from older config files over to their replacements.
+    fn migrate(&mut self) {
+        let memory_saved = &mut self.notifications.memory_saved;
+        match self.sound_on_save.take() {
+            Some(false) => memory_saved.sound = "none".to_string(),
+            Some(true) if memory_saved.sound == "none" => {
+                memory_saved.sound = "default".to_string()
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Nightly sidecar recycle and database compaction.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MaintenanceSettings {
+    pub enabled: bool,
+    /// Local hour (0-23) at which maintenance runs.
+    pub hour: u32,
+}
+
+impl Default for MaintenanceSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hour: 3,
+        }
+    }
+}
+
+/// Scheduled sync with a Notion database. The integration token is kept in
+/// the keychain, not in the preferences file.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotionSyncSettings {
+    pub enabled: bool,
+    pub database_id: String,
+    pub interval_minutes: u64,
+    /// Also create a page for each memory tagged `pinned`.
+    pub push_pinned: bool,
+}
+
+impl Default for NotionSyncSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            database_id: String::new(),
+            interval_minutes: 30,
+            push_pinned: false,
+        }
+    }
+}
+
+/// Look up issue keys (ENG-123) mentioned in memories in Linear or Jira and
+/// keep their title and status with the memories. The API token is kept in
+/// the keychain.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct IssueTrackerSettings {
+    pub enabled: bool,
+    pub provider: crate::issue_tracker::IssueTrackerProvider,
+    /// Jira site, e.g. `https://acme.atlassian.net`.
+    pub jira_url: String,
+    /// Account the Jira API token belongs to.
+    pub jira_email: String,
+    /// Project prefixes to look up (e.g. `ENG`); empty looks up every key.
+    pub project_keys: Vec<String>,
+    pub interval_minutes: u64,
+}
+
+impl Default for IssueTrackerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: crate::issue_tracker::IssueTrackerProvider::default(),
+            jira_url: String::new(),
+            jira_email: String::new(),
+            project_keys: Vec::new(),
+            interval_minutes: 60,
+        }
+    }
+}
+
+/// Capture the user's merged pull requests and review comments from
+/// GitHub. The personal access token is kept in the keychain.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GitHubSettings {
+    pub enabled: bool,
+    pub interval_minutes: u64,
+    /// `owner/name` repositories to capture; empty captures all of them.
+    pub repos: Vec<String>,
+    pub capture_reviews: bool,
+}
+
+impl Default for GitHubSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_minutes: 60,
+            repos: Vec::new(),
+            capture_reviews: true,
+        }
+    }
+}
+
+/// "Remember this?" prompts for memories from an anniversary of today.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ResurfacingSettings {
+    pub enabled: bool,
+    /// Hours between prompts.
+    pub interval_hours: u64,
+    /// Offer memories created this many days before today.
+    pub anniversary_days: Vec<u32>,
+    /// When no anniversary matches, offer a memory that was never recalled.
+    pub include_never_recalled: bool,
+    /// No prompts from this hour until `quiet_end_hour` (local, 0-23).
+    pub quiet_start_hour: u32,
+    pub quiet_end_hour: u32,
+}
+
+impl Default for ResurfacingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_hours: 24,
+            anniversary_days: vec![7, 30, 365],
+            include_never_recalled: true,
+            quiet_start_hour: 20,
+            quiet_end_hour: 9,
+        }
+    }
+}
+
+/// The shell's localhost `POST /capture` endpoint for scripts and other apps.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CaptureEndpointSettings {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for CaptureEndpointSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 3839,
+        }
+    }
+}
+
+/// Recall ranking weights, passed to the sidecar via environment variables
+/// at startup and through `/api/config/reload` when changed.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SearchSettings {
+    /// Weight of embedding similarity in the final score.
+    pub semantic_weight: f64,
+    /// Boost added when a memory also matched the full-text (keyword) search.
+    pub keyword_weight: f64,
+    /// Weight of how recently a memory was accessed.
+    pub recency_weight: f64,
+}
+
+impl Default for SearchSettings {
+    fn default() -> Self {
+        // Mirrors the sidecar's built-in weights in memory/recall.js
+        Self {
+            semantic_weight: 0.45,
+            keyword_weight: 0.1,
+            recency_weight: 0.15,
+        }
+    }
+}
+
+impl SearchSettings {
+    pub fn to_env(&self) -> Vec<(String, String)> {
+        vec![
+            ("ENGRAM_SEARCH_SEMANTIC_WEIGHT".to_string(), self.semantic_weight.to_string()),
+            ("ENGRAM_SEARCH_KEYWORD_WEIGHT".to_string(), self.keyword_weight.to_string()),
+            ("ENGRAM_SEARCH_RECENCY_WEIGHT".to_string(), self.recency_weight.to_string()),
+        ]
+    }
 }
 
 impl Default for DesktopPreferences {
     fn default() -> Self {
         Self {
             start_at_login: false,
-            sound_on_save: true,
+            sound_on_save: None,
+            notifications: NotificationSettings::default(),
             rest_port: "3838".to_string(),
             enable_rest_api: true,
             log_level: "info".to_string(),
+            append_window_minutes: 5,
+            log_retention_days: 7,
+            log_max_file_mb: 10,
+            low_disk_threshold_mb: 500,
+            timezone: String::new(),
+            search: SearchSettings::default(),
+            maintenance: MaintenanceSettings::default(),
+            startup_timeout_secs: 30,
+            health_check: HealthCheckSettings::default(),
+            restart_backoff: RestartBackoffSettings::default(),
+            shortcuts: ShortcutSettings::default(),
+            tray: TrayLayout::default(),
+            env_overrides: HashMap::new(),
+            sidecar_args: String::new(),
+            developer: DeveloperSettings::default(),
+            tag_rules: Vec::new(),
+            normalize_ingest: false,
+            run_as_service: false,
+            notion: NotionSyncSettings::default(),
+            issue_tracker: IssueTrackerSettings::default(),
+            github: GitHubSettings::default(),
+            calendar_context: false,
+            capture_frontmost_app: false,
+            resurfacing: ResurfacingSettings::default(),
+            developer_mode: false,
+            last_save_dir: String::new(),
+            capture_endpoint: CaptureEndpointSettings::default(),
         }
     }
 }
 
+// --- Managed state ---
+
+/// The token `reset_database` expects, issued by `request_reset`.
+static RESET_TOKEN: std::sync::Mutex<Option<(String, Instant)>> = std::sync::Mutex::new(None);
+
+/// How long `search_memories` reuses a result, so typing doesn't send a
+/// search per keystroke that was already answered.
+const SEARCH_CACHE_TTL: Duration = Duration::from_secs(5);
+const SEARCH_CACHE_SIZE: usize = 32;
+
+/// Port, query, limit, threshold bits, and tags of a cached search.
+type SearchKey = (u16, String, usize, u64, Vec<String>);
+
+/// Recent `search_memories` results, oldest first.
+static SEARCH_CACHE: std::sync::Mutex<Vec<(SearchKey, Instant, Vec<MemorySearchResult>)>> =
+    std::sync::Mutex::new(Vec::new());
+
+/// Remembers the most recent quick capture (memory id and when it was made)
+/// so that follow-up captures can be appended to it.
+#[derive(Default)]
+pub struct QuickAddState {
+    pub last_capture: Mutex<Option<(String, Instant)>>,
+}
+
 // --- Tauri commands ---
 
 #[tauri::command]
@@ -74,6 +859,18 @@ pub async fn get_status(state: State<'_, SidecarState>) -> Result<AppStatus, Str
     let sidecar_status = state.status.lock().await.clone();
     let port = *state.port.lock().await;
     let is_running = matches!(sidecar_status, SidecarStatus::Running);
+    let tz = timestamps::preferred_timezone(&read_preferences().unwrap_or_default().timezone);
+    let checked_at = timestamps::now(&tz).to_rfc3339();
+    let reduced_performance = *state.onnx_fallback.lock().await;
+    let process = state.process_usage().await;
+    let restart_count = *state.restart_count.lock().await;
+    let profile = state.profile.lock().await.clone();
+    let ready = state.ready.lock().await.clone();
+    let db_path = ready.as_ref().map(|info| info.db_path.clone());
+    let (last_crash_at, last_crash_reason) = match state.last_crash.lock().await.clone() {
+        Some((at, reason)) => (Some(timestamps::format_epoch(at, &tz)), Some(reason)),
+        None => (None, None),
+    };
 
     // Try to get live stats from the REST API
     if is_running {
@@ -92,6 +889,15 @@ pub async fn get_status(state: State<'_, SidecarState>) -> Result<AppStatus, Str
                     memory_count: data.memories.unwrap_or(0),
                     uptime: data.uptime,
                     version: data.version.unwrap_or_else(|| "unknown".to_string()),
+                    checked_at,
+                    timezone: tz.name().to_string(),
+                    reduced_performance,
+                    process,
+                    restart_count,
+                    last_crash_at,
+                    last_crash_reason,
+                    profile,
+                    db_path,
                 });
             }
         }
@@ -103,6 +909,7 @@ pub async fn get_status(state: State<'_, SidecarState>) -> Result<AppStatus, Str
         SidecarStatus::Starting => "starting",
         SidecarStatus::Running => "running",
         SidecarStatus::Crashed => "crashed",
+        SidecarStatus::Paused => "paused",
     };
 
     Ok(AppStatus {
@@ -111,7 +918,16 @@ pub async fn get_status(state: State<'_, SidecarState>) -> Result<AppStatus, Str
         port,
         memory_count: 0,
         uptime: None,
-        version: "unknown".to_string(),
+        version: ready.map_or_else(|| "unknown".to_string(), |info| info.version),
+        checked_at,
+        timezone: tz.name().to_string(),
+        reduced_performance,
+        process,
+        restart_count,
+        last_crash_at,
+        last_crash_reason,
+        profile,
+        db_path,
     })
 }
 
@@ -152,179 +968,3463 @@ pub async fn get_detected_agents() -> Result<Vec<DetectedAgent>, String> {
     agents.push(detect_agent("windsurf", "Windsurf", &windsurf_path));
 
     // ChatGPT — uses in-app settings, not a config file
-    let chatgpt_installed = std::path::Path::new("/Applications/ChatGPT.app").exists();
+    let chatgpt_installed = agent_installed("chatgpt");
     agents.push(DetectedAgent {
         id: "chatgpt".to_string(),
         name: "ChatGPT".to_string(),
         config_path: "Settings > MCP Servers (in-app)".to_string(),
         connected: false,
         available: chatgpt_installed,
+        installed: chatgpt_installed,
+        config_dir_exists: false,
+        orphaned: false,
+        icon: agent_icon("chatgpt"),
     });
 
     Ok(agents)
 }
 
 #[tauri::command]
-pub async fn configure_agent(agent_name: String) -> Result<String, String> {
-    configure_agent_internal(&agent_name)
+pub async fn configure_agent(app: tauri::AppHandle, agent_name: String) -> Result<String, String> {
+    let result = configure_agent_internal(&agent_name)?;
+    crate::tray::refresh_agents(&app);
+    Ok(result)
 }
 
+/// Remove the Engram entry from the configs of agents that are no longer
+/// installed. Each config is backed up first, so an entry can be restored
+/// if the agent is reinstalled.
 #[tauri::command]
-pub async fn complete_onboarding(
-    agents: Vec<String>,
-    _seed_options: SeedOptions,
-) -> Result<String, String> {
+pub async fn remove_orphaned_agent_entries() -> Result<Vec<String>, String> {
     let mut results = Vec::new();
-
-    for agent_id in &agents {
-        match configure_agent_internal(agent_id) {
-            Ok(msg) => results.push(msg),
-            Err(e) => results.push(format!("Failed to configure {}: {}", agent_id, e)),
+    for agent in get_detected_agents().await? {
+        if !agent.orphaned {
+            continue;
+        }
+        match remove_engram_entry(&agent.id) {
+            Ok(()) => results.push(format!("Removed Engram from {}", agent.name)),
+            Err(e) => results.push(format!("Failed to clean up {}: {}", agent.name, e)),
         }
     }
+    Ok(results)
+}
 
-    // Mark onboarding as complete
-    let engram_dir = get_engram_data_dir()?;
-    fs::create_dir_all(&engram_dir).map_err(|e| e.to_string())?;
-    let marker = engram_dir.join(".desktop-initialized");
-    fs::write(&marker, "").map_err(|e| e.to_string())?;
+/// Add the `engram-note` helper to zsh, bash, or fish.
+#[tauri::command]
+pub async fn install_shell_hook(
+    shell: String,
+    state: State<'_, SidecarState>,
+) -> Result<String, String> {
+    crate::windows::ensure_not_presenting()?;
+    let port = *state.port.lock().await;
+    crate::shell_hooks::install(&shell, port)
+}
 
-    Ok(results.join("\n"))
+#[tauri::command]
+pub async fn uninstall_shell_hook(shell: String) -> Result<String, String> {
+    crate::windows::ensure_not_presenting()?;
+    crate::shell_hooks::uninstall(&shell)
+}
+
+#[tauri::command]
+pub async fn validate_agent_config(agent_id: String) -> Result<AgentConfigReport, String> {
+    validate_agent_config_internal(&agent_id)
+}
+
+#[tauri::command]
+pub async fn simulate_mcp_session(agent_id: String) -> Result<McpSimulationResult, String> {
+    let config_path = agent_config_path(&agent_id)?;
+    let content = fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read config: {}", e))?;
+    let config: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse config JSON: {}", e))?;
+    let entry = config
+        .get("mcpServers")
+        .and_then(|s| s.get("engram"))
+        .ok_or_else(|| format!("Engram is not configured for {}", agent_id))?;
+
+    let mut result = crate::mcp::simulate_session(entry).await;
+    if !result.success {
+        let diagnostics_dir = get_engram_data_dir()?.join("diagnostics");
+        let tz = timestamps::preferred_timezone(&read_preferences().unwrap_or_default().timezone);
+        match crate::mcp::save_failure_report(&diagnostics_dir, &tz, &agent_id, &config_path, entry, &result) {
+            Ok(path) => result.report_path = Some(path.to_string_lossy().to_string()),
+            Err(e) => engram_log!("Failed to save MCP simulation report: {}", e),
+        }
+    }
+
+    Ok(result)
+}
+
+/// Work through why an agent isn't getting memories: its config entry,
+/// whether it needs a restart to load it, sidecar health, an MCP handshake
+/// with the configured command, and recent recalls. Returns the checks
+/// run and the likely causes, most likely first.
+#[tauri::command]
+pub async fn diagnose_agent(
+    app: tauri::AppHandle,
+    agent_id: String,
+    state: State<'_, SidecarState>,
+) -> Result<crate::diagnostics::Diagnosis, String> {
+    let agent = get_detected_agents()
+        .await?
+        .into_iter()
+        .find(|agent| agent.id == agent_id)
+        .ok_or_else(|| format!("Unknown agent: {}", agent_id))?;
+    let mut diagnosis = crate::diagnostics::Diagnosis::new(&agent.id, &agent.name);
+    let connect = Some(("connect-agent", "Reconnect"));
+
+    // Config entry
+    let report = validate_agent_config_internal(&agent.id)?;
+    let config_ok = report.valid && agent.connected;
+    if !agent.connected {
+        diagnosis.check("config", "Agent config", "failed", "No Engram entry".to_string());
+        diagnosis.cause(
+            1,
+            format!("{} isn't connected to Engram", agent.name),
+            format!("{} has no Engram entry.", report.config_path),
+            connect,
+        );
+    } else if !report.valid {
+        let errors: Vec<&str> = report
+            .findings
+            .iter()
+            .filter(|f| f.severity == "error")
+            .map(|f| f.message.as_str())
+            .collect();
+        diagnosis.check("config", "Agent config", "failed", errors.join("; "));
+        diagnosis.cause(
+            1,
+            format!("{}'s Engram entry is broken", agent.name),
+            errors.join("\n"),
+            connect,
+        );
+    } else {
+        let warnings: Vec<&str> = report
+            .findings
+            .iter()
+            .filter(|f| f.severity == "warning")
+            .map(|f| f.message.as_str())
+            .collect();
+        if warnings.is_empty() {
+            diagnosis.check("config", "Agent config", "passed", "Engram entry is valid".to_string());
+        } else {
+            diagnosis.check("config", "Agent config", "warning", warnings.join("; "));
+            diagnosis.cause(4, "The agent config has warnings".to_string(), warnings.join("\n"), None);
+        }
+    }
+
+    // Restart needed
+    let config_path = PathBuf::from(&report.config_path);
+    match (config_ok, crate::diagnostics::agent_restart_needed(&agent.id, &config_path)) {
+        (false, _) => diagnosis.check("restart", "Agent restart", "skipped", "Fix the config first".to_string()),
+        (true, None) => diagnosis.check(
+            "restart",
+            "Agent restart",
+            "passed",
+            format!("{} isn't running; it loads the config when it starts", agent.name),
+        ),
+        (true, Some(false)) => diagnosis.check(
+            "restart",
+            "Agent restart",
+            "passed",
+            format!("{} started after the config last changed", agent.name),
+        ),
+        (true, Some(true)) => {
+            diagnosis.check(
+                "restart",
+                "Agent restart",
+                "failed",
+                format!("{} started before the config last changed", agent.name),
+            );
+            diagnosis.cause(
+                2,
+                format!("{} needs a restart", agent.name),
+                format!(
+                    "Agents only read their MCP config at launch. Quit {} completely and reopen it.",
+                    agent.name
+                ),
+                None,
+            );
+        }
+    }
+
+    // Sidecar health
+    let status = state.status.lock().await.clone();
+    let port = *state.port.lock().await;
+    let healthy = matches!(status, SidecarStatus::Running)
+        && reqwest::Client::builder()
+            .timeout(Duration::from_secs(3))
+            .build()
+            .map_err(|e| e.to_string())?
+            .get(format!("http://localhost:{}/health", port))
+            .send()
+            .await
+            .is_ok_and(|resp| resp.status().is_success());
+    if healthy {
+        diagnosis.check("sidecar", "Engram server", "passed", format!("Healthy on port {}", port));
+    } else {
+        diagnosis.check("sidecar", "Engram server", "failed", format!("{:?}", status));
+        diagnosis.cause(
+            1,
+            "The Engram server isn't running".to_string(),
+            "Agents reach memories through the sidecar, which isn't responding.".to_string(),
+            Some(("restart-sidecar", "Restart Server")),
+        );
+    }
+
+    // MCP handshake with the agent's own entry
+    let entry = fs::read_to_string(&config_path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|config| config.get("mcpServers")?.get("engram").cloned());
+    match entry.filter(|_| config_ok) {
+        None => diagnosis.check("mcp", "MCP handshake", "skipped", "Fix the config first".to_string()),
+        Some(entry) => {
+            let result = crate::mcp::simulate_session(&entry).await;
+            if result.success {
+                diagnosis.check(
+                    "mcp",
+                    "MCP handshake",
+                    "passed",
+                    format!("{} tools available", result.tools.len()),
+                );
+            } else {
+                let error = result.error.unwrap_or_else(|| "no response".to_string());
+                diagnosis.check("mcp", "MCP handshake", "failed", format!("{}: {}", result.step, error));
+                diagnosis.cause(
+                    1,
+                    "The configured Engram command doesn't start".to_string(),
+                    format!("The MCP session failed at {}: {}", result.step, error),
+                    connect,
+                );
+            }
+        }
+    }
+
+    // Recent recalls
+    let db_path = database::active_db_path(&app).await?;
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let activity =
+        tokio::task::spawn_blocking(move || crate::diagnostics::recall_activity(&db_path, now_ms))
+            .await
+            .map_err(|e| e.to_string())?;
+    match activity {
+        Err(e) => diagnosis.check("recall", "Recent recalls", "skipped", e),
+        Ok((0, _)) => {
+            diagnosis.check("recall", "Recent recalls", "warning", "No memories saved yet".to_string());
+            diagnosis.cause(
+                3,
+                "There are no memories to recall".to_string(),
+                "Add a memory, or ask the agent to remember something, then try again.".to_string(),
+                None,
+            );
+        }
+        Ok((total, 0)) => {
+            diagnosis.check(
+                "recall",
+                "Recent recalls",
+                "warning",
+                format!("None of {} memories recalled in the last 7 days", total),
+            );
+            diagnosis.cause(
+                3,
+                format!("{} isn't calling engram_recall", agent.name),
+                "The tools are available but unused. Ask the agent to check Engram, or add an instruction to use it to your project rules.".to_string(),
+                None,
+            );
+        }
+        Ok((total, recalled)) => diagnosis.check(
+            "recall",
+            "Recent recalls",
+            "passed",
+            format!("{} of {} memories recalled in the last 7 days", recalled, total),
+        ),
+    }
+
+    Ok(diagnosis.finish())
+}
+
+#[tauri::command]
+pub async fn create_support_bundle(
+    app: tauri::AppHandle,
+    state: State<'_, SidecarState>,
+) -> Result<String, String> {
+    let diagnostics_dir = get_engram_data_dir()?.join("diagnostics");
+    fs::create_dir_all(&diagnostics_dir).map_err(|e| e.to_string())?;
+    let tz = timestamps::preferred_timezone(&read_preferences().unwrap_or_default().timezone);
+
+    let recent_logs = {
+        let logs = state.logs.lock().await;
+        logs.query(logs.next_seq().saturating_sub(500), 500, None)
+    };
+
+    // Link the most recent failed MCP simulations, embedding their contents
+    let mcp_failures = recent_reports(&diagnostics_dir, "mcp-simulation-", 10);
+
+    let bundle = serde_json::json!({
+        "createdAt": timestamps::now(&tz).to_rfc3339(),
+        "timezone": tz.name(),
+        "appVersion": app.package_info().version.to_string(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "sidecar": {
+            "status": state.status.lock().await.clone(),
+            "port": *state.port.lock().await,
+            "restartCount": *state.restart_count.lock().await,
+        },
+        "startup": crate::startup::timings(),
+        "recentLogs": recent_logs,
+        "mcpSimulationFailures": mcp_failures,
+        "frontendErrors": recent_reports(&diagnostics_dir, "frontend-error-", 10),
+    });
+
+    let bundle_path = diagnostics_dir.join(format!(
+        "support-bundle-{}.json",
+        timestamps::filename_timestamp(&tz)
+    ));
+    let content = serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())?;
+    fs::write(&bundle_path, content).map_err(|e| e.to_string())?;
+
+    Ok(bundle_path.to_string_lossy().to_string())
+}
+
+/// The activated license, or the community edition without one.
+#[tauri::command]
+pub async fn get_license_state() -> Result<crate::license::LicenseState, String> {
+    Ok(crate::license::state())
+}
+
+/// Verify a license key offline and store it.
+#[tauri::command]
+pub async fn activate_license(key: String) -> Result<crate::license::LicenseState, String> {
+    crate::license::activate(&key)
+}
+
+/// Switch the read-only present window for screen sharing on or off.
+#[tauri::command]
+pub async fn set_present_mode(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    crate::windows::set_present_mode(&app, enabled)
+}
+
+/// Tags that hide a memory in the present window.
+const PRESENT_MASKED_TAGS: &[&str] = &["private", "sensitive", "redact", "redacted"];
+
+/// A memory as the present window may show it. Masked memories keep only
+/// their category, so their content never reaches that webview.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresentedMemory {
+    pub id: String,
+    pub category: String,
+    pub entity: Option<String>,
+    /// `None` when masked.
+    pub content: Option<String>,
+    pub masked: bool,
+}
+
+impl PresentedMemory {
+    fn new(
+        id: String,
+        category: String,
+        entity: Option<String>,
+        content: String,
+        tags: &[String],
+    ) -> Self {
+        if present_masked(tags, &content) {
+            Self {
+                id,
+                category,
+                entity: None,
+                content: None,
+                masked: true,
+            }
+        } else {
+            Self {
+                id,
+                category,
+                entity,
+                content: Some(content),
+                masked: false,
+            }
+        }
+    }
+}
+
+/// Whether a memory is tagged or redacted as unfit for screen sharing.
+fn present_masked(tags: &[String], content: &str) -> bool {
+    tags.iter()
+        .any(|tag| PRESENT_MASKED_TAGS.iter().any(|masked| tag.eq_ignore_ascii_case(masked)))
+        || content.contains("[REDACTED]")
+}
+
+/// Recent memories, or those matching `query`, for the present window, with
+/// flagged memories masked before they leave the backend.
+#[tauri::command]
+pub async fn get_present_memories(
+    state: State<'_, SidecarState>,
+    query: Option<String>,
+) -> Result<Vec<PresentedMemory>, String> {
+    let port = *state.port.lock().await;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let query = query.unwrap_or_default();
+    if query.trim().is_empty() {
+        let url = format!("http://localhost:{}/api/memories", port);
+        let page: MemoryListResponse = client
+            .get(&url)
+            .query(&[("limit", 50)])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch memories: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to fetch memories: {}", e))?;
+        return Ok(page
+            .memories
+            .into_iter()
+            .map(|m| {
+                let category = m.category.unwrap_or_default();
+                PresentedMemory::new(m.id, category, m.entity, m.content, &m.tags)
+            })
+            .collect());
+    }
+
+    let url = format!("http://localhost:{}/api/memories/search", port);
+    let hits = client
+        .post(&url)
+        .json(&serde_json::json!({ "query": query.trim(), "limit": 20, "threshold": 0.3 }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to search memories: {}", e))?
+        .json::<MemorySearchResponse>()
+        .await
+        .map_err(|e| format!("Failed to search memories: {}", e))?
+        .memories;
+    Ok(hits
+        .into_iter()
+        .map(|m| PresentedMemory::new(m.id, m.category, m.entity, m.content, &m.tags))
+        .collect())
+}
+
+/// Open the API explorer. Only available in developer mode.
+#[tauri::command]
+pub async fn open_api_explorer(app: tauri::AppHandle) -> Result<(), String> {
+    if !read_preferences()?.developer_mode {
+        return Err("Turn on developer mode in Preferences to use the API explorer".to_string());
+    }
+    crate::windows::show_api_explorer(&app)
+}
+
+/// Send a request to the sidecar's REST API on behalf of the API explorer.
+/// `path` is relative to the sidecar, e.g. `/api/memories?limit=5`. Only
+/// available in developer mode.
+#[tauri::command]
+pub async fn api_request(
+    method: String,
+    path: String,
+    body: Option<serde_json::Value>,
+    state: State<'_, SidecarState>,
+) -> Result<ApiResponse, String> {
+    crate::windows::ensure_not_presenting()?;
+    if !read_preferences()?.developer_mode {
+        return Err("Developer mode is off".to_string());
+    }
+    if !path.starts_with('/') || path.starts_with("//") {
+        return Err(format!("Path must start with a single /: {}", path));
+    }
+    let method = reqwest::Method::from_bytes(method.trim().to_uppercase().as_bytes())
+        .map_err(|_| format!("Invalid method: {}", method))?;
+
+    let port = *state.port.lock().await;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(60))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut request = client.request(method, format!("http://localhost:{}{}", port, path));
+    if let Some(body) = body {
+        request = request.json(&body);
+    }
+
+    let started = Instant::now();
+    let resp = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach sidecar: {}", e))?;
+    let status = resp.status().as_u16();
+    let text = resp.text().await.map_err(|e| e.to_string())?;
+    let body = serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text));
+    Ok(ApiResponse {
+        status,
+        duration_ms: started.elapsed().as_millis() as u64,
+        body,
+    })
+}
+
+/// Run a DuckDB SQL query, e.g. memories per source per month, against the
+/// analytics copy of the memories rather than the live database. The copy
+/// has `memories` and `memory_tags` tables and is rebuilt hourly.
+#[tauri::command]
+pub async fn run_analytics_query(
+    app: tauri::AppHandle,
+    sql: String,
+) -> Result<crate::analytics::AnalyticsResult, String> {
+    crate::windows::ensure_not_presenting()?;
+    if !read_preferences()?.developer_mode {
+        return Err("Developer mode is off".to_string());
+    }
+    if sql.trim().is_empty() {
+        return Err("Enter a query".to_string());
+    }
+    crate::analytics::run_query(&app, sql).await
+}
+
+/// Reports in `diagnostics_dir` whose names start with `prefix`, newest
+/// first, with their contents embedded.
+fn recent_reports(diagnostics_dir: &PathBuf, prefix: &str, limit: usize) -> Vec<serde_json::Value> {
+    report_paths(diagnostics_dir, prefix)
+        .iter()
+        .rev()
+        .take(limit)
+        .map(|path| {
+            let report = fs::read_to_string(path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok());
+            serde_json::json!({ "path": path.to_string_lossy(), "report": report })
+        })
+        .collect()
+}
+
+/// Report files starting with `prefix`, oldest first (names end in a timestamp).
+fn report_paths(diagnostics_dir: &PathBuf, prefix: &str) -> Vec<PathBuf> {
+    let mut reports: Vec<PathBuf> = fs::read_dir(diagnostics_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .map_or(false, |n| n.to_string_lossy().starts_with(prefix))
+        })
+        .collect();
+    reports.sort();
+    reports
+}
+
+/// Record a dashboard JavaScript error in the desktop log and as a report in
+/// the diagnostics directory, with enough app state to make sense of it.
+/// Support bundles include the most recent reports.
+#[tauri::command]
+pub async fn report_frontend_error(
+    app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+    error: FrontendError,
+    state: State<'_, SidecarState>,
+) -> Result<(), String> {
+    let route = error.route.clone().unwrap_or_default();
+    crate::logging::write(
+        "webview",
+        &format!("{} in {} window at {}: {}", error.kind, window.label(), route, error.message),
+    );
+    if let Some(frame) = error.stack.as_deref().and_then(|s| s.lines().find(|l| l.contains("at "))) {
+        crate::logging::write("webview", &format!("  {}", frame.trim()));
+    }
+
+    let diagnostics_dir = get_engram_data_dir()?.join("diagnostics");
+    fs::create_dir_all(&diagnostics_dir).map_err(|e| e.to_string())?;
+    let tz = timestamps::preferred_timezone(&read_preferences().unwrap_or_default().timezone);
+    let report = serde_json::json!({
+        "createdAt": timestamps::now(&tz).to_rfc3339(),
+        "appVersion": app.package_info().version.to_string(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "window": window.label(),
+        "sidecar": {
+            "status": state.status.lock().await.clone(),
+            "port": *state.port.lock().await,
+            "restartCount": *state.restart_count.lock().await,
+        },
+        "error": error,
+    });
+
+    // Errors can repeat quickly; milliseconds keep reports from the same
+    // second apart
+    let existing = report_paths(&diagnostics_dir, "frontend-error-");
+    let path = diagnostics_dir.join(format!(
+        "frontend-error-{}-{:03}.json",
+        timestamps::filename_timestamp(&tz),
+        chrono::Utc::now().timestamp_subsec_millis()
+    ));
+    let content = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write report: {}", e))?;
+
+    let excess = (existing.len() + 1).saturating_sub(FRONTEND_ERROR_REPORTS_MAX);
+    for old in existing.iter().take(excess) {
+        let _ = fs::remove_file(old);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn complete_onboarding(
+    agents: Vec<String>,
+    _seed_options: SeedOptions,
+) -> Result<String, String> {
+    crate::windows::ensure_not_presenting()?;
+    let mut results = Vec::new();
+
+    for agent_id in &agents {
+        match configure_agent_internal(agent_id) {
+            Ok(msg) => results.push(msg),
+            Err(e) => results.push(format!("Failed to configure {}: {}", agent_id, e)),
+        }
+    }
+
+    // Mark onboarding as complete
+    let engram_dir = get_engram_data_dir()?;
+    fs::create_dir_all(&engram_dir).map_err(|e| e.to_string())?;
+    let marker = engram_dir.join(".desktop-initialized");
+    fs::write(&marker, "").map_err(|e| e.to_string())?;
+
+    Ok(results.join("\n"))
 }
 
 #[tauri::command]
 pub async fn get_preferences() -> Result<DesktopPreferences, String> {
+    let mut prefs = read_preferences()?;
+    prefs.rest_port = crate::profiles::port(&crate::profiles::active()).to_string();
+    Ok(prefs)
+}
+
+#[tauri::command]
+pub async fn save_preferences(
+    app: tauri::AppHandle,
+    mut prefs: DesktopPreferences,
+    state: State<'_, SidecarState>,
+) -> Result<crate::config_reload::ConfigApplyReport, String> {
+    crate::windows::ensure_not_presenting()?;
+    prefs.migrate();
+    crate::sidecar::parse_sidecar_args(&prefs.sidecar_args)?;
+    crate::tag_rules::validate(&prefs.tag_rules)?;
+    crate::service::validate(&prefs)?;
+    let port = crate::profiles::parse_port(&prefs.rest_port)?;
+    prefs.rest_port = port.to_string();
+    let profile = crate::profiles::active();
+    let mut previous = read_preferences().unwrap_or_default();
+    previous.rest_port = crate::profiles::port(&profile).to_string();
+    if prefs.rest_port != previous.rest_port {
+        crate::profiles::set_port(&profile, port)?;
+    }
+    if prefs.shortcuts != previous.shortcuts {
+        crate::presets::check_shortcuts(&prefs.shortcuts)?;
+        crate::windows::register_quick_add_shortcut(
+            &app,
+            &prefs.shortcuts.quick_add,
+            Some(&previous.shortcuts.quick_add),
+        )?;
+    }
+    write_preferences(&prefs)?;
+    if prefs.tray != previous.tray {
+        crate::tray::apply_layout(&app, &prefs)?;
+    }
+
+    crate::logging::configure(prefs.log_max_file_mb, prefs.log_retention_days);
+    state.health_settings.send_replace(prefs.health_check.clone());
+    // Takes effect when the sidecar restarts, which `config_reload` triggers
+    *state.port.lock().await = port;
+    if prefs.run_as_service != previous.run_as_service {
+        // Handing over restarts the sidecar, which applies everything else too
+        crate::service::switch(&app, prefs.run_as_service).await?;
+        return Ok(crate::config_reload::ConfigApplyReport::default());
+    }
+    crate::config_reload::apply(&app, &previous, &prefs).await
+}
+
+/// Use a user-supplied audio file as the save confirmation sound.
+/// Returns the new `notifications.memorySaved.sound` value.
+#[tauri::command]
+pub async fn set_custom_sound(path: String) -> Result<String, String> {
+    crate::windows::ensure_not_presenting()?;
+    let sound = crate::notifications::install_custom_sound(std::path::Path::new(&path))?;
+    let mut prefs = read_preferences().unwrap_or_default();
+    prefs.notifications.memory_saved.sound = sound.clone();
+    write_preferences(&prefs)?;
+    Ok(sound)
+}
+
+/// Play the configured custom save sound, for previewing it in preferences.
+#[tauri::command]
+pub async fn preview_save_sound() -> Result<(), String> {
+    let sound = read_preferences().unwrap_or_default().notifications.memory_saved.sound;
+    match sound.strip_prefix(crate::notifications::CUSTOM_SOUND_PREFIX) {
+        Some(file_name) => crate::notifications::play_custom_sound(file_name),
+        None => Err("No custom sound is configured".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn list_profiles() -> Result<Vec<crate::profiles::ProfileInfo>, String> {
+    crate::profiles::list()
+}
+
+/// Create a profile with its own data directory, preferences, and port.
+#[tauri::command]
+pub async fn create_profile(
+    app: tauri::AppHandle,
+    name: String,
+    port: Option<u16>,
+) -> Result<crate::profiles::ProfileInfo, String> {
+    crate::windows::ensure_not_presenting()?;
+    let profile = crate::profiles::create(&name, port)?;
+    // The tray's profile menu appears once a second profile exists
+    if let Err(e) = crate::tray::apply_layout(&app, &read_preferences().unwrap_or_default()) {
+        engram_log!("Failed to update tray menu: {}", e);
+    }
+    Ok(profile)
+}
+
+/// Restart the sidecar on another profile's data directory and port.
+#[tauri::command]
+pub async fn switch_profile(app: tauri::AppHandle, name: String) -> Result<(), String> {
+    crate::windows::ensure_not_presenting()?;
+    crate::profiles::switch(&app, &name).await
+}
+
+#[tauri::command]
+pub async fn list_presets() -> Result<Vec<crate::presets::Preset>, String> {
+    crate::presets::list()
+}
+
+/// Save the current shortcut, tray, and notification settings as a named preset.
+#[tauri::command]
+pub async fn save_preset(name: String) -> Result<crate::presets::Preset, String> {
+    crate::windows::ensure_not_presenting()?;
+    crate::presets::save_current(&name)
+}
+
+#[tauri::command]
+pub async fn apply_preset(app: tauri::AppHandle, name: String) -> Result<(), String> {
+    crate::windows::ensure_not_presenting()?;
+    crate::presets::apply(&app, &name)
+}
+
+#[tauri::command]
+pub async fn delete_preset(name: String) -> Result<(), String> {
+    crate::windows::ensure_not_presenting()?;
+    crate::presets::delete(&name)
+}
+
+/// Write a preset to the Desktop as a shareable JSON file. Returns its path.
+#[tauri::command]
+pub async fn export_preset(name: String) -> Result<String, String> {
+    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+    let path = crate::presets::export(&name, &home.join("Desktop"))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Store a preset shared by someone else. It is not applied; the report
+/// lists shortcut conflicts that would block applying it.
+#[tauri::command]
+pub async fn import_preset(contents: String) -> Result<crate::presets::PresetImport, String> {
+    crate::windows::ensure_not_presenting()?;
+    crate::presets::import(&contents)
+}
+
+/// Write the desktop preferences to `path`, or a file chosen in a save
+/// dialog, for `import_preferences` on another machine. Secrets live in the
+/// keychain and are not included. Returns the written path, or `None` if the
+/// dialog was cancelled.
+#[tauri::command]
+pub async fn export_preferences(
+    app: tauri::AppHandle,
+    path: Option<String>,
+) -> Result<Option<String>, String> {
+    let prefs = read_preferences()?;
+    let export_path = match path {
+        Some(path) => PathBuf::from(path),
+        None => match choose_save_path(&app, "Engram preferences.json", "json").await? {
+            Some(path) => path,
+            None => return Ok(None),
+        },
+    };
+    let file = PreferencesFile {
+        kind: PREFERENCES_FILE_KIND.to_string(),
+        version: PREFERENCES_FILE_VERSION,
+        preferences: serde_json::to_value(&prefs).map_err(|e| e.to_string())?,
+    };
+    let content = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+    fs::write(&export_path, content)
+        .map_err(|e| format!("Failed to save {}: {}", export_path.display(), e))?;
+    engram_log!("Exported preferences to {}", export_path.display());
+    Ok(Some(export_path.to_string_lossy().to_string()))
+}
+
+/// Validate and apply preferences written by `export_preferences`, as
+/// `save_preferences` would. Settings tied to this machine (developer
+/// sidecar paths, the last save folder) are kept, and custom sounds that
+/// aren't installed here fall back to the default.
+#[tauri::command]
+pub async fn import_preferences(
+    app: tauri::AppHandle,
+    path: String,
+    state: State<'_, SidecarState>,
+) -> Result<PreferencesImport, String> {
+    crate::windows::ensure_not_presenting()?;
+    let content = fs::read_to_string(path.trim())
+        .map_err(|e| format!("Failed to read {}: {}", path.trim(), e))?;
+    let file: PreferencesFile = serde_json::from_str(&content)
+        .map_err(|e| format!("Not an Engram preferences file: {}", e))?;
+    if file.kind != PREFERENCES_FILE_KIND {
+        return Err("Not an Engram preferences file".to_string());
+    }
+    if file.version > PREFERENCES_FILE_VERSION {
+        return Err(format!(
+            "Preferences were exported by a newer version of Engram (format {})",
+            file.version
+        ));
+    }
+
+    let mut warnings = Vec::new();
+    let known = serde_json::to_value(DesktopPreferences::default()).map_err(|e| e.to_string())?;
+    if let (Some(imported), Some(known)) = (file.preferences.as_object(), known.as_object()) {
+        for key in imported.keys().filter(|key| !known.contains_key(*key)) {
+            warnings.push(format!("Ignored unknown setting \"{}\"", key));
+        }
+    }
+    let mut prefs: DesktopPreferences = serde_json::from_value(file.preferences)
+        .map_err(|e| format!("Invalid preferences: {}", e))?;
+    let current = read_preferences().unwrap_or_default();
+    prefs.developer.node_path = current.developer.node_path;
+    prefs.developer.entry_script = current.developer.entry_script;
+    prefs.last_save_dir = current.last_save_dir;
+    warnings.extend(crate::presets::reset_missing_sounds(&mut prefs.notifications)?);
+
+    let applied = save_preferences(app, prefs, state).await?;
+    engram_log!("Imported preferences from {}", path.trim());
+    Ok(PreferencesImport { applied, warnings })
+}
+
+/// The LaunchAgent that opens Engram at login.
+#[cfg(target_os = "macos")]
+fn login_item_path() -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+    Ok(home.join("Library/LaunchAgents/com.engram.app.plist"))
+}
+
+#[tauri::command]
+pub async fn set_start_at_login(enabled: bool) -> Result<(), String> {
+    crate::windows::ensure_not_presenting()?;
+    #[cfg(target_os = "macos")]
+    {
+        let plist_path = login_item_path()?;
+        let plist_dir = plist_path.parent().ok_or("No parent directory")?;
+
+        if enabled {
+            fs::create_dir_all(plist_dir).map_err(|e| e.to_string())?;
+
+            let exe_path = std::env::current_exe()
+                .map_err(|e| format!("Could not determine executable path: {}", e))?;
+
+            let plist_content = format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>com.engram.app</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{}</string>
+    </array>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <false/>
+</dict>
+</plist>"#,
+                exe_path.display()
+            );
+
+            fs::write(&plist_path, plist_content).map_err(|e| e.to_string())?;
+        } else if plist_path.exists() {
+            fs::remove_file(&plist_path).map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write the memories matching `filter` (all of them by default) to `path`,
+/// or to a file chosen in a save dialog when no path is given. With `encrypt`,
+/// the file is encrypted with that password (age, scrypt). Returns the
+/// written path, or `None` if the dialog was cancelled.
+#[tauri::command]
+pub async fn export_data(
+    app: tauri::AppHandle,
+    format: Option<ExportFormat>,
+    path: Option<String>,
+    filter: Option<ExportFilter>,
+    encrypt: Option<String>,
+    state: State<'_, SidecarState>,
+) -> Result<Option<String>, String> {
+    crate::windows::ensure_not_presenting()?;
+    let format = format.unwrap_or_default();
+    let filter = filter.unwrap_or_default();
+    if encrypt.as_deref().is_some_and(str::is_empty) {
+        return Err("The export password can't be empty".to_string());
+    }
+    // Encrypted exports are age files, e.g. `Engram export ....json.age`
+    let extension = match encrypt {
+        Some(_) => format!("{}.age", format.extension()),
+        None => format.extension().to_string(),
+    };
+    let port = *state.port.lock().await;
+    let tz = timestamps::preferred_timezone(&read_preferences().unwrap_or_default().timezone);
+    let export_path = match path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let file_name = format!(
+                "Engram export {}.{}",
+                timestamps::filename_label(&tz),
+                extension
+            );
+            let filter_extension = if encrypt.is_some() { "age" } else { format.extension() };
+            match choose_save_path(&app, &file_name, filter_extension).await? {
+                Some(path) => path,
+                None => return Ok(None),
+            }
+        }
+    };
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    // Written beside the target and renamed at the end, so a failed export
+    // never leaves a truncated file under the chosen name
+    let partial_path = export_path.with_extension(format!("{}.partial", extension));
+    let file = fs::File::create(&partial_path)
+        .map_err(|e| format!("Failed to create {}: {}", partial_path.display(), e))?;
+    let result = match ExportOutput::new(file, encrypt.as_deref()) {
+        Ok(out) => write_export(&app, &client, port, format, &filter, tz, out).await,
+        Err(e) => Err(format!("Failed to encrypt export: {}", e)),
+    };
+    let exported = match result {
+        Ok(exported) => exported,
+        Err(e) => {
+            let _ = fs::remove_file(&partial_path);
+            return Err(e);
+        }
+    };
+    fs::rename(&partial_path, &export_path)
+        .map_err(|e| format!("Failed to save {}: {}", export_path.display(), e))?;
+
+    engram_log!("Exported {} memories to {}", exported, export_path.display());
+    Ok(Some(export_path.to_string_lossy().to_string()))
+}
+
+/// Write every memory to a standalone SQLite file (the memories table with
+/// its schema and indexes) at `path`, or one chosen in a save dialog, for
+/// data tools that read SQLite directly. Embeddings are left out unless
+/// `include_embeddings`. Returns the written path, or `None` if the dialog
+/// was cancelled.
+#[tauri::command]
+pub async fn export_sqlite(
+    app: tauri::AppHandle,
+    path: Option<String>,
+    include_embeddings: Option<bool>,
+) -> Result<Option<String>, String> {
+    crate::windows::ensure_not_presenting()?;
+    let tz = timestamps::preferred_timezone(&read_preferences().unwrap_or_default().timezone);
+    let export_path = match path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let file_name = format!("Engram export {}.db", timestamps::filename_label(&tz));
+            match choose_save_path(&app, &file_name, "db").await? {
+                Some(path) => path,
+                None => return Ok(None),
+            }
+        }
+    };
+    let db_path = database::active_db_path(&app).await?;
+
+    let partial_path = export_path.with_extension("db.partial");
+    let include_embeddings = include_embeddings.unwrap_or(false);
+    let copy_path = partial_path.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        database::export_memories(&db_path, &copy_path, include_embeddings)
+    })
+    .await
+    .unwrap_or_else(|e| Err(e.to_string()));
+    let exported = match result {
+        Ok(exported) => exported,
+        Err(e) => {
+            let _ = fs::remove_file(&partial_path);
+            return Err(e);
+        }
+    };
+    fs::rename(&partial_path, &export_path)
+        .map_err(|e| format!("Failed to save {}: {}", export_path.display(), e))?;
+
+    engram_log!("Exported {} memories to {}", exported, export_path.display());
+    Ok(Some(export_path.to_string_lossy().to_string()))
+}
+
+/// Fetches the memories matching a filter a page at a time for an export.
+struct ExportPager<'a> {
+    app: &'a tauri::AppHandle,
+    request: reqwest::RequestBuilder,
+    offset: u64,
+    /// The first page, fetched on open to learn the total.
+    pending: Option<Vec<serde_json::Value>>,
+    done: bool,
+    /// Offsets shift if memories are added mid-export; repeats are skipped by id
+    seen: std::collections::HashSet<String>,
+    total: u64,
+}
+
+impl<'a> ExportPager<'a> {
+    async fn open(
+        app: &'a tauri::AppHandle,
+        client: &reqwest::Client,
+        port: u16,
+        filter: &ExportFilter,
+    ) -> Result<ExportPager<'a>, String> {
+        let request = client
+            .get(format!("http://localhost:{}/api/memories", port))
+            .query(&filter.query_params());
+        let mut pager = ExportPager {
+            app,
+            request,
+            offset: 0,
+            pending: None,
+            done: false,
+            seen: std::collections::HashSet::new(),
+            total: 0,
+        };
+        let page = pager.fetch().await?;
+        pager.total = page["pagination"]["total"].as_u64().unwrap_or_default();
+        pager.pending = Some(page["memories"].as_array().cloned().unwrap_or_default());
+        Ok(pager)
+    }
+
+    async fn fetch(&self) -> Result<serde_json::Value, String> {
+        self.request
+            .try_clone()
+            .ok_or("Failed to build memories request")?
+            .query(&[("limit", EXPORT_PAGE_SIZE), ("offset", self.offset)])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch memories: {}", e))?
+            .json::<serde_json::Value>()
+            .await
+            .map_err(|e| format!("Failed to fetch memories: {}", e))
+    }
+
+    /// The next page's memories not seen before, or `None` at the end.
+    /// Emits `export-progress` for the previous page.
+    async fn next_page(&mut self) -> Result<Option<Vec<serde_json::Value>>, String> {
+        if self.offset > 0 {
+            let exported = self.seen.len() as u64;
+            let _ = self.app.emit(
+                "export-progress",
+                ExportProgress {
+                    exported,
+                    total: self.total.max(exported),
+                },
+            );
+        }
+        let memories = match self.pending.take() {
+            Some(memories) => memories,
+            None if self.done => return Ok(None),
+            None => {
+                let page = self.fetch().await?;
+                page["memories"].as_array().cloned().unwrap_or_default()
+            }
+        };
+        if memories.is_empty() {
+            return Ok(None);
+        }
+        self.offset += memories.len() as u64;
+        self.done = (memories.len() as u64) < EXPORT_PAGE_SIZE;
+        Ok(Some(
+            memories
+                .into_iter()
+                .filter(|m| self.seen.insert(m["id"].as_str().unwrap_or_default().to_string()))
+                .collect(),
+        ))
+    }
+}
+
+/// Page through the memories matching `filter` and write them to `out`, emitting
+/// `export-progress` after each page. Returns how many were written.
+async fn write_export(
+    app: &tauri::AppHandle,
+    client: &reqwest::Client,
+    port: u16,
+    format: ExportFormat,
+    filter: &ExportFilter,
+    tz: chrono_tz::Tz,
+    out: ExportOutput,
+) -> Result<u64, String> {
+    let mut pager = ExportPager::open(app, client, port, filter).await?;
+    let mut writer = ExportWriter::begin(out, format, tz, pager.total)
+        .map_err(|e| format!("Failed to write export: {}", e))?;
+    while let Some(memories) = pager.next_page().await? {
+        for memory in &memories {
+            writer
+                .write(memory)
+                .map_err(|e| format!("Failed to write export: {}", e))?;
+        }
+    }
+    let (exported, out) = writer
+        .finish()
+        .map_err(|e| format!("Failed to write export: {}", e))?;
+    out.finish().map_err(|e| format!("Failed to write export: {}", e))?;
+    Ok(exported)
+}
+
+/// Write the memories matching `filter` as an Obsidian vault: one Markdown
+/// note with YAML frontmatter per memory, in an `Engram` folder inside
+/// `path` or a folder chosen in a dialog. Notes from a previous export are
+/// replaced. Returns the notes folder, or `None` if the dialog was cancelled.
+#[tauri::command]
+pub async fn export_obsidian_vault(
+    app: tauri::AppHandle,
+    path: Option<String>,
+    filter: Option<ExportFilter>,
+    state: State<'_, SidecarState>,
+) -> Result<Option<String>, String> {
+    crate::windows::ensure_not_presenting()?;
+    let filter = filter.unwrap_or_default();
+    let port = *state.port.lock().await;
+    let tz = timestamps::preferred_timezone(&read_preferences().unwrap_or_default().timezone);
+    let vault = match path {
+        Some(path) => PathBuf::from(path),
+        None => match choose_folder(&app, "Export to Obsidian Vault").await? {
+            Some(path) => path,
+            None => return Ok(None),
+        },
+    };
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let notes_dir = vault.join("Engram");
+    let mut writer = VaultWriter::create(&notes_dir, tz)
+        .map_err(|e| format!("Failed to create {}: {}", notes_dir.display(), e))?;
+    let mut pager = ExportPager::open(&app, &client, port, &filter).await?;
+    while let Some(memories) = pager.next_page().await? {
+        for memory in &memories {
+            writer
+                .write(memory)
+                .map_err(|e| format!("Failed to write note: {}", e))?;
+        }
+    }
+
+    engram_log!("Exported {} notes to {}", writer.written(), notes_dir.display());
+    Ok(Some(notes_dir.to_string_lossy().to_string()))
+}
+
+/// Write the memories matching `filter` to a Parquet file (id, content,
+/// tags, created_at, source) at `path`, or one chosen in a save dialog, for
+/// loading into pandas or DuckDB. Returns the written path, or `None` if
+/// the dialog was cancelled.
+#[tauri::command]
+pub async fn export_parquet(
+    app: tauri::AppHandle,
+    path: Option<String>,
+    filter: Option<ExportFilter>,
+    state: State<'_, SidecarState>,
+) -> Result<Option<String>, String> {
+    crate::windows::ensure_not_presenting()?;
+    let filter = filter.unwrap_or_default();
+    let port = *state.port.lock().await;
+    let tz = timestamps::preferred_timezone(&read_preferences().unwrap_or_default().timezone);
+    let export_path = match path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let file_name = format!("Engram export {}.parquet", timestamps::filename_label(&tz));
+            match choose_save_path(&app, &file_name, "parquet").await? {
+                Some(path) => path,
+                None => return Ok(None),
+            }
+        }
+    };
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let partial_path = export_path.with_extension("parquet.partial");
+    let file = fs::File::create(&partial_path)
+        .map_err(|e| format!("Failed to create {}: {}", partial_path.display(), e))?;
+    let result = async {
+        let mut writer =
+            ParquetWriter::create(file).map_err(|e| format!("Failed to write export: {}", e))?;
+        let mut pager = ExportPager::open(&app, &client, port, &filter).await?;
+        while let Some(memories) = pager.next_page().await? {
+            writer
+                .write_page(&memories)
+                .map_err(|e| format!("Failed to write export: {}", e))?;
+        }
+        writer.finish().map_err(|e| format!("Failed to write export: {}", e))
+    }
+    .await;
+    let exported = match result {
+        Ok(exported) => exported,
+        Err(e) => {
+            let _ = fs::remove_file(&partial_path);
+            return Err(e);
+        }
+    };
+    fs::rename(&partial_path, &export_path)
+        .map_err(|e| format!("Failed to save {}: {}", export_path.display(), e))?;
+
+    engram_log!("Exported {} memories to {}", exported, export_path.display());
+    Ok(Some(export_path.to_string_lossy().to_string()))
+}
+
+/// Ask where to save `file_name`, starting in the folder last saved to, or
+/// the Desktop. The chosen folder is remembered. `None` if the dialog was
+/// cancelled.
+async fn choose_save_path(
+    app: &tauri::AppHandle,
+    file_name: &str,
+    extension: &str,
+) -> Result<Option<PathBuf>, String> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let mut prefs = read_preferences().unwrap_or_default();
+    let last_dir = Some(PathBuf::from(&prefs.last_save_dir))
+        .filter(|dir| !prefs.last_save_dir.is_empty() && dir.is_dir());
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let mut dialog = app
+        .dialog()
+        .file()
+        .set_title("Export Memories")
+        .set_file_name(file_name)
+        .add_filter(extension.to_uppercase(), &[extension]);
+    if let Some(dir) = last_dir.or_else(dirs::desktop_dir) {
+        dialog = dialog.set_directory(dir);
+    }
+    dialog.save_file(move |path| {
+        let _ = tx.send(path);
+    });
+    let path = match rx.await.map_err(|e| e.to_string())? {
+        Some(path) => path
+            .into_path()
+            .map_err(|e| format!("Invalid save location: {}", e))?,
+        None => return Ok(None),
+    };
+
+    if let Some(dir) = path.parent() {
+        let dir = dir.to_string_lossy().to_string();
+        if dir != prefs.last_save_dir {
+            prefs.last_save_dir = dir;
+            if let Err(e) = write_preferences(&prefs) {
+                engram_log!("Failed to remember save folder: {}", e);
+            }
+        }
+    }
+    Ok(Some(path))
+}
+
+/// Ask for a folder, starting in the Documents folder. `None` if the dialog
+/// was cancelled.
+async fn choose_folder(app: &tauri::AppHandle, title: &str) -> Result<Option<PathBuf>, String> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let mut dialog = app.dialog().file().set_title(title);
+    if let Some(documents) = dirs::document_dir() {
+        dialog = dialog.set_directory(documents);
+    }
+    dialog.pick_folder(move |path| {
+        let _ = tx.send(path);
+    });
+    match rx.await.map_err(|e| e.to_string())? {
+        Some(path) => path
+            .into_path()
+            .map(Some)
+            .map_err(|e| format!("Invalid folder: {}", e)),
+        None => Ok(None),
+    }
+}
+
+/// Issue the single-use token `reset_database` requires, so a stray call
+/// can't wipe the database. Valid for `RESET_TOKEN_TTL`.
+#[tauri::command]
+pub async fn request_reset() -> Result<String, String> {
+    crate::windows::ensure_not_presenting()?;
+    let seed = format!(
+        "{:?}-{}-{:?}",
+        std::time::SystemTime::now(),
+        std::process::id(),
+        Instant::now()
+    );
+    let token: String = sha256(seed.as_bytes())
+        .iter()
+        .take(16)
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    let mut pending = RESET_TOKEN.lock().map_err(|e| e.to_string())?;
+    *pending = Some((token.clone(), Instant::now()));
+    Ok(token)
+}
+
+/// Delete the database and start over with an empty one. Needs a token from
+/// `request_reset`; the database is kept as a `pre-reset` snapshot first.
+#[tauri::command]
+pub async fn reset_database(app: tauri::AppHandle, token: String) -> Result<(), String> {
+    crate::windows::ensure_not_presenting()?;
+    {
+        let mut pending = RESET_TOKEN.lock().map_err(|e| e.to_string())?;
+        match pending.take() {
+            Some((expected, issued)) if expected == token && issued.elapsed() < RESET_TOKEN_TTL => {}
+            Some((_, issued)) if issued.elapsed() >= RESET_TOKEN_TTL => {
+                return Err("The reset confirmation expired; try again".to_string());
+            }
+            _ => return Err("Reset was not confirmed".to_string()),
+        }
+    }
+    crate::sidecar::ensure_compatible(&app).await?;
+    crate::sidecar::stop(&app).await?;
+
+    let engram_dir = get_engram_data_dir()?;
+    let db_path = engram_dir.join("memory.db");
+    if db_path.exists() {
+        let snapshot_path = db_path.clone();
+        let snapshot =
+            tokio::task::spawn_blocking(move || crate::backups::create(&snapshot_path, "pre-reset"))
+                .await
+                .unwrap_or_else(|e| Err(e.to_string()));
+        match snapshot {
+            Ok(backup) => engram_log!("Database kept as snapshot {} before reset", backup.path),
+            Err(e) => {
+                // Never delete without a copy; bring the sidecar back instead
+                crate::sidecar::start(&app).await?;
+                return Err(format!("Reset cancelled, the database could not be backed up: {}", e));
+            }
+        }
+        fs::remove_file(&db_path)
+            .map_err(|e| format!("Failed to delete database: {}", e))?;
+    }
+    // Also delete WAL and SHM files
+    let _ = fs::remove_file(engram_dir.join("memory.db-wal"));
+    let _ = fs::remove_file(engram_dir.join("memory.db-shm"));
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    crate::sidecar::start(&app).await
+}
+
+/// What `factory_reset` removed, and what it couldn't.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FactoryResetReport {
+    pub removed: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+impl FactoryResetReport {
+    fn record(&mut self, what: String, result: Result<(), String>) {
+        match result {
+            Ok(()) => self.removed.push(what),
+            Err(e) => self.failed.push(format!("{}: {}", what, e)),
+        }
+    }
+}
+
+/// Remove everything Engram has installed: the Engram entry from every
+/// agent config, shell hooks, the login item and launchd service, and the
+/// whole data directory with every profile's memories. Only runs when
+/// `confirmation` is `FACTORY_RESET_CONFIRMATION` typed out. The sidecar is
+/// left stopped; the app should quit afterwards.
+#[tauri::command]
+pub async fn factory_reset(
+    app: tauri::AppHandle,
+    confirmation: String,
+) -> Result<FactoryResetReport, String> {
+    crate::windows::ensure_not_presenting()?;
+    if confirmation.trim() != FACTORY_RESET_CONFIRMATION {
+        return Err(format!(
+            "Type {} to confirm the factory reset",
+            FACTORY_RESET_CONFIRMATION
+        ));
+    }
+    engram_log!("Factory reset requested");
+    crate::sidecar::stop(&app).await?;
+
+    let mut report = FactoryResetReport::default();
+    for agent in get_detected_agents().await? {
+        if agent.connected {
+            report.record(
+                format!("Engram entry in {}", agent.config_path),
+                remove_engram_entry(&agent.id),
+            );
+        }
+    }
+    for shell in crate::shell_hooks::installed() {
+        let result = crate::shell_hooks::uninstall(shell).map(|_| ());
+        report.record(format!("{} shell hook", shell), result);
+    }
+
+    if crate::service::installed() {
+        report.record("Engram launchd service".to_string(), crate::service::uninstall());
+    }
+    #[cfg(target_os = "macos")]
+    {
+        if login_item_path().is_ok_and(|path| path.exists()) {
+            report.record(
+                "Start at login LaunchAgent".to_string(),
+                set_start_at_login(false).await,
+            );
+        }
+    }
+
+    let root = crate::profiles::root_dir()?;
+    let mut data_dirs = vec![root];
+    // On Linux both the XDG directory and a legacy ~/.engram can exist
+    if let Some(legacy) = dirs::home_dir().map(|home| home.join(".engram")) {
+        if !data_dirs.contains(&legacy) {
+            data_dirs.push(legacy);
+        }
+    }
+    for dir in data_dirs.into_iter().filter(|dir| dir.exists()) {
+        let result = fs::remove_dir_all(&dir).map_err(|e| e.to_string());
+        report.record(dir.to_string_lossy().to_string(), result);
+    }
+    Ok(report)
+}
+
+/// Restore a file written by `export_data`. The file is read in full before
+/// anything is deleted, so a bad file leaves the database untouched.
+#[tauri::command]
+pub async fn import_data(
+    app: tauri::AppHandle,
+    path: String,
+    mode: ImportMode,
+    state: State<'_, SidecarState>,
+) -> Result<ImportSummary, String> {
+    crate::windows::ensure_not_presenting()?;
+    crate::sidecar::ensure_compatible(&app).await?;
+    let port = *state.port.lock().await;
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let export: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid export file: {}", e))?;
+    let records = export
+        .get("memories")
+        .unwrap_or(&export)
+        .as_array()
+        .ok_or("Not an Engram export: no memories array")?;
+    let memories: Vec<serde_json::Value> = records
+        .iter()
+        .filter(|m| m["content"].as_str().is_some_and(|c| !c.trim().is_empty()))
+        .map(|m| {
+            serde_json::json!({
+                "content": m["content"],
+                "category": m["category"],
+                "entity": m["entity"],
+                "confidence": m["confidence"],
+                "namespace": m["namespace"],
+                "tags": m["tags"],
+                "source": m["source"],
+            })
+        })
+        .collect();
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(300))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    if matches!(mode, ImportMode::Replace) {
+        let existing = list_memories(&client, port, &[]).await?;
+        let ids: Vec<&str> = existing.iter().map(|m| m.id.as_str()).collect();
+        for chunk in ids.chunks(EXPORT_PAGE_SIZE as usize) {
+            let url = format!("http://localhost:{}/api/memories/bulk-delete", port);
+            let resp = client
+                .post(&url)
+                .json(&serde_json::json!({ "ids": chunk }))
+                .send()
+                .await
+                .map_err(|e| format!("Failed to delete existing memories: {}", e))?;
+            if !resp.status().is_success() {
+                let error = resp.text().await.unwrap_or_default();
+                return Err(format!("Failed to delete existing memories: {}", error));
+            }
+        }
+        // Memories stored by an agent mid-delete would otherwise be merged with the import
+        let remaining = list_memories(&client, port, &[]).await?.len();
+        if remaining > 0 {
+            return Err(format!(
+                "{} existing memories could not be deleted; nothing was imported",
+                remaining
+            ));
+        }
+        if !ids.is_empty() {
+            engram_log!("Deleted {} memories before import", ids.len());
+        }
+    }
+
+    let total = memories.len() as u64;
+    let mut summary = ImportSummary {
+        found: records.len() as u64,
+        ..ImportSummary::default()
+    };
+    let mut processed = 0;
+    let _ = app.emit("import-progress", ImportProgress { processed, total });
+    for batch in memories.chunks(IMPORT_BATCH_SIZE) {
+        let result = commit_import(&client, port, batch).await?;
+        summary.created += result.created;
+        summary.duplicates += result.duplicates;
+        summary.merged += result.merged;
+        summary.rejected += result.rejected;
+        summary.errors.extend(result.errors);
+        processed += batch.len() as u64;
+        let _ = app.emit("import-progress", ImportProgress { processed, total });
+    }
+
+    engram_log!(
+        "Imported {} of {} memories from {}",
+        summary.created, summary.found, path
+    );
+    notify(
+        &app,
+        NotificationEvent::ImportComplete,
+        "Import complete",
+        &format!("Imported {} of {} memories", summary.created, summary.found),
+    );
+    Ok(summary)
+}
+
+/// Sign the memories in `ids` into a shareable pack with `metadata`,
+/// written to `path` or a file chosen in a save dialog. Returns the written
+/// path, or `None` if the dialog was cancelled.
+#[tauri::command]
+pub async fn create_memory_pack(
+    app: tauri::AppHandle,
+    ids: Vec<String>,
+    metadata: crate::packs::PackMetadata,
+    path: Option<String>,
+    state: State<'_, SidecarState>,
+) -> Result<Option<String>, String> {
+    crate::windows::ensure_not_presenting()?;
+    crate::sidecar::ensure_compatible(&app).await?;
+    let port = *state.port.lock().await;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut memories = Vec::new();
+    for id in &ids {
+        let url = format!("http://localhost:{}/api/memories/{}", port, id);
+        let resp = client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch memory {}: {}", id, e))?;
+        if !resp.status().is_success() {
+            return Err(format!("Memory {} not found", id));
+        }
+        let body: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
+        let memory: crate::packs::PackMemory = serde_json::from_value(serde_json::json!({
+            "originalId": body["memory"]["id"],
+            "content": body["memory"]["content"],
+            "category": body["memory"]["category"],
+            "entity": body["memory"]["entity"],
+            "confidence": body["memory"]["confidence"],
+            "namespace": body["memory"]["namespace"],
+            "tags": body["memory"]["tags"],
+            "createdAt": body["memory"]["createdAt"],
+        }))
+        .map_err(|e| format!("Unexpected memory {}: {}", id, e))?;
+        memories.push(memory);
+    }
+
+    let pack_path = match path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let tz = timestamps::preferred_timezone(&read_preferences().unwrap_or_default().timezone);
+            let file_name = format!(
+                "Engram pack {}.{}",
+                timestamps::filename_label(&tz),
+                crate::packs::PACK_EXTENSION
+            );
+            match choose_save_path(&app, &file_name, crate::packs::PACK_EXTENSION).await? {
+                Some(path) => path,
+                None => return Ok(None),
+            }
+        }
+    };
+    let version = app.package_info().version.to_string();
+    crate::packs::create(&pack_path, metadata, memories, &version)?;
+    engram_log!("Wrote memory pack of {} memories to {}", ids.len(), pack_path.display());
+    Ok(Some(pack_path.to_string_lossy().to_string()))
+}
+
+/// Verify the pack at `path` and list what it contains, without importing.
+#[tauri::command]
+pub async fn preview_memory_pack(path: String) -> Result<crate::packs::PackPreview, String> {
+    crate::packs::read(&PathBuf::from(path))
+}
+
+/// Merge a verified pack into the database through the import pipeline, so
+/// memories already present are skipped as duplicates. Imported memories
+/// keep the pack title as their source.
+#[tauri::command]
+pub async fn import_memory_pack(
+    app: tauri::AppHandle,
+    path: String,
+    state: State<'_, SidecarState>,
+) -> Result<ImportSummary, String> {
+    crate::windows::ensure_not_presenting()?;
+    crate::sidecar::ensure_compatible(&app).await?;
+    let pack = crate::packs::read(&PathBuf::from(&path))?;
+    let port = *state.port.lock().await;
+    let source = format!("pack:{}", pack.metadata.title);
+    let memories: Vec<serde_json::Value> = pack
+        .memories
+        .iter()
+        .map(|m| {
+            serde_json::json!({
+                "content": m.content,
+                "category": m.category,
+                "entity": m.entity,
+                "confidence": m.confidence,
+                "namespace": m.namespace,
+                "tags": m.tags,
+                "source": source,
+            })
+        })
+        .collect();
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(300))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut summary = ImportSummary {
+        found: memories.len() as u64,
+        ..ImportSummary::default()
+    };
+    for batch in memories.chunks(IMPORT_BATCH_SIZE) {
+        let result = commit_import(&client, port, batch).await?;
+        summary.created += result.created;
+        summary.duplicates += result.duplicates;
+        summary.merged += result.merged;
+        summary.rejected += result.rejected;
+        summary.errors.extend(result.errors);
+    }
+    engram_log!(
+        "Imported {} of {} memories from pack \"{}\" (signed by {})",
+        summary.created, summary.found, pack.metadata.title, pack.signer
+    );
+    Ok(summary)
+}
+
+/// Memory counts by tag, source, and week for the statistics view.
+#[tauri::command]
+pub async fn get_stats(app: tauri::AppHandle) -> Result<database::MemoryStats, String> {
+    let db_path = database::active_db_path(&app).await?;
+    let tz = timestamps::preferred_timezone(&read_preferences().unwrap_or_default().timezone);
+    tokio::task::spawn_blocking(move || database::memory_stats(&db_path, &tz))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Memories created per day from `from` to `to` (local `YYYY-MM-DD` dates,
+/// inclusive), counted from the database so the dashboard can draw activity
+/// graphs without fetching the memories. `to` defaults to today and `from`
+/// to 30 days before it.
+#[tauri::command]
+pub async fn get_memory_timeline(
+    app: tauri::AppHandle,
+    from: Option<String>,
+    to: Option<String>,
+) -> Result<Vec<database::DayCount>, String> {
+    let tz = timestamps::preferred_timezone(&read_preferences().unwrap_or_default().timezone);
+    let parse = |date: &str| {
+        chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date \"{}\", expected YYYY-MM-DD", date.trim()))
+    };
+    let to = match to {
+        Some(to) => parse(&to)?,
+        None => timestamps::now(&tz).date_naive(),
+    };
+    let from = match from {
+        Some(from) => parse(&from)?,
+        None => to - chrono::Duration::days(29),
+    };
+    if from > to {
+        return Err("The start date is after the end date".to_string());
+    }
+    if (to - from).num_days() >= database::TIMELINE_MAX_DAYS {
+        return Err(format!(
+            "The range can be at most {} days",
+            database::TIMELINE_MAX_DAYS
+        ));
+    }
+
+    let db_path = database::active_db_path(&app).await?;
+    tokio::task::spawn_blocking(move || database::memory_timeline(&db_path, &tz, from, to))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Current database size, growth rate, and when it will reach the next size milestone.
+#[tauri::command]
+pub async fn get_disk_usage(app: tauri::AppHandle) -> Result<database::DiskUsageTrend, String> {
+    let db_path = database::active_db_path(&app).await?;
+    let now = chrono::Utc::now().timestamp();
+    tokio::task::spawn_blocking(move || database::disk_usage_trend(&db_path, now))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Fold the memories from another Engram database (e.g. one copied from an
+/// old laptop) into the current one. The sidecar is stopped while merging,
+/// and the current database is kept as a `pre-merge` snapshot first.
+#[tauri::command]
+pub async fn merge_databases(
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<database::MergeReport, String> {
+    crate::windows::ensure_not_presenting()?;
+    crate::sidecar::ensure_compatible(&app).await?;
+    let db_path = database::active_db_path(&app).await?;
+    crate::sidecar::stop(&app).await?;
+
+    let other = PathBuf::from(path.trim());
+    let result = tokio::task::spawn_blocking(move || {
+        crate::backups::create(&db_path, "pre-merge")
+            .map_err(|e| format!("Merge cancelled, the database could not be backed up: {}", e))?;
+        database::merge_memories(&db_path, &other)
+    })
+    .await
+    .unwrap_or_else(|e| Err(e.to_string()));
+
+    crate::sidecar::start(&app).await?;
+    let report = result?;
+    engram_log!(
+        "Merged {} of {} memories from {} ({} already present, {} duplicates)",
+        report.inserted,
+        report.found,
+        path.trim(),
+        report.skipped_ids,
+        report.skipped_duplicates
+    );
+    Ok(report)
+}
+
+/// Compact the database now rather than waiting for scheduled maintenance.
+/// The sidecar is stopped while `VACUUM` runs and started again after.
+#[tauri::command]
+pub async fn vacuum_database(app: tauri::AppHandle) -> Result<database::VacuumReport, String> {
+    crate::windows::ensure_not_presenting()?;
+    crate::sidecar::ensure_compatible(&app).await?;
+    let report = crate::maintenance::run_maintenance(&app).await?;
+    engram_log!("Database compacted, reclaimed {} bytes", report.reclaimed_bytes);
+    Ok(report)
+}
+
+/// Snapshot the database into the backups directory as `<name>-<timestamp>.db`.
+#[tauri::command]
+pub async fn backup_database(
+    app: tauri::AppHandle,
+    name: String,
+) -> Result<crate::backups::BackupInfo, String> {
+    let db_path = database::active_db_path(&app).await?;
+    let backup = tokio::task::spawn_blocking(move || crate::backups::create(&db_path, &name))
+        .await
+        .map_err(|e| e.to_string())??;
+    engram_log!("Database snapshot created at: {}", backup.path);
+    Ok(backup)
+}
+
+/// Database snapshots, newest first.
+#[tauri::command]
+pub async fn list_backups() -> Result<Vec<crate::backups::BackupInfo>, String> {
+    crate::backups::list()
+}
+
+/// Open the active profile's data directory (database, logs, backups) in
+/// Finder or Explorer. Returns the directory.
+#[tauri::command]
+pub async fn open_data_dir(app: tauri::AppHandle) -> Result<String, String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let data_dir = get_engram_data_dir()?;
+    fs::create_dir_all(&data_dir)
+        .map_err(|e| format!("Failed to create {}: {}", data_dir.display(), e))?;
+    app.opener()
+        .open_path(data_dir.to_string_lossy(), None::<&str>)
+        .map_err(|e| format!("Failed to open {}: {}", data_dir.display(), e))?;
+    Ok(data_dir.to_string_lossy().to_string())
+}
+
+/// Stop the sidecar, replace the database with a snapshot, and start the
+/// sidecar again. The current database is snapshotted as `pre-restore`
+/// first, and the sidecar is restarted even if the restore fails.
+#[tauri::command]
+pub async fn restore_backup(app: tauri::AppHandle, file_name: String) -> Result<(), String> {
+    crate::windows::ensure_not_presenting()?;
+    let backup = crate::backups::find(&file_name)?;
+    let db_path = database::active_db_path(&app).await?;
+    crate::sidecar::stop(&app).await?;
+
+    let result = tokio::task::spawn_blocking(move || {
+        if db_path.exists() {
+            crate::backups::create(&db_path, "pre-restore")?;
+        }
+        crate::backups::restore(&backup, &db_path)
+    })
+    .await
+    .unwrap_or_else(|e| Err(e.to_string()));
+
+    crate::sidecar::start(&app).await?;
+    if result.is_ok() {
+        engram_log!("Database restored from snapshot {}", file_name);
+        let _ = app.emit("database-restored", &file_name);
+    }
+    result
+}
+
+/// Group likely low-value memories (very short, stale, never recalled,
+/// near-duplicates) into batches the dashboard can archive in bulk.
+#[tauri::command]
+pub async fn get_cleanup_suggestions(
+    state: State<'_, SidecarState>,
+) -> Result<crate::cleanup::CleanupSuggestions, String> {
+    crate::windows::ensure_not_presenting()?;
+    let port = *state.port.lock().await;
+    // Duplicate detection compares every pair of embeddings
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(60))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let get = |path: &str| {
+        let url = format!("http://localhost:{}{}", port, path);
+        let client = client.clone();
+        async move {
+            let resp = client
+                .get(&url)
+                .send()
+                .await
+                .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+            resp.json::<serde_json::Value>()
+                .await
+                .map_err(|e| e.to_string())
+        }
+    };
+    let (memories, stale, never_recalled, duplicates) = tokio::try_join!(
+        list_memories(&client, port, &[]),
+        get("/api/analytics/stale?days=90&limit=500"),
+        get("/api/analytics/never-recalled?limit=500"),
+        get("/api/analytics/duplicates"),
+    )?;
+
+    let array = |value: &serde_json::Value, key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default()
+    };
+    Ok(crate::cleanup::suggest(&crate::cleanup::CleanupInputs {
+        memories: memories
+            .iter()
+            .filter_map(|m| serde_json::to_value(m).ok())
+            .collect(),
+        stale: array(&stale, "items"),
+        never_recalled: array(&never_recalled, "items"),
+        duplicate_clusters: array(&duplicates, "clusters"),
+    }))
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveReport {
+    pub archived: u64,
+    /// JSON file holding the archived memories, for restoring them later.
+    pub path: String,
+}
+
+/// Save memories to ~/.engram/archive, then delete them from the database.
+#[tauri::command]
+pub async fn archive_memories(
+    app: tauri::AppHandle,
+    ids: Vec<String>,
+    state: State<'_, SidecarState>,
+) -> Result<ArchiveReport, String> {
+    crate::windows::ensure_not_presenting()?;
+    if ids.is_empty() {
+        return Err("No memories selected".to_string());
+    }
+    crate::sidecar::ensure_compatible(&app).await?;
+    let port = *state.port.lock().await;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut memories = Vec::new();
+    for id in &ids {
+        match fetch_memory(&client, port, id).await {
+            Ok(memory) => memories.push(memory),
+            Err(e) => engram_log!("Skipping memory {} in archive: {}", id, e),
+        }
+    }
+    if memories.is_empty() {
+        return Err("None of the selected memories were found".to_string());
+    }
+
+    let tz = timestamps::preferred_timezone(&read_preferences().unwrap_or_default().timezone);
+    let archive_dir = get_engram_data_dir()?.join("archive");
+    fs::create_dir_all(&archive_dir).map_err(|e| e.to_string())?;
+    let path = archive_dir.join(format!("archive-{}.json", timestamps::filename_timestamp(&tz)));
+    let content = serde_json::to_string_pretty(&serde_json::json!({
+        "archivedAt": timestamps::now(&tz).to_rfc3339(),
+        "memories": memories,
+    }))
+    .map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write archive: {}", e))?;
+
+    let archived_ids: Vec<&str> = memories.iter().map(|m| m.id.as_str()).collect();
+    let url = format!("http://localhost:{}/api/memories/bulk-delete", port);
+    let result: serde_json::Value = client
+        .post(&url)
+        .json(&serde_json::json!({ "ids": archived_ids }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to delete archived memories: {}", e))?
+        .json()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let archived = result.get("deleted").and_then(|d| d.as_u64()).unwrap_or(0);
+    engram_log!("Archived {} memories to {}", archived, path.display());
+    Ok(ArchiveReport {
+        archived,
+        path: path.to_string_lossy().to_string(),
+    })
+}
+
+/// Stop the sidecar and keep it stopped until it is started or restarted explicitly.
+#[tauri::command]
+pub async fn stop_sidecar(app: tauri::AppHandle) -> Result<(), String> {
+    crate::sidecar::stop(&app).await
+}
+
+#[tauri::command]
+pub async fn restart_sidecar(app: tauri::AppHandle) -> Result<(), String> {
+    crate::sidecar::restart(&app).await
+}
+
+/// Stop the sidecar without it counting as a crash, e.g. during a demo.
+/// Capture stays off until `resume_sidecar`.
+#[tauri::command]
+pub async fn pause_sidecar(app: tauri::AppHandle) -> Result<(), String> {
+    crate::sidecar::pause(&app).await
+}
+
+#[tauri::command]
+pub async fn resume_sidecar(app: tauri::AppHandle) -> Result<(), String> {
+    crate::sidecar::resume(&app).await
+}
+
+/// Reset the crash counter so a sidecar that gave up auto-restarting will try again.
+#[tauri::command]
+pub async fn clear_restart_failures(app: tauri::AppHandle) -> Result<(), String> {
+    crate::sidecar::clear_restart_failures(&app).await
+}
+
+#[tauri::command]
+pub async fn append_to_last_memory(
+    app: tauri::AppHandle,
+    text: String,
+    state: State<'_, SidecarState>,
+    quick_add: State<'_, QuickAddState>,
+) -> Result<String, String> {
+    crate::windows::ensure_not_presenting()?;
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Memory content is empty".to_string());
+    }
+
+    let port = *state.port.lock().await;
+    let prefs = read_preferences().unwrap_or_default();
+    let window = Duration::from_secs(prefs.append_window_minutes * 60);
+    let tag_rules = CompiledRules::new(&prefs.tag_rules);
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut last_capture = quick_add.last_capture.lock().await;
+    let previous_id = match last_capture.as_ref() {
+        Some((id, captured_at)) if captured_at.elapsed() <= window => Some(id.clone()),
+        _ => None,
+    };
+
+    let previous = match previous_id {
+        Some(id) => fetch_memory(&client, port, &id).await.ok(),
+        None => None,
+    };
+
+    // Appending updates the previous memory in place, keeping its id,
+    // creation time, and recall history
+    let memory_id = match previous {
+        Some(prev) => {
+            let mut body = serde_json::json!({
+                "content": format!("{}\n{}", prev.content, text),
+                "tags": prev.tags,
+            });
+            prepare_ingest(&prefs, &tag_rules, &mut body, QUICK_ADD_SOURCE);
+            update_memory(&client, port, &prev.id, &body).await?;
+            prev.id
+        }
+        None => {
+            let mut body = serde_json::json!({ "content": text });
+            prepare_ingest(&prefs, &tag_rules, &mut body, QUICK_ADD_SOURCE);
+            create_memory(&client, port, &body).await?
+        }
+    };
+
+    *last_capture = Some((memory_id.clone(), Instant::now()));
+    notify(&app, NotificationEvent::MemorySaved, "Memory saved", text);
+    Ok(memory_id)
+}
+
+/// Save a memory from the quick-add panel or a global-shortcut capture
+/// without going through the webview's REST calls. The shell's ingest steps
+/// run first; then the save notification (and its sound) and a
+/// `memory-added` event follow. Returns the new memory's id.
+#[tauri::command]
+pub async fn add_memory(
+    app: tauri::AppHandle,
+    content: String,
+    tags: Option<Vec<String>>,
+    source: Option<String>,
+    category: Option<String>,
+    entity: Option<String>,
+) -> Result<String, String> {
+    crate::windows::ensure_not_presenting()?;
+    ingest_memory(
+        &app,
+        &content,
+        tags.unwrap_or_default(),
+        source.as_deref(),
+        category,
+        entity,
+    )
+    .await
+}
+
+/// Store a capture from the shell: normalize and tag it per preferences,
+/// save it through the sidecar (which redacts secrets and skips
+/// duplicates), and announce it. Shared by quick add and the capture
+/// endpoint.
+pub async fn ingest_memory(
+    app: &tauri::AppHandle,
+    content: &str,
+    tags: Vec<String>,
+    source: Option<&str>,
+    category: Option<String>,
+    entity: Option<String>,
+) -> Result<String, String> {
+    let content = content.trim();
+    if content.is_empty() {
+        return Err("Memory content is empty".to_string());
+    }
+
+    let port = *app.state::<SidecarState>().port.lock().await;
+    let prefs = read_preferences().unwrap_or_default();
+    let tag_rules = CompiledRules::new(&prefs.tag_rules);
+    let mut body = serde_json::json!({
+        "content": content,
+        "tags": tags,
+        "source": source,
+        "category": category,
+        "entity": entity.filter(|e| !e.trim().is_empty()),
+    });
+    if let Some(fields) = body.as_object_mut() {
+        fields.retain(|_, v| !v.is_null());
+    }
+    prepare_ingest(&prefs, &tag_rules, &mut body, source.unwrap_or(QUICK_ADD_SOURCE));
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let memory_id = create_memory(&client, port, &body).await?;
+
+    notify(app, NotificationEvent::MemorySaved, "Memory saved", content);
+    let _ = app.emit(
+        "memory-added",
+        serde_json::json!({ "id": memory_id, "source": body["source"] }),
+    );
+    Ok(memory_id)
+}
+
+/// Size the quick-add panel to its rendered content (logical pixels).
+#[tauri::command]
+pub fn fit_quick_add_window(app: tauri::AppHandle, width: f64, height: f64) {
+    crate::windows::fit_quick_add(&app, width, height);
+}
+
+#[tauri::command]
+pub fn hide_quick_add_window(app: tauri::AppHandle) {
+    crate::windows::hide_quick_add(&app);
+}
+
+#[tauri::command]
+pub async fn get_sidecar_logs(
+    state: State<'_, SidecarState>,
+    offset: Option<u64>,
+    limit: Option<usize>,
+    filter: Option<String>,
+) -> Result<SidecarLogs, String> {
+    let logs = state.logs.lock().await;
+    let limit = limit.unwrap_or(500);
+    let filter = filter.as_deref().filter(|f| !f.is_empty());
+    let lines = logs.query(offset.unwrap_or(0), limit, filter);
+
+    // If the page was cut short by the limit, resume right after the last line returned
+    let next_offset = match lines.last() {
+        Some(last) if lines.len() == limit => last.seq + 1,
+        _ => logs.next_seq(),
+    };
+
+    Ok(SidecarLogs { lines, next_offset })
+}
+
+/// Search memories through the sidecar. `tags` keeps results with all of
+/// the given tags. Repeated searches within `SEARCH_CACHE_TTL` are answered
+/// from the cache.
+#[tauri::command]
+pub async fn search_memories(
+    state: State<'_, SidecarState>,
+    query: String,
+    limit: Option<usize>,
+    tags: Option<Vec<String>>,
+    threshold: Option<f64>,
+) -> Result<Vec<MemorySearchResult>, String> {
+    crate::windows::ensure_not_presenting()?;
+    let port = *state.port.lock().await;
+    let query = query.trim().to_string();
+    let limit = limit.unwrap_or(5);
+    let threshold = threshold.unwrap_or(0.3);
+    let mut tags = tags.unwrap_or_default();
+    tags.sort();
+    tags.dedup();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let key: SearchKey = (port, query.clone(), limit, threshold.to_bits(), tags.clone());
+    if let Ok(mut cache) = SEARCH_CACHE.lock() {
+        cache.retain(|(_, at, _)| at.elapsed() < SEARCH_CACHE_TTL);
+        if let Some((_, _, results)) = cache.iter().find(|(cached, _, _)| *cached == key) {
+            return Ok(results.clone());
+        }
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+    // The sidecar can't filter by tag, so over-fetch when filtering
+    let fetch_limit = if tags.is_empty() { limit } else { limit * 5 };
+    let url = format!("http://localhost:{}/api/memories/search", port);
+    let resp = client
+        .post(&url)
+        .json(&serde_json::json!({ "query": query, "limit": fetch_limit, "threshold": threshold }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to search memories: {}", e))?;
+    if !resp.status().is_success() {
+        return Err(format!("Failed to search memories: HTTP {}", resp.status()));
+    }
+    let mut results = resp
+        .json::<MemorySearchResponse>()
+        .await
+        .map_err(|e| format!("Invalid search response: {}", e))?
+        .memories;
+    results.retain(|memory| tags.iter().all(|tag| memory.tags.contains(tag)));
+    results.truncate(limit);
+
+    if let Ok(mut cache) = SEARCH_CACHE.lock() {
+        cache.push((key, Instant::now(), results.clone()));
+        let excess = cache.len().saturating_sub(SEARCH_CACHE_SIZE);
+        cache.drain(..excess);
+    }
+    Ok(results)
+}
+
+/// Rank a query's results under the saved search settings and under `settings`
+/// side by side, so the preferences UI can show the effect before saving.
+#[tauri::command]
+pub async fn preview_search_settings(
+    state: State<'_, SidecarState>,
+    query: String,
+    settings: SearchSettings,
+    limit: Option<usize>,
+) -> Result<SearchPreview, String> {
+    crate::windows::ensure_not_presenting()?;
+    let port = *state.port.lock().await;
+    let limit = limit.unwrap_or(10);
+    let current_settings = read_preferences().unwrap_or_default().search;
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    // The sidecar ranks each search with the given weights; `preview` keeps
+    // these searches from counting as accesses
+    let url = format!("http://localhost:{}/api/memories/search", port);
+    let rank = |settings: &SearchSettings| {
+        let request = client.post(&url).json(&serde_json::json!({
+            "query": query,
+            "limit": limit,
+            "weights": settings,
+            "preview": true,
+        }));
+        async move {
+            let hits = request
+                .send()
+                .await
+                .map_err(|e| format!("Failed to search memories: {}", e))?
+                .json::<SearchResponse>()
+                .await
+                .map_err(|e| e.to_string())?
+                .memories;
+            Ok::<_, String>(
+                hits.into_iter()
+                    .map(|hit| RankedMemory {
+                        id: hit.id,
+                        content: hit.content,
+                        score: hit.score,
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        }
+    };
+    let (current, proposed) = tokio::try_join!(rank(&current_settings), rank(&settings))?;
+
+    Ok(SearchPreview {
+        current,
+        proposed,
+        query,
+    })
+}
+
+/// Import every note in an Apple Notes folder, one memory per note.
+#[tauri::command]
+pub async fn import_from_apple_notes(
+    app: tauri::AppHandle,
+    folder: String,
+    state: State<'_, SidecarState>,
+) -> Result<ImportSummary, String> {
+    crate::windows::ensure_not_presenting()?;
+    let port = *state.port.lock().await;
+    let notes = tokio::task::spawn_blocking(move || crate::apple_notes::read_folder(&folder))
+        .await
+        .map_err(|e| e.to_string())??;
+    if notes.is_empty() {
+        return Ok(ImportSummary::default());
+    }
+
+    let prefs = read_preferences().unwrap_or_default();
+    let tag_rules = CompiledRules::new(&prefs.tag_rules);
+    let memories: Vec<serde_json::Value> = notes
+        .iter()
+        .filter(|note| !note.markdown.is_empty() || !note.attachments.is_empty())
+        .map(|note| {
+            let mut content = format!("From Apple Notes ({}): {}", note.title, note.markdown);
+            if content.chars().count() > APPLE_NOTE_MAX_CHARS {
+                content = content.chars().take(APPLE_NOTE_MAX_CHARS).collect::<String>() + "…";
+            }
+            if !note.attachments.is_empty() {
+                content.push_str(&format!("\n\nAttachments: {}", note.attachments.join(", ")));
+            }
+            let mut tags = vec!["apple-notes".to_string()];
+            if let Some(date) = note.modified.get(..10) {
+                tags.push(date.to_string());
+            }
+            let mut memory = serde_json::json!({
+                "content": content,
+                "category": "fact",
+                "confidence": 0.75,
+                "tags": tags,
+                "source": "import:apple-notes",
+            });
+            prepare_ingest(&prefs, &tag_rules, &mut memory, "import:apple-notes");
+            memory
+        })
+        .collect();
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(300))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut summary = commit_import(&client, port, &memories).await?;
+    summary.found = notes.len() as u64;
+    engram_log!(
+        "Imported {} of {} Apple Notes",
+        summary.created, summary.found
+    );
+    notify(
+        &app,
+        NotificationEvent::ImportComplete,
+        "Apple Notes import complete",
+        &format!("Imported {} of {} notes", summary.created, summary.found),
+    );
+    Ok(summary)
+}
+
+/// A capture after the shell's ingest pipeline, for the quick-add panel to save.
+#[derive(Debug, Serialize)]
+pub struct PreparedCapture {
+    pub content: String,
+    pub tags: Vec<String>,
+    /// Where the capture came from, when the frontmost app was recorded.
+    pub source: Option<String>,
+}
+
+/// Run a quick-add capture through normalization (if enabled) and the tag
+/// rules before the panel saves it.
+#[tauri::command]
+pub fn prepare_capture(content: String, source: Option<String>) -> PreparedCapture {
+    let prefs = read_preferences().unwrap_or_default();
+    let rules = CompiledRules::new(&prefs.tag_rules);
+    let mut body = serde_json::json!({ "content": content });
+    prepare_ingest(&prefs, &rules, &mut body, source.as_deref().unwrap_or(QUICK_ADD_SOURCE));
+    PreparedCapture {
+        content: body["content"].as_str().unwrap_or_default().to_string(),
+        tags: serde_json::from_value(body["tags"].clone()).unwrap_or_default(),
+        source: body["source"].as_str().map(str::to_string),
+    }
+}
+
+/// The shell's ingest steps for a memory body about to be stored: optional
+/// normalization, which tags non-English content with `lang:<code>`, then
+/// the tag rules, plus the active session's tag and, for quick-add captures,
+/// the meeting in progress and the app they were made in.
+fn prepare_ingest(
+    prefs: &DesktopPreferences,
+    rules: &CompiledRules,
+    body: &mut serde_json::Value,
+    source: &str,
+) {
+    if prefs.normalize_ingest {
+        let content = body.get("content").and_then(|c| c.as_str()).unwrap_or_default();
+        let normalized = crate::normalize::normalize(content);
+        body["content"] = serde_json::json!(normalized.content);
+        if let Some(language) = normalized.language.filter(|code| *code != "eng") {
+            let mut tags: Vec<String> =
+                serde_json::from_value(body["tags"].clone()).unwrap_or_default();
+            let tag = format!("lang:{}", language);
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+            body["tags"] = serde_json::json!(tags);
+        }
+    }
+    rules.apply_to_body(body, source, None);
+    if let Some(session) = crate::sessions::active() {
+        let mut tags: Vec<String> =
+            serde_json::from_value(body["tags"].clone()).unwrap_or_default();
+        if !tags.contains(&session.tag()) {
+            tags.push(session.tag());
+        }
+        body["tags"] = serde_json::json!(tags);
+    }
+    if prefs.calendar_context && source == QUICK_ADD_SOURCE {
+        if let Some(meeting) = crate::calendar::current_meeting() {
+            let mut tags: Vec<String> =
+                serde_json::from_value(body["tags"].clone()).unwrap_or_default();
+            let tag = format!("meeting:{}", meeting);
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+            body["tags"] = serde_json::json!(tags);
+        }
+    }
+    if prefs.capture_frontmost_app && source == QUICK_ADD_SOURCE {
+        if let Some(app) = crate::frontmost::recorded() {
+            let mut tags: Vec<String> =
+                serde_json::from_value(body["tags"].clone()).unwrap_or_default();
+            if !tags.contains(&app.tag()) {
+                tags.push(app.tag());
+            }
+            body["tags"] = serde_json::json!(tags);
+            body["source"] = serde_json::json!(format!("{}:{}", QUICK_ADD_SOURCE, app.bundle_id));
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_calendar_access() -> Result<crate::calendar::CalendarAccess, String> {
+    tokio::task::spawn_blocking(crate::calendar::access)
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Ask for calendar access (showing the system prompt the first time) before
+/// calendar context is turned on, and load the current meeting if granted.
+#[tauri::command]
+pub async fn request_calendar_access() -> Result<crate::calendar::CalendarAccess, String> {
+    tokio::task::spawn_blocking(|| {
+        let access = crate::calendar::request_access()?;
+        if access == crate::calendar::CalendarAccess::Granted {
+            if let Err(e) = crate::calendar::refresh() {
+                engram_log!("Calendar refresh failed: {}", e);
+            }
+        }
+        Ok(access)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Apply the tag rules to memories already stored. `scope` is "all",
+/// "untagged", or "namespace:<name>". Tags are only ever added.
+#[tauri::command]
+pub async fn apply_tag_rules(
+    scope: String,
+    state: State<'_, SidecarState>,
+) -> Result<TagRulesReport, String> {
+    crate::windows::ensure_not_presenting()?;
+    let scope = TagRuleScope::parse(&scope)?;
+    let prefs = read_preferences().unwrap_or_default();
+    let rules = CompiledRules::new(&prefs.tag_rules);
+    let port = *state.port.lock().await;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let query: Vec<(&str, String)> = scope
+        .namespace()
+        .map(|namespace| ("namespace", namespace.to_string()))
+        .into_iter()
+        .collect();
+    let memories = list_memories(&client, port, &query).await?;
+
+    let mut report = TagRulesReport::default();
+    for memory in memories.iter().filter(|m| scope.includes(&m.tags)) {
+        report.scanned += 1;
+        let matched = rules.tags_for(&TagContext {
+            content: &memory.content,
+            source: memory.source.as_deref().unwrap_or_default(),
+            path: None,
+        });
+        let mut tags = memory.tags.clone();
+        for tag in matched {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        if tags.len() == memory.tags.len() {
+            continue;
+        }
+        match update_memory_tags(&client, port, &memory.id, &tags).await {
+            Ok(()) => report.updated += 1,
+            Err(e) => {
+                engram_log!("Failed to tag memory {}: {}", memory.id, e);
+                report.failed += 1;
+            }
+        }
+    }
+    engram_log!(
+        "Tag rules updated {} of {} memories ({} failed)",
+        report.updated, report.scanned, report.failed
+    );
+    Ok(report)
+}
+
+/// Every tag in use with how many memories carry it, most used first.
+#[tauri::command]
+pub async fn list_tags(
+    state: State<'_, SidecarState>,
+) -> Result<Vec<database::CountEntry>, String> {
+    let port = *state.port.lock().await;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let url = format!("http://localhost:{}/api/tags", port);
+    let resp = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch tags: {}", e))?;
+    if !resp.status().is_success() {
+        let error = resp.text().await.unwrap_or_default();
+        return Err(format!("Failed to fetch tags: {}", error));
+    }
+    let listing: TagListResponse = resp.json().await.map_err(|e| e.to_string())?;
+    Ok(listing.tags)
+}
+
+/// Rename `from` to `to` on every memory carrying it.
+#[tauri::command]
+pub async fn rename_tag(
+    from: String,
+    to: String,
+    state: State<'_, SidecarState>,
+) -> Result<TagEditReport, String> {
+    crate::windows::ensure_not_presenting()?;
+    let body = serde_json::json!({ "from": valid_tag(&from)?, "to": valid_tag(&to)? });
+    let port = *state.port.lock().await;
+    let report = edit_tags(port, "rename", &body).await?;
+    engram_log!("Renamed tag {} to {} on {} memories", from.trim(), to.trim(), report.updated);
+    Ok(report)
+}
+
+/// Replace each of `sources` with `target` on every memory carrying it, so
+/// near-duplicate tags collapse into one.
+#[tauri::command]
+pub async fn merge_tags(
+    sources: Vec<String>,
+    target: String,
+    state: State<'_, SidecarState>,
+) -> Result<TagEditReport, String> {
+    crate::windows::ensure_not_presenting()?;
+    let sources = sources
+        .iter()
+        .map(|source| valid_tag(source))
+        .collect::<Result<Vec<_>, _>>()?;
+    let target = valid_tag(&target)?;
+    let body = serde_json::json!({ "sources": sources, "target": target });
+    let port = *state.port.lock().await;
+    let report = edit_tags(port, "merge", &body).await?;
+    engram_log!(
+        "Merged tags {} into {} on {} memories",
+        sources.join(", "),
+        target,
+        report.updated
+    );
+    Ok(report)
+}
+
+/// Remove `tag` from every memory carrying it. The memories are kept.
+#[tauri::command]
+pub async fn delete_tag(
+    tag: String,
+    state: State<'_, SidecarState>,
+) -> Result<TagEditReport, String> {
+    crate::windows::ensure_not_presenting()?;
+    let tag = valid_tag(&tag)?;
+    let port = *state.port.lock().await;
+    let report = edit_tags(port, "delete", &serde_json::json!({ "tag": tag })).await?;
+    engram_log!("Removed tag {} from {} memories", tag, report.updated);
+    Ok(report)
+}
+
+/// Which node binary and entry script the sidecar would start with, and why.
+#[tauri::command]
+pub async fn get_sidecar_resolution(
+    app: tauri::AppHandle,
+) -> Result<crate::sidecar::SidecarResolution, String> {
+    let prefs = read_preferences().unwrap_or_default();
+    crate::sidecar::resolve_sidecar(&app, &prefs)
+}
+
+/// How long each phase of this launch took, for spotting startup regressions.
+#[tauri::command]
+pub async fn get_startup_timings() -> Result<crate::startup::StartupTimings, String> {
+    Ok(crate::startup::timings())
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionReport {
+    pub label: String,
+    pub duration_secs: u64,
+    /// Memories created during the session, all now carrying its tag.
+    pub memories: usize,
+    /// The summary memory, unless nothing was captured.
+    pub summary_id: Option<String>,
+}
+
+/// Start tagging captures with `session:<label>` until `end_session`.
+#[tauri::command]
+pub async fn start_session(label: String) -> Result<crate::sessions::Session, String> {
+    crate::windows::ensure_not_presenting()?;
+    crate::sessions::start(&label)
+}
+
+#[tauri::command]
+pub async fn get_session() -> Result<Option<crate::sessions::Session>, String> {
+    Ok(crate::sessions::active())
+}
+
+/// End the active session: tag every memory created since it started
+/// (including ones agents stored directly), then store a summary of them.
+#[tauri::command]
+pub async fn end_session(state: State<'_, SidecarState>) -> Result<SessionReport, String> {
+    crate::windows::ensure_not_presenting()?;
+    let session = crate::sessions::active().ok_or("No session is active")?;
+    let tag = session.tag();
+    let port = *state.port.lock().await;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let captured = list_memories(
+        &client,
+        port,
+        &[("since", session.started_at.to_string())],
+    )
+    .await?;
+    for memory in captured.iter().filter(|m| !m.tags.contains(&tag)) {
+        let mut tags = memory.tags.clone();
+        tags.push(tag.clone());
+        if let Err(e) = update_memory_tags(&client, port, &memory.id, &tags).await {
+            engram_log!("Failed to tag memory {} with {}: {}", memory.id, tag, e);
+        }
+    }
+
+    let duration_secs = session.elapsed_secs();
+    let mut summary_id = None;
+    if !captured.is_empty() {
+        let ids: Vec<&str> = captured.iter().map(|m| m.id.as_str()).collect();
+        let url = format!("http://localhost:{}/api/summarize", port);
+        let resp = client
+            .post(&url)
+            .json(&serde_json::json!({ "memoryIds": ids }))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to summarize session: {}", e))?;
+        if !resp.status().is_success() {
+            let error = resp.text().await.unwrap_or_default();
+            return Err(format!("Failed to summarize session: {}", error));
+        }
+        let result: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
+        let summary = result["summary"].as_str().unwrap_or_default();
+        let body = serde_json::json!({
+            "content": format!(
+                "Session \"{}\" ({}, {} memories): {}",
+                session.label,
+                crate::sessions::format_duration(duration_secs),
+                captured.len(),
+                summary
+            ),
+            "tags": [tag.clone(), "session-summary"],
+        });
+        summary_id = Some(create_memory(&client, port, &body).await?);
+    }
+
+    crate::sessions::clear()?;
+    engram_log!(
+        "Ended session \"{}\" with {} memories",
+        session.label,
+        captured.len()
+    );
+    Ok(SessionReport {
+        label: session.label,
+        duration_secs,
+        memories: captured.len(),
+        summary_id,
+    })
+}
+
+/// Compare the running sidecar bundle with the latest release's.
+#[tauri::command]
+pub async fn check_sidecar_update(
+    app: tauri::AppHandle,
+) -> Result<crate::bundle_update::SidecarUpdateInfo, String> {
+    crate::bundle_update::check(&app).await
+}
+
+/// Compare the running sidecar with the latest npm release, with a
+/// summarized changelog of what is new.
+#[tauri::command]
+pub async fn check_sidecar_release(
+    app: tauri::AppHandle,
+) -> Result<crate::bundle_update::SidecarReleaseInfo, String> {
+    crate::bundle_update::check_release(&app).await
+}
+
+/// Install the latest verified sidecar bundle and restart on it.
+#[tauri::command]
+pub async fn apply_sidecar_update(app: tauri::AppHandle) -> Result<String, String> {
+    crate::windows::ensure_not_presenting()?;
+    crate::bundle_update::apply(&app).await
+}
+
+/// Outcome of one Notion sync run.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotionSyncReport {
+    pub pulled: ImportSummary,
+    pub pushed: usize,
+}
+
+/// Save the Notion sync settings, storing `token` in the keychain if given
+/// (an empty token removes it). When enabled, the token and database are
+/// checked against Notion first; returns the database's title.
+#[tauri::command]
+pub async fn configure_notion_sync(
+    mut settings: NotionSyncSettings,
+    token: Option<String>,
+) -> Result<Option<String>, String> {
+    crate::windows::ensure_not_presenting()?;
+    settings.database_id = crate::notion::normalize_database_id(&settings.database_id);
+    if let Some(token) = token.as_deref().map(str::trim) {
+        if token.is_empty() {
+            crate::notion::clear_token()?;
+        } else {
+            crate::notion::set_token(token)?;
+        }
+    }
+
+    let mut database_title = None;
+    if settings.enabled {
+        if settings.database_id.is_empty() {
+            return Err("Enter the Notion database to sync with".to_string());
+        }
+        let client = crate::notion::NotionClient::from_keychain()?;
+        let (title, _) = client.database(&settings.database_id).await?;
+        database_title = Some(title);
+    }
+
+    let mut prefs = read_preferences().unwrap_or_default();
+    prefs.notion = settings;
+    write_preferences(&prefs)?;
+    Ok(database_title)
+}
+
+/// Sync with Notion now instead of waiting for the schedule.
+#[tauri::command]
+pub async fn run_notion_sync(app: tauri::AppHandle) -> Result<NotionSyncReport, String> {
+    crate::windows::ensure_not_presenting()?;
+    sync_notion(&app).await
+}
+
+/// Pull pages created since the last run as memories, then push pinned
+/// memories that have no page yet if `push_pinned` is set. Pages created
+/// by the push are never pulled back.
+pub async fn sync_notion(app: &tauri::AppHandle) -> Result<NotionSyncReport, String> {
+    let prefs = read_preferences().unwrap_or_default();
+    let settings = prefs.notion.clone();
+    if settings.database_id.is_empty() {
+        return Err("No Notion database is configured".to_string());
+    }
+    let port = *app.state::<SidecarState>().port.lock().await;
+    let notion = crate::notion::NotionClient::from_keychain()?;
+    let mut sync = crate::notion::load_state(&settings.database_id);
+    let mut report = NotionSyncReport::default();
+
+    let pushed_pages: Vec<&String> = sync.pushed_memories.values().collect();
+    let pages: Vec<crate::notion::NotionPage> = notion
+        .pages_since(&settings.database_id, sync.pulled_until.as_deref())
+        .await?
+        .into_iter()
+        .filter(|page| !sync.pulled_pages.contains(&page.id) && !pushed_pages.contains(&&page.id))
+        .collect();
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(300))
+        .build()
+        .map_err(|e| e.to_string())?;
+    if !pages.is_empty() {
+        let tag_rules = CompiledRules::new(&prefs.tag_rules);
+        let mut memories = Vec::new();
+        for page in &pages {
+            let text = notion.page_text(&page.id).await?;
+            if page.title.is_empty() && text.is_empty() {
+                continue;
+            }
+            let content = truncate_chars(
+                format!("From Notion ({}): {}", page.title, text),
+                NOTION_PAGE_MAX_CHARS,
+            );
+            let mut memory = serde_json::json!({
+                "content": content.trim_end(),
+                "category": "fact",
+                "confidence": 0.75,
+                "tags": ["notion"],
+                "source": NOTION_SOURCE,
+            });
+            prepare_ingest(&prefs, &tag_rules, &mut memory, NOTION_SOURCE);
+            memories.push(memory);
+        }
+        if !memories.is_empty() {
+            report.pulled = commit_import(&client, port, &memories).await?;
+        }
+        report.pulled.found = pages.len() as u64;
+        for page in &pages {
+            sync.pulled_pages.insert(page.id.clone());
+        }
+        sync.pulled_until = pages.last().map(|page| page.created_time.clone());
+        crate::notion::save_state(&sync)?;
+    }
+
+    if settings.push_pinned {
+        let tagged = list_memories(&client, port, &[("tags", PINNED_TAG.to_string())]).await?;
+        let pinned: Vec<&MemoryRecord> = tagged
+            .iter()
+            .filter(|m| m.source.as_deref() != Some(NOTION_SOURCE))
+            .filter(|m| !sync.pushed_memories.contains_key(&m.id))
+            .collect();
+        if !pinned.is_empty() {
+            let (_, title_property) = notion.database(&settings.database_id).await?;
+            for memory in pinned {
+                let title = crate::notion::page_title(&memory.content);
+                let page_id = notion
+                    .create_page(&settings.database_id, &title_property, &title, &memory.content)
+                    .await?;
+                sync.pushed_memories.insert(memory.id.clone(), page_id);
+                report.pushed += 1;
+                crate::notion::save_state(&sync)?;
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Outcome of one issue enrichment run.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueEnrichmentReport {
+    /// Memories that mention at least one issue key.
+    pub memories: usize,
+    /// Distinct issues found in the tracker.
+    pub issues: usize,
+    /// Keys the tracker has no issue for.
+    pub missing: usize,
+}
+
+/// Save the issue tracker settings, storing `token` in the keychain for the
+/// selected provider if given (an empty token removes it). When enabled, the
+/// credentials are checked first; returns the account name.
+#[tauri::command]
+pub async fn configure_issue_tracker(
+    mut settings: IssueTrackerSettings,
+    token: Option<String>,
+) -> Result<Option<String>, String> {
+    crate::windows::ensure_not_presenting()?;
+    settings.project_keys = settings
+        .project_keys
+        .iter()
+        .map(|key| key.trim().to_uppercase())
+        .filter(|key| !key.is_empty())
+        .collect();
+    if let Some(token) = token.as_deref().map(str::trim) {
+        if token.is_empty() {
+            crate::issue_tracker::clear_token(settings.provider)?;
+        } else {
+            crate::issue_tracker::set_token(settings.provider, token)?;
+        }
+    }
+
+    let mut account = None;
+    if settings.enabled {
+        let client = crate::issue_tracker::IssueClient::from_settings(&settings)?;
+        account = Some(client.verify().await?);
+    }
+
+    let mut prefs = read_preferences().unwrap_or_default();
+    prefs.issue_tracker = settings;
+    write_preferences(&prefs)?;
+    Ok(account)
+}
+
+/// Refresh issue context now instead of waiting for the schedule.
+#[tauri::command]
+pub async fn run_issue_enrichment(
+    app: tauri::AppHandle,
+) -> Result<IssueEnrichmentReport, String> {
+    crate::windows::ensure_not_presenting()?;
+    enrich_issue_refs(&app).await
+}
+
+/// Look up every issue key mentioned in memories and store the issues'
+/// current title and status as the memories' refs.
+pub async fn enrich_issue_refs(app: &tauri::AppHandle) -> Result<IssueEnrichmentReport, String> {
+    let settings = read_preferences().unwrap_or_default().issue_tracker;
+    let tracker = crate::issue_tracker::IssueClient::from_settings(&settings)?;
+    let port = *app.state::<SidecarState>().port.lock().await;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let memories = list_memories(&client, port, &[]).await?;
+
+    let mentions: Vec<(&MemoryRecord, Vec<String>)> = memories
+        .iter()
+        .map(|m| (m, crate::issue_tracker::issue_keys(&m.content, &settings.project_keys)))
+        .filter(|(_, keys)| !keys.is_empty())
+        .collect();
+
+    let mut issues: HashMap<String, Option<crate::issue_tracker::Issue>> = HashMap::new();
+    for (_, keys) in &mentions {
+        for key in keys {
+            if !issues.contains_key(key) {
+                let issue = tracker.fetch(key).await?;
+                issues.insert(key.clone(), issue);
+            }
+        }
+    }
+
+    for (memory, keys) in &mentions {
+        let refs: Vec<&crate::issue_tracker::Issue> =
+            keys.iter().filter_map(|key| issues[key].as_ref()).collect();
+        let url = format!("http://localhost:{}/api/memories/{}/refs", port, memory.id);
+        let resp = client
+            .put(&url)
+            .json(&serde_json::json!({ "refs": refs }))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to update memory refs: {}", e))?;
+        if !resp.status().is_success() {
+            let error = resp.text().await.unwrap_or_default();
+            engram_log!("Failed to update refs of memory {}: {}", memory.id, error);
+        }
+    }
+
+    let found = issues.values().filter(|issue| issue.is_some()).count();
+    Ok(IssueEnrichmentReport {
+        memories: mentions.len(),
+        issues: found,
+        missing: issues.len() - found,
+    })
+}
+
+/// Save the GitHub capture settings, storing `token` in the keychain if given
+/// (an empty token removes it). When enabled, the token is checked first;
+/// returns the account's login.
+#[tauri::command]
+pub async fn configure_github_sync(
+    mut settings: GitHubSettings,
+    token: Option<String>,
+) -> Result<Option<String>, String> {
+    crate::windows::ensure_not_presenting()?;
+    settings.repos = settings
+        .repos
+        .iter()
+        .map(|repo| repo.trim().to_lowercase())
+        .filter(|repo| !repo.is_empty())
+        .collect();
+    if let Some(token) = token.as_deref().map(str::trim) {
+        if token.is_empty() {
+            crate::github::clear_token()?;
+        } else {
+            crate::github::set_token(token)?;
+        }
+    }
+
+    let mut login = None;
+    if settings.enabled {
+        login = Some(crate::github::GitHubClient::from_keychain()?.login().await?);
+    }
+
+    let mut prefs = read_preferences().unwrap_or_default();
+    prefs.github = settings;
+    write_preferences(&prefs)?;
+    Ok(login)
+}
+
+/// Capture GitHub activity now instead of waiting for the schedule.
+#[tauri::command]
+pub async fn run_github_sync(app: tauri::AppHandle) -> Result<ImportSummary, String> {
+    crate::windows::ensure_not_presenting()?;
+    sync_github(&app).await
+}
+
+/// Store merged pull requests and new review comments since the previous
+/// run as memories tagged `github` and `repo:<owner/name>`.
+pub async fn sync_github(app: &tauri::AppHandle) -> Result<ImportSummary, String> {
+    let prefs = read_preferences().unwrap_or_default();
+    let settings = prefs.github.clone();
+    let github = crate::github::GitHubClient::from_keychain()?;
+    let login = github.login().await?;
+    let mut sync = crate::github::load_state(&login);
+    let since = crate::github::search_since(&sync);
+    let today = crate::github::today();
+    let wanted = |repo: &str| settings.repos.is_empty() || settings.repos.contains(&repo.to_lowercase());
+
+    let mut captured = Vec::new();
+    let mut memories = Vec::new();
+    let tag_rules = CompiledRules::new(&prefs.tag_rules);
+    let mut push_memory = |content: String, repo: &str| {
+        let mut memory = serde_json::json!({
+            "content": truncate_chars(content, GITHUB_ACTIVITY_MAX_CHARS),
+            "category": "fact",
+            "confidence": 0.8,
+            "tags": ["github", format!("repo:{}", repo)],
+            "source": GITHUB_SOURCE,
+        });
+        prepare_ingest(&prefs, &tag_rules, &mut memory, GITHUB_SOURCE);
+        memories.push(memory);
+    };
+
+    for pr in github.merged_pull_requests(&login, &since).await? {
+        if !wanted(&pr.repo) || sync.captured.contains(&pr.url) {
+            continue;
+        }
+        let mut content = format!("Merged {}#{}: {}", pr.repo, pr.number, pr.title);
+        if !pr.body.is_empty() {
+            content.push_str(&format!("\n\n{}", pr.body));
+        }
+        push_memory(content, &pr.repo);
+        captured.push(pr.url);
+    }
+
+    if settings.capture_reviews {
+        for review in github.review_activity(&login, &since, &sync.captured).await? {
+            if !wanted(&review.repo) {
+                continue;
+            }
+            let content = format!(
+                "Reviewed {}#{} ({}):\n{}",
+                review.repo,
+                review.number,
+                review.title,
+                review.comments.join("\n")
+            );
+            push_memory(content, &review.repo);
+            captured.extend(review.ids);
+        }
+    }
+
+    let mut summary = ImportSummary::default();
+    if !memories.is_empty() {
+        let port = *app.state::<SidecarState>().port.lock().await;
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(300))
+            .build()
+            .map_err(|e| e.to_string())?;
+        summary = commit_import(&client, port, &memories).await?;
+        summary.found = memories.len() as u64;
+    }
+    sync.captured.extend(captured);
+    sync.since = Some(today);
+    crate::github::save_state(&sync)?;
+    Ok(summary)
+}
+
+/// The capture endpoint's URL and token, for wiring up scripts.
+#[tauri::command]
+pub async fn get_capture_endpoint() -> Result<crate::capture::CaptureEndpointInfo, String> {
+    crate::capture::info(&read_preferences().unwrap_or_default().capture_endpoint)
+}
+
+/// Turn the capture endpoint on or off, or move it to another port, and
+/// restart it. A token is created the first time it is enabled, and
+/// replaced when `rotate_token` is set.
+#[tauri::command]
+pub async fn configure_capture_endpoint(
+    app: tauri::AppHandle,
+    settings: CaptureEndpointSettings,
+    rotate_token: Option<bool>,
+) -> Result<crate::capture::CaptureEndpointInfo, String> {
+    crate::windows::ensure_not_presenting()?;
+    let port = crate::profiles::parse_port(&settings.port.to_string())?;
+    let mut prefs = read_preferences().unwrap_or_default();
+    if port == crate::profiles::port(&crate::profiles::active()) {
+        return Err(format!("Port {} is used by the REST API", port));
+    }
+    let has_token = crate::capture::info(&settings)?.token.is_some();
+    if rotate_token.unwrap_or(false) || (settings.enabled && !has_token) {
+        crate::capture::rotate_token()?;
+    }
+
+    crate::capture::restart(&app, &settings).await?;
+    prefs.capture_endpoint = settings;
+    write_preferences(&prefs)?;
+    crate::capture::info(&prefs.capture_endpoint)
+}
+
+/// Report the node, npx, and npm registry availability the sidecar and
+/// agent integrations depend on, with hints for anything missing.
+#[tauri::command]
+pub async fn check_runtime_dependencies(
+    app: tauri::AppHandle,
+) -> Result<crate::runtime::RuntimeDependencyReport, String> {
+    let bundled = crate::sidecar::uses_bundled_runtime(&app);
+    Ok(crate::runtime::check_dependencies(bundled).await)
+}
+
+#[tauri::command]
+pub async fn check_health(state: State<'_, SidecarState>) -> Result<bool, String> {
+    let port = *state.port.lock().await;
+    Ok(health_check(port).await)
+}
+
+/// Check each part of the sidecar separately so the UI can show which one is degraded.
+/// The database check writes, reads back, and deletes a probe memory.
+#[tauri::command]
+pub async fn check_health_deep(state: State<'_, SidecarState>) -> Result<DeepHealthReport, String> {
+    let port = *state.port.lock().await;
+    let onnx_fallback = *state.onnx_fallback.lock().await;
+    let tz = timestamps::preferred_timezone(&read_preferences().unwrap_or_default().timezone);
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let subsystem = |name: &str, status: &str, latency_ms: Option<u64>, detail: Option<String>| {
+        SubsystemHealth {
+            name: name.to_string(),
+            status: status.to_string(),
+            latency_ms,
+            detail,
+        }
+    };
+    let mut subsystems = Vec::new();
+
+    // REST: the status endpoint also reports the embedding model state
+    let started = Instant::now();
+    let url = format!("http://localhost:{}/api/status", port);
+    let status: Option<serde_json::Value> = match client.get(&url).send().await {
+        Ok(resp) if resp.status().is_success() => resp.json().await.ok(),
+        _ => None,
+    };
+    let rest_latency = started.elapsed().as_millis() as u64;
+    match &status {
+        Some(_) => subsystems.push(subsystem("rest", "ok", Some(rest_latency), None)),
+        None => subsystems.push(subsystem(
+            "rest",
+            "down",
+            None,
+            Some(format!("No response from port {}", port)),
+        )),
+    }
+
+    // Database: round-trip a probe memory
+    if status.is_some() {
+        let started = Instant::now();
+        let probe = serde_json::json!({
+            "content": format!("engram health probe {}", timestamps::now(&tz).to_rfc3339()),
+            "category": "fact",
+            "namespace": "engram-health",
+            "confidence": 0.1,
+        });
+        let result = match create_memory(&client, port, &probe).await {
+            Ok(id) => {
+                let read = fetch_memory(&client, port, &id).await.map(|_| ());
+                let url = format!("http://localhost:{}/api/memories/{}", port, id);
+                let deleted = client.delete(&url).send().await;
+                read.and_then(|_| match deleted {
+                    Ok(resp) if resp.status().is_success() => Ok(()),
+                    Ok(resp) => Err(format!("Delete returned {}", resp.status())),
+                    Err(e) => Err(format!("Delete failed: {}", e)),
+                })
+            }
+            Err(e) => Err(e),
+        };
+        let latency = Some(started.elapsed().as_millis() as u64);
+        match result {
+            Ok(()) => subsystems.push(subsystem("database", "ok", latency, None)),
+            Err(e) => subsystems.push(subsystem("database", "down", latency, Some(e))),
+        }
+    } else {
+        subsystems.push(subsystem(
+            "database",
+            "down",
+            None,
+            Some("Sidecar is not reachable".to_string()),
+        ));
+    }
+
+    // Embeddings: model availability as reported by the sidecar
+    let model = status.as_ref().and_then(|s| s.get("model"));
+    let flag = |key: &str| model.and_then(|m| m.get(key)).and_then(|v| v.as_bool()).unwrap_or(false);
+    let (embed_status, embed_detail) = if model.is_none() {
+        ("down", Some("Sidecar is not reachable".to_string()))
+    } else if flag("loading") {
+        ("degraded", Some("Embedding model is still loading".to_string()))
+    } else if !flag("available") {
+        (
+            "degraded",
+            Some("Embedding model unavailable; search falls back to keywords".to_string()),
+        )
+    } else if onnx_fallback {
+        (
+            "degraded",
+            Some("Running on the WASM fallback (reduced performance)".to_string()),
+        )
+    } else {
+        ("ok", None)
+    };
+    subsystems.push(subsystem("embeddings", embed_status, None, embed_detail));
+
+    // MCP: agents launch the server over stdio via npx, so check it can be found.
+    // simulate_mcp_session exercises a full handshake.
+    match find_executable("npx") {
+        Some(path) => subsystems.push(subsystem(
+            "mcp",
+            "ok",
+            None,
+            Some(format!("npx found at {}", path.display())),
+        )),
+        None => subsystems.push(subsystem(
+            "mcp",
+            "down",
+            None,
+            Some("npx is not on PATH; agents cannot launch the MCP server".to_string()),
+        )),
+    }
+
+    Ok(DeepHealthReport {
+        healthy: subsystems.iter().all(|s| s.status == "ok"),
+        checked_at: timestamps::now(&tz).to_rfc3339(),
+        subsystems,
+    })
+}
+
+// --- Helper functions ---
+
+/// Data directory of the active profile (~/.engram, or the XDG data dir on
+/// Linux, for the default profile).
+pub fn get_engram_data_dir() -> Result<PathBuf, String> {
+    crate::profiles::data_dir(&crate::profiles::active())
+}
+
+pub fn read_preferences() -> Result<DesktopPreferences, String> {
     let config_path = get_engram_data_dir()?.join("desktop-config.json");
     if config_path.exists() {
         let content = fs::read_to_string(&config_path).map_err(|e| e.to_string())?;
-        serde_json::from_str(&content).map_err(|e| e.to_string())
+        let mut prefs: DesktopPreferences =
+            serde_json::from_str(&content).map_err(|e| e.to_string())?;
+        prefs.migrate();
+        Ok(prefs)
     } else {
         Ok(DesktopPreferences::default())
     }
 }
 
-#[tauri::command]
-pub async fn save_preferences(prefs: DesktopPreferences) -> Result<(), String> {
+pub fn write_preferences(prefs: &DesktopPreferences) -> Result<(), String> {
     let engram_dir = get_engram_data_dir()?;
     fs::create_dir_all(&engram_dir).map_err(|e| e.to_string())?;
     let config_path = engram_dir.join("desktop-config.json");
-    let content = serde_json::to_string_pretty(&prefs).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(prefs).map_err(|e| e.to_string())?;
     fs::write(&config_path, content).map_err(|e| e.to_string())
 }
 
-#[tauri::command]
-pub async fn set_start_at_login(enabled: bool) -> Result<(), String> {
-    #[cfg(target_os = "macos")]
-    {
-        let home = dirs::home_dir().ok_or("Could not determine home directory")?;
-        let plist_dir = home.join("Library/LaunchAgents");
-        let plist_path = plist_dir.join("com.engram.app.plist");
+async fn fetch_memory(
+    client: &reqwest::Client,
+    port: u16,
+    id: &str,
+) -> Result<MemoryRecord, String> {
+    let url = format!("http://localhost:{}/api/memories/{}", port, id);
+    let resp = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch memory: {}", e))?;
+    if !resp.status().is_success() {
+        return Err(format!("Memory {} not found", id));
+    }
+    resp.json::<MemoryResponse>()
+        .await
+        .map(|r| r.memory)
+        .map_err(|e| e.to_string())
+}
 
-        if enabled {
-            fs::create_dir_all(&plist_dir).map_err(|e| e.to_string())?;
+/// Every memory matching `query`, fetched `EXPORT_PAGE_SIZE` at a time
+/// until a short page. Repeats from offsets shifting mid-listing are
+/// skipped by id.
+async fn list_memories(
+    client: &reqwest::Client,
+    port: u16,
+    query: &[(&str, String)],
+) -> Result<Vec<MemoryRecord>, String> {
+    let url = format!("http://localhost:{}/api/memories", port);
+    let mut seen = std::collections::HashSet::new();
+    let mut memories = Vec::new();
+    let mut offset: u64 = 0;
+    loop {
+        let page: MemoryListResponse = client
+            .get(&url)
+            .query(query)
+            .query(&[("limit", EXPORT_PAGE_SIZE), ("offset", offset)])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch memories: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to fetch memories: {}", e))?;
+        let fetched = page.memories.len() as u64;
+        offset += fetched;
+        memories.extend(page.memories.into_iter().filter(|m| seen.insert(m.id.clone())));
+        if fetched < EXPORT_PAGE_SIZE {
+            return Ok(memories);
+        }
+    }
+}
 
-            let exe_path = std::env::current_exe()
-                .map_err(|e| format!("Could not determine executable path: {}", e))?;
+/// Create a memory through the REST API and return its id.
+async fn create_memory(
+    client: &reqwest::Client,
+    port: u16,
+    body: &serde_json::Value,
+) -> Result<String, String> {
+    let url = format!("http://localhost:{}/api/memories", port);
+    let resp = client
+        .post(&url)
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to create memory: {}", e))?;
+    if !resp.status().is_success() {
+        let error = resp.text().await.unwrap_or_default();
+        return Err(format!("Failed to create memory: {}", error));
+    }
+    resp.json::<MemoryResponse>()
+        .await
+        .map(|r| r.memory.id)
+        .map_err(|e| e.to_string())
+}
 
-            let plist_content = format!(
-                r#"<?xml version="1.0" encoding="UTF-8"?>
-<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
-<plist version="1.0">
-<dict>
-    <key>Label</key>
-    <string>com.engram.app</string>
-    <key>ProgramArguments</key>
-    <array>
-        <string>{}</string>
-    </array>
-    <key>RunAtLoad</key>
-    <true/>
-    <key>KeepAlive</key>
-    <false/>
-</dict>
-</plist>"#,
-                exe_path.display()
-            );
+/// Replace a memory's tags in place, keeping its id and recall history.
+async fn update_memory_tags(
+    client: &reqwest::Client,
+    port: u16,
+    id: &str,
+    tags: &[String],
+) -> Result<(), String> {
+    update_memory(client, port, id, &serde_json::json!({ "tags": tags })).await
+}
 
-            fs::write(&plist_path, plist_content).map_err(|e| e.to_string())?;
-        } else if plist_path.exists() {
-            fs::remove_file(&plist_path).map_err(|e| e.to_string())?;
-        }
+/// Update the fields in `body` in place through the REST API. New content
+/// is re-embedded by the sidecar.
+async fn update_memory(
+    client: &reqwest::Client,
+    port: u16,
+    id: &str,
+    body: &serde_json::Value,
+) -> Result<(), String> {
+    let url = format!("http://localhost:{}/api/memories/{}", port, id);
+    let resp = client
+        .patch(&url)
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to update memory: {}", e))?;
+    if !resp.status().is_success() {
+        let error = resp.text().await.unwrap_or_default();
+        return Err(format!("Failed to update memory: {}", error));
     }
-
     Ok(())
 }
 
-#[tauri::command]
-pub async fn export_data(state: State<'_, SidecarState>) -> Result<String, String> {
-    let port = *state.port.lock().await;
-    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
-    let timestamp = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map_err(|e| e.to_string())?
-        .as_secs();
-    let export_path = home
-        .join("Desktop")
-        .join(format!("engram-export-{}.json", timestamp));
+/// A tag name given for a rename, merge, or delete, trimmed.
+fn valid_tag(tag: &str) -> Result<String, String> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return Err("Tag name is empty".to_string());
+    }
+    Ok(tag.to_string())
+}
 
+/// Run one of the sidecar's tag edits (`rename`, `merge`, or `delete`),
+/// which rewrites every affected memory in a single transaction.
+async fn edit_tags(
+    port: u16,
+    action: &str,
+    body: &serde_json::Value,
+) -> Result<TagEditReport, String> {
     let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(10))
+        .timeout(Duration::from_secs(60))
         .build()
         .map_err(|e| e.to_string())?;
-
-    let url = format!("http://localhost:{}/api/memories?limit=10000", port);
+    let url = format!("http://localhost:{}/api/tags/{}", port, action);
     let resp = client
-        .get(&url)
+        .post(&url)
+        .json(body)
         .send()
         .await
-        .map_err(|e| format!("Failed to fetch memories: {}", e))?;
-
-    let body = resp.text().await.map_err(|e| e.to_string())?;
-    fs::write(&export_path, &body).map_err(|e| e.to_string())?;
-
-    Ok(export_path.to_string_lossy().to_string())
+        .map_err(|e| format!("Failed to {} tag: {}", action, e))?;
+    if !resp.status().is_success() {
+        let error = resp.text().await.unwrap_or_default();
+        return Err(format!("Failed to {} tag: {}", action, error));
+    }
+    resp.json::<TagEditReport>().await.map_err(|e| e.to_string())
 }
 
-#[tauri::command]
-pub async fn reset_database(app: tauri::AppHandle) -> Result<(), String> {
-    crate::sidecar::stop_sidecar(&app).await?;
-
-    let engram_dir = get_engram_data_dir()?;
-    let db_path = engram_dir.join("memory.db");
-    if db_path.exists() {
-        fs::remove_file(&db_path)
-            .map_err(|e| format!("Failed to delete database: {}", e))?;
+/// Cut `content` to `max` characters, marking the cut with an ellipsis.
+fn truncate_chars(content: String, max: usize) -> String {
+    if content.chars().count() > max {
+        content.chars().take(max).collect::<String>() + "…"
+    } else {
+        content
     }
-    // Also delete WAL and SHM files
-    let _ = fs::remove_file(engram_dir.join("memory.db-wal"));
-    let _ = fs::remove_file(engram_dir.join("memory.db-shm"));
-
-    tokio::time::sleep(Duration::from_secs(1)).await;
-    crate::sidecar::start_sidecar(&app)
 }
 
-#[tauri::command]
-pub async fn restart_sidecar(app: tauri::AppHandle) -> Result<(), String> {
-    crate::sidecar::stop_sidecar(&app).await?;
-    tokio::time::sleep(Duration::from_secs(1)).await;
-    crate::sidecar::start_sidecar(&app)
+/// Send memory candidates through the sidecar's import pipeline, which
+/// deduplicates, runs secret detection, and generates embeddings.
+async fn commit_import(
+    client: &reqwest::Client,
+    port: u16,
+    memories: &[serde_json::Value],
+) -> Result<ImportSummary, String> {
+    let url = format!("http://localhost:{}/api/import/commit", port);
+    let resp = client
+        .post(&url)
+        .json(&serde_json::json!({ "memories": memories }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to import memories: {}", e))?;
+    if !resp.status().is_success() {
+        let error = resp.text().await.unwrap_or_default();
+        return Err(format!("Failed to import memories: {}", error));
+    }
+    resp.json::<ImportCommitResponse>()
+        .await
+        .map(|r| r.results)
+        .map_err(|e| e.to_string())
 }
 
+/// Copy the REST base URL, on the port the sidecar is actually listening
+/// on, for pasting into scripts, Raycast, or curl. Returns the copied text.
 #[tauri::command]
-pub async fn check_health(state: State<'_, SidecarState>) -> Result<bool, String> {
+pub async fn copy_connection_info(
+    app: tauri::AppHandle,
+    state: State<'_, SidecarState>,
+) -> Result<String, String> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
     let port = *state.port.lock().await;
-    Ok(health_check(port).await)
+    // The local REST API takes no token; an auth header line goes here once it does
+    let text = format!("http://localhost:{}/api", port);
+    app.clipboard()
+        .write_text(text.clone())
+        .map_err(|e| format!("Failed to copy connection info: {}", e))?;
+    Ok(text)
 }
 
-// --- Helper functions ---
-
-fn get_engram_data_dir() -> Result<PathBuf, String> {
-    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
-    Ok(home.join(".engram"))
+/// Connection details for wiring custom scripts against the local instance.
+pub fn connection_info(port: u16) -> serde_json::Value {
+    serde_json::json!({
+        "port": port,
+        "baseUrl": format!("http://localhost:{}/api", port),
+        "healthUrl": format!("http://localhost:{}/health", port),
+        "token": "<not required for local access>",
+        "mcp": mcp_launch_command(),
+    })
 }
 
 fn detect_agent(id: &str, name: &str, config_path: &PathBuf) -> DetectedAgent {
-    let available = config_path.parent().map_or(false, |p| p.exists());
+    let config_dir_exists = config_path.parent().map_or(false, |p| p.exists());
+    let installed = agent_installed(id);
     let connected = if config_path.exists() {
         fs::read_to_string(config_path)
             .ok()
@@ -339,25 +4439,101 @@ fn detect_agent(id: &str, name: &str, config_path: &PathBuf) -> DetectedAgent {
         name: name.to_string(),
         config_path: config_path.to_string_lossy().to_string(),
         connected,
-        available,
+        available: installed,
+        installed,
+        config_dir_exists,
+        orphaned: connected && !installed,
+        icon: agent_icon(id),
     }
 }
 
-fn configure_agent_internal(agent_name: &str) -> Result<String, String> {
-    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+/// Whether the agent's app is installed: an app bundle on macOS, an install
+/// directory or uninstall registry entry on Windows, or a command on PATH
+/// on Linux. Claude Code is a CLI everywhere.
+fn agent_installed(id: &str) -> bool {
+    let Some(home) = dirs::home_dir() else {
+        return false;
+    };
+    if id == "claude-code" {
+        return find_executable("claude").is_some() || home.join(".claude/local/claude").exists();
+    }
 
-    // ChatGPT requires manual in-app configuration
-    if agent_name == "chatgpt" {
-        return Ok(
-            "ChatGPT requires manual setup:\n\
-             1. Open ChatGPT app\n\
-             2. Go to Settings > Developer > MCP Servers\n\
-             3. Add a new server with command: npx -y @hbarefoot/engram start --mcp-only"
-                .to_string(),
-        );
+    if cfg!(target_os = "macos") {
+        let bundle = match id {
+            "claude-desktop" => "Claude.app",
+            "cursor" => "Cursor.app",
+            "windsurf" => "Windsurf.app",
+            "chatgpt" => "ChatGPT.app",
+            _ => return false,
+        };
+        [PathBuf::from("/Applications"), home.join("Applications")]
+            .iter()
+            .any(|dir| dir.join(bundle).exists())
+    } else if cfg!(windows) {
+        let (install_dir, display_name) = match id {
+            "claude-desktop" => ("AnthropicClaude", "Claude"),
+            "cursor" => ("Programs\\cursor", "Cursor"),
+            "windsurf" => ("Programs\\Windsurf", "Windsurf"),
+            "chatgpt" => ("Programs\\ChatGPT", "ChatGPT"),
+            _ => return false,
+        };
+        dirs::data_local_dir().is_some_and(|dir| dir.join(install_dir).exists())
+            || windows_uninstall_entry_exists(display_name)
+    } else {
+        let command = match id {
+            "claude-desktop" => "claude-desktop",
+            "cursor" => "cursor",
+            "windsurf" => "windsurf",
+            _ => return false,
+        };
+        find_executable(command).is_some()
     }
+}
+
+/// Whether a per-user or machine-wide uninstall entry's DisplayName
+/// contains `display_name`.
+fn windows_uninstall_entry_exists(display_name: &str) -> bool {
+    const UNINSTALL_KEYS: [&str; 2] = [
+        "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Uninstall",
+        "HKLM\\Software\\Microsoft\\Windows\\CurrentVersion\\Uninstall",
+    ];
+    UNINSTALL_KEYS.iter().any(|key| {
+        std::process::Command::new("reg")
+            .args(["query", key, "/s", "/d", "/f", display_name])
+            .output()
+            .is_ok_and(|output| {
+                output.status.success()
+                    && String::from_utf8_lossy(&output.stdout).contains("DisplayName")
+            })
+    })
+}
 
-    let config_path = match agent_name {
+/// The bundled icon for an agent as a `data:` URI the webview can show
+/// directly. Unknown agents get a generic icon.
+fn agent_icon(id: &str) -> String {
+    let svg = match id {
+        "claude-desktop" => include_str!("../icons/agents/claude-desktop.svg"),
+        "claude-code" => include_str!("../icons/agents/claude-code.svg"),
+        "cursor" => include_str!("../icons/agents/cursor.svg"),
+        "windsurf" => include_str!("../icons/agents/windsurf.svg"),
+        "chatgpt" => include_str!("../icons/agents/chatgpt.svg"),
+        _ => include_str!("../icons/agents/generic.svg"),
+    };
+    let encoded: String = svg
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '%' | '#' | '<' | '>' | '"' | '\'' | '&' | '\n' => format!("%{:02X}", c as u32),
+            c => c.to_string(),
+        })
+        .collect();
+    format!("data:image/svg+xml;charset=utf-8,{}", encoded)
+}
+
+/// Resolve the MCP config file path for an agent that is configured via a file.
+fn agent_config_path(agent_id: &str) -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+    let path = match agent_id {
         "claude-desktop" => {
             if cfg!(target_os = "macos") {
                 home.join("Library/Application Support/Claude/claude_desktop_config.json")
@@ -368,8 +4544,24 @@ fn configure_agent_internal(agent_name: &str) -> Result<String, String> {
         "claude-code" => home.join(".claude/mcp.json"),
         "cursor" => home.join(".cursor/mcp.json"),
         "windsurf" => home.join(".windsurf/mcp.json"),
-        _ => return Err(format!("Unknown agent: {}", agent_name)),
+        _ => return Err(format!("Unknown agent: {}", agent_id)),
     };
+    Ok(path)
+}
+
+fn configure_agent_internal(agent_name: &str) -> Result<String, String> {
+    // ChatGPT requires manual in-app configuration
+    if agent_name == "chatgpt" {
+        return Ok(
+            "ChatGPT requires manual setup:\n\
+             1. Open ChatGPT app\n\
+             2. Go to Settings > Developer > MCP Servers\n\
+             3. Add a new server with command: npx -y @hbarefoot/engram start --mcp-only"
+                .to_string(),
+        );
+    }
+
+    let config_path = agent_config_path(agent_name)?;
 
     // Ensure parent directory exists
     if let Some(parent) = config_path.parent() {
@@ -377,58 +4569,390 @@ fn configure_agent_internal(agent_name: &str) -> Result<String, String> {
             .map_err(|e| format!("Failed to create config directory: {}", e))?;
     }
 
-    // Read existing config or create empty
-    let existing_content = if config_path.exists() {
-        fs::read_to_string(&config_path)
-            .map_err(|e| format!("Failed to read config: {}", e))?
-    } else {
-        "{}".to_string()
+    // Build the engram MCP server entry
+    let mut engram_entry = mcp_launch_command();
+    engram_entry["env"] = serde_json::json!({});
+
+    let mut backed_up = false;
+    for _ in 0..CONFIG_WRITE_ATTEMPTS {
+        // Read existing config or create empty
+        let snapshot = ConfigSnapshot::read(&config_path)?;
+        let mut config: serde_json::Value = serde_json::from_str(&snapshot.content)
+            .map_err(|e| format!("Failed to parse config JSON: {}", e))?;
+
+        // Check if engram is already configured
+        if is_engram_configured(&config) {
+            return Ok(format!("Engram is already configured for {}.", agent_name));
+        }
+
+        // Merge into config
+        let servers = config
+            .as_object_mut()
+            .ok_or("Config is not a JSON object")?
+            .entry("mcpServers")
+            .or_insert_with(|| serde_json::json!({}));
+
+        servers
+            .as_object_mut()
+            .ok_or("mcpServers is not a JSON object")?
+            .insert("engram".to_string(), engram_entry.clone());
+
+        let updated = serde_json::to_string_pretty(&config)
+            .map_err(|e| format!("Failed to serialize config: {}", e))?;
+
+        // The agent or another installer may have saved the file while we
+        // merged; start over from their version rather than overwrite it
+        if snapshot.changed()? {
+            engram_log!(
+                "{} changed while it was being updated; merging again",
+                config_path.display()
+            );
+            continue;
+        }
+
+        // Create backup before modifying
+        if config_path.exists() && !backed_up {
+            create_config_backup(&config_path)?;
+            backed_up = true;
+        }
+
+        fs::write(&config_path, &updated)
+            .map_err(|e| format!("Failed to write config: {}", e))?;
+
+        return Ok(format!(
+            "Successfully configured engram for {}. Config written to: {}",
+            agent_name,
+            config_path.display()
+        ));
+    }
+
+    Err(format!(
+        "{} kept changing while Engram was updating it. Close the agent and try again.",
+        config_path.display()
+    ))
+}
+
+/// An agent config as it was read, to tell whether something else saved it
+/// before we write our merge back.
+struct ConfigSnapshot {
+    path: PathBuf,
+    content: String,
+    /// SHA-256 of the file, or `None` if it didn't exist.
+    digest: Option<Vec<u8>>,
+}
+
+impl ConfigSnapshot {
+    /// Read the config at `path`; a missing file reads as `{}`.
+    fn read(path: &PathBuf) -> Result<Self, String> {
+        let (content, digest) = match read_if_exists(path)? {
+            Some(bytes) => {
+                let digest = Some(sha256(&bytes));
+                let content = String::from_utf8(bytes)
+                    .map_err(|e| format!("Failed to read config: {}", e))?;
+                (content, digest)
+            }
+            None => ("{}".to_string(), None),
+        };
+        Ok(Self {
+            path: path.clone(),
+            content,
+            digest,
+        })
+    }
+
+    /// Whether the file on disk differs from what was read. Content is
+    /// compared rather than mtime, which editors bump without changing
+    /// anything and coarse filesystems round to the second.
+    fn changed(&self) -> Result<bool, String> {
+        let current = read_if_exists(&self.path)?.map(|bytes| sha256(&bytes));
+        Ok(current != self.digest)
+    }
+}
+
+fn read_if_exists(path: &PathBuf) -> Result<Option<Vec<u8>>, String> {
+    match fs::read(path) {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("Failed to read config: {}", e)),
+    }
+}
+
+fn sha256(bytes: &[u8]) -> Vec<u8> {
+    Sha256::digest(bytes).to_vec()
+}
+
+/// Take the Engram entry out of an agent's config, backing it up first.
+fn remove_engram_entry(agent_id: &str) -> Result<(), String> {
+    let config_path = agent_config_path(agent_id)?;
+    let mut backed_up = false;
+    for _ in 0..CONFIG_WRITE_ATTEMPTS {
+        let snapshot = ConfigSnapshot::read(&config_path)?;
+        let mut config: serde_json::Value = serde_json::from_str(&snapshot.content)
+            .map_err(|e| format!("Failed to parse config JSON: {}", e))?;
+        let removed = config
+            .get_mut("mcpServers")
+            .and_then(|servers| servers.as_object_mut())
+            .and_then(|servers| servers.remove("engram"));
+        if removed.is_none() {
+            return Ok(());
+        }
+
+        let updated = serde_json::to_string_pretty(&config)
+            .map_err(|e| format!("Failed to serialize config: {}", e))?;
+        if snapshot.changed()? {
+            continue;
+        }
+        if !backed_up {
+            create_config_backup(&config_path)?;
+            backed_up = true;
+        }
+        return fs::write(&config_path, &updated)
+            .map_err(|e| format!("Failed to write config: {}", e));
+    }
+    Err(format!("{} kept changing; try again", config_path.display()))
+}
+
+fn validate_agent_config_internal(agent_id: &str) -> Result<AgentConfigReport, String> {
+    let mut findings = Vec::new();
+    let mut add = |severity: &str, code: &str, message: String| {
+        findings.push(ConfigFinding {
+            severity: severity.to_string(),
+            code: code.to_string(),
+            message,
+        });
     };
 
-    let mut config: serde_json::Value = serde_json::from_str(&existing_content)
-        .map_err(|e| format!("Failed to parse config JSON: {}", e))?;
+    if agent_id == "chatgpt" {
+        add(
+            "info",
+            "in-app-config",
+            "ChatGPT is configured in-app and cannot be validated from here.".to_string(),
+        );
+        return Ok(AgentConfigReport {
+            agent_id: agent_id.to_string(),
+            config_path: String::new(),
+            valid: true,
+            findings,
+        });
+    }
+
+    let config_path = agent_config_path(agent_id)?;
+    let report = |findings: Vec<ConfigFinding>| AgentConfigReport {
+        agent_id: agent_id.to_string(),
+        config_path: config_path.to_string_lossy().to_string(),
+        valid: !findings.iter().any(|f| f.severity == "error"),
+        findings,
+    };
 
-    // Check if engram is already configured
-    if is_engram_configured(&config) {
-        return Ok(format!("Engram is already configured for {}.", agent_name));
+    if !config_path.exists() {
+        add(
+            "error",
+            "missing-config",
+            format!("Config file does not exist: {}", config_path.display()),
+        );
+        return Ok(report(findings));
     }
 
-    // Create backup before modifying
-    if config_path.exists() {
-        create_config_backup(&config_path)?;
+    let content = match fs::read_to_string(&config_path) {
+        Ok(content) => content,
+        Err(e) => {
+            add("error", "unreadable-config", format!("Failed to read config: {}", e));
+            return Ok(report(findings));
+        }
+    };
+
+    let config: serde_json::Value = match serde_json::from_str(&content) {
+        Ok(config) => config,
+        Err(e) => {
+            add("error", "invalid-json", format!("Config is not valid JSON: {}", e));
+            return Ok(report(findings));
+        }
+    };
+
+    let servers = match config.get("mcpServers") {
+        Some(serde_json::Value::Object(servers)) => servers,
+        Some(_) => {
+            add("error", "invalid-servers", "mcpServers is not a JSON object".to_string());
+            return Ok(report(findings));
+        }
+        None => {
+            add("error", "missing-engram", "No mcpServers section found".to_string());
+            return Ok(report(findings));
+        }
+    };
+
+    // Other entries that also launch engram will run a second server against the same DB
+    let duplicates: Vec<&String> = servers
+        .iter()
+        .filter(|(name, entry)| name.as_str() != "engram" && launches_engram(entry))
+        .map(|(name, _)| name)
+        .collect();
+    for name in duplicates {
+        add(
+            "warning",
+            "duplicate-server",
+            format!("Server \"{}\" also launches Engram; remove one of the entries", name),
+        );
     }
 
-    // Build the engram MCP server entry
-    let engram_entry = serde_json::json!({
-        "command": "npx",
-        "args": ["-y", "@hbarefoot/engram", "start", "--mcp-only"],
-        "env": {}
-    });
+    let entry = match servers.get("engram") {
+        Some(entry) => entry,
+        None => {
+            add("error", "missing-engram", "No \"engram\" entry in mcpServers".to_string());
+            return Ok(report(findings));
+        }
+    };
+
+    match entry.get("command").and_then(|c| c.as_str()) {
+        Some(command) => match find_executable(command) {
+            Some(path) if is_executable(&path) => {}
+            Some(path) => add(
+                "error",
+                "command-not-executable",
+                format!("{} is not executable", path.display()),
+            ),
+            None => add(
+                "error",
+                "command-not-found",
+                format!("Command \"{}\" was not found on PATH", command),
+            ),
+        },
+        None => add(
+            "error",
+            "missing-command",
+            "The engram entry has no \"command\" string".to_string(),
+        ),
+    }
+
+    let mut args: Vec<&str> = entry
+        .get("args")
+        .and_then(|a| a.as_array())
+        .map(|a| a.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+    let mut command = entry.get("command").and_then(|c| c.as_str()).unwrap_or("");
+    let command_stem = |command: &str| {
+        std::path::Path::new(command)
+            .file_stem()
+            .map(|n| n.to_string_lossy().to_lowercase())
+            .unwrap_or_default()
+    };
+    // Windows entries wrap the real command: cmd /c npx ...
+    if command_stem(command) == "cmd" && args.len() > 1 && args[0].eq_ignore_ascii_case("/c") {
+        command = args[1];
+        args.drain(..2);
+    }
+    let command_name = command_stem(command);
+
+    if command_name == "npx" {
+        if !args.iter().any(|a| a.starts_with("@hbarefoot/engram")) {
+            add(
+                "error",
+                "wrong-package",
+                "npx args do not reference @hbarefoot/engram".to_string(),
+            );
+        }
+        if !args.contains(&"-y") && !args.contains(&"--yes") {
+            add(
+                "warning",
+                "missing-yes-flag",
+                "npx without -y may hang waiting for an install prompt".to_string(),
+            );
+        }
+    }
+    if !args.contains(&"start") {
+        add(
+            "error",
+            "missing-start",
+            "Args are missing the \"start\" subcommand".to_string(),
+        );
+    }
+    if !args.contains(&"--mcp-only") {
+        add(
+            "warning",
+            "missing-mcp-only",
+            "Args are missing --mcp-only; the agent will also start a REST server that conflicts with the desktop app".to_string(),
+        );
+    }
+    if entry.get("env").map_or(false, |env| !env.is_object()) {
+        add("error", "invalid-env", "\"env\" must be a JSON object".to_string());
+    }
 
-    // Merge into config
-    let servers = config
-        .as_object_mut()
-        .ok_or("Config is not a JSON object")?
-        .entry("mcpServers")
-        .or_insert_with(|| serde_json::json!({}));
+    Ok(report(findings))
+}
 
-    servers
-        .as_object_mut()
-        .ok_or("mcpServers is not a JSON object")?
-        .insert("engram".to_string(), engram_entry);
+/// Whether an MCP server entry starts Engram, judged by its command and args.
+fn launches_engram(entry: &serde_json::Value) -> bool {
+    let command = entry.get("command").and_then(|c| c.as_str()).unwrap_or("");
+    let args = entry
+        .get("args")
+        .and_then(|a| a.as_array())
+        .map(|a| a.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>())
+        .unwrap_or_default();
+    command.contains("engram")
+        || args
+            .iter()
+            .any(|a| a.starts_with("@hbarefoot/engram") || a.ends_with("engram.js"))
+}
 
-    // Write updated config
-    let updated = serde_json::to_string_pretty(&config)
-        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+/// Locate a command the way a shell would: as a path if it contains a
+/// separator, otherwise by searching PATH. On Windows, a name without an
+/// extension also matches PATHEXT variants, so "npx" finds npx.cmd.
+pub fn find_executable(command: &str) -> Option<PathBuf> {
+    let candidate = std::path::Path::new(command);
+    let names: Vec<String> = if cfg!(windows) && candidate.extension().is_none() {
+        std::env::var("PATHEXT")
+            .unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string())
+            .split(';')
+            .filter(|ext| !ext.is_empty())
+            .map(|ext| format!("{}{}", command, ext.to_lowercase()))
+            .collect()
+    } else {
+        vec![command.to_string()]
+    };
+    if candidate.components().count() > 1 {
+        return names.iter().map(PathBuf::from).find(|p| p.exists());
+    }
+    let path_var = std::env::var_os("PATH")?;
+    std::env::split_paths(&path_var)
+        .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+        .find(|p| p.is_file())
+}
 
-    fs::write(&config_path, &updated)
-        .map_err(|e| format!("Failed to write config: {}", e))?;
+/// How agents launch the MCP server. npx is a .cmd shim on Windows, which
+/// agents can only start through cmd. Where the app keeps its data outside
+/// ~/.engram (the XDG data dir on Linux), agents are pointed at it as well.
+fn mcp_launch_command() -> serde_json::Value {
+    let mut args: Vec<String> = ["-y", "@hbarefoot/engram", "start", "--mcp-only"]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+    match crate::profiles::sidecar_config(crate::profiles::DEFAULT_PROFILE) {
+        Ok(Some(config)) => {
+            args.push("--config".to_string());
+            args.push(config.to_string_lossy().into_owned());
+        }
+        Ok(None) => {}
+        Err(e) => engram_log!("Failed to prepare MCP config: {}", e),
+    }
+    if cfg!(windows) {
+        let mut cmd_args = vec!["/c".to_string(), "npx".to_string()];
+        cmd_args.extend(args);
+        serde_json::json!({ "command": "cmd", "args": cmd_args })
+    } else {
+        serde_json::json!({ "command": "npx", "args": args })
+    }
+}
 
-    Ok(format!(
-        "Successfully configured engram for {}. Config written to: {}",
-        agent_name,
-        config_path.display()
-    ))
+fn is_executable(path: &std::path::Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::metadata(path).map_or(false, |m| m.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        path.is_file()
+    }
 }
 
 fn is_engram_configured(config: &serde_json::Value) -> bool {
@@ -461,10 +4985,42 @@ fn create_config_backup(config_path: &PathBuf) -> Result<(), String> {
     fs::copy(config_path, &backup_path)
         .map_err(|e| format!("Failed to create backup: {}", e))?;
 
-    eprintln!(
-        "[engram] Config backup created at: {}",
+    engram_log!(
+        "Config backup created at: {}",
         backup_path.display()
     );
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn present_masked_checks_tags_and_redactions() {
+        let tags = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert!(present_masked(&tags(&["work", "Private"]), "Quarterly numbers"));
+        assert!(present_masked(&tags(&[]), "API key is [REDACTED]"));
+        assert!(!present_masked(&tags(&["work", "privacy"]), "Quarterly numbers"));
+
+        let memory = PresentedMemory::new(
+            "m1".to_string(),
+            "fact".to_string(),
+            Some("acme".to_string()),
+            "Salary is 100k".to_string(),
+            &tags(&["sensitive"]),
+        );
+        assert!(memory.masked);
+        assert_eq!(memory.content, None);
+        assert_eq!(memory.entity, None);
+    }
+
+    #[test]
+    fn valid_tag_trims_and_rejects_empty_names() {
+        assert_eq!(valid_tag("  devops ").unwrap(), "devops");
+        assert_eq!(valid_tag("lang:deu").unwrap(), "lang:deu");
+        assert!(valid_tag("").is_err());
+        assert!(valid_tag(" \t").is_err());
+    }
+}
@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::commands::DesktopPreferences;
+use crate::sidecar::{SidecarState, SidecarStatus};
+
+const RELOAD_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How a change to a sidecar-facing preference reaches the running sidecar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ApplyMode {
+    /// Sent to `/api/config/reload`.
+    Live,
+    /// Only read when the sidecar starts.
+    Restart,
+}
+
+struct SidecarSetting {
+    /// Preference key, as reported back to the frontend.
+    key: &'static str,
+    mode: ApplyMode,
+    changed: fn(&DesktopPreferences, &DesktopPreferences) -> bool,
+}
+
+/// Preferences the sidecar consumes, and whether it can pick up a change live.
+const CAPABILITIES: &[SidecarSetting] = &[
+    SidecarSetting {
+        key: "logLevel",
+        mode: ApplyMode::Live,
+        changed: |a, b| a.log_level != b.log_level,
+    },
+    SidecarSetting {
+        key: "search",
+        mode: ApplyMode::Live,
+        changed: |a, b| a.search != b.search,
+    },
+    SidecarSetting {
+        key: "restPort",
+        mode: ApplyMode::Restart,
+        changed: |a, b| a.rest_port != b.rest_port,
+    },
+    SidecarSetting {
+        key: "envOverrides",
+        mode: ApplyMode::Restart,
+        changed: |a, b| a.env_overrides != b.env_overrides,
+    },
+    SidecarSetting {
+        key: "sidecarArgs",
+        mode: ApplyMode::Restart,
+        changed: |a, b| a.sidecar_args != b.sidecar_args,
+    },
+    SidecarSetting {
+        key: "developer",
+        mode: ApplyMode::Restart,
+        changed: |a, b| a.developer != b.developer,
+    },
+];
+
+/// What saving preferences did to the running sidecar.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigApplyReport {
+    /// Settings applied through `/api/config/reload`.
+    pub reloaded: Vec<String>,
+    /// Settings that needed the sidecar restarted, empty if it wasn't.
+    pub restarted_for: Vec<String>,
+}
+
+/// Body for `/api/config/reload` with every live-reloadable setting.
+fn reload_body(prefs: &DesktopPreferences) -> serde_json::Value {
+    serde_json::json!({ "logLevel": prefs.log_level, "search": prefs.search })
+}
+
+/// Push changed sidecar settings to a running sidecar: live where the
+/// sidecar supports it, otherwise (or if the reload fails) by restarting.
+/// A stopped sidecar picks everything up when it next starts.
+pub async fn apply(
+    app: &AppHandle,
+    previous: &DesktopPreferences,
+    prefs: &DesktopPreferences,
+) -> Result<ConfigApplyReport, String> {
+    let changed: Vec<&SidecarSetting> = CAPABILITIES
+        .iter()
+        .filter(|setting| (setting.changed)(previous, prefs))
+        .collect();
+    let mut report = ConfigApplyReport::default();
+    if changed.is_empty() {
+        return Ok(report);
+    }
+
+    let state = app.state::<SidecarState>();
+    if !matches!(*state.status.lock().await, SidecarStatus::Running) {
+        return Ok(report);
+    }
+
+    let keys = |mode: ApplyMode| -> Vec<String> {
+        changed
+            .iter()
+            .filter(|setting| setting.mode == mode)
+            .map(|setting| setting.key.to_string())
+            .collect()
+    };
+    let live = keys(ApplyMode::Live);
+    let mut restart = keys(ApplyMode::Restart);
+
+    if restart.is_empty() {
+        let port = *state.port.lock().await;
+        match reload(port, prefs).await {
+            Ok(()) => {
+                engram_log!("Reloaded sidecar config: {}", live.join(", "));
+                report.reloaded = live;
+                return Ok(report);
+            }
+            Err(e) => {
+                engram_log!("Sidecar config reload failed, restarting instead: {}", e);
+                restart = live;
+            }
+        }
+    } else {
+        // The restart applies the live settings too
+        restart.extend(live);
+    }
+
+    engram_log!("Restarting sidecar to apply: {}", restart.join(", "));
+    crate::sidecar::restart(app).await?;
+    report.restarted_for = restart;
+    Ok(report)
+}
+
+async fn reload(port: u16, prefs: &DesktopPreferences) -> Result<(), String> {
+    let client = reqwest::Client::builder()
+        .timeout(RELOAD_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let url = format!("http://localhost:{}/api/config/reload", port);
+    let resp = client
+        .post(&url)
+        .json(&reload_body(prefs))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        // Sidecars older than the reload endpoint answer 404
+        return Err(format!("HTTP {}", resp.status()));
+    }
+    Ok(())
+}
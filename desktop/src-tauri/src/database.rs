@@ -0,0 +1,564 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, Duration, TimeZone};
+use chrono_tz::Tz;
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Manager};
+
+/// Minimum time between stored size samples.
+pub const SIZE_SAMPLE_INTERVAL_SECS: i64 = 24 * 60 * 60;
+/// Samples kept, roughly a year of daily history.
+const MAX_SIZE_SAMPLES: usize = 366;
+/// Only recent history feeds the growth rate, so old bulk imports age out.
+const TREND_WINDOW_SECS: i64 = 90 * 24 * 60 * 60;
+/// Sizes the projection counts towards; the first one above the current size is used.
+const SIZE_MILESTONES: [u64; 6] = [
+    256 << 20,
+    512 << 20,
+    1 << 30,
+    2 << 30,
+    5 << 30,
+    10 << 30,
+];
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VacuumReport {
+    pub size_before: u64,
+    pub size_after: u64,
+    pub reclaimed_bytes: u64,
+}
+
+/// Outcome of `merge_memories`.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeReport {
+    /// Memories in the other database.
+    pub found: u64,
+    pub inserted: u64,
+    /// Already present with the same id.
+    pub skipped_ids: u64,
+    /// Already present under another id with the same content.
+    pub skipped_duplicates: u64,
+}
+
+/// Weeks of growth history returned by `memory_stats`.
+const STATS_WEEKS: usize = 26;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CountEntry {
+    pub name: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeekCount {
+    /// Local date of the Monday the week starts on.
+    pub week_start: String,
+    /// Memories created that week.
+    pub added: u64,
+    /// Memories existing at the end of the week.
+    pub total: u64,
+}
+
+/// Memory counts for the statistics view.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryStats {
+    pub total: u64,
+    /// Most used first. Memories without tags are not counted.
+    pub by_tag: Vec<CountEntry>,
+    /// By the agent or importer that created them, most used first.
+    pub by_source: Vec<CountEntry>,
+    /// The last `STATS_WEEKS` weeks, oldest first.
+    pub by_week: Vec<WeekCount>,
+}
+
+/// Longest range `memory_timeline` returns, in days.
+pub const TIMELINE_MAX_DAYS: i64 = 3 * 366;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DayCount {
+    /// Local date, `YYYY-MM-DD`.
+    pub date: String,
+    pub created: u64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SizeSample {
+    /// Unix seconds.
+    pub at: i64,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeProjection {
+    pub target_bytes: u64,
+    /// Days until `target_bytes` at the current growth rate.
+    pub days: f64,
+}
+
+/// Database size history and where it is heading.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsageTrend {
+    pub current_bytes: u64,
+    /// Growth over the trend window; `None` until there are two days of samples.
+    pub bytes_per_day: Option<f64>,
+    /// `None` while the database is not growing.
+    pub projection: Option<SizeProjection>,
+    pub samples: Vec<SizeSample>,
+}
+
+/// Path of the sidecar's SQLite database.
+pub fn db_path() -> Result<PathBuf, String> {
+    Ok(crate::commands::get_engram_data_dir()?.join("memory.db"))
+}
+
+/// The database the sidecar reported opening in its ready line, which
+/// differs from `db_path` when its config points elsewhere.
+pub async fn active_db_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let state = app.state::<crate::sidecar::SidecarState>();
+    let reported = state.ready.lock().await.as_ref().map(|info| PathBuf::from(&info.db_path));
+    match reported {
+        Some(path) => Ok(path),
+        None => db_path(),
+    }
+}
+
+/// Combined size of the database file and its WAL.
+pub fn db_size(path: &Path) -> u64 {
+    let wal = path.with_extension("db-wal");
+    [path.to_path_buf(), wal]
+        .iter()
+        .filter_map(|p| fs::metadata(p).ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Fold the WAL back into the main file and rebuild it to release free pages.
+/// The sidecar must be stopped first: VACUUM needs exclusive access.
+pub fn checkpoint_and_vacuum(path: &Path) -> Result<VacuumReport, String> {
+    if !path.exists() {
+        return Err(format!("Database not found at: {}", path.display()));
+    }
+    let size_before = db_size(path);
+
+    let conn = Connection::open(path).map_err(|e| format!("Failed to open database: {}", e))?;
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+        .map_err(|e| format!("WAL checkpoint failed: {}", e))?;
+    conn.execute("VACUUM", [])
+        .map_err(|e| format!("VACUUM failed: {}", e))?;
+    drop(conn);
+
+    let size_after = db_size(path);
+    Ok(VacuumReport {
+        size_before,
+        size_after,
+        reclaimed_bytes: size_before.saturating_sub(size_after),
+    })
+}
+
+fn content_hash(content: &str) -> Vec<u8> {
+    Sha256::digest(content.trim().as_bytes()).to_vec()
+}
+
+/// `path` as a read-only SQLite URI, so attaching it never writes to it.
+fn read_only_uri(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    let mut uri = String::from("file:");
+    // Windows drive paths are written as file:/C:/...
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+    for c in path.chars() {
+        match c {
+            '%' | '?' | '#' | ' ' => uri.push_str(&format!("%{:02X}", c as u32)),
+            '\\' => uri.push('/'),
+            c => uri.push(c),
+        }
+    }
+    uri.push_str("?mode=ro");
+    uri
+}
+
+/// Copy memories from the database at `other` into the one at `path`,
+/// skipping any whose id or trimmed content is already there. Only columns
+/// both schemas have are copied, so an older database merges too. The
+/// sidecar must be stopped first.
+pub fn merge_memories(path: &Path, other: &Path) -> Result<MergeReport, String> {
+    if !other.is_file() {
+        return Err(format!("Database not found at: {}", other.display()));
+    }
+    if fs::canonicalize(path).ok() == fs::canonicalize(other).ok() {
+        return Err("That is the database Engram is already using".to_string());
+    }
+
+    let mut conn = Connection::open(path).map_err(|e| format!("Failed to open database: {}", e))?;
+    conn.execute("ATTACH DATABASE ?1 AS other", [read_only_uri(other)])
+        .map_err(|e| format!("Failed to open {}: {}", other.display(), e))?;
+
+    let columns = |schema: &str| -> Result<Vec<String>, String> {
+        let mut stmt = conn
+            .prepare(&format!("PRAGMA {}.table_info(memories)", schema))
+            .map_err(|e| e.to_string())?;
+        let names = stmt
+            .query_map([], |row| row.get::<_, String>(1))
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        Ok(names)
+    };
+    let theirs = columns("other")?;
+    if theirs.is_empty() {
+        return Err(format!("{} is not an Engram database", other.display()));
+    }
+    let shared: Vec<String> = columns("main")?
+        .into_iter()
+        .filter(|name| theirs.contains(name))
+        .collect();
+    let column_list = shared.join(", ");
+
+    let mut ids = HashSet::new();
+    let mut hashes = HashSet::new();
+    {
+        let mut stmt = conn
+            .prepare("SELECT id, content FROM main.memories")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .map_err(|e| e.to_string())?;
+        for row in rows {
+            let (id, content) = row.map_err(|e| e.to_string())?;
+            ids.insert(id);
+            hashes.insert(content_hash(&content));
+        }
+    }
+
+    let mut report = MergeReport::default();
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    {
+        let mut select = tx
+            .prepare("SELECT rowid, id, content FROM other.memories ORDER BY created_at")
+            .map_err(|e| format!("Failed to read {}: {}", other.display(), e))?;
+        let mut insert = tx
+            .prepare(&format!(
+                "INSERT INTO main.memories ({0}) SELECT {0} FROM other.memories WHERE rowid = ?1",
+                column_list
+            ))
+            .map_err(|e| e.to_string())?;
+        let rows = select
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })
+            .map_err(|e| e.to_string())?;
+        for row in rows {
+            let (rowid, id, content) = row.map_err(|e| e.to_string())?;
+            report.found += 1;
+            if ids.contains(&id) {
+                report.skipped_ids += 1;
+                continue;
+            }
+            // Also catches duplicates within the other database
+            if !hashes.insert(content_hash(&content)) {
+                report.skipped_duplicates += 1;
+                continue;
+            }
+            insert
+                .execute([rowid])
+                .map_err(|e| format!("Failed to copy memory {}: {}", id, e))?;
+            ids.insert(id);
+            report.inserted += 1;
+        }
+    }
+    tx.commit().map_err(|e| format!("Failed to save merged memories: {}", e))?;
+    Ok(report)
+}
+
+/// Copy the memories table (its schema, indexes, and rows) from the
+/// database at `path` into a new standalone SQLite file at `dest`, for
+/// opening in data tools. Embeddings are left out unless
+/// `include_embeddings`. Returns how many memories were copied.
+pub fn export_memories(path: &Path, dest: &Path, include_embeddings: bool) -> Result<u64, String> {
+    if !path.is_file() {
+        return Err(format!("Database not found at: {}", path.display()));
+    }
+    if dest.exists() {
+        fs::remove_file(dest).map_err(|e| format!("Failed to replace {}: {}", dest.display(), e))?;
+    }
+
+    let mut conn = Connection::open(dest)
+        .map_err(|e| format!("Failed to create {}: {}", dest.display(), e))?;
+    conn.execute("ATTACH DATABASE ?1 AS source", [read_only_uri(path)])
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+
+    let schema = {
+        let mut stmt = conn
+            .prepare(
+                "SELECT sql FROM source.sqlite_master
+                 WHERE tbl_name = 'memories' AND type IN ('table', 'index') AND sql IS NOT NULL
+                 ORDER BY type = 'index'",
+            )
+            .map_err(|e| e.to_string())?;
+        let statements = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        statements
+    };
+    if schema.is_empty() {
+        return Err("The database has no memories table".to_string());
+    }
+    let columns = {
+        let mut stmt = conn
+            .prepare("PRAGMA source.table_info(memories)")
+            .map_err(|e| e.to_string())?;
+        let names = stmt
+            .query_map([], |row| row.get::<_, String>(1))
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        names
+    };
+    let select_list: Vec<String> = columns
+        .iter()
+        .map(|name| match name.as_str() {
+            "embedding" if !include_embeddings => "NULL".to_string(),
+            name => name.to_string(),
+        })
+        .collect();
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    // Unqualified CREATE statements land in the new file
+    for sql in &schema {
+        tx.execute_batch(sql)
+            .map_err(|e| format!("Failed to create the memories table: {}", e))?;
+    }
+    let copied = tx
+        .execute(
+            &format!(
+                "INSERT INTO main.memories ({}) SELECT {} FROM source.memories ORDER BY created_at",
+                columns.join(", "),
+                select_list.join(", ")
+            ),
+            [],
+        )
+        .map_err(|e| format!("Failed to copy memories: {}", e))?;
+    tx.commit().map_err(|e| format!("Failed to write {}: {}", dest.display(), e))?;
+    conn.execute("DETACH DATABASE source", [])
+        .map_err(|e| e.to_string())?;
+    Ok(copied as u64)
+}
+
+fn size_history_path() -> Result<PathBuf, String> {
+    Ok(crate::commands::get_engram_data_dir()?.join("db-size-history.json"))
+}
+
+fn read_size_history() -> Vec<SizeSample> {
+    size_history_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Store the current database size, unless a sample was taken within the
+/// last `SIZE_SAMPLE_INTERVAL_SECS`. Called periodically by the scheduler.
+pub fn record_size_sample(path: &Path, now: i64) -> Result<(), String> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let mut samples = read_size_history();
+    if samples
+        .last()
+        .is_some_and(|last| now - last.at < SIZE_SAMPLE_INTERVAL_SECS)
+    {
+        return Ok(());
+    }
+
+    samples.push(SizeSample {
+        at: now,
+        bytes: db_size(path),
+    });
+    let excess = samples.len().saturating_sub(MAX_SIZE_SAMPLES);
+    samples.drain(..excess);
+
+    let content = serde_json::to_string(&samples).map_err(|e| e.to_string())?;
+    fs::write(size_history_path()?, content)
+        .map_err(|e| format!("Failed to save size history: {}", e))
+}
+
+/// Fit a line through recent samples (least squares) and project when the
+/// database will reach the next size milestone.
+pub fn disk_usage_trend(path: &Path, now: i64) -> DiskUsageTrend {
+    let current_bytes = db_size(path);
+    let samples = read_size_history();
+
+    let mut points: Vec<(f64, f64)> = samples
+        .iter()
+        .filter(|s| now - s.at <= TREND_WINDOW_SECS)
+        .map(|s| (s.at as f64 / 86400.0, s.bytes as f64))
+        .collect();
+    points.push((now as f64 / 86400.0, current_bytes as f64));
+
+    let span_days = points.last().map(|p| p.0).unwrap_or(0.0) - points[0].0;
+    let bytes_per_day = if span_days >= 2.0 {
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+        let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+        let covariance: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+        let variance: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+        Some(covariance / variance)
+    } else {
+        None
+    };
+
+    let projection = bytes_per_day.filter(|rate| *rate > 0.0).and_then(|rate| {
+        let target_bytes = SIZE_MILESTONES
+            .iter()
+            .copied()
+            .find(|m| *m > current_bytes)?;
+        Some(SizeProjection {
+            target_bytes,
+            days: (target_bytes - current_bytes) as f64 / rate,
+        })
+    });
+
+    DiskUsageTrend {
+        current_bytes,
+        bytes_per_day,
+        projection,
+        samples,
+    }
+}
+
+fn sorted_counts(counts: HashMap<String, u64>) -> Vec<CountEntry> {
+    let mut entries: Vec<CountEntry> = counts
+        .into_iter()
+        .map(|(name, count)| CountEntry { name, count })
+        .collect();
+    entries.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    entries
+}
+
+/// Count memories by tag, source, and week created, reading the database
+/// directly so it works while the sidecar is busy. Weeks start on Monday in `tz`.
+pub fn memory_stats(path: &Path, tz: &Tz) -> Result<MemoryStats, String> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let mut stmt = conn
+        .prepare("SELECT tags, source, created_at FROM memories")
+        .map_err(|e| format!("Failed to read memories: {}", e))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, Option<String>>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })
+        .map_err(|e| format!("Failed to read memories: {}", e))?;
+
+    let mut total = 0;
+    let mut by_tag: HashMap<String, u64> = HashMap::new();
+    let mut by_source: HashMap<String, u64> = HashMap::new();
+    let mut by_week: BTreeMap<chrono::NaiveDate, u64> = BTreeMap::new();
+    for row in rows {
+        let (tags, source, created_at) = row.map_err(|e| format!("Failed to read memories: {}", e))?;
+        total += 1;
+        let tags: Vec<String> = tags
+            .and_then(|tags| serde_json::from_str(&tags).ok())
+            .unwrap_or_default();
+        for tag in tags {
+            *by_tag.entry(tag).or_default() += 1;
+        }
+        let source = source.filter(|s| !s.is_empty()).unwrap_or_else(|| "manual".to_string());
+        *by_source.entry(source).or_default() += 1;
+        if let Some(created) = tz.timestamp_millis_opt(created_at).single() {
+            let date = created.date_naive();
+            let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+            *by_week.entry(monday).or_default() += 1;
+        }
+    }
+
+    let today = crate::timestamps::now(tz).date_naive();
+    let this_week = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    let first_week = this_week - Duration::weeks(STATS_WEEKS as i64 - 1);
+    let mut running: u64 = by_week.range(..first_week).map(|(_, count)| count).sum();
+    let weeks = (0..STATS_WEEKS)
+        .map(|i| {
+            let week = first_week + Duration::weeks(i as i64);
+            let added = by_week.get(&week).copied().unwrap_or(0);
+            running += added;
+            WeekCount {
+                week_start: week.format("%Y-%m-%d").to_string(),
+                added,
+                total: running,
+            }
+        })
+        .collect();
+
+    Ok(MemoryStats {
+        total,
+        by_tag: sorted_counts(by_tag),
+        by_source: sorted_counts(by_source),
+        by_week: weeks,
+    })
+}
+
+/// Memories created on each local day in `tz` from `from` to `to`
+/// inclusive, with days that had none included as zero.
+pub fn memory_timeline(
+    path: &Path,
+    tz: &Tz,
+    from: chrono::NaiveDate,
+    to: chrono::NaiveDate,
+) -> Result<Vec<DayCount>, String> {
+    let local_midnight = |date: chrono::NaiveDate| {
+        tz.from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap_or_default())
+            .earliest()
+            .map(|t| t.timestamp_millis())
+            .ok_or_else(|| format!("Invalid date: {}", date))
+    };
+    let start = local_midnight(from)?;
+    let end = local_midnight(to + Duration::days(1))?;
+
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let mut stmt = conn
+        .prepare("SELECT created_at FROM memories WHERE created_at >= ?1 AND created_at < ?2")
+        .map_err(|e| format!("Failed to read memories: {}", e))?;
+    let rows = stmt
+        .query_map([start, end], |row| row.get::<_, i64>(0))
+        .map_err(|e| format!("Failed to read memories: {}", e))?;
+
+    let mut by_day: HashMap<chrono::NaiveDate, u64> = HashMap::new();
+    for created_at in rows {
+        let created_at = created_at.map_err(|e| format!("Failed to read memories: {}", e))?;
+        if let Some(created) = tz.timestamp_millis_opt(created_at).single() {
+            *by_day.entry(created.date_naive()).or_default() += 1;
+        }
+    }
+
+    Ok(from
+        .iter_days()
+        .take_while(|day| *day <= to)
+        .map(|day| DayCount {
+            date: day.format("%Y-%m-%d").to_string(),
+            created: by_day.get(&day).copied().unwrap_or(0),
+        })
+        .collect())
+}
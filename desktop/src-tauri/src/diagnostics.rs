@@ -0,0 +1,129 @@
+use std::path::Path;
+
+use rusqlite::{Connection, OpenFlags};
+use serde::Serialize;
+
+/// A memory counts as recently recalled if it was accessed within this window.
+pub const RECALL_WINDOW_MS: i64 = 7 * 24 * 60 * 60 * 1000;
+
+/// One step of the "memories aren't showing up" checklist.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticCheck {
+    pub id: String,
+    pub label: String,
+    /// "passed", "failed", "warning", or "skipped"
+    pub outcome: String,
+    pub detail: String,
+}
+
+/// Something that would explain missing memories, with the fix if there is
+/// one the app can apply.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LikelyCause {
+    /// 1 is the most likely.
+    pub priority: u8,
+    pub title: String,
+    pub detail: String,
+    /// "connect-agent" or "restart-sidecar"; `None` when the user has to act.
+    pub fix: Option<String>,
+    pub fix_label: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnosis {
+    pub agent_id: String,
+    pub agent_name: String,
+    pub checks: Vec<DiagnosticCheck>,
+    /// Most likely first.
+    pub causes: Vec<LikelyCause>,
+}
+
+impl Diagnosis {
+    pub fn new(agent_id: &str, agent_name: &str) -> Self {
+        Self {
+            agent_id: agent_id.to_string(),
+            agent_name: agent_name.to_string(),
+            checks: Vec::new(),
+            causes: Vec::new(),
+        }
+    }
+
+    pub fn check(&mut self, id: &str, label: &str, outcome: &str, detail: String) {
+        self.checks.push(DiagnosticCheck {
+            id: id.to_string(),
+            label: label.to_string(),
+            outcome: outcome.to_string(),
+            detail,
+        });
+    }
+
+    pub fn cause(&mut self, priority: u8, title: String, detail: String, fix: Option<(&str, &str)>) {
+        self.causes.push(LikelyCause {
+            priority,
+            title,
+            detail,
+            fix: fix.map(|(fix, _)| fix.to_string()),
+            fix_label: fix.map(|(_, label)| label.to_string()),
+        });
+    }
+
+    pub fn finish(mut self) -> Self {
+        self.causes.sort_by_key(|cause| cause.priority);
+        self
+    }
+}
+
+/// Process names the agent's app runs under.
+fn process_names(agent_id: &str) -> &'static [&'static str] {
+    match agent_id {
+        "claude-desktop" if cfg!(target_os = "macos") => &["Claude"],
+        "claude-desktop" => &["claude", "claude-desktop"],
+        "claude-code" => &["claude"],
+        "cursor" => &["Cursor", "cursor"],
+        "windsurf" => &["Windsurf", "windsurf"],
+        _ => &[],
+    }
+}
+
+/// Whether the agent started before its config last changed and so still
+/// has the old config loaded. `None` when the agent isn't running.
+pub fn agent_restart_needed(agent_id: &str, config_path: &Path) -> Option<bool> {
+    use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+
+    let names = process_names(agent_id);
+    let modified = std::fs::metadata(config_path)
+        .and_then(|m| m.modified())
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_secs();
+
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::new());
+    let started = system
+        .processes()
+        .values()
+        .filter(|process| {
+            let name = process.name().to_string_lossy();
+            let name = name.strip_suffix(".exe").unwrap_or(&name);
+            names.contains(&name)
+        })
+        .map(|process| process.start_time())
+        .min()?;
+    Some(started < modified)
+}
+
+/// Memories in total, and how many an agent recalled within `RECALL_WINDOW_MS`.
+pub fn recall_activity(db_path: &Path, now_ms: i64) -> Result<(u64, u64), String> {
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    conn.query_row(
+        "SELECT COUNT(*), COUNT(CASE WHEN last_accessed >= ?1 THEN 1 END) FROM memories",
+        [now_ms - RECALL_WINDOW_MS],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+    .map_err(|e| format!("Failed to read memories: {}", e))
+}
@@ -0,0 +1,478 @@
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, ListBuilder, StringArray, StringBuilder, TimestampMillisecondArray};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use chrono_tz::Tz;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use serde::{Deserialize, Serialize};
+
+use crate::timestamps;
+
+/// File formats `export_data` can write.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// `{ "memories": [...] }`, what `import_data` reads back.
+    #[default]
+    Json,
+    /// One memory object per line.
+    Jsonl,
+    /// One row per memory, tags joined with `;`.
+    Csv,
+    /// A readable digest with a section per day.
+    Markdown,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Jsonl => "jsonl",
+            Self::Csv => "csv",
+            Self::Markdown => "md",
+        }
+    }
+}
+
+/// Limits an export to matching memories. Empty fields match everything.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ExportFilter {
+    /// Memories carrying all of these tags.
+    pub tags: Vec<String>,
+    /// Created at or after, Unix milliseconds.
+    pub since: Option<i64>,
+    /// Created before, Unix milliseconds.
+    pub until: Option<i64>,
+    /// Words that must all appear in the content, entity, or tags.
+    pub query: Option<String>,
+}
+
+impl ExportFilter {
+    /// The filter as `GET /api/memories` query parameters.
+    pub fn query_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        let tags: Vec<&str> = self.tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()).collect();
+        if !tags.is_empty() {
+            params.push(("tags", tags.join(",")));
+        }
+        if let Some(since) = self.since {
+            params.push(("since", since.to_string()));
+        }
+        if let Some(until) = self.until {
+            params.push(("until", until.to_string()));
+        }
+        if let Some(query) = self.query.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+            params.push(("q", query.to_string()));
+        }
+        params
+    }
+}
+
+/// Emitted as `export-progress` after each page of an export.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportProgress {
+    pub exported: u64,
+    pub total: u64,
+}
+
+/// Where an export is written: the file itself, or an age stream encrypting
+/// into it with a passphrase.
+pub enum ExportOutput {
+    Plain(BufWriter<File>),
+    Encrypted(age::stream::StreamWriter<BufWriter<File>>),
+}
+
+impl ExportOutput {
+    pub fn new(file: File, password: Option<&str>) -> io::Result<Self> {
+        let out = BufWriter::new(file);
+        Ok(match password {
+            None => Self::Plain(out),
+            Some(password) => {
+                let encryptor = age::Encryptor::with_user_passphrase(
+                    age::secrecy::SecretString::new(password.to_string()),
+                );
+                let stream = encryptor
+                    .wrap_output(out)
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+                Self::Encrypted(stream)
+            }
+        })
+    }
+
+    /// Write the final encrypted chunk, if any, and flush the file.
+    pub fn finish(self) -> io::Result<()> {
+        let mut out = match self {
+            Self::Plain(out) => out,
+            Self::Encrypted(stream) => stream.finish()?,
+        };
+        out.flush()
+    }
+}
+
+impl Write for ExportOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Plain(out) => out.write(buf),
+            Self::Encrypted(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Plain(out) => out.flush(),
+            Self::Encrypted(stream) => stream.flush(),
+        }
+    }
+}
+
+const CSV_COLUMNS: [&str; 11] = [
+    "id",
+    "content",
+    "category",
+    "entity",
+    "confidence",
+    "namespace",
+    "tags",
+    "source",
+    "accessCount",
+    "createdAt",
+    "lastAccessed",
+];
+
+/// Writes memories as they are fetched, so an export never holds the whole
+/// database in memory.
+pub struct ExportWriter<W: Write> {
+    out: W,
+    format: ExportFormat,
+    tz: Tz,
+    written: u64,
+    /// Day heading of the last Markdown entry.
+    current_day: Option<String>,
+}
+
+impl<W: Write> ExportWriter<W> {
+    /// Start an export of `total` memories (used in the Markdown header).
+    pub fn begin(mut out: W, format: ExportFormat, tz: Tz, total: u64) -> io::Result<Self> {
+        match format {
+            ExportFormat::Json => write!(
+                out,
+                "{{\n  \"exportedAt\": {},\n  \"memories\": [",
+                serde_json::json!(timestamps::now(&tz).to_rfc3339())
+            )?,
+            ExportFormat::Jsonl => {}
+            ExportFormat::Csv => writeln!(out, "{}", CSV_COLUMNS.join(","))?,
+            ExportFormat::Markdown => write!(
+                out,
+                "# Engram memories\n\nExported {}, {} memories.\n",
+                timestamps::now(&tz).to_rfc3339(),
+                total
+            )?,
+        }
+        Ok(Self {
+            out,
+            format,
+            tz,
+            written: 0,
+            current_day: None,
+        })
+    }
+
+    pub fn write(&mut self, memory: &serde_json::Value) -> io::Result<()> {
+        match self.format {
+            ExportFormat::Json => {
+                let separator = if self.written == 0 { "" } else { "," };
+                write!(self.out, "{}\n    {}", separator, memory)?;
+            }
+            ExportFormat::Jsonl => writeln!(self.out, "{}", memory)?,
+            ExportFormat::Csv => self.write_csv_row(memory)?,
+            ExportFormat::Markdown => self.write_markdown_entry(memory)?,
+        }
+        self.written += 1;
+        Ok(())
+    }
+
+    /// Close the document and flush it; returns how many memories were
+    /// written, and the output for callers that need to finalize it.
+    pub fn finish(mut self) -> io::Result<(u64, W)> {
+        if let ExportFormat::Json = self.format {
+            let close = if self.written == 0 { "]\n}\n" } else { "\n  ]\n}\n" };
+            self.out.write_all(close.as_bytes())?;
+        }
+        self.out.flush()?;
+        Ok((self.written, self.out))
+    }
+
+    fn write_csv_row(&mut self, memory: &serde_json::Value) -> io::Result<()> {
+        let row: Vec<String> = CSV_COLUMNS
+            .iter()
+            .map(|&column| match column {
+                "tags" => tags(memory).join(";"),
+                "createdAt" | "lastAccessed" => timestamp(&memory[column], &self.tz),
+                _ => match &memory[column] {
+                    serde_json::Value::Null => String::new(),
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                },
+            })
+            .map(|field| csv_field(&field))
+            .collect();
+        writeln!(self.out, "{}", row.join(","))
+    }
+
+    fn write_markdown_entry(&mut self, memory: &serde_json::Value) -> io::Result<()> {
+        let created = timestamp(&memory["createdAt"], &self.tz);
+        let day = created.get(..10).unwrap_or("Undated").to_string();
+        if self.current_day.as_deref() != Some(day.as_str()) {
+            write!(self.out, "\n## {}\n", day)?;
+            self.current_day = Some(day);
+        }
+
+        let mut details = Vec::new();
+        if let Some(category) = memory["category"].as_str() {
+            details.push(category.to_string());
+        }
+        if let Some(entity) = memory["entity"].as_str().filter(|e| !e.is_empty()) {
+            details.push(entity.to_string());
+        }
+        let tags = tags(memory);
+        if !tags.is_empty() {
+            details.push(tags.iter().map(|t| format!("`{}`", t)).collect::<Vec<_>>().join(" "));
+        }
+        let content = memory["content"].as_str().unwrap_or_default().trim();
+        write!(self.out, "\n{}\n", content)?;
+        if !details.is_empty() {
+            write!(self.out, "\n_{}_\n", details.join(" · "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes memories as a Parquet file with typed columns, for loading into
+/// pandas or DuckDB without parsing JSON.
+pub struct ParquetWriter {
+    writer: ArrowWriter<File>,
+    schema: SchemaRef,
+    written: u64,
+}
+
+impl ParquetWriter {
+    pub fn create(file: File) -> io::Result<Self> {
+        let tag = Field::new("item", DataType::Utf8, true);
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Utf8, false),
+            Field::new("content", DataType::Utf8, false),
+            Field::new("tags", DataType::List(Arc::new(tag)), false),
+            Field::new(
+                "created_at",
+                DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+                true,
+            ),
+            Field::new("source", DataType::Utf8, true),
+        ]));
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        let writer = ArrowWriter::try_new(file, schema.clone(), Some(properties))
+            .map_err(parquet_error)?;
+        Ok(Self {
+            writer,
+            schema,
+            written: 0,
+        })
+    }
+
+    /// Append a page of memories.
+    pub fn write_page(&mut self, memories: &[serde_json::Value]) -> io::Result<()> {
+        if memories.is_empty() {
+            return Ok(());
+        }
+        let text = |key: &str| -> StringArray {
+            memories.iter().map(|m| m[key].as_str()).collect()
+        };
+        let required_text = |key: &str| -> StringArray {
+            memories
+                .iter()
+                .map(|m| Some(m[key].as_str().unwrap_or_default()))
+                .collect()
+        };
+        let mut tag_lists = ListBuilder::new(StringBuilder::new());
+        for memory in memories {
+            for tag in tags(memory) {
+                tag_lists.values().append_value(tag);
+            }
+            tag_lists.append(true);
+        }
+        let created_at = memories
+            .iter()
+            .map(|m| m["createdAt"].as_i64())
+            .collect::<TimestampMillisecondArray>()
+            .with_timezone("UTC");
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(required_text("id")),
+            Arc::new(required_text("content")),
+            Arc::new(tag_lists.finish()),
+            Arc::new(created_at),
+            Arc::new(text("source")),
+        ];
+        let batch = RecordBatch::try_new(self.schema.clone(), columns)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+        self.writer.write(&batch).map_err(parquet_error)?;
+        self.written += memories.len() as u64;
+        Ok(())
+    }
+
+    /// Write the file footer; returns how many memories were written.
+    pub fn finish(self) -> io::Result<u64> {
+        self.writer.close().map_err(parquet_error)?;
+        Ok(self.written)
+    }
+}
+
+fn parquet_error(e: parquet::errors::ParquetError) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, e.to_string())
+}
+
+/// Longest note title, in characters, before the file name is cut.
+const NOTE_TITLE_MAX_CHARS: usize = 60;
+
+/// Writes each memory as a Markdown note with YAML frontmatter, for browsing
+/// in Obsidian.
+pub struct VaultWriter {
+    dir: PathBuf,
+    tz: Tz,
+    /// File names taken in this export, lowercased for case-insensitive
+    /// filesystems.
+    used: HashSet<String>,
+    written: u64,
+}
+
+impl VaultWriter {
+    /// Prepare `dir`, removing the notes a previous export left there.
+    pub fn create(dir: &Path, tz: Tz) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "md") && is_engram_note(&path) {
+                fs::remove_file(&path)?;
+            }
+        }
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            tz,
+            used: HashSet::new(),
+            written: 0,
+        })
+    }
+
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    pub fn write(&mut self, memory: &serde_json::Value) -> io::Result<()> {
+        let id = memory["id"].as_str().unwrap_or_default();
+        let content = memory["content"].as_str().unwrap_or_default().trim();
+        let mut name = note_title(content);
+        if !self.used.insert(name.to_lowercase()) {
+            name = format!("{} ({})", name, id.get(..8).unwrap_or(id));
+            self.used.insert(name.to_lowercase());
+        }
+
+        let mut note = String::from("---\n");
+        let quoted = |value: &str| serde_json::Value::from(value).to_string();
+        note.push_str(&format!("engram-id: {}\n", quoted(id)));
+        for (key, field) in [("created", "createdAt"), ("accessed", "lastAccessed")] {
+            let at = timestamp(&memory[field], &self.tz);
+            if !at.is_empty() {
+                note.push_str(&format!("{}: {}\n", key, at));
+            }
+        }
+        for key in ["category", "entity", "source", "namespace"] {
+            if let Some(value) = memory[key].as_str().filter(|v| !v.is_empty()) {
+                note.push_str(&format!("{}: {}\n", key, quoted(value)));
+            }
+        }
+        if let Some(confidence) = memory["confidence"].as_f64() {
+            note.push_str(&format!("confidence: {}\n", confidence));
+        }
+        let tags = tags(memory);
+        if !tags.is_empty() {
+            note.push_str("tags:\n");
+            for tag in tags {
+                // Obsidian tags can't contain spaces
+                note.push_str(&format!("  - {}\n", quoted(&tag.replace(' ', "-"))));
+            }
+        }
+        note.push_str("---\n\n");
+        note.push_str(content);
+        note.push('\n');
+
+        fs::write(self.dir.join(format!("{}.md", name)), note)?;
+        self.written += 1;
+        Ok(())
+    }
+}
+
+/// A note's file name: the first line of the memory without characters
+/// Obsidian or the filesystem reject, cut to `NOTE_TITLE_MAX_CHARS`.
+fn note_title(content: &str) -> String {
+    let first_line = content.lines().next().unwrap_or_default();
+    let cleaned: String = first_line
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '^' | '[' | ']' => ' ',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect();
+    let words: Vec<&str> = cleaned.split_whitespace().collect();
+    let title = crate::labels::truncate_end(&words.join(" "), NOTE_TITLE_MAX_CHARS);
+    let title = title.trim_start_matches('.').trim();
+    if title.is_empty() {
+        "Untitled".to_string()
+    } else {
+        title.to_string()
+    }
+}
+
+/// Whether the note at `path` was written by an export, so a user's own
+/// notes in the folder are left alone.
+fn is_engram_note(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|note| {
+        note.strip_prefix("---\n")
+            .is_some_and(|frontmatter| frontmatter.starts_with("engram-id: "))
+    })
+}
+
+/// A millisecond timestamp field as RFC 3339, or empty if unset.
+fn timestamp(value: &serde_json::Value, tz: &Tz) -> String {
+    value
+        .as_i64()
+        .map(|ms| timestamps::format_epoch(ms / 1000, tz))
+        .unwrap_or_default()
+}
+
+fn tags(memory: &serde_json::Value) -> Vec<&str> {
+    memory["tags"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|t| t.as_str())
+        .collect()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
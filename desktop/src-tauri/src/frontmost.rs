@@ -0,0 +1,62 @@
+use std::sync::Mutex;
+
+/// Engram's own bundle; captures made from its windows have no outside app.
+const OWN_BUNDLE_ID: &str = "com.engram.app";
+
+/// Prints the frontmost app as `bundle id\tname` through NSWorkspace, which
+/// needs no Accessibility or Automation permission.
+const FRONTMOST_SCRIPT: &str = r#"
+ObjC.import('AppKit');
+const app = $.NSWorkspace.sharedWorkspace.frontmostApplication;
+app.bundleIdentifier.js + '\t' + app.localizedName.js
+"#;
+
+/// The app that was in front when the quick-add panel was opened.
+static AT_CAPTURE: Mutex<Option<FrontmostApp>> = Mutex::new(None);
+
+#[derive(Debug, Clone)]
+pub struct FrontmostApp {
+    pub bundle_id: String,
+    pub name: String,
+}
+
+impl FrontmostApp {
+    pub fn tag(&self) -> String {
+        format!("app:{}", self.name)
+    }
+}
+
+fn read() -> Option<FrontmostApp> {
+    if !cfg!(target_os = "macos") {
+        return None;
+    }
+    let output = std::process::Command::new("osascript")
+        .args(["-l", "JavaScript", "-e", FRONTMOST_SCRIPT])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (bundle_id, name) = stdout.trim().split_once('\t')?;
+    if bundle_id.is_empty() || name.is_empty() {
+        return None;
+    }
+    Some(FrontmostApp {
+        bundle_id: bundle_id.to_string(),
+        name: name.to_string(),
+    })
+}
+
+/// Note the frontmost app; called just before the quick-add panel takes
+/// focus. Nothing is recorded when the panel is opened from Engram itself.
+pub fn record() {
+    let app = read().filter(|app| app.bundle_id != OWN_BUNDLE_ID);
+    if let Ok(mut recorded) = AT_CAPTURE.lock() {
+        *recorded = app;
+    }
+}
+
+pub fn recorded() -> Option<FrontmostApp> {
+    AT_CAPTURE.lock().ok()?.clone()
+}
@@ -0,0 +1,292 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::time::sleep;
+
+use crate::commands;
+use crate::sidecar::{SidecarState, SidecarStatus};
+
+const API_BASE: &str = "https://api.github.com";
+/// Keychain account holding the personal access token.
+const TOKEN_ACCOUNT: &str = "github-token";
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// How far back the first sync looks.
+const INITIAL_LOOKBACK_DAYS: i64 = 14;
+/// Pull requests per search; later ones are picked up on the next run.
+const SEARCH_PAGE_SIZE: u32 = 30;
+
+/// A merged pull request authored by the user.
+#[derive(Debug, Clone)]
+pub struct MergedPullRequest {
+    pub repo: String,
+    pub number: u64,
+    pub title: String,
+    pub body: String,
+    pub url: String,
+}
+
+/// The user's review comments on one pull request that have not been
+/// captured yet.
+#[derive(Debug, Clone)]
+pub struct ReviewActivity {
+    pub repo: String,
+    pub number: u64,
+    pub title: String,
+    pub url: String,
+    /// Comment ids, recorded once the activity is stored.
+    pub ids: Vec<String>,
+    /// Review summaries and inline comments (`path: comment`), oldest first.
+    pub comments: Vec<String>,
+}
+
+/// What has been captured, kept in ~/.engram/github-sync.json.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SyncState {
+    /// Account the state belongs to; a different token starts over.
+    pub login: String,
+    /// Day (`YYYY-MM-DD`) the previous run searched from.
+    pub since: Option<String>,
+    /// Pull request URLs and review comment ids already stored.
+    pub captured: HashSet<String>,
+}
+
+fn state_path() -> Result<PathBuf, String> {
+    Ok(crate::profiles::root_dir()?.join("github-sync.json"))
+}
+
+pub fn load_state(login: &str) -> SyncState {
+    let state: SyncState = state_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    if state.login == login {
+        state
+    } else {
+        SyncState {
+            login: login.to_string(),
+            ..SyncState::default()
+        }
+    }
+}
+
+pub fn save_state(state: &SyncState) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+    fs::write(state_path()?, json).map_err(|e| format!("Failed to save GitHub sync state: {}", e))
+}
+
+/// The day to search from: the previous run's, or the lookback window on
+/// the first run. Searches are day-granular, so overlap is skipped by id.
+pub fn search_since(state: &SyncState) -> String {
+    state.since.clone().unwrap_or_else(|| {
+        (chrono::Utc::now() - chrono::Duration::days(INITIAL_LOOKBACK_DAYS))
+            .format("%Y-%m-%d")
+            .to_string()
+    })
+}
+
+pub fn today() -> String {
+    chrono::Utc::now().format("%Y-%m-%d").to_string()
+}
+
+pub fn set_token(token: &str) -> Result<(), String> {
+    crate::keychain::set(TOKEN_ACCOUNT, token)
+}
+
+pub fn clear_token() -> Result<(), String> {
+    crate::keychain::delete(TOKEN_ACCOUNT)
+}
+
+/// `owner/name` from a search result's `repository_url`.
+fn repo_name(repository_url: &str) -> String {
+    repository_url
+        .trim_start_matches(API_BASE)
+        .trim_start_matches("/repos/")
+        .to_string()
+}
+
+pub struct GitHubClient {
+    client: reqwest::Client,
+    token: String,
+}
+
+impl GitHubClient {
+    /// The client for the token in the keychain.
+    pub fn from_keychain() -> Result<Self, String> {
+        let token = crate::keychain::get(TOKEN_ACCOUNT)?.ok_or("No GitHub token is configured")?;
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent("Engram")
+            .build()
+            .map_err(|e| e.to_string())?;
+        Ok(Self { client, token })
+    }
+
+    async fn get(&self, path: &str) -> Result<serde_json::Value, String> {
+        let resp = self
+            .client
+            .get(format!("{}{}", API_BASE, path))
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach GitHub: {}", e))?;
+        let status = resp.status();
+        let value: serde_json::Value = resp.json().await.unwrap_or_default();
+        if !status.is_success() {
+            let message = value["message"].as_str().unwrap_or("request failed");
+            return Err(format!("GitHub API error ({}): {}", status, message));
+        }
+        Ok(value)
+    }
+
+    /// Login of the token's user.
+    pub async fn login(&self) -> Result<String, String> {
+        let user = self.get("/user").await?;
+        user["login"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| "GitHub did not return the token's user".to_string())
+    }
+
+    async fn search_pull_requests(&self, query: &str) -> Result<Vec<serde_json::Value>, String> {
+        let path = format!(
+            "/search/issues?q={}&sort=updated&order=asc&per_page={}",
+            urlencode(query),
+            SEARCH_PAGE_SIZE
+        );
+        let result = self.get(&path).await?;
+        Ok(result["items"].as_array().cloned().unwrap_or_default())
+    }
+
+    /// Pull requests by `login` merged on or after `since`.
+    pub async fn merged_pull_requests(
+        &self,
+        login: &str,
+        since: &str,
+    ) -> Result<Vec<MergedPullRequest>, String> {
+        let query = format!("is:pr is:merged author:{} merged:>={}", login, since);
+        Ok(self
+            .search_pull_requests(&query)
+            .await?
+            .iter()
+            .map(|item| MergedPullRequest {
+                repo: repo_name(item["repository_url"].as_str().unwrap_or_default()),
+                number: item["number"].as_u64().unwrap_or_default(),
+                title: item["title"].as_str().unwrap_or_default().to_string(),
+                body: item["body"].as_str().unwrap_or_default().trim().to_string(),
+                url: item["html_url"].as_str().unwrap_or_default().to_string(),
+            })
+            .collect())
+    }
+
+    /// Review comments `login` left on other people's pull requests updated
+    /// on or after `since`, skipping comment ids in `captured`.
+    pub async fn review_activity(
+        &self,
+        login: &str,
+        since: &str,
+        captured: &HashSet<String>,
+    ) -> Result<Vec<ReviewActivity>, String> {
+        let query = format!("is:pr reviewed-by:{} -author:{} updated:>={}", login, login, since);
+        let mut activity = Vec::new();
+        for item in self.search_pull_requests(&query).await? {
+            let repo = repo_name(item["repository_url"].as_str().unwrap_or_default());
+            let number = item["number"].as_u64().unwrap_or_default();
+            let mut entry = ReviewActivity {
+                repo: repo.clone(),
+                number,
+                title: item["title"].as_str().unwrap_or_default().to_string(),
+                url: item["html_url"].as_str().unwrap_or_default().to_string(),
+                ids: Vec::new(),
+                comments: Vec::new(),
+            };
+
+            let reviews = self
+                .get(&format!("/repos/{}/pulls/{}/reviews?per_page=100", repo, number))
+                .await?;
+            for review in reviews.as_array().into_iter().flatten() {
+                let id = format!("review:{}", review["id"]);
+                let body = review["body"].as_str().unwrap_or_default().trim();
+                if review["user"]["login"] == login && !body.is_empty() && !captured.contains(&id) {
+                    entry.ids.push(id);
+                    entry.comments.push(body.to_string());
+                }
+            }
+
+            let comments = self
+                .get(&format!("/repos/{}/pulls/{}/comments?per_page=100", repo, number))
+                .await?;
+            for comment in comments.as_array().into_iter().flatten() {
+                let id = format!("comment:{}", comment["id"]);
+                let body = comment["body"].as_str().unwrap_or_default().trim();
+                if comment["user"]["login"] == login && !body.is_empty() && !captured.contains(&id) {
+                    let path = comment["path"].as_str().unwrap_or_default();
+                    entry.ids.push(id);
+                    entry.comments.push(format!("{}: {}", path, body));
+                }
+            }
+
+            if !entry.comments.is_empty() {
+                activity.push(entry);
+            }
+        }
+        Ok(activity)
+    }
+}
+
+fn urlencode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Spawn the GitHub capture loop. While enabled in preferences, the user's
+/// merged pull requests and review comments since the previous run are
+/// stored as memories every `interval_minutes`. Runs are skipped while the
+/// sidecar is down.
+pub fn setup_sync_schedule(app: &AppHandle) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut last_run: Option<Instant> = None;
+
+        loop {
+            sleep(SCHEDULE_CHECK_INTERVAL).await;
+
+            let settings = commands::read_preferences().unwrap_or_default().github;
+            let interval = Duration::from_secs(settings.interval_minutes.max(15) * 60);
+            if !settings.enabled || last_run.is_some_and(|t| t.elapsed() < interval) {
+                continue;
+            }
+            let state = app_handle.state::<SidecarState>();
+            if !matches!(*state.status.lock().await, SidecarStatus::Running) {
+                continue;
+            }
+            last_run = Some(Instant::now());
+
+            match commands::sync_github(&app_handle).await {
+                Ok(report) => {
+                    if report.created > 0 {
+                        engram_log!("GitHub sync captured {} memories", report.created);
+                    }
+                    let _ = app_handle.emit("github-sync-complete", &report);
+                }
+                Err(e) => {
+                    engram_log!("GitHub sync failed: {}", e);
+                    let _ = app_handle.emit("github-sync-failed", e);
+                }
+            }
+        }
+    });
+}
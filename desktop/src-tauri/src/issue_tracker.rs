@@ -0,0 +1,247 @@
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::time::sleep;
+
+use crate::commands::{self, IssueTrackerSettings};
+use crate::sidecar::{SidecarState, SidecarStatus};
+
+const LINEAR_API_URL: &str = "https://api.linear.app/graphql";
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+static ISSUE_KEY: OnceLock<Regex> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueTrackerProvider {
+    #[default]
+    Linear,
+    Jira,
+}
+
+impl IssueTrackerProvider {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Linear => "linear",
+            Self::Jira => "jira",
+        }
+    }
+
+    /// Keychain account holding the provider's API token.
+    fn token_account(self) -> String {
+        format!("{}-token", self.as_str())
+    }
+}
+
+/// An issue's current state, stored as a ref on memories that mention it.
+#[derive(Debug, Clone, Serialize)]
+pub struct Issue {
+    pub key: String,
+    pub provider: &'static str,
+    pub title: String,
+    pub status: String,
+    pub url: String,
+}
+
+pub fn set_token(provider: IssueTrackerProvider, token: &str) -> Result<(), String> {
+    crate::keychain::set(&provider.token_account(), token)
+}
+
+pub fn clear_token(provider: IssueTrackerProvider) -> Result<(), String> {
+    crate::keychain::delete(&provider.token_account())
+}
+
+/// Issue keys such as `ENG-123` in `content`, sorted and without repeats.
+/// With `project_keys`, only keys in those projects are returned; without,
+/// anything shaped like a key is (lookups of the rest just find nothing).
+pub fn issue_keys(content: &str, project_keys: &[String]) -> Vec<String> {
+    let pattern = ISSUE_KEY.get_or_init(|| {
+        Regex::new(r"\b([A-Z][A-Z0-9]{1,9})-(\d{1,6})\b").expect("valid issue key pattern")
+    });
+    pattern
+        .captures_iter(content)
+        .filter(|caps| {
+            project_keys.is_empty() || project_keys.iter().any(|p| p.eq_ignore_ascii_case(&caps[1]))
+        })
+        .map(|caps| caps[0].to_string())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+pub struct IssueClient {
+    client: reqwest::Client,
+    settings: IssueTrackerSettings,
+    token: String,
+}
+
+impl IssueClient {
+    /// The client for the configured provider and the token in the keychain.
+    pub fn from_settings(settings: &IssueTrackerSettings) -> Result<Self, String> {
+        let account = settings.provider.token_account();
+        let token = crate::keychain::get(&account)?
+            .ok_or_else(|| format!("No {} token is configured", settings.provider.as_str()))?;
+        if settings.provider == IssueTrackerProvider::Jira
+            && (settings.jira_url.trim().is_empty() || settings.jira_email.trim().is_empty())
+        {
+            return Err("Jira needs the site URL and the account email".to_string());
+        }
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(20))
+            .build()
+            .map_err(|e| e.to_string())?;
+        Ok(Self {
+            client,
+            settings: settings.clone(),
+            token,
+        })
+    }
+
+    fn jira_url(&self, path: &str) -> String {
+        format!("{}{}", self.settings.jira_url.trim().trim_end_matches('/'), path)
+    }
+
+    async fn linear_query(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        let resp = self
+            .client
+            .post(LINEAR_API_URL)
+            // Linear personal API keys are sent without a scheme
+            .header("Authorization", &self.token)
+            .json(&serde_json::json!({ "query": query, "variables": variables }))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach Linear: {}", e))?;
+        let status = resp.status();
+        let body: serde_json::Value = resp.json().await.unwrap_or_default();
+        if !status.is_success() && body["data"].is_null() {
+            let message = body["errors"][0]["message"].as_str().unwrap_or("request failed");
+            return Err(format!("Linear API error ({}): {}", status, message));
+        }
+        Ok(body)
+    }
+
+    /// Name of the account the token belongs to, to check the settings.
+    pub async fn verify(&self) -> Result<String, String> {
+        match self.settings.provider {
+            IssueTrackerProvider::Linear => {
+                let body = self
+                    .linear_query("query { viewer { name } }", serde_json::json!({}))
+                    .await?;
+                body["data"]["viewer"]["name"]
+                    .as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| "Linear rejected the API key".to_string())
+            }
+            IssueTrackerProvider::Jira => {
+                let resp = self
+                    .client
+                    .get(self.jira_url("/rest/api/2/myself"))
+                    .basic_auth(self.settings.jira_email.trim(), Some(&self.token))
+                    .send()
+                    .await
+                    .map_err(|e| format!("Failed to reach Jira: {}", e))?;
+                if !resp.status().is_success() {
+                    return Err(format!("Jira rejected the credentials (HTTP {})", resp.status()));
+                }
+                let body: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
+                Ok(body["displayName"].as_str().unwrap_or_default().to_string())
+            }
+        }
+    }
+
+    /// The issue with `key`, or `None` if the workspace has no such issue.
+    pub async fn fetch(&self, key: &str) -> Result<Option<Issue>, String> {
+        let provider = self.settings.provider.as_str();
+        match self.settings.provider {
+            IssueTrackerProvider::Linear => {
+                let body = self
+                    .linear_query(
+                        "query($id: String!) { issue(id: $id) { identifier title url state { name } } }",
+                        serde_json::json!({ "id": key }),
+                    )
+                    .await?;
+                let issue = &body["data"]["issue"];
+                if issue.is_null() {
+                    return Ok(None);
+                }
+                Ok(Some(Issue {
+                    key: issue["identifier"].as_str().unwrap_or(key).to_string(),
+                    provider,
+                    title: issue["title"].as_str().unwrap_or_default().to_string(),
+                    status: issue["state"]["name"].as_str().unwrap_or_default().to_string(),
+                    url: issue["url"].as_str().unwrap_or_default().to_string(),
+                }))
+            }
+            IssueTrackerProvider::Jira => {
+                let resp = self
+                    .client
+                    .get(self.jira_url(&format!("/rest/api/2/issue/{}?fields=summary,status", key)))
+                    .basic_auth(self.settings.jira_email.trim(), Some(&self.token))
+                    .send()
+                    .await
+                    .map_err(|e| format!("Failed to reach Jira: {}", e))?;
+                if resp.status() == reqwest::StatusCode::NOT_FOUND {
+                    return Ok(None);
+                }
+                if !resp.status().is_success() {
+                    return Err(format!("Jira API error fetching {}: HTTP {}", key, resp.status()));
+                }
+                let issue: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
+                Ok(Some(Issue {
+                    key: issue["key"].as_str().unwrap_or(key).to_string(),
+                    provider,
+                    title: issue["fields"]["summary"].as_str().unwrap_or_default().to_string(),
+                    status: issue["fields"]["status"]["name"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                    url: self.jira_url(&format!("/browse/{}", key)),
+                }))
+            }
+        }
+    }
+}
+
+/// Spawn the issue enrichment loop. While enabled in preferences, every
+/// `interval_minutes` the issues mentioned by memories are looked up and
+/// their current title and status stored with the memories. Runs are
+/// skipped while the sidecar is down.
+pub fn setup_enrichment_schedule(app: &AppHandle) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut last_run: Option<Instant> = None;
+
+        loop {
+            sleep(SCHEDULE_CHECK_INTERVAL).await;
+
+            let settings = commands::read_preferences().unwrap_or_default().issue_tracker;
+            let interval = Duration::from_secs(settings.interval_minutes.max(5) * 60);
+            if !settings.enabled || last_run.is_some_and(|t| t.elapsed() < interval) {
+                continue;
+            }
+            let state = app_handle.state::<SidecarState>();
+            if !matches!(*state.status.lock().await, SidecarStatus::Running) {
+                continue;
+            }
+            last_run = Some(Instant::now());
+
+            match commands::enrich_issue_refs(&app_handle).await {
+                Ok(report) => {
+                    let _ = app_handle.emit("issue-enrichment-complete", &report);
+                }
+                Err(e) => {
+                    engram_log!("Issue enrichment failed: {}", e);
+                    let _ = app_handle.emit("issue-enrichment-failed", e);
+                }
+            }
+        }
+    });
+}
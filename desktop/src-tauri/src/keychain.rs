@@ -0,0 +1,30 @@
+use keyring::Entry;
+
+/// Keychain service name; each secret is stored under its own account.
+const SERVICE: &str = "com.engram.app";
+
+fn entry(account: &str) -> Result<Entry, String> {
+    Entry::new(SERVICE, account).map_err(|e| format!("Failed to open keychain: {}", e))
+}
+
+pub fn set(account: &str, secret: &str) -> Result<(), String> {
+    entry(account)?
+        .set_password(secret)
+        .map_err(|e| format!("Failed to save {} to the keychain: {}", account, e))
+}
+
+/// The stored secret, or `None` if there is none.
+pub fn get(account: &str) -> Result<Option<String>, String> {
+    match entry(account)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read {} from the keychain: {}", account, e)),
+    }
+}
+
+pub fn delete(account: &str) -> Result<(), String> {
+    match entry(account)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Failed to remove {} from the keychain: {}", account, e)),
+    }
+}
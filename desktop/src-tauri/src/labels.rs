@@ -0,0 +1,52 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Longest dynamic label the tray menu shows, in user-perceived characters.
+pub const TRAY_LABEL_MAX: usize = 40;
+const ELLIPSIS: &str = "…";
+
+/// `text` on one line, cut to `max` grapheme clusters with a trailing
+/// ellipsis. Cutting by grapheme keeps emoji and combining marks whole.
+pub fn truncate_end(text: &str, max: usize) -> String {
+    let text = single_line(text);
+    let graphemes: Vec<&str> = text.graphemes(true).collect();
+    if graphemes.len() <= max {
+        return text;
+    }
+    let keep = max.saturating_sub(1);
+    graphemes[..keep].concat().trim_end().to_string() + ELLIPSIS
+}
+
+/// `text` cut to `max` grapheme clusters by dropping the middle, which keeps
+/// both the root and the file name of a path visible.
+pub fn truncate_middle(text: &str, max: usize) -> String {
+    let text = single_line(text);
+    let graphemes: Vec<&str> = text.graphemes(true).collect();
+    if graphemes.len() <= max {
+        return text;
+    }
+    let keep = max.saturating_sub(1);
+    let tail = keep / 2;
+    let head = keep - tail;
+    format!(
+        "{}{}{}",
+        graphemes[..head].concat(),
+        ELLIPSIS,
+        graphemes[graphemes.len() - tail..].concat()
+    )
+}
+
+/// A tray label from arbitrary text: paths are cut in the middle, anything
+/// else at the end.
+pub fn tray_label(text: &str) -> String {
+    let looks_like_path = text.starts_with('/') || text.starts_with('~') || text.contains('\\');
+    if looks_like_path {
+        truncate_middle(text, TRAY_LABEL_MAX)
+    } else {
+        truncate_end(text, TRAY_LABEL_MAX)
+    }
+}
+
+/// Menu items are one line; newlines and tabs become spaces.
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
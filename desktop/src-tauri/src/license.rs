@@ -0,0 +1,189 @@
+use std::fs;
+use std::path::PathBuf;
+
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::bundle_update::decode_hex;
+
+/// Hex Ed25519 key that license keys are signed with, set by the release
+/// build. Builds without it run as the community edition.
+const LICENSE_PUBLIC_KEY: Option<&str> = option_env!("ENGRAM_LICENSE_PUBLIC_KEY");
+const LICENSE_FILE: &str = "license.key";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Edition {
+    #[default]
+    Community,
+    Pro,
+    Team,
+}
+
+/// Capabilities a license can unlock. Commands for paid features check
+/// them with `require`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Feature {
+    CloudSync,
+    TeamSharing,
+}
+
+impl Edition {
+    fn features(self) -> &'static [Feature] {
+        match self {
+            Self::Community => &[],
+            Self::Pro => &[Feature::CloudSync],
+            Self::Team => &[Feature::CloudSync, Feature::TeamSharing],
+        }
+    }
+}
+
+/// The signed part of a license key.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LicensePayload {
+    licensee: String,
+    edition: Edition,
+    /// Granted on top of the edition's own features.
+    #[serde(default)]
+    features: Vec<Feature>,
+    /// Unix seconds; `None` never expires.
+    #[serde(default)]
+    expires_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LicenseStatus {
+    /// No key activated.
+    None,
+    Valid,
+    Expired,
+    /// The stored key doesn't verify, e.g. it was edited by hand.
+    Invalid,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LicenseState {
+    pub status: LicenseStatus,
+    /// Community unless the license is valid.
+    pub edition: Edition,
+    pub licensee: Option<String>,
+    /// RFC 3339.
+    pub expires_at: Option<String>,
+    pub features: Vec<Feature>,
+    pub message: Option<String>,
+}
+
+impl LicenseState {
+    fn community(status: LicenseStatus, message: Option<String>) -> Self {
+        Self {
+            status,
+            edition: Edition::Community,
+            licensee: None,
+            expires_at: None,
+            features: Vec::new(),
+            message,
+        }
+    }
+}
+
+fn license_path() -> Result<PathBuf, String> {
+    Ok(crate::profiles::root_dir()?.join(LICENSE_FILE))
+}
+
+/// Check a `<payload hex>.<signature hex>` key, where the payload is the
+/// license JSON and the signature covers its bytes.
+fn verify(key: &str) -> Result<LicensePayload, String> {
+    let public_key = LICENSE_PUBLIC_KEY.ok_or("This build cannot activate licenses")?;
+    let public_key: [u8; 32] = decode_hex(public_key)?
+        .try_into()
+        .map_err(|_| "Invalid license public key".to_string())?;
+    let public_key = VerifyingKey::from_bytes(&public_key)
+        .map_err(|e| format!("Invalid license public key: {}", e))?;
+
+    let (payload_hex, signature_hex) = key
+        .trim()
+        .split_once('.')
+        .ok_or("Not an Engram license key")?;
+    let payload = decode_hex(payload_hex).map_err(|_| "Not an Engram license key".to_string())?;
+    let signature: [u8; 64] = decode_hex(signature_hex)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("Not an Engram license key")?;
+    public_key
+        .verify(&payload, &Signature::from_bytes(&signature))
+        .map_err(|_| "License key signature is not valid".to_string())?;
+    serde_json::from_slice(&payload).map_err(|e| format!("Invalid license data: {}", e))
+}
+
+fn evaluate(key: &str) -> LicenseState {
+    let payload = match verify(key) {
+        Ok(payload) => payload,
+        Err(e) => return LicenseState::community(LicenseStatus::Invalid, Some(e)),
+    };
+    let expires_at = payload
+        .expires_at
+        .and_then(|at| chrono::DateTime::from_timestamp(at, 0))
+        .map(|at| at.to_rfc3339());
+    if payload
+        .expires_at
+        .is_some_and(|at| at <= chrono::Utc::now().timestamp())
+    {
+        return LicenseState {
+            licensee: Some(payload.licensee),
+            expires_at,
+            ..LicenseState::community(LicenseStatus::Expired, Some("License has expired".to_string()))
+        };
+    }
+
+    let mut features = payload.edition.features().to_vec();
+    for feature in payload.features {
+        if !features.contains(&feature) {
+            features.push(feature);
+        }
+    }
+    LicenseState {
+        status: LicenseStatus::Valid,
+        edition: payload.edition,
+        licensee: Some(payload.licensee),
+        expires_at,
+        features,
+        message: None,
+    }
+}
+
+/// The license in ~/.engram/license.key, re-verified on every call so an
+/// expiry takes effect without a restart.
+pub fn state() -> LicenseState {
+    let key = license_path().ok().and_then(|path| fs::read_to_string(path).ok());
+    match key {
+        Some(key) if !key.trim().is_empty() => evaluate(&key),
+        _ => LicenseState::community(LicenseStatus::None, None),
+    }
+}
+
+/// Verify `key` and store it. Invalid or expired keys are not stored.
+pub fn activate(key: &str) -> Result<LicenseState, String> {
+    let state = evaluate(key);
+    if state.status != LicenseStatus::Valid {
+        return Err(state.message.unwrap_or_else(|| "License key is not valid".to_string()));
+    }
+    fs::write(license_path()?, key.trim())
+        .map_err(|e| format!("Failed to save license: {}", e))?;
+    engram_log!("Activated {:?} license", state.edition);
+    Ok(state)
+}
+
+/// Fail with a message naming the feature unless the license includes it.
+/// Commands for paid features call this first.
+#[allow(dead_code)] // No paid features have shipped yet
+pub fn require(feature: Feature) -> Result<(), String> {
+    if state().features.contains(&feature) {
+        Ok(())
+    } else {
+        Err(format!("{:?} needs an Engram license that includes it", feature))
+    }
+}
@@ -0,0 +1,158 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use chrono::{Local, NaiveDate};
+
+/// Log to stderr and to the persistent desktop log file.
+macro_rules! engram_log {
+    ($($arg:tt)*) => {{
+        let line = format!($($arg)*);
+        eprintln!("[engram] {}", line);
+        $crate::logging::write("desktop", &line);
+    }};
+}
+
+struct FileLogger {
+    dir: PathBuf,
+    max_file_bytes: u64,
+    retention_days: u32,
+    current_date: String,
+    file: Option<File>,
+    written: u64,
+}
+
+static LOGGER: Mutex<Option<FileLogger>> = Mutex::new(None);
+
+/// Start writing logs to `dir/desktop-YYYY-MM-DD.log`.
+/// Files are rotated once they exceed `max_file_mb` and deleted after `retention_days`.
+pub fn init(dir: PathBuf, max_file_mb: u64, retention_days: u32) {
+    if let Err(e) = fs::create_dir_all(&dir) {
+        eprintln!("[engram] Failed to create log directory {}: {}", dir.display(), e);
+        return;
+    }
+
+    let logger = FileLogger {
+        dir,
+        max_file_bytes: max_file_mb.max(1) * 1024 * 1024,
+        retention_days,
+        current_date: String::new(),
+        file: None,
+        written: 0,
+    };
+    logger.prune();
+
+    if let Ok(mut guard) = LOGGER.lock() {
+        *guard = Some(logger);
+    }
+}
+
+/// Apply changed rotation/retention settings to the running logger.
+pub fn configure(max_file_mb: u64, retention_days: u32) {
+    if let Ok(mut guard) = LOGGER.lock() {
+        if let Some(logger) = guard.as_mut() {
+            logger.max_file_bytes = max_file_mb.max(1) * 1024 * 1024;
+            logger.retention_days = retention_days;
+            logger.prune();
+        }
+    }
+}
+
+/// Append a line to the current log file. No-op until `init` has been called.
+pub fn write(source: &str, text: &str) {
+    if let Ok(mut guard) = LOGGER.lock() {
+        if let Some(logger) = guard.as_mut() {
+            logger.write(source, text);
+        }
+    }
+}
+
+impl FileLogger {
+    fn write(&mut self, source: &str, text: &str) {
+        let now = Local::now();
+        let line = format!(
+            "{} [{}] {}\n",
+            now.format("%Y-%m-%dT%H:%M:%S%.3f%:z"),
+            source,
+            text
+        );
+
+        let date = now.format("%Y-%m-%d").to_string();
+        if date != self.current_date {
+            self.current_date = date;
+            self.file = None;
+            self.prune();
+        }
+
+        if self.file.is_some() && self.written + line.len() as u64 > self.max_file_bytes {
+            self.rotate();
+        }
+
+        if self.file.is_none() {
+            self.open();
+        }
+
+        if let Some(file) = self.file.as_mut() {
+            if file.write_all(line.as_bytes()).is_ok() {
+                self.written += line.len() as u64;
+            }
+        }
+    }
+
+    fn current_path(&self) -> PathBuf {
+        self.dir.join(format!("desktop-{}.log", self.current_date))
+    }
+
+    fn open(&mut self) {
+        let path = self.current_path();
+        match OpenOptions::new().create(true).append(true).open(&path) {
+            Ok(file) => {
+                self.written = file.metadata().map(|m| m.len()).unwrap_or(0);
+                self.file = Some(file);
+            }
+            Err(e) => eprintln!("[engram] Failed to open log file {}: {}", path.display(), e),
+        }
+    }
+
+    /// Move the current file aside as `desktop-YYYY-MM-DD.N.log` using the next free N.
+    fn rotate(&mut self) {
+        self.file = None;
+        let current = self.current_path();
+        let mut n = 1;
+        let rotated = loop {
+            let candidate = self
+                .dir
+                .join(format!("desktop-{}.{}.log", self.current_date, n));
+            if !candidate.exists() {
+                break candidate;
+            }
+            n += 1;
+        };
+        if let Err(e) = fs::rename(&current, &rotated) {
+            eprintln!("[engram] Failed to rotate log file: {}", e);
+        }
+        self.written = 0;
+    }
+
+    /// Delete log files whose date is older than the retention window.
+    fn prune(&self) {
+        let cutoff = Local::now().date_naive() - chrono::Duration::days(self.retention_days as i64);
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let date = name
+                .strip_prefix("desktop-")
+                .and_then(|rest| rest.get(..10))
+                .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+            if let Some(date) = date {
+                if date < cutoff {
+                    let _ = fs::remove_file(entry.path());
+                }
+            }
+        }
+    }
+}
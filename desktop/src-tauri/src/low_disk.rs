@@ -0,0 +1,100 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter};
+use tokio::time::sleep;
+
+use crate::notifications::{notify, NotificationEvent};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// Read by the REST and MCP servers before every write; see `getReadOnlyReason`.
+const FLAG_FILE: &str = "read-only.json";
+/// Free space needed above the threshold before writes resume, so hovering
+/// around the threshold doesn't flip the mode every check.
+const RESUME_MARGIN_BYTES: u64 = 100 << 20;
+
+/// Free bytes on the volume holding `path`.
+pub fn free_space(path: &Path) -> Option<u64> {
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
+fn flag_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(FLAG_FILE)
+}
+
+/// Check once a minute whether the database volume is below
+/// `low_disk_threshold_mb`, and make Engram read-only until space is
+/// recovered. A threshold of 0 turns the check off.
+pub fn setup_disk_watch(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if let Err(e) = check(&app).await {
+                engram_log!("Disk space check failed: {}", e);
+            }
+            sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+async fn check(app: &AppHandle) -> Result<(), String> {
+    let threshold = crate::commands::read_preferences()
+        .unwrap_or_default()
+        .low_disk_threshold_mb
+        << 20;
+    let db_path = crate::database::active_db_path(app).await?;
+    let data_dir = db_path.parent().ok_or("Database has no parent directory")?;
+    let flag = flag_path(data_dir);
+    let read_only = flag.exists();
+
+    if threshold == 0 {
+        if read_only {
+            resume(app, &flag, "Low disk protection was turned off")?;
+        }
+        return Ok(());
+    }
+    let Some(free) = free_space(data_dir) else {
+        return Ok(());
+    };
+    if !read_only && free < threshold {
+        let reason = format!(
+            "Only {} MB of disk space is left; free up space to save new memories",
+            free >> 20
+        );
+        let content = serde_json::json!({ "reason": reason, "freeBytes": free });
+        fs::write(&flag, content.to_string())
+            .map_err(|e| format!("Failed to write {}: {}", flag.display(), e))?;
+        engram_log!("Switched to read-only: {} MB free", free >> 20);
+        notify(app, NotificationEvent::LowDisk, "Engram is read-only", &reason);
+        let _ = app.emit(
+            "read-only-changed",
+            serde_json::json!({ "readOnly": true, "reason": reason }),
+        );
+    } else if read_only && free >= threshold + RESUME_MARGIN_BYTES {
+        resume(app, &flag, &format!("Disk space recovered ({} MB free)", free >> 20))?;
+    }
+    Ok(())
+}
+
+fn resume(app: &AppHandle, flag: &Path, why: &str) -> Result<(), String> {
+    fs::remove_file(flag).map_err(|e| format!("Failed to remove {}: {}", flag.display(), e))?;
+    engram_log!("{}; writes resumed", why);
+    notify(
+        app,
+        NotificationEvent::LowDisk,
+        "Engram is saving again",
+        &format!("{}. New memories are saved again.", why),
+    );
+    let _ = app.emit(
+        "read-only-changed",
+        serde_json::json!({ "readOnly": false, "reason": null }),
+    );
+    Ok(())
+}
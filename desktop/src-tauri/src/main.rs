@@ -1,45 +1,143 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+#[macro_use]
+mod logging;
+
+mod analytics;
+mod apple_notes;
+mod backups;
+mod bundle_update;
+mod calendar;
+mod capture;
+mod cleanup;
 mod commands;
+mod config_reload;
+mod database;
+mod diagnostics;
+mod export;
+mod frontmost;
+mod github;
+mod issue_tracker;
+mod keychain;
+mod labels;
+mod license;
+mod low_disk;
+mod maintenance;
+mod mcp;
+mod normalize;
+mod notifications;
+mod notion;
+mod packs;
+mod power;
+mod presets;
+mod profiles;
+mod resurfacing;
+mod runtime;
+mod service;
+mod sessions;
+mod shell_hooks;
 mod sidecar;
+mod startup;
+mod tag_rules;
+mod timestamps;
 mod tray;
+mod windows;
 
+use commands::QuickAddState;
 use sidecar::SidecarState;
-use tauri::{Emitter, Manager};
 
 fn main() {
+    startup::begin();
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_opener::init())
         .manage(SidecarState::default())
+        .manage(QuickAddState::default())
         .setup(|app| {
+            let setup_started = std::time::Instant::now();
+
+            // Persist desktop and sidecar logs under ~/.engram/logs
+            let prefs = commands::read_preferences().unwrap_or_default();
+            if let Ok(data_dir) = profiles::root_dir() {
+                logging::init(
+                    data_dir.join("logs"),
+                    prefs.log_max_file_mb,
+                    prefs.log_retention_days,
+                );
+            }
+
             // Hide dock icon -- run as a menu bar (Accessory) app
             #[cfg(target_os = "macos")]
             {
                 app.set_activation_policy(tauri::ActivationPolicy::Accessory);
             }
 
-            // Create the system tray
-            if let Err(e) = tray::create_tray(app.handle()) {
-                eprintln!("[engram] Failed to create tray: {}", e);
-            }
+            // Create the system tray; agent status is filled in once detected
+            startup::timed("tray", || {
+                if let Err(e) = tray::create_tray(app.handle()) {
+                    engram_log!("Failed to create tray: {}", e);
+                }
+            });
+            tray::refresh_agents(app.handle());
 
-            // Start the sidecar Node.js process (synchronous spawn)
-            if let Err(e) = sidecar::start_sidecar(app.handle()) {
-                eprintln!("[engram] Failed to start sidecar: {}", e);
-            }
-
-            // Set up crash recovery and health check loop
-            sidecar::setup_sidecar_lifecycle(app.handle());
+            // Start sleep/wake monitoring, the sidecar supervisor, crash recovery,
+            // and health check loop, then the sidecar itself on the active profile
+            startup::timed("sidecar-spawn", || {
+                power::setup_power_monitor();
+                sidecar::setup_sidecar_lifecycle(app.handle());
+                profiles::init(app.handle());
+                sidecar::request_start(app.handle());
+            });
 
             // Register global shortcuts
-            register_global_shortcuts(app.handle());
+            startup::timed("shortcuts", || register_global_shortcuts(app.handle()));
+
+            // Schedules read their settings from disk; start them off the setup path
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                startup::timed("schedules", || {
+                    // Nightly sidecar recycle, if enabled in preferences
+                    maintenance::setup_maintenance_schedule(&app_handle);
+
+                    // Scheduled Notion sync, if configured
+                    notion::setup_sync_schedule(&app_handle);
+
+                    // Scheduled issue tracker lookups, if configured
+                    issue_tracker::setup_enrichment_schedule(&app_handle);
+
+                    // Scheduled GitHub activity capture, if configured
+                    github::setup_sync_schedule(&app_handle);
 
+                    // Meeting in progress for quick-add captures, if enabled
+                    calendar::setup_calendar_refresh();
+
+                    // "Remember this?" prompts, if enabled
+                    resurfacing::setup_resurfacing_schedule(&app_handle);
+
+                    // Deliver notifications held during quiet hours
+                    notifications::setup_quiet_hours_flush(&app_handle);
+
+                    // Read-only while the database volume is nearly full
+                    low_disk::setup_disk_watch(&app_handle);
+
+                    // Localhost capture endpoint, if enabled
+                    capture::setup_capture_endpoint(&app_handle);
+
+                    // Hourly analytics copy, while developer mode is on
+                    analytics::setup_refresh_schedule(&app_handle);
+                });
+            });
+
+            startup::record("setup", setup_started);
             Ok(())
         })
         .on_window_event(|window, event| {
-            // Hide window on close instead of quitting the app
+            // Hide windows on close instead of quitting the app
             if let tauri::WindowEvent::CloseRequested { api, .. } = event {
                 let _ = window.hide();
                 api.prevent_close();
@@ -50,36 +148,110 @@ fn main() {
             commands::check_first_run,
             commands::get_detected_agents,
             commands::configure_agent,
+            commands::remove_orphaned_agent_entries,
+            commands::validate_agent_config,
+            commands::simulate_mcp_session,
+            commands::diagnose_agent,
+            commands::create_support_bundle,
+            commands::report_frontend_error,
+            commands::get_license_state,
+            commands::activate_license,
+            commands::open_api_explorer,
+            commands::set_present_mode,
+            commands::get_present_memories,
+            commands::api_request,
+            commands::run_analytics_query,
             commands::complete_onboarding,
             commands::get_preferences,
             commands::save_preferences,
+            commands::export_preferences,
+            commands::import_preferences,
+            commands::set_custom_sound,
+            commands::preview_save_sound,
+            commands::list_profiles,
+            commands::create_profile,
+            commands::switch_profile,
+            commands::list_presets,
+            commands::save_preset,
+            commands::apply_preset,
+            commands::delete_preset,
+            commands::export_preset,
+            commands::import_preset,
             commands::set_start_at_login,
             commands::export_data,
+            commands::export_obsidian_vault,
+            commands::export_sqlite,
+            commands::export_parquet,
+            commands::import_data,
+            commands::create_memory_pack,
+            commands::preview_memory_pack,
+            commands::import_memory_pack,
+            commands::request_reset,
             commands::reset_database,
+            commands::factory_reset,
+            commands::get_disk_usage,
+            commands::get_stats,
+            commands::get_memory_timeline,
+            commands::vacuum_database,
+            commands::merge_databases,
+            commands::backup_database,
+            commands::list_backups,
+            commands::restore_backup,
+            commands::open_data_dir,
+            commands::get_cleanup_suggestions,
+            commands::archive_memories,
+            commands::stop_sidecar,
             commands::restart_sidecar,
+            commands::pause_sidecar,
+            commands::resume_sidecar,
+            commands::clear_restart_failures,
+            commands::add_memory,
+            commands::append_to_last_memory,
+            commands::fit_quick_add_window,
+            commands::hide_quick_add_window,
+            commands::get_sidecar_logs,
+            commands::copy_connection_info,
+            commands::search_memories,
+            commands::preview_search_settings,
+            commands::import_from_apple_notes,
+            commands::prepare_capture,
+            commands::apply_tag_rules,
+            commands::list_tags,
+            commands::rename_tag,
+            commands::merge_tags,
+            commands::delete_tag,
+            commands::install_shell_hook,
+            commands::uninstall_shell_hook,
+            commands::start_session,
+            commands::get_session,
+            commands::end_session,
+            commands::get_sidecar_resolution,
+            commands::get_startup_timings,
+            commands::check_sidecar_update,
+            commands::check_sidecar_release,
+            commands::apply_sidecar_update,
+            commands::configure_notion_sync,
+            commands::run_notion_sync,
+            commands::configure_issue_tracker,
+            commands::run_issue_enrichment,
+            commands::configure_github_sync,
+            commands::run_github_sync,
+            commands::get_capture_endpoint,
+            commands::configure_capture_endpoint,
+            commands::get_calendar_access,
+            commands::request_calendar_access,
+            commands::check_runtime_dependencies,
             commands::check_health,
+            commands::check_health_deep,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
 
 fn register_global_shortcuts(app: &tauri::AppHandle) {
-    use tauri_plugin_global_shortcut::GlobalShortcutExt;
-
-    let app_handle = app.clone();
-
-    // Register Cmd+Shift+M for Quick Add Memory
-    let result = app.global_shortcut().on_shortcut("CmdOrCtrl+Shift+M", move |_app, _shortcut, event| {
-        if event.state == tauri_plugin_global_shortcut::ShortcutState::Pressed {
-            if let Some(window) = app_handle.get_webview_window("main") {
-                let _ = window.show();
-                let _ = window.set_focus();
-            }
-            let _ = app_handle.emit("open-quick-add", ());
-        }
-    });
-
-    if let Err(e) = result {
-        eprintln!("[engram] Failed to register Cmd+Shift+M shortcut: {}", e);
+    // Quick Add Memory, Cmd+Shift+M unless changed in preferences
+    let shortcuts = commands::read_preferences().unwrap_or_default().shortcuts;
+    if let Err(e) = windows::register_quick_add_shortcut(app, &shortcuts.quick_add, None) {
+        engram_log!("Failed to register quick add shortcut: {}", e);
     }
 }
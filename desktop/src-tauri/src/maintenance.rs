@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use chrono::{Local, Timelike};
+use tauri::{AppHandle, Emitter};
+use tokio::time::sleep;
+
+use crate::notifications::{self, NotificationEvent};
+use crate::{commands, database, sidecar};
+
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// How often to check whether a database size sample is due.
+const SIZE_SAMPLE_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Spawn the nightly maintenance loop. When enabled in preferences, the
+/// sidecar is recycled once a day at the configured hour: stopped, its
+/// database checkpointed and vacuumed, and started again. This keeps long
+/// uptimes from accumulating memory in the Node process.
+///
+/// The same loop samples the database size daily for `get_disk_usage`.
+pub fn setup_maintenance_schedule(app: &AppHandle) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut last_run: Option<chrono::NaiveDate> = None;
+        let mut last_size_check: Option<std::time::Instant> = None;
+
+        loop {
+            sleep(SCHEDULE_CHECK_INTERVAL).await;
+
+            if last_size_check.map_or(true, |t| t.elapsed() >= SIZE_SAMPLE_CHECK_INTERVAL) {
+                last_size_check = Some(std::time::Instant::now());
+                if let Ok(db_path) = database::active_db_path(&app_handle).await {
+                    let now = chrono::Utc::now().timestamp();
+                    let result = tokio::task::spawn_blocking(move || {
+                        database::record_size_sample(&db_path, now)
+                    })
+                    .await
+                    .unwrap_or_else(|e| Err(e.to_string()));
+                    if let Err(e) = result {
+                        engram_log!("Failed to sample database size: {}", e);
+                    }
+                }
+            }
+
+            let settings = commands::read_preferences().unwrap_or_default().maintenance;
+            if !settings.enabled {
+                continue;
+            }
+
+            let now = Local::now();
+            let today = now.date_naive();
+            if now.hour() != settings.hour || last_run == Some(today) {
+                continue;
+            }
+            last_run = Some(today);
+
+            engram_log!("Starting scheduled maintenance");
+            match run_maintenance(&app_handle).await {
+                Ok(report) => {
+                    engram_log!(
+                        "Scheduled maintenance complete, reclaimed {} bytes",
+                        report.reclaimed_bytes
+                    );
+                    let _ = app_handle.emit("maintenance-complete", &report);
+                    notifications::notify(
+                        &app_handle,
+                        NotificationEvent::MaintenanceComplete,
+                        "Maintenance complete",
+                        &format!("Reclaimed {} KB", report.reclaimed_bytes / 1024),
+                    );
+                }
+                Err(e) => {
+                    engram_log!("Scheduled maintenance failed: {}", e);
+                    let _ = app_handle.emit("maintenance-failed", e);
+                }
+            }
+        }
+    });
+}
+
+/// Stop the sidecar, compact the database, and start the sidecar again.
+/// The sidecar is restarted even if compaction fails.
+pub async fn run_maintenance(app: &AppHandle) -> Result<database::VacuumReport, String> {
+    sidecar::stop(app).await?;
+
+    let result = match database::active_db_path(app).await {
+        Ok(db_path) => {
+            tokio::task::spawn_blocking(move || database::checkpoint_and_vacuum(&db_path))
+                .await
+                .unwrap_or_else(|e| Err(e.to_string()))
+        }
+        Err(e) => Err(e),
+    };
+
+    sidecar::start(app).await?;
+    result
+}
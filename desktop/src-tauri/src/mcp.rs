@@ -0,0 +1,335 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono_tz::Tz;
+use serde::Serialize;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+
+use crate::timestamps;
+
+/// How long to wait for each handshake step. Generous because `npx` may have
+/// to download the package on first run.
+const STEP_TIMEOUT: Duration = Duration::from_secs(60);
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptEntry {
+    /// Milliseconds since the simulated session started.
+    pub elapsed_ms: u64,
+    /// "sent", "received", "stderr", or "note"
+    pub direction: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpSimulationResult {
+    pub success: bool,
+    /// Step the session reached: "spawn", "initialize", "tools/list", "tools/call", or "done".
+    pub step: String,
+    pub error: Option<String>,
+    pub server_info: Option<serde_json::Value>,
+    pub tools: Vec<String>,
+    pub transcript: Vec<TranscriptEntry>,
+    /// Troubleshooting file written when the simulation fails.
+    pub report_path: Option<String>,
+}
+
+type Transcript = Arc<Mutex<Vec<TranscriptEntry>>>;
+
+fn record(transcript: &Transcript, started: Instant, direction: &str, message: String) {
+    if let Ok(mut entries) = transcript.lock() {
+        entries.push(TranscriptEntry {
+            elapsed_ms: started.elapsed().as_millis() as u64,
+            direction: direction.to_string(),
+            message,
+        });
+    }
+}
+
+struct Session {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: Lines<BufReader<ChildStdout>>,
+    transcript: Transcript,
+    started: Instant,
+}
+
+impl Session {
+    async fn send(&mut self, message: serde_json::Value) -> Result<(), String> {
+        let line = message.to_string();
+        record(&self.transcript, self.started, "sent", line.clone());
+        self.stdin
+            .write_all(format!("{}\n", line).as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write to server stdin: {}", e))?;
+        self.stdin.flush().await.map_err(|e| e.to_string())
+    }
+
+    /// Send a JSON-RPC request and wait for the response with the matching id.
+    async fn request(
+        &mut self,
+        id: u64,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        self.send(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        }))
+        .await?;
+
+        let response = tokio::time::timeout(STEP_TIMEOUT, self.read_response(id))
+            .await
+            .map_err(|_| {
+                format!("Timed out after {}s waiting for {}", STEP_TIMEOUT.as_secs(), method)
+            })??;
+
+        if let Some(error) = response.get("error") {
+            return Err(format!("{} returned an error: {}", method, error));
+        }
+        Ok(response.get("result").cloned().unwrap_or(serde_json::Value::Null))
+    }
+
+    async fn read_response(&mut self, id: u64) -> Result<serde_json::Value, String> {
+        loop {
+            let line = self
+                .stdout
+                .next_line()
+                .await
+                .map_err(|e| format!("Failed to read server stdout: {}", e))?
+                .ok_or("Server closed stdout before responding")?;
+
+            record(&self.transcript, self.started, "received", line.clone());
+
+            match serde_json::from_str::<serde_json::Value>(&line) {
+                Ok(message) if message.get("id").and_then(|v| v.as_u64()) == Some(id) => {
+                    return Ok(message);
+                }
+                Ok(_) => {}
+                Err(_) => record(
+                    &self.transcript,
+                    self.started,
+                    "note",
+                    "Non-JSON output on stdout; MCP clients will reject this".to_string(),
+                ),
+            }
+        }
+    }
+}
+
+/// Run the MCP server described by an agent's `mcpServers.engram` entry and walk
+/// through initialize, tools/list, and a harmless recall call, recording every
+/// message exchanged.
+pub async fn simulate_session(entry: &serde_json::Value) -> McpSimulationResult {
+    let started = Instant::now();
+    let transcript: Transcript = Arc::new(Mutex::new(Vec::new()));
+    let mut result = McpSimulationResult {
+        success: false,
+        step: "spawn".to_string(),
+        error: None,
+        server_info: None,
+        tools: Vec::new(),
+        transcript: Vec::new(),
+        report_path: None,
+    };
+
+    match run_session(entry, &transcript, started, &mut result).await {
+        Ok(()) => {
+            result.success = true;
+            result.step = "done".to_string();
+        }
+        Err(e) => {
+            record(&transcript, started, "note", e.clone());
+            result.error = Some(e);
+        }
+    }
+
+    result.transcript = transcript.lock().map(|t| t.clone()).unwrap_or_default();
+    result
+}
+
+async fn run_session(
+    entry: &serde_json::Value,
+    transcript: &Transcript,
+    started: Instant,
+    result: &mut McpSimulationResult,
+) -> Result<(), String> {
+    let program = entry
+        .get("command")
+        .and_then(|c| c.as_str())
+        .ok_or("The engram entry has no \"command\" string")?;
+    let args: Vec<String> = entry
+        .get("args")
+        .and_then(|a| a.as_array())
+        .map(|a| a.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+        .unwrap_or_default();
+
+    // Resolve through PATH (and PATHEXT on Windows, where npx is npx.cmd)
+    let resolved = crate::commands::find_executable(program)
+        .unwrap_or_else(|| std::path::PathBuf::from(program));
+    let mut command = Command::new(&resolved);
+    command
+        .args(&args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    if let Some(env) = entry.get("env").and_then(|e| e.as_object()) {
+        for (key, value) in env {
+            if let Some(value) = value.as_str() {
+                command.env(key, value);
+            }
+        }
+    }
+
+    record(
+        transcript,
+        started,
+        "note",
+        format!("Spawning: {} {}", program, args.join(" ")),
+    );
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("Failed to spawn {}: {}", program, e))?;
+
+    let stdin = child.stdin.take().ok_or("Failed to open server stdin")?;
+    let stdout = child.stdout.take().ok_or("Failed to open server stdout")?;
+    if let Some(stderr) = child.stderr.take() {
+        let transcript = transcript.clone();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                record(&transcript, started, "stderr", line);
+            }
+        });
+    }
+
+    let mut session = Session {
+        child,
+        stdin,
+        stdout: BufReader::new(stdout).lines(),
+        transcript: transcript.clone(),
+        started,
+    };
+
+    let outcome = handshake(&mut session, result).await;
+
+    let _ = session.child.kill().await;
+    if let Ok(Some(status)) = session.child.try_wait() {
+        record(transcript, started, "note", format!("Server exited: {}", status));
+    }
+
+    outcome
+}
+
+async fn handshake(session: &mut Session, result: &mut McpSimulationResult) -> Result<(), String> {
+    result.step = "initialize".to_string();
+    let init = session
+        .request(
+            1,
+            "initialize",
+            serde_json::json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": {},
+                "clientInfo": {
+                    "name": "engram-desktop-simulator",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            }),
+        )
+        .await?;
+    result.server_info = init.get("serverInfo").cloned();
+    session
+        .send(serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/initialized",
+        }))
+        .await?;
+
+    result.step = "tools/list".to_string();
+    let tools = session.request(2, "tools/list", serde_json::json!({})).await?;
+    result.tools = tools
+        .get("tools")
+        .and_then(|t| t.as_array())
+        .map(|t| {
+            t.iter()
+                .filter_map(|tool| tool.get("name").and_then(|n| n.as_str()).map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+    if !result.tools.iter().any(|t| t == "engram_recall") {
+        return Err("Server did not advertise the engram_recall tool".to_string());
+    }
+
+    result.step = "tools/call".to_string();
+    let call = session
+        .request(
+            3,
+            "tools/call",
+            serde_json::json!({
+                "name": "engram_recall",
+                "arguments": { "query": "engram connectivity check", "limit": 1 },
+            }),
+        )
+        .await?;
+    if call.get("isError").and_then(|v| v.as_bool()).unwrap_or(false) {
+        return Err(format!("engram_recall reported an error: {}", call));
+    }
+
+    Ok(())
+}
+
+/// Write a failed simulation's transcript and environment to
+/// `<diagnostics_dir>/mcp-simulation-<agent>-<timestamp>.json` so it can be shared
+/// and picked up by the support bundle. Env values from the agent entry are
+/// redacted since they commonly hold API tokens.
+pub fn save_failure_report(
+    diagnostics_dir: &Path,
+    tz: &Tz,
+    agent_id: &str,
+    config_path: &Path,
+    entry: &serde_json::Value,
+    result: &McpSimulationResult,
+) -> Result<PathBuf, String> {
+    fs::create_dir_all(diagnostics_dir).map_err(|e| e.to_string())?;
+
+    let mut entry = entry.clone();
+    if let Some(env) = entry.get_mut("env").and_then(|e| e.as_object_mut()) {
+        for value in env.values_mut() {
+            *value = serde_json::Value::String("<redacted>".to_string());
+        }
+    }
+
+    let report = serde_json::json!({
+        "agentId": agent_id,
+        "configPath": config_path.to_string_lossy(),
+        "createdAt": timestamps::now(tz).to_rfc3339(),
+        "entry": entry,
+        "environment": {
+            "os": std::env::consts::OS,
+            "arch": std::env::consts::ARCH,
+            "desktopVersion": env!("CARGO_PKG_VERSION"),
+            "path": std::env::var("PATH").unwrap_or_default(),
+        },
+        "result": result,
+    });
+
+    let path = diagnostics_dir.join(format!(
+        "mcp-simulation-{}-{}.json",
+        agent_id,
+        timestamps::filename_timestamp(tz)
+    ));
+    let content = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write report: {}", e))?;
+
+    engram_log!("MCP simulation report saved to: {}", path.display());
+    Ok(path)
+}
@@ -0,0 +1,103 @@
+use unicode_normalization::UnicodeNormalization;
+
+/// Normalized content ready to store, and its language when detected.
+pub struct Normalized {
+    pub content: String,
+    /// ISO 639-3 code (e.g. "deu"), only when detection is confident.
+    pub language: Option<&'static str>,
+}
+
+/// Clean up captured text before it is stored: strip ANSI escapes and
+/// terminal redraw noise that agents often paste along, then NFC-normalize
+/// so visually identical text matches in keyword search.
+pub fn normalize(text: &str) -> Normalized {
+    let content: String = clean_terminal_noise(&strip_ansi(text)).nfc().collect();
+    let language = detect_language(&content);
+    Normalized { content, language }
+}
+
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let info = whatlang::detect(text)?;
+    info.is_reliable().then(|| info.lang().code())
+}
+
+/// Remove ANSI escape sequences: CSI (`ESC [ … final`), OSC (`ESC ] … BEL`
+/// or `ESC \`), and other escapes up to their final byte.
+fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' && c != '\u{9b}' {
+            out.push(c);
+            continue;
+        }
+        let kind = if c == '\u{9b}' { Some('[') } else { chars.next() };
+        match kind {
+            Some('[') => {
+                // Parameter and intermediate bytes, then one final byte
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\u{7}' {
+                        break;
+                    }
+                    if c == '\u{1b}' {
+                        if chars.peek() == Some(&'\\') {
+                            chars.next();
+                        }
+                        break;
+                    }
+                }
+            }
+            // nF escapes such as `ESC ( B`: intermediates, then a final byte
+            Some(c) if (' '..='/').contains(&c) => {
+                for c in chars.by_ref() {
+                    if !(' '..='/').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Resolve carriage-return redraws (progress bars, spinners) to their final
+/// state, apply backspaces, drop other control characters, and collapse runs
+/// of blank lines.
+fn clean_terminal_noise(text: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut blank_run = 0;
+    for raw in text.split('\n') {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        let visible = raw.rsplit('\r').find(|s| !s.trim().is_empty()).unwrap_or("");
+        let mut line = String::with_capacity(visible.len());
+        for c in visible.chars() {
+            match c {
+                '\u{8}' => {
+                    line.pop();
+                }
+                '\t' => line.push(c),
+                c if c.is_control() => {}
+                c => line.push(c),
+            }
+        }
+        let line = line.trim_end().to_string();
+        if line.is_empty() {
+            blank_run += 1;
+            if blank_run > 1 {
+                continue;
+            }
+        } else {
+            blank_run = 0;
+        }
+        lines.push(line);
+    }
+    lines.join("\n").trim().to_string()
+}
@@ -0,0 +1,259 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{Datelike, Local, NaiveTime, Weekday};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::commands::{self, EventNotification, QuietHours};
+
+/// Prefix marking a `sound` setting that refers to a file in ~/.engram/sounds.
+pub const CUSTOM_SOUND_PREFIX: &str = "custom:";
+const CUSTOM_SOUND_EXTENSIONS: [&str; 5] = ["wav", "aiff", "aif", "mp3", "m4a"];
+const CUSTOM_SOUND_MAX_BYTES: u64 = 5 * 1024 * 1024;
+const QUIET_HOURS_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// Held notifications beyond this many drop the oldest.
+const DEFERRED_MAX: usize = 20;
+
+/// Notifications held back during quiet hours, oldest first.
+static DEFERRED: Mutex<Vec<(NotificationEvent, String, String)>> = Mutex::new(Vec::new());
+
+/// Events the desktop app can notify about. Each has its own entry in
+/// `NotificationSettings`.
+#[derive(Debug, Clone, Copy)]
+pub enum NotificationEvent {
+    MemorySaved,
+    SidecarCrashed,
+    MaintenanceComplete,
+    ImportComplete,
+    Resurfaced,
+    LowDisk,
+}
+
+impl NotificationEvent {
+    fn name(self) -> &'static str {
+        match self {
+            NotificationEvent::MemorySaved => "memorySaved",
+            NotificationEvent::SidecarCrashed => "sidecarCrashed",
+            NotificationEvent::MaintenanceComplete => "maintenanceComplete",
+            NotificationEvent::ImportComplete => "importComplete",
+            NotificationEvent::Resurfaced => "resurfaced",
+            NotificationEvent::LowDisk => "lowDisk",
+        }
+    }
+
+    /// Whether the event waits out quiet hours. Crashes and low disk space
+    /// need attention now, and a save confirms something the user just did.
+    fn deferrable(self) -> bool {
+        !matches!(
+            self,
+            NotificationEvent::SidecarCrashed
+                | NotificationEvent::MemorySaved
+                | NotificationEvent::LowDisk
+        )
+    }
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").ok()
+}
+
+/// Whether `now` falls in any quiet range, or on a weekend if those are quiet.
+fn is_quiet(quiet: &QuietHours, now: chrono::DateTime<Local>) -> bool {
+    if !quiet.enabled {
+        return false;
+    }
+    if quiet.weekends && matches!(now.weekday(), Weekday::Sat | Weekday::Sun) {
+        return true;
+    }
+    let time = now.time();
+    quiet.ranges.iter().any(|range| {
+        let (Some(start), Some(end)) = (parse_time(&range.start), parse_time(&range.end)) else {
+            return false;
+        };
+        if start <= end {
+            start <= time && time < end
+        } else {
+            time >= start || time < end
+        }
+    })
+}
+
+/// Notify the user about `event` as configured in preferences.
+/// Disabled events are dropped. "banner" shows a system notification;
+/// "silent" only emits a `notification` event for the in-app UI.
+pub fn notify(app: &AppHandle, event: NotificationEvent, title: &str, body: &str) {
+    let settings = commands::read_preferences().unwrap_or_default().notifications;
+    let config: &EventNotification = match event {
+        NotificationEvent::MemorySaved => &settings.memory_saved,
+        NotificationEvent::SidecarCrashed => &settings.sidecar_crashed,
+        NotificationEvent::MaintenanceComplete => &settings.maintenance_complete,
+        NotificationEvent::ImportComplete => &settings.import_complete,
+        NotificationEvent::Resurfaced => &settings.resurfaced,
+        NotificationEvent::LowDisk => &settings.low_disk,
+    };
+    if !config.enabled {
+        return;
+    }
+    if event.deferrable() && is_quiet(&settings.quiet_hours, Local::now()) {
+        if let Ok(mut deferred) = DEFERRED.lock() {
+            deferred.push((event, title.to_string(), body.to_string()));
+            let overflow = deferred.len().saturating_sub(DEFERRED_MAX);
+            deferred.drain(..overflow);
+        }
+        return;
+    }
+
+    let _ = app.emit(
+        "notification",
+        serde_json::json!({
+            "event": event.name(),
+            "title": title,
+            "body": body,
+            "style": config.style,
+            "sound": config.sound,
+        }),
+    );
+
+    // Custom sounds are played here for both styles; the banner stays quiet
+    let custom_sound = config.sound.strip_prefix(CUSTOM_SOUND_PREFIX);
+    if let Some(file_name) = custom_sound {
+        if let Err(e) = play_custom_sound(file_name) {
+            engram_log!("Failed to play custom sound: {}", e);
+        }
+    }
+
+    if config.style != "banner" {
+        return;
+    }
+
+    let mut builder = app.notification().builder().title(title).body(body);
+    if config.sound != "none" && custom_sound.is_none() {
+        builder = builder.sound(config.sound.clone());
+    }
+    if let Err(e) = builder.show() {
+        engram_log!("Failed to show notification: {}", e);
+    }
+}
+
+/// Spawn the loop that delivers notifications held during quiet hours once
+/// the window ends.
+pub fn setup_quiet_hours_flush(app: &AppHandle) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(QUIET_HOURS_CHECK_INTERVAL).await;
+
+            let quiet = commands::read_preferences()
+                .unwrap_or_default()
+                .notifications
+                .quiet_hours;
+            if is_quiet(&quiet, Local::now()) {
+                continue;
+            }
+            let held = match DEFERRED.lock() {
+                Ok(mut deferred) => std::mem::take(&mut *deferred),
+                Err(_) => continue,
+            };
+            for (event, title, body) in held {
+                notify(&app_handle, event, &title, &body);
+            }
+        }
+    });
+}
+
+fn sounds_dir() -> Result<PathBuf, String> {
+    Ok(crate::profiles::root_dir()?.join("sounds"))
+}
+
+/// Validate a user-supplied sound file and copy it into ~/.engram/sounds.
+/// Returns the `sound` preference value that refers to the copy.
+pub fn install_custom_sound(source: &Path) -> Result<String, String> {
+    let extension = source
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .filter(|e| CUSTOM_SOUND_EXTENSIONS.contains(&e.as_str()))
+        .ok_or_else(|| {
+            format!(
+                "Unsupported sound format; use one of: {}",
+                CUSTOM_SOUND_EXTENSIONS.join(", ")
+            )
+        })?;
+
+    let metadata =
+        fs::metadata(source).map_err(|e| format!("Cannot read {}: {}", source.display(), e))?;
+    if !metadata.is_file() {
+        return Err(format!("{} is not a file", source.display()));
+    }
+    if metadata.len() > CUSTOM_SOUND_MAX_BYTES {
+        return Err(format!(
+            "Sound file is too large (max {} MB)",
+            CUSTOM_SOUND_MAX_BYTES / 1024 / 1024
+        ));
+    }
+
+    let dir = sounds_dir()?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let file_name = format!("memory-saved.{}", extension);
+    fs::copy(source, dir.join(&file_name))
+        .map_err(|e| format!("Failed to copy sound file: {}", e))?;
+
+    engram_log!("Installed custom sound from {}", source.display());
+    Ok(format!("{}{}", CUSTOM_SOUND_PREFIX, file_name))
+}
+
+/// Play a sound from ~/.engram/sounds with the platform's command-line player.
+/// Returns once the player has been launched.
+pub fn play_custom_sound(file_name: &str) -> Result<(), String> {
+    // Only plain file names; the preference must not point outside the sounds dir
+    if file_name.contains('/') || file_name.contains('\\') || file_name.starts_with('.') {
+        return Err(format!("Invalid sound name: {}", file_name));
+    }
+    let path = sounds_dir()?.join(file_name);
+    if !path.exists() {
+        return Err(format!("Sound file not found: {}", path.display()));
+    }
+
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut c = std::process::Command::new("afplay");
+        c.arg(&path);
+        c
+    };
+
+    #[cfg(target_os = "linux")]
+    let mut command = {
+        let mut c = std::process::Command::new("paplay");
+        c.arg(&path);
+        c
+    };
+
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut c = std::process::Command::new("powershell");
+        c.args([
+            "-NoProfile",
+            "-Command",
+            &format!(
+                "(New-Object Media.SoundPlayer '{}').PlaySync()",
+                path.display().to_string().replace('\'', "''")
+            ),
+        ]);
+        c
+    };
+
+    command
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map(|mut child| {
+            // Reap the player once it finishes
+            std::thread::spawn(move || {
+                let _ = child.wait();
+            });
+        })
+        .map_err(|e| format!("Failed to launch sound player: {}", e))
+}
@@ -0,0 +1,360 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::time::sleep;
+
+use crate::commands::{self, NotionSyncSettings};
+use crate::sidecar::{SidecarState, SidecarStatus};
+
+const API_BASE: &str = "https://api.notion.com/v1";
+const NOTION_VERSION: &str = "2022-06-28";
+/// Keychain account holding the integration token.
+const TOKEN_ACCOUNT: &str = "notion-token";
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// Notion rejects rich text objects longer than this.
+const RICH_TEXT_MAX_CHARS: usize = 2000;
+/// Notion accepts at most this many blocks when creating a page.
+const MAX_CHILDREN: usize = 100;
+const TITLE_MAX_CHARS: usize = 100;
+
+/// A page pulled from the synced database.
+#[derive(Debug, Clone)]
+pub struct NotionPage {
+    pub id: String,
+    pub title: String,
+    /// Plain text of the page's top-level blocks, one per line.
+    pub text: String,
+    pub created_time: String,
+}
+
+/// What has already crossed in each direction, kept in
+/// ~/.engram/notion-sync.json so neither side is imported twice.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SyncState {
+    /// Database the state belongs to; switching databases starts over.
+    pub database_id: String,
+    /// `created_time` of the newest page pulled so far.
+    pub pulled_until: Option<String>,
+    pub pulled_pages: HashSet<String>,
+    /// Memory id to the page it was pushed as.
+    pub pushed_memories: HashMap<String, String>,
+}
+
+fn state_path() -> Result<PathBuf, String> {
+    Ok(crate::profiles::root_dir()?.join("notion-sync.json"))
+}
+
+pub fn load_state(database_id: &str) -> SyncState {
+    let state: SyncState = state_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    if state.database_id == database_id {
+        state
+    } else {
+        SyncState {
+            database_id: database_id.to_string(),
+            ..SyncState::default()
+        }
+    }
+}
+
+pub fn save_state(state: &SyncState) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+    fs::write(state_path()?, json).map_err(|e| format!("Failed to save Notion sync state: {}", e))
+}
+
+pub fn token() -> Result<Option<String>, String> {
+    crate::keychain::get(TOKEN_ACCOUNT)
+}
+
+pub fn set_token(token: &str) -> Result<(), String> {
+    crate::keychain::set(TOKEN_ACCOUNT, token)
+}
+
+pub fn clear_token() -> Result<(), String> {
+    crate::keychain::delete(TOKEN_ACCOUNT)
+}
+
+/// Accepts a bare database id or a database URL copied from Notion.
+pub fn normalize_database_id(input: &str) -> String {
+    let path = input.trim().split(['?', '#']).next().unwrap_or_default();
+    let last = path.rsplit('/').next().unwrap_or_default();
+    let hex: String = last.chars().filter(|c| c.is_ascii_hexdigit()).collect();
+    if hex.len() >= 32 {
+        hex[hex.len() - 32..].to_lowercase()
+    } else {
+        input.trim().to_string()
+    }
+}
+
+pub struct NotionClient {
+    client: reqwest::Client,
+    token: String,
+}
+
+impl NotionClient {
+    pub fn new(token: String) -> Result<Self, String> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| e.to_string())?;
+        Ok(Self { client, token })
+    }
+
+    /// The client for the token in the keychain.
+    pub fn from_keychain() -> Result<Self, String> {
+        let token = token()?.ok_or("No Notion token is configured")?;
+        Self::new(token)
+    }
+
+    async fn request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value, String> {
+        let mut request = self
+            .client
+            .request(method, format!("{}{}", API_BASE, path))
+            .bearer_auth(&self.token)
+            .header("Notion-Version", NOTION_VERSION);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let resp = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach Notion: {}", e))?;
+        let status = resp.status();
+        let value: serde_json::Value = resp.json().await.unwrap_or_default();
+        if !status.is_success() {
+            let message = value["message"].as_str().unwrap_or("request failed");
+            return Err(format!("Notion API error ({}): {}", status, message));
+        }
+        Ok(value)
+    }
+
+    /// The database's title and the name of its title property, which
+    /// pushed pages have to fill in.
+    pub async fn database(&self, database_id: &str) -> Result<(String, String), String> {
+        let db = self
+            .request(reqwest::Method::GET, &format!("/databases/{}", database_id), None)
+            .await?;
+        let title = plain_text(&db["title"]);
+        let title_property = db["properties"]
+            .as_object()
+            .and_then(|props| {
+                props
+                    .iter()
+                    .find(|(_, prop)| prop["type"] == "title")
+                    .map(|(name, _)| name.clone())
+            })
+            .ok_or("Notion database has no title property")?;
+        Ok((title, title_property))
+    }
+
+    /// Pages created at or after `since`, oldest first.
+    pub async fn pages_since(
+        &self,
+        database_id: &str,
+        since: Option<&str>,
+    ) -> Result<Vec<NotionPage>, String> {
+        let mut pages = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut body = serde_json::json!({
+                "sorts": [{ "timestamp": "created_time", "direction": "ascending" }],
+                "page_size": 100,
+            });
+            // created_time is only minute-precise, so pages from the last
+            // synced minute come back and are skipped by id instead.
+            if let Some(since) = since {
+                body["filter"] = serde_json::json!({
+                    "timestamp": "created_time",
+                    "created_time": { "on_or_after": since },
+                });
+            }
+            if let Some(cursor) = &cursor {
+                body["start_cursor"] = serde_json::json!(cursor);
+            }
+            let result = self
+                .request(
+                    reqwest::Method::POST,
+                    &format!("/databases/{}/query", database_id),
+                    Some(body),
+                )
+                .await?;
+
+            for page in result["results"].as_array().into_iter().flatten() {
+                let title = page["properties"]
+                    .as_object()
+                    .and_then(|props| props.values().find(|prop| prop["type"] == "title"))
+                    .map(|prop| plain_text(&prop["title"]))
+                    .unwrap_or_default();
+                pages.push(NotionPage {
+                    id: page["id"].as_str().unwrap_or_default().to_string(),
+                    title,
+                    text: String::new(),
+                    created_time: page["created_time"].as_str().unwrap_or_default().to_string(),
+                });
+            }
+
+            match result["next_cursor"].as_str() {
+                Some(next) if result["has_more"] == true => cursor = Some(next.to_string()),
+                _ => break,
+            }
+        }
+        Ok(pages)
+    }
+
+    /// Plain text of a page's first 100 top-level blocks.
+    pub async fn page_text(&self, page_id: &str) -> Result<String, String> {
+        let result = self
+            .request(
+                reqwest::Method::GET,
+                &format!("/blocks/{}/children?page_size=100", page_id),
+                None,
+            )
+            .await?;
+        let lines: Vec<String> = result["results"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|block| {
+                let kind = block["type"].as_str()?;
+                let text = plain_text(&block[kind]["rich_text"]);
+                if text.is_empty() {
+                    return None;
+                }
+                Some(match kind {
+                    "heading_1" => format!("# {}", text),
+                    "heading_2" => format!("## {}", text),
+                    "heading_3" => format!("### {}", text),
+                    "bulleted_list_item" | "numbered_list_item" => format!("- {}", text),
+                    "to_do" if block["to_do"]["checked"] == true => format!("- [x] {}", text),
+                    "to_do" => format!("- [ ] {}", text),
+                    "quote" => format!("> {}", text),
+                    _ => text,
+                })
+            })
+            .collect();
+        Ok(lines.join("\n"))
+    }
+
+    /// Create a page with `content` as its body and return its id.
+    pub async fn create_page(
+        &self,
+        database_id: &str,
+        title_property: &str,
+        title: &str,
+        content: &str,
+    ) -> Result<String, String> {
+        let children: Vec<serde_json::Value> = chunk_chars(content, RICH_TEXT_MAX_CHARS)
+            .into_iter()
+            .take(MAX_CHILDREN)
+            .map(|chunk| {
+                serde_json::json!({
+                    "object": "block",
+                    "type": "paragraph",
+                    "paragraph": { "rich_text": [{ "type": "text", "text": { "content": chunk } }] },
+                })
+            })
+            .collect();
+        let mut properties = serde_json::Map::new();
+        properties.insert(
+            title_property.to_string(),
+            serde_json::json!({ "title": [{ "type": "text", "text": { "content": title } }] }),
+        );
+        let body = serde_json::json!({
+            "parent": { "database_id": database_id },
+            "properties": properties,
+            "children": children,
+        });
+        let page = self.request(reqwest::Method::POST, "/pages", Some(body)).await?;
+        page["id"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or("Notion did not return the new page's id".to_string())
+    }
+}
+
+/// Concatenated `plain_text` of a rich text array.
+fn plain_text(rich_text: &serde_json::Value) -> String {
+    rich_text
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|part| part["plain_text"].as_str())
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+fn chunk_chars(text: &str, size: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    chars.chunks(size).map(|chunk| chunk.iter().collect()).collect()
+}
+
+/// A pushed page's title: the memory's first line, shortened.
+pub fn page_title(content: &str) -> String {
+    let first_line = content.lines().next().unwrap_or_default().trim();
+    if first_line.chars().count() > TITLE_MAX_CHARS {
+        first_line.chars().take(TITLE_MAX_CHARS - 1).collect::<String>() + "…"
+    } else {
+        first_line.to_string()
+    }
+}
+
+fn due(settings: &NotionSyncSettings, last_run: Option<Instant>) -> bool {
+    let interval = Duration::from_secs(settings.interval_minutes.max(5) * 60);
+    last_run.map_or(true, |t| t.elapsed() >= interval)
+}
+
+/// Spawn the Notion sync loop. While enabled in preferences, new pages in
+/// the configured database are pulled as memories every
+/// `interval_minutes`, and pinned memories are pushed back as pages if
+/// `push_pinned` is set. Runs are skipped while the sidecar is down.
+pub fn setup_sync_schedule(app: &AppHandle) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut last_run: Option<Instant> = None;
+
+        loop {
+            sleep(SCHEDULE_CHECK_INTERVAL).await;
+
+            let settings = commands::read_preferences().unwrap_or_default().notion;
+            if !settings.enabled || !due(&settings, last_run) {
+                continue;
+            }
+            let state = app_handle.state::<SidecarState>();
+            if !matches!(*state.status.lock().await, SidecarStatus::Running) {
+                continue;
+            }
+            last_run = Some(Instant::now());
+
+            match commands::sync_notion(&app_handle).await {
+                Ok(report) => {
+                    if report.pulled.created > 0 || report.pushed > 0 {
+                        engram_log!(
+                            "Notion sync pulled {} pages and pushed {} memories",
+                            report.pulled.created,
+                            report.pushed
+                        );
+                    }
+                    let _ = app_handle.emit("notion-sync-complete", &report);
+                }
+                Err(e) => {
+                    engram_log!("Notion sync failed: {}", e);
+                    let _ = app_handle.emit("notion-sync-failed", e);
+                }
+            }
+        }
+    });
+}
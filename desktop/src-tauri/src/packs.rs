@@ -0,0 +1,199 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use sha2::{Digest, Sha256};
+
+use crate::bundle_update::decode_hex;
+
+pub const PACK_EXTENSION: &str = "engrampack";
+const PACK_FORMAT: &str = "engram-memory-pack";
+const PACK_VERSION: u32 = 1;
+/// This install's pack signing key, created on first use.
+const SIGNING_KEY_FILE: &str = "pack-signing.key";
+
+/// What the author says about a pack. Shown before importing it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PackMetadata {
+    pub title: String,
+    pub description: Option<String>,
+    pub author: Option<String>,
+    /// Terms the memories are shared under, e.g. "CC-BY-4.0" or "Internal".
+    pub license: Option<String>,
+}
+
+/// A memory as stored in a pack. The original id and creation time are
+/// kept as provenance; importing creates new memories.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackMemory {
+    pub original_id: String,
+    pub content: String,
+    pub category: Option<String>,
+    pub entity: Option<String>,
+    pub confidence: Option<f64>,
+    pub namespace: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Unix milliseconds.
+    pub created_at: Option<i64>,
+}
+
+/// Everything the signature covers.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackContents {
+    format: String,
+    version: u32,
+    metadata: PackMetadata,
+    /// RFC 3339.
+    created_at: String,
+    app_version: String,
+    memories: Vec<PackMemory>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackSignature {
+    /// Hex Ed25519 public key of the install that made the pack.
+    public_key: String,
+    /// Hex signature over the raw `contents` JSON.
+    signature: String,
+}
+
+/// Contents are embedded as raw JSON so the signature is checked against
+/// the exact bytes that were signed, not a re-serialization.
+#[derive(Debug, Serialize, Deserialize)]
+struct MemoryPack<'a> {
+    #[serde(borrow)]
+    contents: &'a RawValue,
+    signature: PackSignature,
+}
+
+/// A verified pack, read but not yet imported.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackPreview {
+    pub metadata: PackMetadata,
+    pub created_at: String,
+    pub app_version: String,
+    /// Short fingerprint of the key that signed the pack.
+    pub signer: String,
+    /// Signed by this install, e.g. a pack you made yourself.
+    pub signed_by_you: bool,
+    pub memories: Vec<PackMemory>,
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn fingerprint(key: &VerifyingKey) -> String {
+    encode_hex(&Sha256::digest(key.as_bytes())[..8])
+}
+
+fn signing_key_path() -> Result<PathBuf, String> {
+    Ok(crate::profiles::root_dir()?.join(SIGNING_KEY_FILE))
+}
+
+/// Load the signing key, creating one the first time a pack is made.
+fn signing_key() -> Result<SigningKey, String> {
+    let path = signing_key_path()?;
+    if let Ok(hex) = fs::read_to_string(&path) {
+        let seed: [u8; 32] = decode_hex(&hex)?
+            .try_into()
+            .map_err(|_| format!("Invalid pack signing key in {}", path.display()))?;
+        return Ok(SigningKey::from_bytes(&seed));
+    }
+    let seed: [u8; 32] = rand::random();
+    fs::write(&path, encode_hex(&seed))
+        .map_err(|e| format!("Failed to save pack signing key: {}", e))?;
+    engram_log!("Created pack signing key {}", path.display());
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+/// Sign `memories` and write them with `metadata` to `path`.
+pub fn create(
+    path: &Path,
+    metadata: PackMetadata,
+    memories: Vec<PackMemory>,
+    app_version: &str,
+) -> Result<(), String> {
+    if metadata.title.trim().is_empty() {
+        return Err("A memory pack needs a title".to_string());
+    }
+    if memories.is_empty() {
+        return Err("Select at least one memory for the pack".to_string());
+    }
+    let contents = PackContents {
+        format: PACK_FORMAT.to_string(),
+        version: PACK_VERSION,
+        metadata,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        app_version: app_version.to_string(),
+        memories,
+    };
+    let signed = serde_json::to_string_pretty(&contents).map_err(|e| e.to_string())?;
+    let key = signing_key()?;
+    let signature = PackSignature {
+        public_key: encode_hex(key.verifying_key().as_bytes()),
+        signature: encode_hex(&key.sign(signed.as_bytes()).to_bytes()),
+    };
+    let contents = RawValue::from_string(signed).map_err(|e| e.to_string())?;
+    let pack = MemoryPack {
+        contents: &contents,
+        signature,
+    };
+    let json = serde_json::to_string_pretty(&pack).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Read the pack at `path`, refusing it unless the signature matches its
+/// contents.
+pub fn read(path: &Path) -> Result<PackPreview, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let pack: MemoryPack =
+        serde_json::from_str(&content).map_err(|e| format!("Not an Engram memory pack: {}", e))?;
+    let signed = pack.contents.get();
+    let public_key: [u8; 32] = decode_hex(&pack.signature.public_key)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("Memory pack has an invalid signing key")?;
+    let public_key = VerifyingKey::from_bytes(&public_key)
+        .map_err(|_| "Memory pack has an invalid signing key".to_string())?;
+    let signature: [u8; 64] = decode_hex(&pack.signature.signature)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("Memory pack has an invalid signature")?;
+    public_key
+        .verify(signed.as_bytes(), &Signature::from_bytes(&signature))
+        .map_err(|_| "Memory pack was modified after it was signed".to_string())?;
+
+    let contents: PackContents =
+        serde_json::from_str(signed).map_err(|e| format!("Not an Engram memory pack: {}", e))?;
+    if contents.format != PACK_FORMAT {
+        return Err("Not an Engram memory pack".to_string());
+    }
+    if contents.version > PACK_VERSION {
+        return Err(format!(
+            "This pack needs a newer version of Engram (pack version {})",
+            contents.version
+        ));
+    }
+
+    // Checked without creating a key for installs that never made a pack
+    let signed_by_you = signing_key_path()?.exists()
+        && signing_key().is_ok_and(|key| key.verifying_key() == public_key);
+    Ok(PackPreview {
+        metadata: contents.metadata,
+        created_at: contents.created_at,
+        app_version: contents.app_version,
+        signer: fingerprint(&public_key),
+        signed_by_you,
+        memories: contents.memories,
+    })
+}
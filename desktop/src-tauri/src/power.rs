@@ -0,0 +1,110 @@
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+use std::io::{BufRead, BufReader};
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use tokio::sync::watch;
+
+/// Prints `sleep` and `wake` lines as NSWorkspace posts its will-sleep and
+/// did-wake notifications, then keeps the run loop going so they arrive.
+/// The script exits when Engram does, as its next write hits a closed pipe.
+#[cfg(target_os = "macos")]
+const MACOS_WATCH_SCRIPT: &str = r#"
+ObjC.import('AppKit');
+const out = $.NSFileHandle.fileHandleWithStandardOutput;
+const write = (line) => out.writeData($(line + '\n').dataUsingEncoding($.NSUTF8StringEncoding));
+const center = $.NSWorkspace.sharedWorkspace.notificationCenter;
+center.addObserverForNameObjectQueueUsingBlock($.NSWorkspaceWillSleepNotification, $(), $.NSOperationQueue.mainQueue, () => write('sleep'));
+center.addObserverForNameObjectQueueUsingBlock($.NSWorkspaceDidWakeNotification, $(), $.NSOperationQueue.mainQueue, () => write('wake'));
+$.NSRunLoop.currentRunLoop.run;
+"#;
+
+/// logind's PrepareForSleep signal: `true` before sleep, `false` after wake.
+#[cfg(target_os = "linux")]
+const LOGIND_SLEEP_MATCH: &str =
+    "type='signal',interface='org.freedesktop.login1.Manager',member='PrepareForSleep'";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PowerState {
+    /// Between a will-sleep and the following did-wake notification.
+    pub asleep: bool,
+    /// Sleeps announced since launch, so a sleep that began and ended
+    /// between two looks is still noticed.
+    pub sleeps: u64,
+}
+
+static POWER: OnceLock<watch::Sender<PowerState>> = OnceLock::new();
+
+fn sender() -> &'static watch::Sender<PowerState> {
+    POWER.get_or_init(|| watch::channel(PowerState::default()).0)
+}
+
+pub fn subscribe() -> watch::Receiver<PowerState> {
+    sender().subscribe()
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn will_sleep() {
+    engram_log!("System going to sleep");
+    sender().send_modify(|state| {
+        state.asleep = true;
+        state.sleeps += 1;
+    });
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn did_wake() {
+    engram_log!("System woke");
+    sender().send_modify(|state| state.asleep = false);
+}
+
+/// Listen for OS sleep and wake notifications: NSWorkspace on macOS and
+/// logind over D-Bus on Linux. Elsewhere, or if the listener can't start,
+/// nothing is announced and the health check falls back to noticing a
+/// wake from the wall clock jumping.
+pub fn setup_power_monitor() {
+    #[cfg(target_os = "macos")]
+    let command = {
+        let mut command = Command::new("osascript");
+        command.args(["-l", "JavaScript", "-e", MACOS_WATCH_SCRIPT]);
+        command
+    };
+    #[cfg(target_os = "linux")]
+    let command = {
+        let mut command = Command::new("dbus-monitor");
+        command.args(["--system", LOGIND_SLEEP_MATCH]);
+        command
+    };
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    std::thread::spawn(move || {
+        if let Err(e) = watch_notifications(command) {
+            engram_log!("Sleep/wake notifications unavailable: {}", e);
+        }
+    });
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn watch_notifications(mut command: Command) -> Result<(), String> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to start listener: {}", e))?;
+    let stdout = child.stdout.take().ok_or("Listener has no output")?;
+    for line in BufReader::new(stdout).lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        match line.trim() {
+            "sleep" | "boolean true" => will_sleep(),
+            "wake" | "boolean false" => did_wake(),
+            _ => {}
+        }
+    }
+    let _ = child.wait();
+    // Don't leave health checks paused if the listener dies mid-sleep
+    if sender().borrow().asleep {
+        did_wake();
+    }
+    Err("Listener exited".to_string())
+}
@@ -0,0 +1,256 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_global_shortcut::Shortcut;
+
+use crate::commands::{self, NotificationSettings, ShortcutSettings, TrayLayout};
+use crate::notifications::CUSTOM_SOUND_PREFIX;
+
+/// Bumped when the preset file format changes incompatibly.
+const PRESET_VERSION: u32 = 1;
+
+/// Accelerators used by tray menu items; a global shortcut on one of them
+/// would swallow the key press before the menu sees it.
+const RESERVED_ACCELERATORS: [(&str, &str); 3] = [
+    ("CmdOrCtrl+D", "Open Dashboard"),
+    ("CmdOrCtrl+,", "Preferences"),
+    ("CmdOrCtrl+Q", "Quit Engram"),
+];
+
+/// A named, shareable bundle of shortcut, tray, and notification settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Preset {
+    #[serde(default = "default_version")]
+    pub version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub shortcuts: ShortcutSettings,
+    #[serde(default)]
+    pub tray: TrayLayout,
+    #[serde(default)]
+    pub notifications: NotificationSettings,
+}
+
+fn default_version() -> u32 {
+    PRESET_VERSION
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetImport {
+    pub preset: Preset,
+    /// Shortcut problems that must be resolved before the preset can be applied.
+    pub conflicts: Vec<String>,
+    /// Settings that were adjusted to work on this machine.
+    pub warnings: Vec<String>,
+}
+
+fn presets_dir() -> Result<PathBuf, String> {
+    Ok(crate::profiles::root_dir()?.join("presets"))
+}
+
+/// File name for a preset: its name lowercased, with anything other than
+/// letters and digits collapsed to dashes.
+fn file_name(name: &str) -> Result<String, String> {
+    let slug = name
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        return Err("Preset name must contain letters or digits".to_string());
+    }
+    Ok(format!("{}.json", slug))
+}
+
+fn write(preset: &Preset) -> Result<(), String> {
+    let dir = presets_dir()?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(preset).map_err(|e| e.to_string())?;
+    fs::write(dir.join(file_name(&preset.name)?), content)
+        .map_err(|e| format!("Failed to save preset: {}", e))
+}
+
+fn load(name: &str) -> Result<Preset, String> {
+    let path = presets_dir()?.join(file_name(name)?);
+    let content =
+        fs::read_to_string(&path).map_err(|_| format!("Preset \"{}\" not found", name))?;
+    serde_json::from_str(&content).map_err(|e| format!("Invalid preset file: {}", e))
+}
+
+/// All saved presets, sorted by name. Unreadable files are skipped.
+pub fn list() -> Result<Vec<Preset>, String> {
+    let dir = presets_dir()?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut presets: Vec<Preset> = fs::read_dir(&dir)
+        .map_err(|e| e.to_string())?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|e| e == "json"))
+        .filter_map(|entry| fs::read_to_string(entry.path()).ok())
+        .filter_map(|content| serde_json::from_str(&content).ok())
+        .collect();
+    presets.sort_by_key(|p| p.name.to_lowercase());
+    Ok(presets)
+}
+
+/// Snapshot the current preferences as a preset, replacing one with the same name.
+pub fn save_current(name: &str) -> Result<Preset, String> {
+    let prefs = commands::read_preferences().unwrap_or_default();
+    let preset = Preset {
+        version: PRESET_VERSION,
+        name: name.trim().to_string(),
+        shortcuts: prefs.shortcuts,
+        tray: prefs.tray,
+        notifications: prefs.notifications,
+    };
+    write(&preset)?;
+    Ok(preset)
+}
+
+pub fn delete(name: &str) -> Result<(), String> {
+    let path = presets_dir()?.join(file_name(name)?);
+    fs::remove_file(&path).map_err(|e| format!("Failed to delete preset: {}", e))
+}
+
+/// Copy a preset into `dest_dir` for sharing. Returns the written file.
+pub fn export(name: &str, dest_dir: &Path) -> Result<PathBuf, String> {
+    let preset = load(name)?;
+    let path = dest_dir.join(format!("engram-preset-{}", file_name(&preset.name)?));
+    let content = serde_json::to_string_pretty(&preset).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| format!("Failed to export preset: {}", e))?;
+    Ok(path)
+}
+
+/// Validate and store a preset from exported JSON.
+/// Custom sounds don't travel with a preset; see `reset_missing_sounds`.
+pub fn import(contents: &str) -> Result<PresetImport, String> {
+    let mut preset: Preset =
+        serde_json::from_str(contents).map_err(|e| format!("Not a valid Engram preset: {}", e))?;
+    if preset.version > PRESET_VERSION {
+        return Err(format!(
+            "Preset was made by a newer version of Engram (format {})",
+            preset.version
+        ));
+    }
+    preset.name = preset.name.trim().to_string();
+    preset.version = PRESET_VERSION;
+
+    let mut warnings = reset_missing_sounds(&mut preset.notifications)?;
+    if load(&preset.name).is_ok() {
+        warnings.push(format!("Replaced the existing preset \"{}\"", preset.name));
+    }
+
+    let conflicts = shortcut_conflicts(&preset.shortcuts);
+    write(&preset)?;
+    engram_log!("Imported preset \"{}\"", preset.name);
+
+    Ok(PresetImport {
+        preset,
+        conflicts,
+        warnings,
+    })
+}
+
+/// Switch custom sounds that aren't installed on this machine back to the
+/// default sound, returning a warning for each.
+pub fn reset_missing_sounds(notifications: &mut NotificationSettings) -> Result<Vec<String>, String> {
+    let mut warnings = Vec::new();
+    let sounds_dir = crate::profiles::root_dir()?.join("sounds");
+    for (event, config) in [
+        ("memory saved", &mut notifications.memory_saved),
+        ("sidecar crashed", &mut notifications.sidecar_crashed),
+        (
+            "maintenance complete",
+            &mut notifications.maintenance_complete,
+        ),
+        ("import complete", &mut notifications.import_complete),
+    ] {
+        if let Some(file) = config.sound.strip_prefix(CUSTOM_SOUND_PREFIX) {
+            if !sounds_dir.join(file).exists() {
+                warnings.push(format!(
+                    "Custom {} sound is not installed here; using the default sound",
+                    event
+                ));
+                config.sound = "default".to_string();
+            }
+        }
+    }
+    Ok(warnings)
+}
+
+/// Problems with a set of shortcuts that can be found without registering
+/// them: invalid accelerators, duplicates, and clashes with tray menu items.
+/// Shortcuts held by other applications only show up when registering.
+pub fn shortcut_conflicts(shortcuts: &ShortcutSettings) -> Vec<String> {
+    let actions = [("Quick Add Memory", shortcuts.quick_add.as_str())];
+    let mut conflicts = Vec::new();
+    let mut seen: Vec<(&str, Shortcut)> = Vec::new();
+
+    for (action, accelerator) in actions {
+        let shortcut = match accelerator.parse::<Shortcut>() {
+            Ok(shortcut) => shortcut,
+            Err(e) => {
+                conflicts.push(format!(
+                    "{}: \"{}\" is not a valid shortcut ({})",
+                    action, accelerator, e
+                ));
+                continue;
+            }
+        };
+        for (reserved, item) in RESERVED_ACCELERATORS {
+            if reserved.parse::<Shortcut>().ok() == Some(shortcut) {
+                conflicts.push(format!(
+                    "{}: {} is used by the tray's {} item",
+                    action, accelerator, item
+                ));
+            }
+        }
+        if let Some((other, _)) = seen.iter().find(|(_, s)| *s == shortcut) {
+            conflicts.push(format!(
+                "{}: {} is also assigned to {}",
+                action, accelerator, other
+            ));
+        }
+        seen.push((action, shortcut));
+    }
+    conflicts
+}
+
+/// `shortcut_conflicts` as an error, for callers about to register.
+pub fn check_shortcuts(shortcuts: &ShortcutSettings) -> Result<(), String> {
+    let conflicts = shortcut_conflicts(shortcuts);
+    if conflicts.is_empty() {
+        Ok(())
+    } else {
+        Err(conflicts.join("; "))
+    }
+}
+
+/// Apply a saved preset: register its shortcuts, then update preferences
+/// and rebuild the tray menu. Nothing changes if a shortcut conflicts.
+pub fn apply(app: &AppHandle, name: &str) -> Result<(), String> {
+    let preset = load(name)?;
+    check_shortcuts(&preset.shortcuts)?;
+
+    let mut prefs = commands::read_preferences().unwrap_or_default();
+    crate::windows::register_quick_add_shortcut(
+        app,
+        &preset.shortcuts.quick_add,
+        Some(&prefs.shortcuts.quick_add),
+    )?;
+
+    prefs.shortcuts = preset.shortcuts;
+    prefs.tray = preset.tray;
+    prefs.notifications = preset.notifications;
+    commands::write_preferences(&prefs)?;
+    crate::tray::apply_layout(app, &prefs)?;
+
+    engram_log!("Applied preset \"{}\"", preset.name);
+    Ok(())
+}
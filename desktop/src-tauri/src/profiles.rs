@@ -0,0 +1,310 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::commands::{self, QuickAddState};
+use crate::sidecar::SidecarState;
+
+/// The profile that lives directly in ~/.engram, as before profiles existed.
+pub const DEFAULT_PROFILE: &str = "default";
+const DEFAULT_PORT: u16 = 3838;
+const MAX_NAME_LEN: usize = 32;
+
+/// Cached active profile name; read from ~/.engram/active-profile on first use.
+static ACTIVE: RwLock<Option<String>> = RwLock::new(None);
+
+/// Stored as profile.json in each non-default profile's data directory.
+#[derive(Debug, Serialize, Deserialize)]
+struct ProfileManifest {
+    port: u16,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileInfo {
+    pub name: String,
+    pub port: u16,
+    pub data_dir: String,
+    pub active: bool,
+}
+
+/// ~/.engram, or on Linux $XDG_DATA_HOME/engram (~/.local/share/engram)
+/// unless an existing ~/.engram is still in use. App-wide files (logs,
+/// presets, sounds, window state, the PID file) live here; everything else
+/// is per profile.
+pub fn root_dir() -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+    let legacy = home.join(".engram");
+    if cfg!(target_os = "linux") {
+        if let Some(data_home) = dirs::data_dir() {
+            let xdg = data_home.join("engram");
+            if xdg.exists() || !legacy.exists() {
+                return Ok(xdg);
+            }
+        }
+    }
+    Ok(legacy)
+}
+
+/// Data directory of a profile: the database, preferences, and sidecar config.
+pub fn data_dir(name: &str) -> Result<PathBuf, String> {
+    let root = root_dir()?;
+    if name == DEFAULT_PROFILE {
+        Ok(root)
+    } else {
+        Ok(root.join("profiles").join(name))
+    }
+}
+
+fn active_file() -> Result<PathBuf, String> {
+    Ok(root_dir()?.join("active-profile"))
+}
+
+fn exists(name: &str) -> bool {
+    name == DEFAULT_PROFILE
+        || data_dir(name)
+            .map(|dir| dir.join("profile.json").exists())
+            .unwrap_or(false)
+}
+
+/// Name of the active profile. Falls back to the default profile if the
+/// saved one no longer exists.
+pub fn active() -> String {
+    if let Some(name) = ACTIVE.read().ok().and_then(|a| a.clone()) {
+        return name;
+    }
+    let name = active_file()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| exists(name))
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+    if let Ok(mut cached) = ACTIVE.write() {
+        *cached = Some(name.clone());
+    }
+    name
+}
+
+fn set_active(name: &str) -> Result<(), String> {
+    fs::write(active_file()?, name).map_err(|e| format!("Failed to save active profile: {}", e))?;
+    if let Ok(mut cached) = ACTIVE.write() {
+        *cached = Some(name.to_string());
+    }
+    Ok(())
+}
+
+/// Parse a `rest_port` preference.
+pub fn parse_port(value: &str) -> Result<u16, String> {
+    value
+        .trim()
+        .parse::<u16>()
+        .ok()
+        .filter(|port| *port >= 1024)
+        .ok_or_else(|| format!("\"{}\" is not a valid port (1024-65535)", value.trim()))
+}
+
+/// REST port of a profile. The default profile's is its `rest_port`
+/// preference; other profiles keep theirs in profile.json.
+pub fn port(name: &str) -> u16 {
+    if name == DEFAULT_PROFILE {
+        return data_dir(name)
+            .ok()
+            .and_then(|dir| fs::read_to_string(dir.join("desktop-config.json")).ok())
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|prefs| prefs["restPort"].as_str().and_then(|p| parse_port(p).ok()))
+            .unwrap_or(DEFAULT_PORT);
+    }
+    data_dir(name)
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join("profile.json")).ok())
+        .and_then(|content| serde_json::from_str::<ProfileManifest>(&content).ok())
+        .map(|manifest| manifest.port)
+        .unwrap_or(DEFAULT_PORT)
+}
+
+/// Sidecar config file pointing the sidecar at a profile's data directory.
+/// The default profile uses the sidecar's own default (~/.engram/config.json)
+/// when that is where its data lives.
+pub fn sidecar_config(name: &str) -> Result<Option<PathBuf>, String> {
+    if name == DEFAULT_PROFILE {
+        let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+        if data_dir(name)? == home.join(".engram") {
+            return Ok(None);
+        }
+    }
+    let dir = data_dir(name)?;
+    let path = dir.join("config.json");
+    if !path.exists() {
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        let config = serde_json::json!({
+            "dataDir": dir.to_string_lossy(),
+            "port": port(name),
+        });
+        let content = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+        fs::write(&path, content).map_err(|e| format!("Failed to write sidecar config: {}", e))?;
+    }
+    Ok(Some(path))
+}
+
+/// Move a profile to another port. Records it in profile.json (the default
+/// profile's preferences already hold it) and in the profile's sidecar
+/// config; the caller restarts the sidecar.
+pub fn set_port(name: &str, port: u16) -> Result<(), String> {
+    if let Some(other) = list()?.iter().find(|p| p.name != name && p.port == port) {
+        return Err(format!(
+            "Port {} is already used by profile \"{}\"",
+            port, other.name
+        ));
+    }
+    let dir = data_dir(name)?;
+    if name != DEFAULT_PROFILE {
+        let manifest =
+            serde_json::to_string_pretty(&ProfileManifest { port }).map_err(|e| e.to_string())?;
+        fs::write(dir.join("profile.json"), manifest)
+            .map_err(|e| format!("Failed to update profile: {}", e))?;
+    }
+    let config_path = dir.join("config.json");
+    if let Ok(content) = fs::read_to_string(&config_path) {
+        if let Ok(mut config) = serde_json::from_str::<serde_json::Value>(&content) {
+            if config.get("port").is_some() {
+                config["port"] = serde_json::json!(port);
+                let content = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+                fs::write(&config_path, content)
+                    .map_err(|e| format!("Failed to write sidecar config: {}", e))?;
+            }
+        }
+    }
+    engram_log!("Profile \"{}\" now uses port {}", name, port);
+    Ok(())
+}
+
+pub fn list() -> Result<Vec<ProfileInfo>, String> {
+    let active = active();
+    let mut names = vec![DEFAULT_PROFILE.to_string()];
+    let profiles_dir = root_dir()?.join("profiles");
+    if let Ok(entries) = fs::read_dir(&profiles_dir) {
+        let mut others: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| exists(name))
+            .collect();
+        others.sort();
+        names.extend(others);
+    }
+
+    names
+        .into_iter()
+        .map(|name| {
+            Ok(ProfileInfo {
+                port: port(&name),
+                data_dir: data_dir(&name)?.to_string_lossy().to_string(),
+                active: name == active,
+                name,
+            })
+        })
+        .collect()
+}
+
+/// Create a profile with its own data directory. Without a port, the first
+/// one above 3838 not used by another profile is assigned.
+pub fn create(name: &str, port: Option<u16>) -> Result<ProfileInfo, String> {
+    let name = name.trim().to_lowercase();
+    let valid = !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!(
+            "Profile names use letters, digits, '-' and '_' (up to {} characters)",
+            MAX_NAME_LEN
+        ));
+    }
+    if exists(&name) {
+        return Err(format!("Profile \"{}\" already exists", name));
+    }
+
+    let taken: Vec<u16> = list()?.iter().map(|p| p.port).collect();
+    let port = match port {
+        Some(port) if taken.contains(&port) => {
+            return Err(format!("Port {} is already used by another profile", port));
+        }
+        Some(port) => port,
+        None => (DEFAULT_PORT + 1..u16::MAX)
+            .find(|p| !taken.contains(p))
+            .ok_or("No free port for the profile")?,
+    };
+
+    let dir = data_dir(&name)?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create profile directory: {}", e))?;
+    let manifest =
+        serde_json::to_string_pretty(&ProfileManifest { port }).map_err(|e| e.to_string())?;
+    fs::write(dir.join("profile.json"), manifest)
+        .map_err(|e| format!("Failed to create profile: {}", e))?;
+    sidecar_config(&name)?;
+
+    engram_log!("Created profile \"{}\" on port {}", name, port);
+    Ok(ProfileInfo {
+        name,
+        port,
+        data_dir: dir.to_string_lossy().to_string(),
+        active: false,
+    })
+}
+
+/// Point the sidecar state at the active profile's port. Called before the
+/// first sidecar start.
+pub fn init(app: &AppHandle) {
+    let name = active();
+    let state = app.state::<SidecarState>();
+    if let Ok(mut port) = state.port.try_lock() {
+        *port = self::port(&name);
+    }
+    if name != DEFAULT_PROFILE {
+        engram_log!("Using profile \"{}\"", name);
+    }
+}
+
+/// Stop the sidecar, make `name` the active profile, apply its preferences,
+/// and start the sidecar on its data directory and port.
+pub async fn switch(app: &AppHandle, name: &str) -> Result<(), String> {
+    let name = name.trim();
+    if !exists(name) {
+        return Err(format!("Profile \"{}\" not found", name));
+    }
+    if name == active() {
+        return Ok(());
+    }
+
+    let previous = commands::read_preferences().unwrap_or_default();
+    crate::sidecar::stop(app).await?;
+    set_active(name)?;
+    engram_log!("Switched to profile \"{}\"", name);
+
+    let state = app.state::<SidecarState>();
+    *state.port.lock().await = port(name);
+    // The last capture belongs to the other profile's database
+    *app.state::<QuickAddState>().last_capture.lock().await = None;
+
+    let prefs = commands::read_preferences().unwrap_or_default();
+    state
+        .health_settings
+        .send_replace(prefs.health_check.clone());
+    crate::logging::configure(prefs.log_max_file_mb, prefs.log_retention_days);
+    if let Err(e) = crate::windows::register_quick_add_shortcut(
+        app,
+        &prefs.shortcuts.quick_add,
+        Some(&previous.shortcuts.quick_add),
+    ) {
+        engram_log!("Failed to apply the profile's quick add shortcut: {}", e);
+    }
+    if let Err(e) = crate::tray::apply_layout(app, &prefs) {
+        engram_log!("Failed to update tray menu: {}", e);
+    }
+
+    let _ = app.emit("profile-changed", name);
+    crate::sidecar::start(app).await
+}
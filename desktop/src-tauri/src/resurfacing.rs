@@ -0,0 +1,253 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{Local, TimeZone, Timelike};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::time::sleep;
+
+use crate::commands::{self, ResurfacingSettings};
+use crate::notifications::{notify, NotificationEvent};
+use crate::sidecar::{SidecarState, SidecarStatus};
+
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// Memories surfaced this recently are not picked again.
+const RECENT_MAX: usize = 50;
+/// Memories from one anniversary day considered for a prompt.
+const DAY_CANDIDATES_MAX: i64 = 500;
+
+/// The memory currently offered in the tray, until it is opened.
+static CURRENT: Mutex<Option<Resurfaced>> = Mutex::new(None);
+
+/// A memory picked for a "Remember this?" prompt.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Resurfaced {
+    pub id: String,
+    pub content: String,
+    /// Why it was picked, e.g. "A year ago today".
+    pub reason: String,
+}
+
+/// When the last prompt was shown and what it showed, kept in
+/// ~/.engram/resurfacing.json so restarts don't prompt again early.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct ResurfacingState {
+    /// Unix seconds.
+    last_at: Option<i64>,
+    recent: Vec<String>,
+}
+
+fn state_path() -> Result<PathBuf, String> {
+    Ok(crate::profiles::root_dir()?.join("resurfacing.json"))
+}
+
+fn load_state() -> ResurfacingState {
+    state_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(state: &ResurfacingState) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+    fs::write(state_path()?, json).map_err(|e| format!("Failed to save resurfacing state: {}", e))
+}
+
+pub fn current() -> Option<Resurfaced> {
+    CURRENT.lock().ok()?.clone()
+}
+
+fn set_current(app: &AppHandle, memory: Option<Resurfaced>) {
+    if let Ok(mut current) = CURRENT.lock() {
+        *current = memory;
+    }
+    let prefs = commands::read_preferences().unwrap_or_default();
+    if let Err(e) = crate::tray::apply_layout(app, &prefs) {
+        engram_log!("Failed to update tray: {}", e);
+    }
+}
+
+/// Forget the offered memory once it has been opened.
+pub fn dismiss(app: &AppHandle) {
+    set_current(app, None);
+}
+
+/// Tray label for the offered memory: its first line, shortened.
+pub fn tray_label(memory: &Resurfaced) -> String {
+    let first_line = memory.content.lines().next().unwrap_or_default();
+    crate::labels::truncate_end(
+        &format!("Remember this? {}", first_line),
+        crate::labels::TRAY_LABEL_MAX,
+    )
+}
+
+/// Whether `hour` falls in the quiet window, which may wrap past midnight.
+/// Equal start and end hours mean no quiet hours.
+fn in_quiet_hours(settings: &ResurfacingSettings, hour: u32) -> bool {
+    let (start, end) = (settings.quiet_start_hour, settings.quiet_end_hour);
+    if start == end {
+        false
+    } else if start < end {
+        (start..end).contains(&hour)
+    } else {
+        hour >= start || hour < end
+    }
+}
+
+fn anniversary_reason(days: u32) -> String {
+    match days {
+        365 => "A year ago today".to_string(),
+        d if d % 365 == 0 => format!("{} years ago today", d / 365),
+        30 => "A month ago today".to_string(),
+        d if d % 30 == 0 => format!("{} months ago today", d / 30),
+        7 => "A week ago today".to_string(),
+        d => format!("{} days ago today", d),
+    }
+}
+
+/// One of `candidates` not in `recent`, varying from run to run.
+fn choose<'a>(
+    candidates: &'a [serde_json::Value],
+    recent: &[String],
+) -> Option<&'a serde_json::Value> {
+    let fresh: Vec<&serde_json::Value> = candidates
+        .iter()
+        .filter(|m| m["id"].as_str().is_some_and(|id| !recent.iter().any(|r| r == id)))
+        .collect();
+    if fresh.is_empty() {
+        return None;
+    }
+    let seed = chrono::Utc::now().timestamp_subsec_nanos() as usize;
+    Some(fresh[seed % fresh.len()])
+}
+
+/// Unix milliseconds of local midnight on `day` and on the day after.
+fn local_day_bounds(day: chrono::NaiveDate) -> Option<(i64, i64)> {
+    let midnight = |date: chrono::NaiveDate| {
+        Local
+            .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+            .earliest()
+            .map(|at| at.timestamp_millis())
+    };
+    Some((midnight(day)?, midnight(day.succ_opt()?)?))
+}
+
+fn to_resurfaced(memory: &serde_json::Value, reason: String) -> Resurfaced {
+    Resurfaced {
+        id: memory["id"].as_str().unwrap_or_default().to_string(),
+        content: memory["content"].as_str().unwrap_or_default().to_string(),
+        reason,
+    }
+}
+
+/// Pick a memory created on one of the configured anniversaries of today,
+/// longest ago first, or else one that has never been recalled.
+async fn pick(
+    port: u16,
+    settings: &ResurfacingSettings,
+    recent: &[String],
+) -> Result<Option<Resurfaced>, String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let today = Local::now().date_naive();
+    let mut anniversaries = settings.anniversary_days.clone();
+    anniversaries.sort_unstable_by(|a, b| b.cmp(a));
+    for days in anniversaries {
+        let day = today - chrono::Duration::days(days as i64);
+        let Some((since, until)) = local_day_bounds(day) else {
+            continue;
+        };
+        let url = format!("http://localhost:{}/api/memories", port);
+        let listing: serde_json::Value = client
+            .get(&url)
+            .query(&[("since", since), ("until", until), ("limit", DAY_CANDIDATES_MAX)])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch memories: {}", e))?
+            .json()
+            .await
+            .map_err(|e| e.to_string())?;
+        let created_that_day = listing["memories"].as_array().cloned().unwrap_or_default();
+        if let Some(memory) = choose(&created_that_day, recent) {
+            return Ok(Some(to_resurfaced(memory, anniversary_reason(days))));
+        }
+    }
+
+    if !settings.include_never_recalled {
+        return Ok(None);
+    }
+    let url = format!("http://localhost:{}/api/analytics/never-recalled?limit=50", port);
+    let never_recalled: serde_json::Value = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch never-recalled memories: {}", e))?
+        .json()
+        .await
+        .map_err(|e| e.to_string())?;
+    let items = never_recalled["items"].as_array().cloned().unwrap_or_default();
+    Ok(choose(&items, recent).map(|m| to_resurfaced(m, "Never recalled".to_string())))
+}
+
+/// Spawn the resurfacing loop. While enabled in preferences, every
+/// `interval_hours` outside quiet hours a memory from an anniversary of
+/// today (or one never recalled) is offered with a notification and a tray
+/// item. Runs are skipped while the sidecar is down.
+pub fn setup_resurfacing_schedule(app: &AppHandle) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            sleep(SCHEDULE_CHECK_INTERVAL).await;
+
+            let settings = commands::read_preferences().unwrap_or_default().resurfacing;
+            if !settings.enabled {
+                continue;
+            }
+            let mut state = load_state();
+            let now = chrono::Utc::now().timestamp();
+            let interval = settings.interval_hours.max(1) as i64 * 3600;
+            if state.last_at.is_some_and(|at| now - at < interval)
+                || in_quiet_hours(&settings, Local::now().hour())
+            {
+                continue;
+            }
+            let sidecar = app_handle.state::<SidecarState>();
+            if !matches!(*sidecar.status.lock().await, SidecarStatus::Running) {
+                continue;
+            }
+            let port = *sidecar.port.lock().await;
+
+            // Counted as a run even when nothing qualifies, so an empty
+            // database isn't queried every minute
+            state.last_at = Some(now);
+            match pick(port, &settings, &state.recent).await {
+                Ok(Some(memory)) => {
+                    state.recent.push(memory.id.clone());
+                    let overflow = state.recent.len().saturating_sub(RECENT_MAX);
+                    state.recent.drain(..overflow);
+                    notify(
+                        &app_handle,
+                        NotificationEvent::Resurfaced,
+                        &format!("Remember this? {}", memory.reason),
+                        &memory.content,
+                    );
+                    let _ = app_handle.emit("memory-resurfaced", &memory);
+                    set_current(&app_handle, Some(memory));
+                }
+                Ok(None) => {}
+                Err(e) => engram_log!("Resurfacing failed: {}", e),
+            }
+            if let Err(e) = save_state(&state) {
+                engram_log!("{}", e);
+            }
+        }
+    });
+}
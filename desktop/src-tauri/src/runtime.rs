@@ -0,0 +1,185 @@
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::commands::find_executable;
+
+/// Oldest Node.js major version the sidecar supports (package.json `engines`).
+pub const MIN_NODE_MAJOR: u32 = 20;
+const REGISTRY_PING_URL: &str = "https://registry.npmjs.org/-/ping";
+const REGISTRY_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeDependency {
+    /// "node", "npx", or "npm-registry"
+    pub name: String,
+    /// Whether Engram currently needs it; node is only needed without the bundled runtime.
+    pub required: bool,
+    pub ok: bool,
+    pub version: Option<String>,
+    pub path: Option<String>,
+    /// What to do about it, when not ok.
+    pub hint: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeDependencyReport {
+    /// The sidecar runs on the node binary shipped with the app.
+    pub bundled_runtime: bool,
+    pub dependencies: Vec<RuntimeDependency>,
+}
+
+/// Run `<program> --version` and return its trimmed output.
+pub fn command_version(program: &Path) -> Option<String> {
+    let output = std::process::Command::new(program)
+        .arg("--version")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!version.is_empty()).then_some(version)
+}
+
+/// Major version from `node --version` output such as "v20.11.1".
+pub fn node_major(version: &str) -> Option<u32> {
+    version
+        .trim_start_matches('v')
+        .split('.')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Check the system `node` for running the sidecar outside the bundled
+/// runtime. Returns the path, or a message saying how to fix it.
+pub fn require_system_node() -> Result<std::path::PathBuf, String> {
+    let node = find_executable("node").ok_or_else(|| {
+        format!(
+            "Node.js was not found on PATH. Install Node.js {} or newer from https://nodejs.org",
+            MIN_NODE_MAJOR
+        )
+    })?;
+    let version = command_version(&node)
+        .ok_or_else(|| format!("Could not run {} --version", node.display()))?;
+    match node_major(&version) {
+        Some(major) if major >= MIN_NODE_MAJOR => Ok(node),
+        _ => Err(format!(
+            "Node.js {} at {} is too old; Engram needs {} or newer",
+            version,
+            node.display(),
+            MIN_NODE_MAJOR
+        )),
+    }
+}
+
+fn check_node(required: bool) -> RuntimeDependency {
+    let path = find_executable("node");
+    let version = path.as_deref().and_then(command_version);
+    let hint = match (&path, &version) {
+        (None, _) => Some(format!(
+            "Install Node.js {} or newer from https://nodejs.org",
+            MIN_NODE_MAJOR
+        )),
+        (Some(path), None) => Some(format!(
+            "{} is present but could not be run",
+            path.display()
+        )),
+        (Some(_), Some(version)) => match node_major(version) {
+            Some(major) if major >= MIN_NODE_MAJOR => None,
+            _ => Some(format!(
+                "Node.js {} is too old; upgrade to {} or newer",
+                version, MIN_NODE_MAJOR
+            )),
+        },
+    };
+    RuntimeDependency {
+        name: "node".to_string(),
+        required,
+        ok: hint.is_none(),
+        version,
+        path: path.map(|p| p.to_string_lossy().to_string()),
+        hint,
+    }
+}
+
+fn check_npx() -> RuntimeDependency {
+    let path = find_executable("npx");
+    let version = path.as_deref().and_then(command_version);
+    let hint = match (&path, &version) {
+        (None, _) => Some(
+            "npx comes with npm; install Node.js from https://nodejs.org so agents can launch Engram"
+                .to_string(),
+        ),
+        (Some(path), None) => Some(format!("{} is present but could not be run", path.display())),
+        _ => None,
+    };
+    RuntimeDependency {
+        name: "npx".to_string(),
+        // Agent configs launch the MCP server with npx
+        required: true,
+        ok: hint.is_none(),
+        version,
+        path: path.map(|p| p.to_string_lossy().to_string()),
+        hint,
+    }
+}
+
+async fn check_registry() -> RuntimeDependency {
+    let result = match reqwest::Client::builder().timeout(REGISTRY_TIMEOUT).build() {
+        Ok(client) => client
+            .get(REGISTRY_PING_URL)
+            .send()
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|resp| {
+                if resp.status().is_success() {
+                    Ok(())
+                } else {
+                    Err(format!("registry returned {}", resp.status()))
+                }
+            }),
+        Err(e) => Err(e.to_string()),
+    };
+    RuntimeDependency {
+        name: "npm-registry".to_string(),
+        required: false,
+        ok: result.is_ok(),
+        version: None,
+        path: Some(REGISTRY_PING_URL.to_string()),
+        hint: result.err().map(|e| {
+            format!(
+                "Cannot reach the npm registry ({}). npx needs it the first time an agent starts Engram; check your network or proxy settings",
+                e
+            )
+        }),
+    }
+}
+
+/// Check everything the sidecar and agent integrations rely on.
+pub async fn check_dependencies(bundled_runtime: bool) -> RuntimeDependencyReport {
+    let (node, npx) =
+        tokio::task::spawn_blocking(move || (check_node(!bundled_runtime), check_npx()))
+            .await
+            .unwrap_or_else(|e| {
+                let failed = |name: &str| RuntimeDependency {
+                    name: name.to_string(),
+                    required: true,
+                    ok: false,
+                    version: None,
+                    path: None,
+                    hint: Some(format!("Check failed: {}", e)),
+                };
+                (failed("node"), failed("npx"))
+            });
+    let registry = check_registry().await;
+
+    RuntimeDependencyReport {
+        bundled_runtime,
+        dependencies: vec![node, npx, registry],
+    }
+}
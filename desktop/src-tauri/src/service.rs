@@ -0,0 +1,177 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use tauri::AppHandle;
+
+use crate::commands::DesktopPreferences;
+use crate::sidecar::LaunchSpec;
+
+/// launchd label of the independently running sidecar.
+const LABEL: &str = "com.engram.sidecar";
+
+/// Whether the sidecar runs as a launchd service that the app only attaches
+/// to. Only macOS has a service manager we install into.
+pub fn enabled(prefs: &DesktopPreferences) -> bool {
+    cfg!(target_os = "macos") && prefs.run_as_service
+}
+
+/// Reject the preference on platforms without service support.
+pub fn validate(prefs: &DesktopPreferences) -> Result<(), String> {
+    if prefs.run_as_service && !cfg!(target_os = "macos") {
+        return Err(
+            "Running the Engram service independently is only supported on macOS".to_string(),
+        );
+    }
+    Ok(())
+}
+
+fn plist_path() -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+    Ok(home
+        .join("Library/LaunchAgents")
+        .join(format!("{}.plist", LABEL)))
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn plist_content(spec: &LaunchSpec, log_path: &Path) -> String {
+    let string = |value: &str| format!("        <string>{}</string>\n", escape_xml(value));
+    let mut program_args = string(&spec.node);
+    for arg in &spec.args {
+        program_args.push_str(&string(arg));
+    }
+    let mut env = String::new();
+    for (key, value) in &spec.env {
+        env.push_str(&format!("        <key>{}</key>\n", escape_xml(key)));
+        env.push_str(&string(value));
+    }
+    let log = escape_xml(&log_path.to_string_lossy());
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{}</string>
+    <key>ProgramArguments</key>
+    <array>
+{}    </array>
+    <key>EnvironmentVariables</key>
+    <dict>
+{}    </dict>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <true/>
+    <key>StandardOutPath</key>
+    <string>{}</string>
+    <key>StandardErrorPath</key>
+    <string>{}</string>
+</dict>
+</plist>
+"#,
+        LABEL, program_args, env, log, log
+    )
+}
+
+fn launchctl(args: &[&str]) -> Result<(), String> {
+    let output = Command::new("launchctl")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run launchctl: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "launchctl {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+fn is_loaded() -> bool {
+    Command::new("launchctl")
+        .args(["list", LABEL])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Install or refresh the launchd plist for `spec` and make sure the service
+/// is loaded. A changed plist is reloaded, which restarts the sidecar with
+/// the new settings; an unchanged one is left running.
+pub fn ensure_running(spec: &LaunchSpec) -> Result<(), String> {
+    let path = plist_path()?;
+    let logs_dir = crate::profiles::root_dir()?.join("logs");
+    fs::create_dir_all(&logs_dir).map_err(|e| e.to_string())?;
+    let content = plist_content(spec, &logs_dir.join("sidecar-service.log"));
+    let path_str = path.to_string_lossy().into_owned();
+
+    let current = fs::read_to_string(&path).ok();
+    if current.as_deref() == Some(content.as_str()) {
+        if !is_loaded() {
+            launchctl(&["load", "-w", &path_str])?;
+        }
+        return Ok(());
+    }
+
+    if current.is_some() && is_loaded() {
+        let _ = launchctl(&["unload", &path_str]);
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path_str, e))?;
+    engram_log!("Installed Engram service ({})", path_str);
+    launchctl(&["load", "-w", &path_str])
+}
+
+/// Stop the service until it is next loaded, keeping its plist.
+pub fn unload() -> Result<(), String> {
+    if !is_loaded() {
+        return Ok(());
+    }
+    let path = plist_path()?;
+    launchctl(&["unload", &path.to_string_lossy()])
+}
+
+/// Whether the service plist is installed, whether or not it is loaded.
+pub fn installed() -> bool {
+    plist_path().is_ok_and(|path| path.exists())
+}
+
+/// Stop the service and remove its plist.
+pub fn uninstall() -> Result<(), String> {
+    let path = plist_path()?;
+    if !path.exists() {
+        return Ok(());
+    }
+    let path_str = path.to_string_lossy().into_owned();
+    if is_loaded() {
+        launchctl(&["unload", "-w", &path_str])?;
+    }
+    fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {}", path_str, e))?;
+    engram_log!("Removed Engram service ({})", path_str);
+    Ok(())
+}
+
+/// Hand the sidecar over between the app and launchd after the preference
+/// changes. Prefs must already be saved so the restart takes the new path.
+pub async fn switch(app: &AppHandle, run_as_service: bool) -> Result<(), String> {
+    // Stops an app-spawned child; in service mode there is nothing to kill
+    crate::sidecar::stop(app).await?;
+    if !run_as_service {
+        uninstall()?;
+        // Give the service's process a moment to release the port
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    crate::sidecar::start(app).await
+}
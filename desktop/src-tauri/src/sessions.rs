@@ -0,0 +1,85 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// A focus session: memories captured while it is active are tagged with
+/// `session:<label>`. Kept in ~/.engram/session.json so it survives an app
+/// restart.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    pub label: String,
+    /// Milliseconds since the epoch, comparable with memories' `createdAt`.
+    pub started_at: i64,
+}
+
+impl Session {
+    pub fn tag(&self) -> String {
+        format!("session:{}", self.label)
+    }
+
+    pub fn elapsed_secs(&self) -> u64 {
+        (now_millis() - self.started_at).max(0) as u64 / 1000
+    }
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn session_path() -> Result<PathBuf, String> {
+    Ok(crate::profiles::root_dir()?.join("session.json"))
+}
+
+pub fn active() -> Option<Session> {
+    let content = fs::read_to_string(session_path().ok()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+pub fn start(label: &str) -> Result<Session, String> {
+    let label = label.trim();
+    if label.is_empty() {
+        return Err("Session label cannot be empty".to_string());
+    }
+    if let Some(current) = active() {
+        return Err(format!(
+            "Session \"{}\" is still active; end it first",
+            current.label
+        ));
+    }
+    let session = Session {
+        label: label.to_string(),
+        started_at: now_millis(),
+    };
+    let path = session_path()?;
+    let json = serde_json::to_string_pretty(&session).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| format!("Failed to save session: {}", e))?;
+    engram_log!("Started session \"{}\"", session.label);
+    Ok(session)
+}
+
+/// Forget the active session once it has been wrapped up.
+pub fn clear() -> Result<(), String> {
+    let path = session_path()?;
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| format!("Failed to end session: {}", e))?;
+    }
+    Ok(())
+}
+
+/// "1h 20m", "45m", or "30s".
+pub fn format_duration(secs: u64) -> String {
+    let (hours, minutes) = (secs / 3600, secs % 3600 / 60);
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}s", secs)
+    }
+}
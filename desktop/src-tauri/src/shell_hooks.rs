@@ -0,0 +1,209 @@
+use std::fs;
+use std::path::PathBuf;
+
+const BLOCK_START: &str = "# >>> engram shell hook >>>";
+const BLOCK_END: &str = "# <<< engram shell hook <<<";
+
+/// Saves its arguments, or whatever is piped into it, as a memory tagged
+/// `terminal`. Plain sh so every hook can share it.
+const NOTE_SCRIPT: &str = r#"#!/bin/sh
+# Installed by Engram. Save a note or command output as a memory:
+#   engram-note "deploys need the VPN"
+#   make test 2>&1 | engram-note
+port="${ENGRAM_PORT:-__PORT__}"
+if [ "$#" -gt 0 ]; then
+  text="$*"
+else
+  text=$(cat)
+fi
+if [ -z "$text" ]; then
+  echo "engram-note: nothing to save" >&2
+  exit 1
+fi
+tab=$(printf '\t')
+content=$(printf '%s\n' "$text" \
+  | tr -d '\000-\010\013-\037' \
+  | sed -e 's/\\/\\\\/g' -e 's/"/\\"/g' -e "s/$tab/\\\\t/g" \
+  | awk '{ printf "%s%s", sep, $0; sep = "\\n" }')
+printf '{"content":"%s","tags":["terminal"]}' "$content" \
+  | curl -fsS -X POST -H 'Content-Type: application/json' --data-binary @- \
+    "http://localhost:$port/api/memories" >/dev/null \
+  && echo "Saved to Engram"
+"#;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Shell {
+    Zsh,
+    Bash,
+    Fish,
+}
+
+impl Shell {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name.trim().to_lowercase().as_str() {
+            "zsh" => Ok(Shell::Zsh),
+            "bash" => Ok(Shell::Bash),
+            "fish" => Ok(Shell::Fish),
+            other => Err(format!(
+                "Unsupported shell \"{}\"; use zsh, bash, or fish",
+                other
+            )),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Shell::Zsh => "zsh",
+            Shell::Bash => "bash",
+            Shell::Fish => "fish",
+        }
+    }
+
+    fn rc_path(self) -> Result<PathBuf, String> {
+        let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+        Ok(match self {
+            Shell::Zsh => home.join(".zshrc"),
+            Shell::Bash => home.join(".bashrc"),
+            Shell::Fish => home.join(".config/fish/config.fish"),
+        })
+    }
+
+    /// Defines `engram-note` and binds Ctrl+X Ctrl+N to save the command
+    /// line being edited.
+    fn hook(self, note: &str) -> String {
+        let note = note.replace('"', "\\\"");
+        match self {
+            Shell::Zsh => format!(
+                r#"engram-note() {{ sh "{note}" "$@"; }}
+_engram_note_buffer() {{
+  [[ -n $BUFFER ]] && print -r -- "$BUFFER" | engram-note >/dev/null && zle -M "Saved to Engram"
+}}
+zle -N _engram_note_buffer
+bindkey '^X^N' _engram_note_buffer
+"#
+            ),
+            Shell::Bash => format!(
+                r#"engram-note() {{ sh "{note}" "$@"; }}
+_engram_note_buffer() {{
+  [ -n "$READLINE_LINE" ] && printf '%s\n' "$READLINE_LINE" | engram-note
+}}
+bind -x '"\C-x\C-n": _engram_note_buffer'
+"#
+            ),
+            Shell::Fish => format!(
+                r#"function engram-note
+    sh "{note}" $argv
+end
+function _engram_note_buffer
+    set -l line (commandline)
+    test -n "$line"; and printf '%s\n' $line | engram-note
+    commandline -f repaint
+end
+bind \cx\cn _engram_note_buffer
+"#
+            ),
+        }
+    }
+}
+
+fn hooks_dir() -> Result<PathBuf, String> {
+    Ok(crate::profiles::root_dir()?.join("shell"))
+}
+
+/// `rc` without a previously installed hook block.
+fn strip_block(rc: &str) -> String {
+    let mut out = Vec::new();
+    let mut inside = false;
+    for line in rc.lines() {
+        if line.trim() == BLOCK_START {
+            inside = true;
+        } else if line.trim() == BLOCK_END {
+            inside = false;
+        } else if !inside {
+            out.push(line);
+        }
+    }
+    let mut text = out.join("\n");
+    if rc.ends_with('\n') && !text.is_empty() {
+        text.push('\n');
+    }
+    text
+}
+
+/// Write the hook for `shell` and source it from the shell's rc file.
+/// Re-installing replaces the previous block, e.g. after a port change.
+pub fn install(shell: &str, port: u16) -> Result<String, String> {
+    let shell = Shell::parse(shell)?;
+    let dir = hooks_dir()?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+    let note = dir.join("engram-note");
+    fs::write(&note, NOTE_SCRIPT.replace("__PORT__", &port.to_string()))
+        .map_err(|e| format!("Failed to write {}: {}", note.display(), e))?;
+    let hook_path = dir.join(format!("hook.{}", shell.name()));
+    fs::write(&hook_path, shell.hook(&note.to_string_lossy()))
+        .map_err(|e| format!("Failed to write {}: {}", hook_path.display(), e))?;
+
+    let rc_path = shell.rc_path()?;
+    let existing = fs::read_to_string(&rc_path).unwrap_or_default();
+    let mut rc = strip_block(&existing);
+    if !rc.is_empty() && !rc.ends_with('\n') {
+        rc.push('\n');
+    }
+    rc.push_str(&format!(
+        "{}\nsource \"{}\"\n{}\n",
+        BLOCK_START,
+        hook_path.to_string_lossy().replace('"', "\\\""),
+        BLOCK_END
+    ));
+    if let Some(parent) = rc_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::write(&rc_path, rc)
+        .map_err(|e| format!("Failed to update {}: {}", rc_path.display(), e))?;
+
+    engram_log!("Installed {} shell hook in {}", shell.name(), rc_path.display());
+    Ok(format!(
+        "Installed the {} hook in {}. Open a new terminal, then use engram-note or Ctrl+X Ctrl+N.",
+        shell.name(),
+        rc_path.display()
+    ))
+}
+
+/// Shells the hook is installed for.
+pub fn installed() -> Vec<&'static str> {
+    let Ok(dir) = hooks_dir() else {
+        return Vec::new();
+    };
+    [Shell::Zsh, Shell::Bash, Shell::Fish]
+        .into_iter()
+        .filter(|s| dir.join(format!("hook.{}", s.name())).exists())
+        .map(Shell::name)
+        .collect()
+}
+
+/// Remove the hook from the shell's rc file, and the shared script once no
+/// shell uses it.
+pub fn uninstall(shell: &str) -> Result<String, String> {
+    let shell = Shell::parse(shell)?;
+    let rc_path = shell.rc_path()?;
+    if let Ok(existing) = fs::read_to_string(&rc_path) {
+        let rc = strip_block(&existing);
+        if rc != existing {
+            fs::write(&rc_path, rc)
+                .map_err(|e| format!("Failed to update {}: {}", rc_path.display(), e))?;
+        }
+    }
+
+    let dir = hooks_dir()?;
+    let _ = fs::remove_file(dir.join(format!("hook.{}", shell.name())));
+    let any_left = [Shell::Zsh, Shell::Bash, Shell::Fish]
+        .iter()
+        .any(|s| dir.join(format!("hook.{}", s.name())).exists());
+    if !any_left {
+        let _ = fs::remove_file(dir.join("engram-note"));
+    }
+
+    engram_log!("Removed {} shell hook from {}", shell.name(), rc_path.display());
+    Ok(format!("Removed the {} hook from {}", shell.name(), rc_path.display()))
+}
@@ -1,14 +1,51 @@
+use std::collections::VecDeque;
 use std::sync::Arc;
-use std::time::Duration;
-use tauri::{AppHandle, Emitter, Listener, Manager};
-use tokio::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use rand::Rng;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::{mpsc, oneshot, watch, Mutex};
 use tauri_plugin_shell::ShellExt;
 use tauri_plugin_shell::process::{CommandChild, CommandEvent};
 use tokio::time::sleep;
 
-const MAX_RESTART_ATTEMPTS: u32 = 3;
-const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
-const STARTUP_GRACE_PERIOD: Duration = Duration::from_secs(5);
+use crate::commands::{HealthCheckSettings, RestartBackoffSettings};
+
+const READINESS_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Oldest sidecar whose REST API this app can drive safely; older ones are
+/// run but destructive commands are refused.
+pub const MIN_SIDECAR_VERSION: &str = "1.4.0";
+/// stderr lines attached to start-failure and crash events.
+const FAILURE_STDERR_LINES: usize = 100;
+const LOG_BUFFER_CAPACITY: usize = 5000;
+const LOG_EVENTS_PER_SECOND: u32 = 50;
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+/// A sidecar that stays up this long has its crash count forgiven.
+const STABLE_UPTIME_RESET: Duration = Duration::from_secs(10 * 60);
+/// Wall-clock time passing this much beyond the health interval means the machine slept.
+const SLEEP_DETECTION_SLACK: Duration = Duration::from_secs(30);
+/// After wake the network stack and the sidecar need a moment; check patiently.
+const WAKE_CHECK_TIMEOUT: Duration = Duration::from_secs(15);
+const WAKE_CHECK_ATTEMPTS: u32 = 3;
+const WAKE_CHECK_RETRY_DELAY: Duration = Duration::from_secs(5);
+/// Tells the sidecar which embedding backend to use instead of native onnxruntime.
+const EMBEDDING_BACKEND_ENV: &str = "ENGRAM_EMBEDDING_BACKEND";
+/// Initial sidecar log level; later changes go through `/api/config/reload`.
+const LOG_LEVEL_ENV: &str = "ENGRAM_LOG_LEVEL";
+/// Asks the sidecar to print a `ReadyInfo` JSON line on stdout once it listens.
+const READY_EVENT_ENV: &str = "ENGRAM_READY_EVENT";
+/// Variable the OS loader searches for onnxruntime's shared library. Windows
+/// resolves DLLs through PATH; there and on Linux the bundled directory is
+/// prepended to the existing value.
+#[cfg(windows)]
+const LIBRARY_PATH_ENV: &str = "PATH";
+#[cfg(target_os = "linux")]
+const LIBRARY_PATH_ENV: &str = "LD_LIBRARY_PATH";
+#[cfg(not(any(windows, target_os = "linux")))]
+const LIBRARY_PATH_ENV: &str = "DYLD_LIBRARY_PATH";
+/// Set by the app to locate the bundled runtime; user overrides would break startup.
+const PROTECTED_ENV: [&str; 2] = ["NODE_PATH", LIBRARY_PATH_ENV];
+/// `engram start` options the app sets itself; the desktop app needs the REST server.
+const PROTECTED_ARGS: [&str; 2] = ["--port", "--mcp-only"];
 
 #[derive(Debug, Clone, serde::Serialize)]
 pub enum SidecarStatus {
@@ -16,35 +53,394 @@ pub enum SidecarStatus {
     Starting,
     Running,
     Crashed,
+    /// Stopped on purpose (e.g. while screen-sharing) until resumed.
+    Paused,
+}
+
+/// Whether the user wants the sidecar running. Crash recovery and health
+/// checks only act while this is `Running`.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+pub enum DesiredState {
+    Running,
+    Stopped,
+    Paused,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+/// A single line of sidecar output, as captured from stdout/stderr.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LogLine {
+    /// Monotonic sequence number, usable as an offset for incremental reads.
+    pub seq: u64,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    /// "stdout", "stderr", or "system" for lifecycle messages from the shell.
+    pub stream: String,
+    pub level: LogLevel,
+    pub text: String,
+}
+
+/// Determine the level of a sidecar output line.
+/// The sidecar logger writes `[<ISO timestamp>] LEVEL: message {meta}` to stderr,
+/// so the stream alone says nothing about severity. Unstructured output falls
+/// back to keyword matching.
+pub fn parse_log_level(stream: &str, text: &str) -> LogLevel {
+    let body = text.trim_start();
+    let body = if body.starts_with('[') {
+        body.find("] ").map_or(body, |i| &body[i + 2..])
+    } else {
+        body
+    };
+
+    if let Some((prefix, _)) = body.split_once(':') {
+        match prefix.trim() {
+            "DEBUG" => return LogLevel::Debug,
+            "INFO" => return LogLevel::Info,
+            "WARN" | "WARNING" => return LogLevel::Warn,
+            "ERROR" | "FATAL" => return LogLevel::Error,
+            _ => {}
+        }
+    }
+
+    let lower = text.to_lowercase();
+    if lower.contains("error") || lower.contains("exception") || lower.contains("fatal") {
+        LogLevel::Error
+    } else if lower.contains("warn") || (stream == "system" && lower.contains("terminated")) {
+        LogLevel::Warn
+    } else {
+        LogLevel::Info
+    }
+}
+
+/// Known ways the sidecar fails, recognised from its stderr output.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SidecarFailureKind {
+    /// The onnxruntime native library could not be loaded.
+    OnnxLoad,
+    /// The REST port was already bound by another process.
+    PortInUse,
+    /// SQLite reported a corrupt or invalid database file.
+    DatabaseCorrupt,
+    /// Node ran out of heap or the system refused an allocation.
+    OutOfMemory,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SidecarFailure {
+    pub kind: SidecarFailureKind,
+    /// The stderr line that identified the failure.
+    pub line: String,
+}
+
+/// Match a stderr line against known failure signatures.
+pub fn classify_failure(line: &str) -> Option<SidecarFailureKind> {
+    let lower = line.to_lowercase();
+    if lower.contains("eaddrinuse") || lower.contains("address already in use") {
+        Some(SidecarFailureKind::PortInUse)
+    } else if lower.contains("sqlite_corrupt")
+        || lower.contains("database disk image is malformed")
+        || lower.contains("sqlite_notadb")
+        || lower.contains("file is not a database")
+    {
+        Some(SidecarFailureKind::DatabaseCorrupt)
+    } else if lower.contains("heap out of memory")
+        || lower.contains("reached heap limit")
+        || lower.contains("allocation failed")
+        || lower.contains("enomem")
+    {
+        Some(SidecarFailureKind::OutOfMemory)
+    } else if lower.contains("onnxruntime")
+        && (lower.contains("error") || lower.contains("failed") || lower.contains("dlopen"))
+    {
+        Some(SidecarFailureKind::OnnxLoad)
+    } else {
+        None
+    }
+}
+
+/// Ring buffer holding the most recent sidecar output lines.
+#[derive(Debug, Default)]
+pub struct LogBuffer {
+    lines: VecDeque<LogLine>,
+    next_seq: u64,
+}
+
+impl LogBuffer {
+    /// Append a line, evicting the oldest once full. Returns the stored line.
+    pub fn push(&mut self, stream: &str, text: &str) -> LogLine {
+        if self.lines.len() >= LOG_BUFFER_CAPACITY {
+            self.lines.pop_front();
+        }
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        let line = LogLine {
+            seq: self.next_seq,
+            timestamp,
+            stream: stream.to_string(),
+            level: parse_log_level(stream, text),
+            text: text.to_string(),
+        };
+        self.lines.push_back(line.clone());
+        self.next_seq += 1;
+        crate::logging::write(&format!("sidecar {}", stream), text);
+        line
+    }
+
+    /// Return up to `limit` lines with a sequence number >= `offset`, optionally
+    /// keeping only lines whose text contains `filter` (case-insensitive).
+    pub fn query(&self, offset: u64, limit: usize, filter: Option<&str>) -> Vec<LogLine> {
+        let filter = filter.map(|f| f.to_lowercase());
+        self.lines
+            .iter()
+            .filter(|line| line.seq >= offset)
+            .filter(|line| {
+                filter
+                    .as_ref()
+                    .map_or(true, |f| line.text.to_lowercase().contains(f.as_str()))
+            })
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Sequence number that the next pushed line will receive.
+    pub fn next_seq(&self) -> u64 {
+        self.next_seq
+    }
+
+    /// The last `count` stderr lines pushed at or after sequence `since`.
+    pub fn stderr_tail(&self, since: u64, count: usize) -> Vec<String> {
+        let lines: Vec<String> = self
+            .query(since, usize::MAX, None)
+            .into_iter()
+            .filter(|line| line.stream == "stderr")
+            .map(|line| line.text)
+            .collect();
+        let skip = lines.len().saturating_sub(count);
+        lines.into_iter().skip(skip).collect()
+    }
+}
+
+/// How a sidecar process ended, sent with the `sidecar-crashed` event.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProcessExit {
+    pub code: Option<i32>,
+    pub signal: Option<i32>,
+    /// Set when the process failed at the OS level rather than exiting.
+    pub error: Option<String>,
+    /// Final stderr lines from this process.
+    pub stderr: Vec<String>,
+}
+
+impl ProcessExit {
+    /// Short human-readable reason, used as the crash reason in `get_status`.
+    pub fn describe(&self) -> String {
+        match (&self.error, self.code, self.signal) {
+            (Some(error), _, _) => format!("Process error: {}", error),
+            (None, _, Some(signal)) => format!("Killed by signal {}", signal),
+            (None, Some(code), None) => format!("Exited with code {}", code),
+            (None, None, None) => "Exited unexpectedly".to_string(),
+        }
+    }
+}
+
+/// Caps how many `sidecar-log` events are emitted per second so a chatty
+/// sidecar can't flood the event bus. Lines over the cap are still buffered
+/// and can be fetched with `get_sidecar_logs`.
+struct LogEventThrottle {
+    window_start: Instant,
+    sent: u32,
+    dropped: u64,
+}
+
+impl LogEventThrottle {
+    fn new() -> Self {
+        Self {
+            window_start: Instant::now(),
+            sent: 0,
+            dropped: 0,
+        }
+    }
+
+    fn emit(&mut self, app: &AppHandle, line: &LogLine) {
+        if self.window_start.elapsed() >= Duration::from_secs(1) {
+            if self.dropped > 0 {
+                let _ = app.emit(
+                    "sidecar-log-dropped",
+                    serde_json::json!({ "count": self.dropped }),
+                );
+            }
+            self.window_start = Instant::now();
+            self.sent = 0;
+            self.dropped = 0;
+        }
+
+        if self.sent < LOG_EVENTS_PER_SECOND {
+            self.sent += 1;
+            let _ = app.emit("sidecar-log", line);
+        } else {
+            self.dropped += 1;
+        }
+    }
 }
 
 pub struct SidecarState {
     pub child: Arc<Mutex<Option<CommandChild>>>,
     pub status: Arc<Mutex<SidecarStatus>>,
+    pub desired_state: Arc<Mutex<DesiredState>>,
     pub restart_count: Arc<Mutex<u32>>,
+    /// When the current process last became ready.
+    pub started_at: Arc<Mutex<Option<Instant>>>,
     pub port: Arc<Mutex<u16>>,
+    pub logs: Arc<Mutex<LogBuffer>>,
+    /// PID of a sidecar left behind by a previous app session that we took
+    /// over instead of spawning a new one. There is no `CommandChild` for it,
+    /// so it is stopped by signalling the PID directly.
+    pub adopted_pid: Arc<Mutex<Option<u32>>>,
+    /// Most recent recognised failure signature from the current process's stderr.
+    pub last_failure: Arc<Mutex<Option<SidecarFailure>>>,
+    /// Set after the native onnxruntime failed to load; the sidecar is then
+    /// launched with the WASM/CPU embedding backend (reduced performance).
+    pub onnx_fallback: Arc<Mutex<bool>>,
+    /// Profile whose data directory the current sidecar serves.
+    pub profile: Arc<Mutex<String>>,
+    /// When the sidecar last crashed (Unix seconds) and why.
+    pub last_crash: Arc<Mutex<Option<(i64, String)>>>,
+    /// Version of the running sidecar when it is older than `MIN_SIDECAR_VERSION`.
+    pub incompatible_version: Arc<Mutex<Option<String>>>,
+    /// Reported by the spawned sidecar once it listens; cleared on each launch.
+    pub ready: Arc<Mutex<Option<ReadyInfo>>>,
+    /// Kept between samples: CPU usage is measured since the previous refresh.
+    system: std::sync::Mutex<sysinfo::System>,
+    /// Health check settings; updated when preferences are saved so the
+    /// health loop picks up changes without a restart.
+    pub health_settings: watch::Sender<HealthCheckSettings>,
+    supervisor_tx: mpsc::UnboundedSender<SupervisorCommand>,
+    supervisor_rx: std::sync::Mutex<Option<mpsc::UnboundedReceiver<SupervisorCommand>>>,
 }
 
 impl Default for SidecarState {
     fn default() -> Self {
+        let (supervisor_tx, supervisor_rx) = mpsc::unbounded_channel();
         Self {
             child: Arc::new(Mutex::new(None)),
             status: Arc::new(Mutex::new(SidecarStatus::Stopped)),
+            desired_state: Arc::new(Mutex::new(DesiredState::Stopped)),
             restart_count: Arc::new(Mutex::new(0)),
+            started_at: Arc::new(Mutex::new(None)),
             port: Arc::new(Mutex::new(3838)),
+            logs: Arc::new(Mutex::new(LogBuffer::default())),
+            adopted_pid: Arc::new(Mutex::new(None)),
+            last_failure: Arc::new(Mutex::new(None)),
+            onnx_fallback: Arc::new(Mutex::new(false)),
+            profile: Arc::new(Mutex::new(crate::profiles::DEFAULT_PROFILE.to_string())),
+            last_crash: Arc::new(Mutex::new(None)),
+            incompatible_version: Arc::new(Mutex::new(None)),
+            ready: Arc::new(Mutex::new(None)),
+            system: std::sync::Mutex::new(sysinfo::System::new()),
+            health_settings: watch::channel(HealthCheckSettings::default()).0,
+            supervisor_tx,
+            supervisor_rx: std::sync::Mutex::new(Some(supervisor_rx)),
+        }
+    }
+}
+
+/// What the sidecar reports about itself on stdout once it is listening:
+/// `{"event":"ready","port":3838,"dbPath":"...","version":"1.4.2","pid":123}`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadyInfo {
+    pub port: u16,
+    pub db_path: String,
+    pub version: String,
+    pub pid: u32,
+}
+
+/// A `ReadyInfo` from a stdout line, if the line is a ready event.
+fn parse_ready_line(line: &str) -> Option<ReadyInfo> {
+    let line = line.trim();
+    if !line.starts_with('{') {
+        return None;
+    }
+    let value: serde_json::Value = serde_json::from_str(line).ok()?;
+    if value["event"] != "ready" {
+        return None;
+    }
+    serde_json::from_value(value).ok()
+}
+
+/// Resource usage of the sidecar process.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessUsage {
+    pub pid: u32,
+    /// Percent of one core, averaged since the previous sample.
+    pub cpu_percent: f32,
+    /// Resident set size in bytes.
+    pub memory_bytes: u64,
+}
+
+impl SidecarState {
+    /// PID of the sidecar we own or adopted, if any.
+    pub async fn pid(&self) -> Option<u32> {
+        let child_pid = self.child.lock().await.as_ref().map(|c| c.pid());
+        match child_pid {
+            Some(pid) => Some(pid),
+            None => *self.adopted_pid.lock().await,
         }
     }
+
+    /// Sample CPU and RSS of the sidecar process. The first sample after a
+    /// (re)start reports 0% CPU since there is no previous measurement.
+    pub async fn process_usage(&self) -> Option<ProcessUsage> {
+        use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate};
+
+        let pid = self.pid().await?;
+        let sys_pid = Pid::from_u32(pid);
+        let mut system = self.system.lock().ok()?;
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[sys_pid]),
+            true,
+            ProcessRefreshKind::new().with_cpu().with_memory(),
+        );
+        let process = system.process(sys_pid)?;
+        Some(ProcessUsage {
+            pid,
+            cpu_percent: process.cpu_usage(),
+            memory_bytes: process.memory(),
+        })
+    }
 }
 
-/// Return the Rust target triple suffix for the current architecture.
+/// Return the Rust target triple suffix for the current platform.
 fn arch_suffix() -> &'static str {
-    match std::env::consts::ARCH {
-        "aarch64" => "aarch64-apple-darwin",
-        "x86_64" => "x86_64-apple-darwin",
-        other => other,
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("windows", "aarch64") => "aarch64-pc-windows-msvc",
+        ("windows", _) => "x86_64-pc-windows-msvc",
+        ("linux", "aarch64") => "aarch64-unknown-linux-gnu",
+        ("linux", _) => "x86_64-unknown-linux-gnu",
+        (_, "aarch64") => "aarch64-apple-darwin",
+        (_, "x86_64") => "x86_64-apple-darwin",
+        (_, other) => other,
     }
 }
 
+/// File name of the bundled node binary, e.g. `node-x86_64-pc-windows-msvc.exe`.
+fn bundled_node_name() -> String {
+    format!("node-{}{}", arch_suffix(), std::env::consts::EXE_SUFFIX)
+}
+
 /// Check the version of an Engram server running on the given port.
 /// Makes a synchronous HTTP GET to /health and parses the version from the JSON response.
 /// Returns Some(version) if it's an Engram server, None otherwise.
@@ -98,13 +494,101 @@ fn kill_process_on_port(port: u16) {
         for pid in pids.trim().lines() {
             let pid = pid.trim();
             if !pid.is_empty() {
-                eprintln!("[engram] Killing old Engram process on port {}: PID {}", port, pid);
+                engram_log!("Killing old Engram process on port {}: PID {}", port, pid);
                 let _ = std::process::Command::new("kill").arg(pid).output();
             }
         }
     }
 }
 
+fn pid_file_path() -> Option<std::path::PathBuf> {
+    // One sidecar runs at a time, whichever profile it serves
+    crate::profiles::root_dir()
+        .ok()
+        .map(|dir| dir.join("sidecar.pid"))
+}
+
+fn write_pid_file(pid: u32) {
+    if let Some(path) = pid_file_path() {
+        if let Err(e) = std::fs::write(&path, pid.to_string()) {
+            engram_log!("Failed to write {}: {}", path.display(), e);
+        }
+    }
+}
+
+fn read_pid_file() -> Option<u32> {
+    let content = std::fs::read_to_string(pid_file_path()?).ok()?;
+    content.trim().parse().ok()
+}
+
+fn remove_pid_file() {
+    if let Some(path) = pid_file_path() {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Remove the PID file only if it still refers to `pid`, so a late exit event
+/// from an old process can't clobber the entry for its replacement.
+fn remove_pid_file_for(pid: u32) {
+    if read_pid_file() == Some(pid) {
+        remove_pid_file();
+    }
+}
+
+/// Whether `pid` is a live Engram node process, judged by its command line.
+fn is_engram_process(pid: u32) -> bool {
+    #[cfg(unix)]
+    {
+        std::process::Command::new("ps")
+            .args(["-p", &pid.to_string(), "-o", "command="])
+            .output()
+            .map(|o| {
+                let command = String::from_utf8_lossy(&o.stdout);
+                o.status.success()
+                    && (command.contains("engram-bundle.cjs") || command.contains("engram.js"))
+            })
+            .unwrap_or(false)
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = pid;
+        false
+    }
+}
+
+fn force_kill(pid: u32) {
+    #[cfg(unix)]
+    let _ = std::process::Command::new("kill")
+        .args(["-9", &pid.to_string()])
+        .output();
+
+    #[cfg(windows)]
+    let _ = std::process::Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/F"])
+        .output();
+}
+
+/// Return the onnxruntime platform directory name for the current OS.
+fn ort_platform() -> &'static str {
+    match std::env::consts::OS {
+        "windows" => "win32",
+        "linux" => "linux",
+        _ => "darwin",
+    }
+}
+
+/// Value for `LIBRARY_PATH_ENV` that lets the loader find onnxruntime in `dir`.
+fn library_path_value(dir: &std::path::Path) -> std::ffi::OsString {
+    if cfg!(any(windows, target_os = "linux")) {
+        let existing = std::env::var_os(LIBRARY_PATH_ENV).unwrap_or_default();
+        let dirs = std::iter::once(dir.to_path_buf()).chain(std::env::split_paths(&existing));
+        std::env::join_paths(dirs).unwrap_or_else(|_| dir.as_os_str().to_os_string())
+    } else {
+        dir.as_os_str().to_os_string()
+    }
+}
+
 /// Return the onnxruntime arch directory name for the current architecture.
 fn ort_arch() -> &'static str {
     match std::env::consts::ARCH {
@@ -114,6 +598,112 @@ fn ort_arch() -> &'static str {
     }
 }
 
+/// Which node binary and entry script the sidecar runs with, and why.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidecarResolution {
+    /// "developer", "bundled", or "system"
+    pub source: String,
+    /// Absolute path, or "node" to use the one on PATH.
+    pub node: String,
+    pub script: String,
+    /// NODE_PATH for the bundled runtime's native modules.
+    pub node_modules: Option<String>,
+    pub reason: String,
+    /// Candidates that were passed over, and why.
+    pub skipped: Vec<String>,
+}
+
+/// Pick the sidecar's node binary and entry script: developer preferences
+/// first, then the bundled runtime (production), then system node running
+/// a checkout's bin/engram.js (development).
+pub fn resolve_sidecar(
+    app: &AppHandle,
+    prefs: &crate::commands::DesktopPreferences,
+) -> Result<SidecarResolution, String> {
+    let mut skipped = Vec::new();
+
+    let developer = &prefs.developer;
+    let node_path = developer.node_path.trim();
+    let entry_script = developer.entry_script.trim();
+    if developer.enabled && node_path.is_empty() && entry_script.is_empty() {
+        skipped.push("Developer mode is on but no paths are set".to_string());
+    } else if developer.enabled {
+        let mut usable = true;
+        if !node_path.is_empty() && !std::path::Path::new(node_path).is_file() {
+            skipped.push(format!("Developer node binary not found: {}", node_path));
+            usable = false;
+        }
+        if !entry_script.is_empty() && !std::path::Path::new(entry_script).is_file() {
+            skipped.push(format!("Developer entry script not found: {}", entry_script));
+            usable = false;
+        }
+        if usable {
+            let script = if entry_script.is_empty() {
+                let root = find_engram_root(app)?;
+                root.join("bin").join("engram.js").to_string_lossy().into_owned()
+            } else {
+                entry_script.to_string()
+            };
+            let node = if node_path.is_empty() { "node" } else { node_path };
+            return Ok(SidecarResolution {
+                source: "developer".to_string(),
+                node: node.to_string(),
+                script,
+                node_modules: None,
+                reason: "Set in developer preferences".to_string(),
+                skipped,
+            });
+        }
+    }
+
+    if let Some(resources_dir) = find_resources_dir(app) {
+        // A downloaded bundle runs on the app's node and native modules
+        let (script, reason) = match crate::bundle_update::installed_bundle(app) {
+            Some((script, version)) => {
+                let reason = format!("Using updated engram-bundle.cjs v{}", version);
+                (script, reason)
+            }
+            None => (
+                resources_dir.join("engram-bundle.cjs"),
+                format!("Found engram-bundle.cjs in {}", resources_dir.display()),
+            ),
+        };
+        return Ok(SidecarResolution {
+            source: "bundled".to_string(),
+            node: resources_dir
+                .join(bundled_node_name())
+                .to_string_lossy()
+                .into_owned(),
+            script: script.to_string_lossy().into_owned(),
+            node_modules: Some(resources_dir.join("node_modules").to_string_lossy().into_owned()),
+            reason,
+            skipped,
+        });
+    }
+    skipped.push("No engram-bundle.cjs in the app resources or ./resources".to_string());
+
+    let engram_root = find_engram_root(app)?;
+    let script_path = engram_root.join("bin").join("engram.js");
+    if !script_path.exists() {
+        return Err(format!("Engram entry point not found at: {}", script_path.display()));
+    }
+    Ok(SidecarResolution {
+        source: "system".to_string(),
+        node: "node".to_string(),
+        script: script_path.to_string_lossy().into_owned(),
+        node_modules: None,
+        reason: format!("Running bin/engram.js from {} with node on PATH", engram_root.display()),
+        skipped,
+    })
+}
+
+/// Whether the sidecar runs on the app's bundled node rather than the system one.
+pub fn uses_bundled_runtime(app: &AppHandle) -> bool {
+    let prefs = crate::commands::read_preferences().unwrap_or_default();
+    resolve_sidecar(app, &prefs).is_ok_and(|r| r.source == "bundled")
+}
+
 /// Find the resources directory containing the bundled sidecar.
 /// Looks for `engram-bundle.cjs` as a marker file.
 fn find_resources_dir(app: &AppHandle) -> Option<std::path::PathBuf> {
@@ -186,173 +776,569 @@ fn find_engram_root(app: &AppHandle) -> Result<std::path::PathBuf, String> {
     Err("Could not locate engram project root. Ensure bin/engram.js is accessible.".to_string())
 }
 
-pub fn start_sidecar(app: &AppHandle) -> Result<(), String> {
+/// The user's `env_overrides`, minus invalid names and variables the app
+/// manages itself. Only names are logged; values may hold secrets.
+fn env_overrides(prefs: &crate::commands::DesktopPreferences) -> Vec<(String, String)> {
+    let mut vars: Vec<(String, String)> = prefs
+        .env_overrides
+        .iter()
+        .filter(|(name, _)| {
+            let valid = !name.is_empty()
+                && !name.starts_with(|c: char| c.is_ascii_digit())
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                engram_log!("Ignoring invalid environment variable name \"{}\"", name);
+            } else if is_protected_env(name) {
+                engram_log!("Ignoring override of {}; it is set by Engram", name);
+            }
+            valid && !is_protected_env(name)
+        })
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    vars.sort();
+
+    if !vars.is_empty() {
+        let names: Vec<&str> = vars.iter().map(|(name, _)| name.as_str()).collect();
+        engram_log!("Sidecar environment overrides: {}", names.join(", "));
+    }
+    vars
+}
+
+/// Environment variable names are case-insensitive on Windows.
+fn is_protected_env(name: &str) -> bool {
+    PROTECTED_ENV.iter().any(|protected| {
+        if cfg!(windows) {
+            protected.eq_ignore_ascii_case(name)
+        } else {
+            *protected == name
+        }
+    })
+}
+
+/// Split a string into arguments the way a POSIX shell would, without
+/// expansion: whitespace separates words, quotes group them, and a
+/// backslash escapes the next character (inside double quotes, only `"`,
+/// `\`, and `$`).
+pub fn split_args(input: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err("Unterminated single quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$')) => current.push(c),
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => return Err("Unterminated double quote".to_string()),
+                        },
+                        Some(c) => current.push(c),
+                        None => return Err("Unterminated double quote".to_string()),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some(c) => {
+                    in_word = true;
+                    current.push(c);
+                }
+                None => return Err("Trailing backslash".to_string()),
+            },
+            c if c.is_whitespace() => {
+                if in_word {
+                    args.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_word {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+/// Split and validate the `sidecar_args` preference.
+pub fn parse_sidecar_args(input: &str) -> Result<Vec<String>, String> {
+    let args = split_args(input).map_err(|e| format!("Invalid sidecar arguments: {}", e))?;
+    for arg in &args {
+        let name = arg.split('=').next().unwrap_or(arg);
+        if PROTECTED_ARGS.contains(&name) {
+            return Err(format!("Sidecar argument {} is managed by Engram", name));
+        }
+        if arg.contains('\0') {
+            return Err("Sidecar arguments cannot contain NUL characters".to_string());
+        }
+    }
+    Ok(args)
+}
+
+/// The program, arguments and environment the sidecar runs with, shared by
+/// the app-spawned child and the launchd service.
+pub struct LaunchSpec {
+    pub node: String,
+    pub args: Vec<String>,
+    pub env: Vec<(String, String)>,
+}
+
+async fn launch_spec(
+    app: &AppHandle,
+    prefs: &crate::commands::DesktopPreferences,
+    port: u16,
+) -> Result<LaunchSpec, String> {
+    let state = app.state::<SidecarState>();
+    let mut env = Vec::new();
+    env.extend(prefs.search.to_env());
+    env.push((LOG_LEVEL_ENV.to_string(), prefs.log_level.clone()));
+    env.push((READY_EVENT_ENV.to_string(), "1".to_string()));
+    env.extend(env_overrides(prefs));
+    if *state.onnx_fallback.lock().await {
+        engram_log!("Using WASM embedding backend (reduced performance)");
+        env.push((EMBEDDING_BACKEND_ENV.to_string(), "wasm".to_string()));
+    }
+    // Bad arguments are rejected when preferences are saved; a hand-edited
+    // config shouldn't keep the sidecar from starting
+    let extra_args = match parse_sidecar_args(&prefs.sidecar_args) {
+        Ok(args) => args,
+        Err(e) => {
+            engram_log!("Ignoring sidecar arguments: {}", e);
+            Vec::new()
+        }
+    };
+    if !extra_args.is_empty() {
+        engram_log!("Extra sidecar arguments: {}", extra_args.join(" "));
+    }
+    let profile = crate::profiles::active();
+    let mut profile_args = Vec::new();
+    if let Some(config) = crate::profiles::sidecar_config(&profile)? {
+        engram_log!("Using profile \"{}\" ({})", profile, config.display());
+        profile_args.push("--config".to_string());
+        profile_args.push(config.to_string_lossy().into_owned());
+    }
+    *state.profile.lock().await = profile;
+    let resolution = resolve_sidecar(app, prefs)?;
+    engram_log!("Using {} sidecar: {}", resolution.source, resolution.reason);
+    for skipped in &resolution.skipped {
+        engram_log!("  skipped: {}", skipped);
+    }
+    engram_log!("  node binary: {}", resolution.node);
+    engram_log!("  script: {}", resolution.script);
+
+    if let Some(node_modules) = &resolution.node_modules {
+        let dylib_dir = std::path::Path::new(node_modules)
+            .join("onnxruntime-node")
+            .join("bin")
+            .join("napi-v3")
+            .join(ort_platform())
+            .join(ort_arch());
+        engram_log!("  NODE_PATH: {}", node_modules);
+        env.insert(0, ("NODE_PATH".to_string(), node_modules.clone()));
+        env.insert(
+            1,
+            (
+                LIBRARY_PATH_ENV.to_string(),
+                library_path_value(&dylib_dir).to_string_lossy().into_owned(),
+            ),
+        );
+    }
+
+    let mut args = vec![
+        resolution.script,
+        "start".to_string(),
+        "--port".to_string(),
+        port.to_string(),
+    ];
+    args.extend(profile_args);
+    args.extend(extra_args);
+    Ok(LaunchSpec {
+        node: resolution.node,
+        args,
+        env,
+    })
+}
+
+/// Attach-only mode: launchd owns the sidecar process, so make sure the
+/// service reflects the current settings and is loaded, then wait for it.
+async fn start_service(
+    app: &AppHandle,
+    prefs: &crate::commands::DesktopPreferences,
+    port: u16,
+) -> Result<(), String> {
+    let state = app.state::<SidecarState>();
+    *state.last_failure.lock().await = None;
+    let spec = launch_spec(app, prefs, port).await?;
+    crate::service::ensure_running(&spec)?;
+    engram_log!("Attaching to the Engram service on port {}", port);
+    let log_start = state.logs.lock().await.next_seq();
+    let startup_timeout = Duration::from_secs(prefs.startup_timeout_secs.max(1));
+    watch_startup(app, log_start, startup_timeout, None);
+    Ok(())
+}
+
+/// Poll the REST API until it answers; only then is the sidecar Running.
+/// The port is re-read on every poll, since a ready line on stdout may
+/// report a different one than requested. `pid` is the process to hand to
+/// the supervisor if it never answers; the launchd service has none, since
+/// launchd restarts it on its own.
+fn watch_startup(
+    app: &AppHandle,
+    log_start: u64,
+    startup_timeout: Duration,
+    pid: Option<u32>,
+) {
+    let state = app.state::<SidecarState>();
+    let status_arc = state.status.clone();
+    let started_at_arc = state.started_at.clone();
+    let logs_arc = state.logs.clone();
+    let port_arc = state.port.clone();
+    let supervisor_tx = state.supervisor_tx.clone();
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let deadline = Instant::now() + startup_timeout;
+        loop {
+            let port = *port_arc.lock().await;
+            if health_check(port).await {
+                *status_arc.lock().await = SidecarStatus::Running;
+                *started_at_arc.lock().await = Some(Instant::now());
+                engram_log!("Sidecar started successfully on port {}", port);
+                let _ = app_handle.emit("sidecar-status", "running");
+                check_compatibility(&app_handle, port).await;
+                return;
+            }
+            // The process monitor already handled an early exit
+            if !matches!(*status_arc.lock().await, SidecarStatus::Starting) {
+                return;
+            }
+            if Instant::now() >= deadline {
+                break;
+            }
+            sleep(READINESS_POLL_INTERVAL).await;
+        }
+
+        let stderr = logs_arc.lock().await.stderr_tail(log_start, FAILURE_STDERR_LINES);
+        engram_log!(
+            "Sidecar did not become ready within {}s",
+            startup_timeout.as_secs()
+        );
+        *status_arc.lock().await = SidecarStatus::Crashed;
+        let _ = app_handle.emit(
+            "sidecar-start-failed",
+            serde_json::json!({
+                "timeoutSecs": startup_timeout.as_secs(),
+                "stderr": stderr,
+            }),
+        );
+
+        // Let the supervisor kill the unresponsive process and apply crash handling
+        if let Some(pid) = pid {
+            let _ = supervisor_tx.send(SupervisorCommand::Unresponsive { pid });
+        }
+    });
+}
+
+/// "1.4.2" or "v1.4.2-beta.1" as (1, 4, 2); missing parts count as 0.
+pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.trim().trim_start_matches('v');
+    let core = core.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>());
+    let major = parts.next()?.ok()?;
+    let minor = parts.next().unwrap_or(Ok(0)).ok()?;
+    let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+    Some((major, minor, patch))
+}
+
+async fn status_version(port: u16) -> Option<String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(3))
+        .build()
+        .ok()?;
+    let url = format!("http://localhost:{}/api/status", port);
+    let status: serde_json::Value = client.get(&url).send().await.ok()?.json().await.ok()?;
+    status["version"].as_str().map(str::to_string)
+}
+
+/// Compare the ready sidecar's version against `MIN_SIDECAR_VERSION` and
+/// emit `sidecar-incompatible` if it is older. A version that can't be read
+/// or parsed is given the benefit of the doubt.
+async fn check_compatibility(app: &AppHandle, port: u16) {
+    let state = app.state::<SidecarState>();
+    let version = status_version(port).await;
+    let running = version.as_deref().and_then(parse_version);
+    let too_old = match (running, parse_version(MIN_SIDECAR_VERSION)) {
+        (Some(running), Some(minimum)) => running < minimum,
+        _ => false,
+    };
+    let mut incompatible = state.incompatible_version.lock().await;
+    if !too_old {
+        if incompatible.take().is_some() {
+            engram_log!("Sidecar version is supported again");
+        }
+        return;
+    }
+    let version = version.unwrap_or_default();
+    engram_log!(
+        "Sidecar v{} is older than the minimum supported v{}",
+        version, MIN_SIDECAR_VERSION
+    );
+    *incompatible = Some(version.clone());
+    let _ = app.emit(
+        "sidecar-incompatible",
+        serde_json::json!({
+            "sidecarVersion": version,
+            "minimumVersion": MIN_SIDECAR_VERSION,
+            "appVersion": app.package_info().version.to_string(),
+        }),
+    );
+}
+
+/// Refuse destructive operations while an incompatible sidecar is running.
+pub async fn ensure_compatible(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<SidecarState>();
+    match state.incompatible_version.lock().await.as_ref() {
+        Some(version) => Err(format!(
+            "The memory server (v{}) is older than this app supports (v{} or newer). \
+             Update Engram before running this operation.",
+            version, MIN_SIDECAR_VERSION
+        )),
+        None => Ok(()),
+    }
+}
+
+async fn start_sidecar(app: &AppHandle) -> Result<(), String> {
     let state = app.state::<SidecarState>();
 
-    // Check status synchronously via try_lock
-    if let Ok(status) = state.status.try_lock() {
+    // Check and claim the start under one lock, so two concurrent starts
+    // can't both get past here and spawn two sidecars
+    {
+        let mut status = state.status.lock().await;
         if matches!(*status, SidecarStatus::Running | SidecarStatus::Starting) {
             return Ok(());
         }
+        *status = SidecarStatus::Starting;
     }
 
-    if let Ok(mut status) = state.status.try_lock() {
-        *status = SidecarStatus::Starting;
+    let result = launch(app).await;
+    if result.is_err() {
+        *state.status.lock().await = SidecarStatus::Crashed;
+    }
+    result
+}
+
+/// Bring up a sidecar once `start_sidecar` has claimed the Starting state:
+/// attach to a compatible one already on the port, or spawn a new one.
+async fn launch(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<SidecarState>();
+    let port = *state.port.lock().await;
+
+    let prefs = crate::commands::read_preferences().unwrap_or_default();
+    if crate::service::enabled(&prefs) {
+        return start_service(app, &prefs, port).await;
     }
 
-    let port = state.port.try_lock().map(|p| *p).unwrap_or(3838);
+    // A sidecar recorded in the PID file that is still alive was orphaned by a
+    // previous app session (we would otherwise own it as a child).
+    let orphan_pid = read_pid_file().filter(|pid| is_engram_process(*pid));
+    if read_pid_file().is_some() && orphan_pid.is_none() {
+        remove_pid_file();
+    }
 
     // Check if port is already in use by an existing Engram instance
-    let addr: std::net::SocketAddr = format!("127.0.0.1:{}", port).parse().unwrap();
-    if std::net::TcpStream::connect_timeout(&addr, Duration::from_millis(500)).is_ok() {
+    let connect = tokio::net::TcpStream::connect(("127.0.0.1", port));
+    let in_use = tokio::time::timeout(Duration::from_millis(500), connect)
+        .await
+        .is_ok_and(|result| result.is_ok());
+    if in_use {
         let expected_version = app.package_info().version.to_string();
+        let running_version = tokio::task::spawn_blocking(move || check_server_version_sync(port))
+            .await
+            .ok()
+            .flatten();
 
-        match check_server_version_sync(port) {
+        match running_version {
             Some(running_version) if running_version == expected_version => {
-                eprintln!(
-                    "[engram] Port {} in use by compatible Engram v{}, attaching",
+                engram_log!(
+                    "Port {} in use by compatible Engram v{}, attaching",
                     port, running_version
                 );
-                if let Ok(mut status) = state.status.try_lock() {
-                    *status = SidecarStatus::Running;
-                }
-                if let Ok(mut count) = state.restart_count.try_lock() {
-                    *count = 0;
+                if let Some(pid) = orphan_pid {
+                    engram_log!("Adopting orphaned sidecar (PID {})", pid);
+                    *state.adopted_pid.lock().await = Some(pid);
                 }
+                *state.status.lock().await = SidecarStatus::Running;
+                *state.restart_count.lock().await = 0;
+                *state.started_at.lock().await = Some(Instant::now());
                 return Ok(());
             }
             Some(running_version) => {
-                eprintln!(
-                    "[engram] Port {} in use by Engram v{} (expected v{}), replacing",
+                engram_log!(
+                    "Port {} in use by Engram v{} (expected v{}), replacing",
                     port, running_version, expected_version
                 );
                 kill_process_on_port(port);
-                std::thread::sleep(Duration::from_secs(1));
+                sleep(Duration::from_secs(1)).await;
             }
             None => {
-                eprintln!(
-                    "[engram] Port {} in use by unknown/old process, replacing",
+                engram_log!(
+                    "Port {} in use by unknown/old process, replacing",
                     port
                 );
                 kill_process_on_port(port);
-                std::thread::sleep(Duration::from_secs(1));
+                sleep(Duration::from_secs(1)).await;
             }
         }
     }
 
-    // Try bundled sidecar first (production), fall back to node (development)
-    let shell = app.shell();
-    let (mut rx, child) = if let Some(resources_dir) = find_resources_dir(app) {
-        let node_binary = resources_dir.join(format!("node-{}", arch_suffix()));
-        let bundle = resources_dir.join("engram-bundle.cjs");
-        let node_modules = resources_dir.join("node_modules");
-        let dylib_dir = node_modules
-            .join("onnxruntime-node")
-            .join("bin")
-            .join("napi-v3")
-            .join("darwin")
-            .join(ort_arch());
+    // An orphan that wasn't adopted above is unhealthy or not serving our port
+    if let Some(pid) = orphan_pid {
+        if is_engram_process(pid) {
+            engram_log!("Terminating orphaned sidecar (PID {})", pid);
+            force_kill(pid);
+            sleep(Duration::from_millis(500)).await;
+        }
+        remove_pid_file();
+    }
 
-        // Convert paths to owned Strings to avoid lifetime issues
-        let node_binary_s = node_binary.to_string_lossy().into_owned();
-        let bundle_s = bundle.to_string_lossy().into_owned();
-        let node_modules_s = node_modules.to_string_lossy().into_owned();
-        let dylib_dir_s = dylib_dir.to_string_lossy().into_owned();
-        let port_s = port.to_string();
-
-        eprintln!("[engram] Using bundled sidecar from: {}", resources_dir.display());
-        eprintln!("[engram]   node binary: {}", node_binary_s);
-        eprintln!("[engram]   bundle: {}", bundle_s);
-        eprintln!("[engram]   NODE_PATH: {}", node_modules_s);
-
-        shell
-            .command(&node_binary_s)
-            .args([bundle_s.as_str(), "start", "--port", &port_s])
-            .env("NODE_PATH", &node_modules_s)
-            .env("DYLD_LIBRARY_PATH", &dylib_dir_s)
-            .spawn()
-            .map_err(|e| format!("Failed to spawn sidecar: {}", e))?
-    } else {
-        let engram_root = find_engram_root(app)?;
-        let script_path = engram_root.join("bin").join("engram.js");
-
-        if !script_path.exists() {
-            if let Ok(mut status) = state.status.try_lock() {
-                *status = SidecarStatus::Crashed;
-            }
-            return Err(format!("Engram entry point not found at: {}", script_path.display()));
-        }
-
-        eprintln!("[engram] Using node to run: {}", script_path.display());
-        shell
-            .command("node")
-            .args([
-                script_path.to_string_lossy().as_ref(),
-                "start",
-                "--port",
-                &port.to_string(),
-            ])
-            .spawn()
-            .map_err(|e| format!("Failed to spawn engram process: {}", e))?
-    };
+    // Output from this launch starts here; used to report why startup failed
+    let log_start = state.logs.lock().await.next_seq();
+    *state.last_failure.lock().await = None;
+    *state.ready.lock().await = None;
 
-    if let Ok(mut child_lock) = state.child.try_lock() {
-        *child_lock = Some(child);
+    let spec = launch_spec(app, &prefs, port).await?;
+
+    // Fail with a remediation hint instead of an opaque spawn error
+    if !std::path::Path::new(&spec.node).is_absolute() {
+        if let Err(e) = crate::runtime::require_system_node() {
+            let _ = app.emit("sidecar-status", "missing-runtime");
+            return Err(e);
+        }
     }
 
+    let (mut rx, child) = app
+        .shell()
+        .command(&spec.node)
+        .args(spec.args)
+        .envs(spec.env)
+        .spawn()
+        .map_err(|e| format!("Failed to spawn sidecar: {}", e))?;
+
+    let child_pid = child.pid();
+    write_pid_file(child_pid);
+    *state.child.lock().await = Some(child);
+
     // Monitor stdout/stderr in background
-    let status_arc = state.status.clone();
-    let child_arc = state.child.clone();
-    let restart_count_arc = state.restart_count.clone();
+    let logs_arc = state.logs.clone();
+    let failure_arc = state.last_failure.clone();
+    let ready_arc = state.ready.clone();
+    let port_arc = state.port.clone();
+    let supervisor_tx = state.supervisor_tx.clone();
     let app_handle = app.clone();
 
     tauri::async_runtime::spawn(async move {
+        let mut throttle = LogEventThrottle::new();
         while let Some(event) = rx.recv().await {
             match event {
                 CommandEvent::Stdout(line) => {
                     let text = String::from_utf8_lossy(&line);
                     eprintln!("[engram stdout] {}", text.trim());
+                    let entry = logs_arc.lock().await.push("stdout", text.trim_end());
+                    throttle.emit(&app_handle, &entry);
+                    if let Some(info) = parse_ready_line(&text) {
+                        let mut port = port_arc.lock().await;
+                        if *port != info.port {
+                            engram_log!(
+                                "Sidecar is listening on port {} instead of {}",
+                                info.port,
+                                *port
+                            );
+                            *port = info.port;
+                        }
+                        engram_log!(
+                            "Sidecar v{} ready (PID {}, database {})",
+                            info.version,
+                            info.pid,
+                            info.db_path
+                        );
+                        crate::startup::mark("sidecar-ready");
+                        let _ = app_handle.emit("sidecar-ready", &info);
+                        *ready_arc.lock().await = Some(info);
+                    }
                 }
                 CommandEvent::Stderr(line) => {
                     let text = String::from_utf8_lossy(&line);
                     eprintln!("[engram stderr] {}", text.trim());
+                    let entry = logs_arc.lock().await.push("stderr", text.trim_end());
+                    throttle.emit(&app_handle, &entry);
+                    if let Some(kind) = classify_failure(&entry.text) {
+                        *failure_arc.lock().await = Some(SidecarFailure {
+                            kind,
+                            line: entry.text.clone(),
+                        });
+                    }
                 }
                 CommandEvent::Terminated(payload) => {
-                    eprintln!(
-                        "[engram] Process terminated with code: {:?}, signal: {:?}",
+                    let stderr = logs_arc.lock().await.stderr_tail(log_start, FAILURE_STDERR_LINES);
+                    engram_log!(
+                        "Process terminated with code: {:?}, signal: {:?}",
                         payload.code, payload.signal
                     );
-                    *status_arc.lock().await = SidecarStatus::Crashed;
-                    *child_arc.lock().await = None;
-                    let _ = app_handle.emit("sidecar-status", "crashed");
-
-                    // Signal restart needed
-                    let mut count = restart_count_arc.lock().await;
-                    if *count < MAX_RESTART_ATTEMPTS {
-                        *count += 1;
-                        let attempt = *count;
-                        drop(count);
-                        eprintln!(
-                            "[engram] Sidecar crashed. Will restart (attempt {}/{})",
-                            attempt, MAX_RESTART_ATTEMPTS
-                        );
-                        let delay = Duration::from_secs(2u64.pow(attempt));
-                        sleep(delay).await;
-                        let _ = app_handle.emit("sidecar-restart-needed", ());
-                    } else {
-                        eprintln!(
-                            "[engram] Sidecar crashed {} times. Giving up auto-restart.",
-                            MAX_RESTART_ATTEMPTS
-                        );
-                        let _ = app_handle.emit("sidecar-status", "failed");
-                    }
+                    let entry = logs_arc.lock().await.push(
+                        "system",
+                        &format!(
+                            "Process terminated with code: {:?}, signal: {:?}",
+                            payload.code, payload.signal
+                        ),
+                    );
+                    throttle.emit(&app_handle, &entry);
+                    let _ = supervisor_tx.send(SupervisorCommand::ProcessExited {
+                        pid: child_pid,
+                        exit: ProcessExit {
+                            code: payload.code,
+                            signal: payload.signal,
+                            error: None,
+                            stderr,
+                        },
+                    });
                     break;
                 }
                 CommandEvent::Error(err) => {
-                    eprintln!("[engram] Process error: {}", err);
-                    *status_arc.lock().await = SidecarStatus::Crashed;
-                    *child_arc.lock().await = None;
-                    let _ = app_handle.emit("sidecar-restart-needed", ());
+                    let stderr = logs_arc.lock().await.stderr_tail(log_start, FAILURE_STDERR_LINES);
+                    engram_log!("Process error: {}", err);
+                    let entry = logs_arc
+                        .lock()
+                        .await
+                        .push("system", &format!("Process error: {}", err));
+                    throttle.emit(&app_handle, &entry);
+                    let _ = supervisor_tx.send(SupervisorCommand::ProcessExited {
+                        pid: child_pid,
+                        exit: ProcessExit {
+                            code: None,
+                            signal: None,
+                            error: Some(err),
+                            stderr,
+                        },
+                    });
                     break;
                 }
                 _ => {}
@@ -360,46 +1346,113 @@ pub fn start_sidecar(app: &AppHandle) -> Result<(), String> {
         }
     });
 
-    // Mark as running after grace period
-    let status_arc = state.status.clone();
-    let restart_count_arc = state.restart_count.clone();
-    let app_handle2 = app.clone();
-    tauri::async_runtime::spawn(async move {
-        sleep(STARTUP_GRACE_PERIOD).await;
-        if health_check(port).await {
-            *status_arc.lock().await = SidecarStatus::Running;
-            *restart_count_arc.lock().await = 0;
-            eprintln!("[engram] Sidecar started successfully on port {}", port);
-            let _ = app_handle2.emit("sidecar-status", "running");
-        } else {
-            *status_arc.lock().await = SidecarStatus::Running;
-            eprintln!("[engram] Sidecar started (health check pending)");
-            let _ = app_handle2.emit("sidecar-status", "running");
-        }
-    });
+    let startup_timeout = Duration::from_secs(prefs.startup_timeout_secs.max(1));
+    watch_startup(app, log_start, startup_timeout, Some(child_pid));
 
     Ok(())
 }
 
-pub async fn stop_sidecar(app: &AppHandle) -> Result<(), String> {
+async fn stop_sidecar(app: &AppHandle) -> Result<(), String> {
     let state = app.state::<SidecarState>();
 
     let mut child_lock = state.child.lock().await;
+    let mut shutdown = "none";
     if let Some(child) = child_lock.take() {
-        child.kill().map_err(|e| format!("Failed to kill sidecar: {}", e))?;
-        eprintln!("[engram] Sidecar stopped");
+        let pid = child.pid();
+        remove_pid_file_for(pid);
+        // Give the sidecar a chance to checkpoint the SQLite WAL before force-killing
+        if terminate_gracefully(pid, SHUTDOWN_TIMEOUT).await {
+            shutdown = "graceful";
+            engram_log!("Sidecar stopped gracefully");
+        } else {
+            child.kill().map_err(|e| format!("Failed to kill sidecar: {}", e))?;
+            shutdown = "forced";
+            engram_log!(
+                "Sidecar did not exit within {}s, killed",
+                SHUTDOWN_TIMEOUT.as_secs()
+            );
+        }
+    } else if let Some(pid) = state.adopted_pid.lock().await.take() {
+        if terminate_gracefully(pid, SHUTDOWN_TIMEOUT).await {
+            shutdown = "graceful";
+        } else {
+            force_kill(pid);
+            shutdown = "forced";
+        }
+        remove_pid_file_for(pid);
+        engram_log!("Adopted sidecar (PID {}) stopped", pid);
     }
 
     *state.status.lock().await = SidecarStatus::Stopped;
     *state.restart_count.lock().await = 0;
+    *state.started_at.lock().await = None;
+
+    let _ = app.emit(
+        "sidecar-status",
+        serde_json::json!({ "status": "stopped", "shutdown": shutdown }),
+    );
+    Ok(())
+}
 
-    let _ = app.emit("sidecar-status", "stopped");
+/// Stop the sidecar like `stop_sidecar`, but leave it Paused rather than
+/// Stopped. An independent launchd service is unloaded so it stays down.
+async fn pause_sidecar(app: &AppHandle) -> Result<(), String> {
+    stop_sidecar(app).await?;
+    if crate::service::enabled(&crate::commands::read_preferences().unwrap_or_default()) {
+        crate::service::unload()?;
+    }
+    *app.state::<SidecarState>().status.lock().await = SidecarStatus::Paused;
+    engram_log!("Sidecar paused");
+    let _ = app.emit("sidecar-status", "paused");
     Ok(())
 }
 
+/// Send SIGTERM and wait up to `timeout` for the process to exit.
+/// Returns false if the process is still alive (or signals are unsupported),
+/// in which case the caller should force-kill it.
+async fn terminate_gracefully(pid: u32, timeout: Duration) -> bool {
+    #[cfg(unix)]
+    {
+        let sent = std::process::Command::new("kill")
+            .args(["-TERM", &pid.to_string()])
+            .output()
+            .map_or(false, |o| o.status.success());
+        if !sent {
+            return false;
+        }
+
+        let deadline = Instant::now() + timeout;
+        while Instant::now() < deadline {
+            if !process_alive(pid) {
+                return true;
+            }
+            sleep(Duration::from_millis(100)).await;
+        }
+        false
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = (pid, timeout);
+        false
+    }
+}
+
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .output()
+        .map_or(false, |o| o.status.success())
+}
+
 pub async fn health_check(port: u16) -> bool {
+    health_check_with_timeout(port, Duration::from_secs(5)).await
+}
+
+pub async fn health_check_with_timeout(port: u16, timeout: Duration) -> bool {
     let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(5))
+        .timeout(timeout)
         .build();
 
     let client = match client {
@@ -414,36 +1467,578 @@ pub async fn health_check(port: u16) -> bool {
     }
 }
 
-/// Set up the restart listener and health check loop.
+type Reply = oneshot::Sender<Result<(), String>>;
+
+/// Requests handled by the supervisor task. The supervisor owns every spawn,
+/// stop, and crash restart, so these transitions are serialized and a crash
+/// restart can never race a manual one into spawning twice.
+pub enum SupervisorCommand {
+    Start(Option<Reply>),
+    Stop(Option<Reply>),
+    Restart(Option<Reply>),
+    /// Stop the process without treating it as a crash, until `Resume`.
+    Pause(Option<Reply>),
+    Resume(Option<Reply>),
+    /// The process with this PID exited (reported by its output monitor).
+    ProcessExited { pid: u32, exit: ProcessExit },
+    /// The process with this PID stopped answering health checks.
+    Unresponsive { pid: u32 },
+    /// A sidecar we attached to without knowing its PID (one started from
+    /// the CLI, or the launchd service) stopped answering health checks.
+    UnresponsiveAttached,
+    /// Delayed restart after a crash; dropped if any other command ran since.
+    CrashRestart { generation: u64 },
+}
+
+fn send_command(app: &AppHandle, command: SupervisorCommand) -> Result<(), String> {
+    app.state::<SidecarState>()
+        .supervisor_tx
+        .send(command)
+        .map_err(|_| "Sidecar supervisor is not running".to_string())
+}
+
+async fn request(
+    app: &AppHandle,
+    command: impl FnOnce(Option<Reply>) -> SupervisorCommand,
+) -> Result<(), String> {
+    let (tx, rx) = oneshot::channel();
+    send_command(app, command(Some(tx)))?;
+    rx.await
+        .map_err(|_| "Sidecar supervisor stopped unexpectedly".to_string())?
+}
+
+pub async fn start(app: &AppHandle) -> Result<(), String> {
+    request(app, SupervisorCommand::Start).await
+}
+
+pub async fn stop(app: &AppHandle) -> Result<(), String> {
+    request(app, SupervisorCommand::Stop).await
+}
+
+pub async fn restart(app: &AppHandle) -> Result<(), String> {
+    request(app, SupervisorCommand::Restart).await
+}
+
+pub async fn pause(app: &AppHandle) -> Result<(), String> {
+    request(app, SupervisorCommand::Pause).await
+}
+
+pub async fn resume(app: &AppHandle) -> Result<(), String> {
+    request(app, SupervisorCommand::Resume).await
+}
+
+/// Forget previous crashes so auto-restart is armed again, and start the
+/// sidecar if it was given up on.
+pub async fn clear_restart_failures(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<SidecarState>();
+    *state.restart_count.lock().await = 0;
+    let crashed = matches!(*state.status.lock().await, SidecarStatus::Crashed);
+    if crashed {
+        start(app).await
+    } else {
+        Ok(())
+    }
+}
+
+/// Queue a start without waiting for it, for use from synchronous setup code.
+pub fn request_start(app: &AppHandle) {
+    if let Err(e) = send_command(app, SupervisorCommand::Start(None)) {
+        engram_log!("Failed to start sidecar: {}", e);
+    }
+}
+
+fn respond(reply: Option<Reply>, result: Result<(), String>) {
+    match reply {
+        Some(reply) => {
+            let _ = reply.send(result);
+        }
+        None => {
+            if let Err(e) = result {
+                engram_log!("Sidecar command failed: {}", e);
+            }
+        }
+    }
+}
+
+async fn run_supervisor(app: AppHandle, mut rx: mpsc::UnboundedReceiver<SupervisorCommand>) {
+    // Bumped by every explicit command so stale crash restarts are discarded
+    let mut generation: u64 = 0;
+    let desired_state = app.state::<SidecarState>().desired_state.clone();
+
+    while let Some(command) = rx.recv().await {
+        match command {
+            SupervisorCommand::Start(reply) => {
+                generation += 1;
+                *desired_state.lock().await = DesiredState::Running;
+                respond(reply, start_sidecar(&app).await);
+            }
+            SupervisorCommand::Stop(reply) => {
+                generation += 1;
+                *desired_state.lock().await = DesiredState::Stopped;
+                respond(reply, stop_sidecar(&app).await);
+            }
+            SupervisorCommand::Restart(reply) => {
+                generation += 1;
+                *desired_state.lock().await = DesiredState::Running;
+                let result = match stop_sidecar(&app).await {
+                    Ok(()) => {
+                        sleep(Duration::from_secs(1)).await;
+                        start_sidecar(&app).await
+                    }
+                    Err(e) => Err(e),
+                };
+                respond(reply, result);
+            }
+            SupervisorCommand::Pause(reply) => {
+                generation += 1;
+                *desired_state.lock().await = DesiredState::Paused;
+                respond(reply, pause_sidecar(&app).await);
+            }
+            SupervisorCommand::Resume(reply) => {
+                generation += 1;
+                *desired_state.lock().await = DesiredState::Running;
+                respond(reply, start_sidecar(&app).await);
+            }
+            SupervisorCommand::ProcessExited { pid, exit } => {
+                // Exits we caused (stop, restart) have already cleared the child
+                let state = app.state::<SidecarState>();
+                let mut child = state.child.lock().await;
+                if child.as_ref().map(|c| c.pid()) != Some(pid) {
+                    continue;
+                }
+                *child = None;
+                drop(child);
+                let _ = app.emit("sidecar-crashed", &exit);
+                handle_crash(&app, Some(pid), generation, exit.describe()).await;
+            }
+            SupervisorCommand::Unresponsive { pid } => {
+                let state = app.state::<SidecarState>();
+                let taken = {
+                    let mut child = state.child.lock().await;
+                    if child.as_ref().map(|c| c.pid()) == Some(pid) {
+                        child.take()
+                    } else {
+                        None
+                    }
+                };
+                if let Some(child) = taken {
+                    let _ = child.kill();
+                } else {
+                    let mut adopted = state.adopted_pid.lock().await;
+                    if *adopted != Some(pid) {
+                        continue;
+                    }
+                    *adopted = None;
+                    force_kill(pid);
+                }
+                let reason = "Stopped responding to health checks".to_string();
+                handle_crash(&app, Some(pid), generation, reason).await;
+            }
+            SupervisorCommand::UnresponsiveAttached => {
+                let state = app.state::<SidecarState>();
+                // A start since the health check now owns a process
+                if state.pid().await.is_some() {
+                    continue;
+                }
+                // Whatever holds the port passed the version check when we attached
+                let port = *state.port.lock().await;
+                kill_process_on_port(port);
+                let reason = "Stopped responding to health checks".to_string();
+                handle_crash(&app, None, generation, reason).await;
+            }
+            SupervisorCommand::CrashRestart { generation: scheduled } => {
+                if scheduled == generation && *desired_state.lock().await == DesiredState::Running {
+                    if let Err(e) = start_sidecar(&app).await {
+                        engram_log!("Failed to restart sidecar: {}", e);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Mark the sidecar crashed and schedule a restart with exponential backoff.
+/// `pid` is `None` for a sidecar we attached to without one.
+async fn handle_crash(app: &AppHandle, pid: Option<u32>, generation: u64, reason: String) {
+    let state = app.state::<SidecarState>();
+    if let Some(pid) = pid {
+        remove_pid_file_for(pid);
+    }
+    match *state.desired_state.lock().await {
+        DesiredState::Running => {}
+        DesiredState::Stopped => {
+            engram_log!("Sidecar exited while stopped by the user, not restarting");
+            *state.status.lock().await = SidecarStatus::Stopped;
+            return;
+        }
+        DesiredState::Paused => {
+            engram_log!("Sidecar exited while paused, not restarting");
+            *state.status.lock().await = SidecarStatus::Paused;
+            return;
+        }
+    }
+
+    *state.status.lock().await = SidecarStatus::Crashed;
+    *state.started_at.lock().await = None;
+    let _ = app.emit("sidecar-status", "crashed");
+    crate::notifications::notify(
+        app,
+        crate::notifications::NotificationEvent::SidecarCrashed,
+        "Engram stopped unexpectedly",
+        "The memory server exited unexpectedly.",
+    );
+
+    let failure = state.last_failure.lock().await.clone();
+    // A recognised stderr signature says more than the exit code does
+    let reason = match &failure {
+        Some(failure) => failure.line.clone(),
+        None => reason,
+    };
+    let crashed_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    *state.last_crash.lock().await = Some((crashed_at, reason));
+
+    if let Some(failure) = &failure {
+        engram_log!("Sidecar failure classified as {:?}: {}", failure.kind, failure.line);
+        let _ = app.emit("sidecar-failure", failure);
+    }
+    match failure.map(|f| f.kind) {
+        // Retry straight away on the WASM backend; only give up if that fails too
+        Some(SidecarFailureKind::OnnxLoad) if !*state.onnx_fallback.lock().await => {
+            *state.onnx_fallback.lock().await = true;
+            engram_log!("onnxruntime failed to load, restarting with WASM embedding fallback");
+            let _ = app.emit("sidecar-status", "reduced-performance");
+            let _ = state.supervisor_tx.send(SupervisorCommand::CrashRestart { generation });
+            return;
+        }
+        // Restarting can't fix these; wait for the user to act on the failure event
+        Some(SidecarFailureKind::DatabaseCorrupt) | Some(SidecarFailureKind::OnnxLoad) => {
+            engram_log!("Not restarting: failure requires user action");
+            let _ = app.emit("sidecar-status", "failed");
+            return;
+        }
+        // The startup port check attaches to or replaces whatever holds the port
+        Some(SidecarFailureKind::PortInUse) => {}
+        // Usually transient; a fresh process starts with an empty heap
+        Some(SidecarFailureKind::OutOfMemory) => {}
+        None => {}
+    }
+
+    let backoff = crate::commands::read_preferences().unwrap_or_default().restart_backoff;
+    let mut count = state.restart_count.lock().await;
+    *count = count.saturating_add(1);
+    let attempt = *count;
+    drop(count);
+
+    match restart_delay(&backoff, attempt) {
+        Some(delay) => {
+            if attempt <= backoff.burst_attempts {
+                engram_log!(
+                    "Sidecar crashed. Will restart in {:.1}s (attempt {}/{})",
+                    delay.as_secs_f64(),
+                    attempt,
+                    backoff.burst_attempts
+                );
+            } else {
+                if attempt == backoff.burst_attempts + 1 {
+                    let _ = app.emit("sidecar-status", "retrying");
+                }
+                engram_log!(
+                    "Sidecar still failing after {} attempts. Retrying in {:.0}s",
+                    attempt - 1,
+                    delay.as_secs_f64()
+                );
+            }
+            let tx = state.supervisor_tx.clone();
+            tauri::async_runtime::spawn(async move {
+                sleep(delay).await;
+                let _ = tx.send(SupervisorCommand::CrashRestart { generation });
+            });
+        }
+        None => {
+            engram_log!(
+                "Sidecar crashed {} times. Giving up auto-restart.",
+                backoff.burst_attempts
+            );
+            let _ = app.emit("sidecar-status", "failed");
+        }
+    }
+}
+
+/// Delay before crash restart `attempt` (1-based), or `None` to give up.
+/// Jitter keeps a flapping sidecar from settling into a fixed rhythm with
+/// whatever is making it fail.
+fn restart_delay(settings: &RestartBackoffSettings, attempt: u32) -> Option<Duration> {
+    let base = if attempt <= settings.burst_attempts {
+        let exponential = settings
+            .base_delay_secs
+            .saturating_mul(2u64.saturating_pow(attempt.saturating_sub(1)));
+        exponential.min(settings.max_delay_secs)
+    } else if settings.slow_retry_secs > 0 {
+        settings.slow_retry_secs
+    } else {
+        return None;
+    };
+
+    let jitter = settings.jitter.clamp(0.0, 1.0);
+    let factor = 1.0 + rand::thread_rng().gen_range(-jitter..=jitter);
+    Some(Duration::from_secs_f64(base as f64 * factor))
+}
+
+/// Health check used right after a system wake: a longer timeout and a few
+/// retries, so a sidecar that is still waking up isn't restarted needlessly.
+async fn wake_recheck(port: u16) -> bool {
+    for attempt in 1..=WAKE_CHECK_ATTEMPTS {
+        if health_check_with_timeout(port, WAKE_CHECK_TIMEOUT).await {
+            return true;
+        }
+        if attempt < WAKE_CHECK_ATTEMPTS {
+            sleep(WAKE_CHECK_RETRY_DELAY).await;
+        }
+    }
+    false
+}
+
+/// Start the supervisor task and health check loop.
 /// Call this once during app setup.
 pub fn setup_sidecar_lifecycle(app: &AppHandle) {
-    // Listen for restart requests (emitted when sidecar crashes)
-    let app_handle = app.clone();
-    app.listen("sidecar-restart-needed", move |_| {
-        let handle = app_handle.clone();
-        if let Err(e) = start_sidecar(&handle) {
-            eprintln!("[engram] Failed to restart sidecar: {}", e);
+    let state = app.state::<SidecarState>();
+    let rx = state.supervisor_rx.lock().ok().and_then(|mut rx| rx.take());
+    match rx {
+        Some(rx) => {
+            tauri::async_runtime::spawn(run_supervisor(app.clone(), rx));
         }
-    });
+        None => engram_log!("Sidecar supervisor already started"),
+    }
+
+    let settings = crate::commands::read_preferences()
+        .unwrap_or_default()
+        .health_check;
+    state.health_settings.send_replace(settings);
 
     // Spawn periodic health check
     let app_handle = app.clone();
+    let mut settings_rx = state.health_settings.subscribe();
     tauri::async_runtime::spawn(async move {
         sleep(Duration::from_secs(10)).await;
+        let mut failures: u32 = 0;
+        let mut power_rx = crate::power::subscribe();
+        let mut sleeps_seen = power_rx.borrow_and_update().sleeps;
 
         loop {
-            sleep(HEALTH_CHECK_INTERVAL).await;
+            let settings = settings_rx.borrow_and_update().clone();
+            let interval = Duration::from_secs(settings.interval_secs.max(1));
+            // Monotonic timers pause while the system sleeps, but the wall clock
+            // keeps going; a large jump catches wakes where the OS didn't announce them.
+            let wall_before = SystemTime::now();
+            tokio::select! {
+                _ = sleep(interval) => {}
+                // New settings: restart the wait with the new interval
+                changed = settings_rx.changed() => {
+                    if changed.is_err() {
+                        break;
+                    }
+                    continue;
+                }
+            }
+
+            // Checks are paused from will-sleep until did-wake
+            if power_rx.borrow().asleep {
+                engram_log!("Health checks paused while the system sleeps");
+                if power_rx.wait_for(|power| !power.asleep).await.is_err() {
+                    break;
+                }
+            }
+            let sleeps = power_rx.borrow_and_update().sleeps;
+            let woke = sleeps != sleeps_seen
+                || SystemTime::now()
+                    .duration_since(wall_before)
+                    .map_or(false, |elapsed| elapsed > interval + SLEEP_DETECTION_SLACK);
+            sleeps_seen = sleeps;
+            if woke {
+                engram_log!("System wake detected, re-verifying sidecar");
+                let _ = app_handle.emit("system-wake", ());
+                failures = 0;
+            }
 
             let state = app_handle.state::<SidecarState>();
+            if *state.desired_state.lock().await != DesiredState::Running {
+                failures = 0;
+                continue;
+            }
             let status = state.status.lock().await.clone();
 
             let port = *state.port.lock().await;
-            if matches!(status, SidecarStatus::Running) && !health_check(port).await {
-                eprintln!("[engram] Health check failed, requesting restart");
-                *state.status.lock().await = SidecarStatus::Crashed;
-                *state.child.lock().await = None;
-                let _ = app_handle.emit("sidecar-restart-needed", ());
+            if !matches!(status, SidecarStatus::Running) {
+                failures = 0;
+                continue;
+            }
+            let timeout = Duration::from_secs(settings.timeout_secs.max(1));
+            let check = async {
+                if woke {
+                    wake_recheck(port).await
+                } else {
+                    health_check_with_timeout(port, timeout).await
+                }
+            };
+            // A check cut off by sleep says nothing about the sidecar; the
+            // next one after wake re-verifies it
+            let healthy = tokio::select! {
+                healthy = check => healthy,
+                _ = power_rx.wait_for(|power| power.asleep) => {
+                    failures = 0;
+                    continue;
+                }
+            };
+            if healthy {
+                failures = 0;
+                let stable = state
+                    .started_at
+                    .lock()
+                    .await
+                    .map_or(false, |t| t.elapsed() >= STABLE_UPTIME_RESET);
+                let mut count = state.restart_count.lock().await;
+                if stable && *count > 0 {
+                    engram_log!(
+                        "Sidecar stable for {}s, resetting restart count",
+                        STABLE_UPTIME_RESET.as_secs()
+                    );
+                    *count = 0;
+                }
+                continue;
+            }
+
+            failures += 1;
+            let threshold = settings.failure_threshold.max(1);
+            if failures < threshold {
+                engram_log!("Health check failed ({}/{})", failures, threshold);
+                continue;
             }
+            failures = 0;
+            engram_log!(
+                "Health check failed {} times in a row, requesting restart",
+                threshold
+            );
+            let command = match state.pid().await {
+                Some(pid) => SupervisorCommand::Unresponsive { pid },
+                None => SupervisorCommand::UnresponsiveAttached,
+            };
+            let _ = state.supervisor_tx.send(command);
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_args_groups_quoted_words() {
+        assert_eq!(
+            split_args(r#"--inspect --title "my sidecar" 'single quoted'"#).unwrap(),
+            vec!["--inspect", "--title", "my sidecar", "single quoted"]
+        );
+        assert_eq!(split_args(r#"--name="a b"c"#).unwrap(), vec!["--name=a bc"]);
+        assert_eq!(split_args(r#""" ''"#).unwrap(), vec!["", ""]);
+        assert!(split_args("  ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn split_args_handles_backslash_escapes() {
+        assert_eq!(split_args(r"a\ b c").unwrap(), vec!["a b", "c"]);
+        assert_eq!(split_args(r#""say \"hi\" \$HOME \n""#).unwrap(), vec![r#"say "hi" $HOME \n"#]);
+        assert_eq!(split_args(r"'no \escape'").unwrap(), vec![r"no \escape"]);
+    }
+
+    #[test]
+    fn split_args_rejects_unterminated_input() {
+        assert!(split_args("'open").is_err());
+        assert!(split_args(r#""open"#).is_err());
+        assert!(split_args(r#""open \"#).is_err());
+        assert!(split_args(r"trailing \").is_err());
+    }
+
+    #[test]
+    fn parse_sidecar_args_rejects_protected_flags() {
+        assert_eq!(
+            parse_sidecar_args("--max-old-space-size=4096").unwrap(),
+            vec!["--max-old-space-size=4096"]
+        );
+        assert!(parse_sidecar_args("--port 4000").is_err());
+        assert!(parse_sidecar_args("--port=4000").is_err());
+        assert!(parse_sidecar_args("--inspect --mcp-only").is_err());
+        assert!(parse_sidecar_args("\"--title\0\"").is_err());
+    }
+
+    fn backoff(jitter: f64) -> RestartBackoffSettings {
+        RestartBackoffSettings {
+            base_delay_secs: 2,
+            max_delay_secs: 5,
+            burst_attempts: 3,
+            slow_retry_secs: 600,
+            jitter,
+        }
+    }
+
+    #[test]
+    fn restart_delay_doubles_up_to_the_cap() {
+        let settings = backoff(0.0);
+        assert_eq!(restart_delay(&settings, 1), Some(Duration::from_secs(2)));
+        assert_eq!(restart_delay(&settings, 2), Some(Duration::from_secs(4)));
+        assert_eq!(restart_delay(&settings, 3), Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn restart_delay_falls_back_to_slow_retries_or_gives_up() {
+        let mut settings = backoff(0.0);
+        assert_eq!(restart_delay(&settings, 4), Some(Duration::from_secs(600)));
+        settings.slow_retry_secs = 0;
+        assert_eq!(restart_delay(&settings, 4), None);
+    }
+
+    #[test]
+    fn restart_delay_jitter_stays_in_range() {
+        let settings = backoff(0.2);
+        for _ in 0..100 {
+            let delay = restart_delay(&settings, 1).unwrap().as_secs_f64();
+            assert!((1.6..=2.4).contains(&delay), "{} out of range", delay);
+        }
+        // Jitter above 1 is clamped so a delay never goes negative
+        let delay = restart_delay(&backoff(5.0), 1).unwrap().as_secs_f64();
+        assert!((0.0..=4.0).contains(&delay));
+    }
+
+    #[test]
+    fn classify_failure_recognises_known_signatures() {
+        use SidecarFailureKind::*;
+        let cases = [
+            ("Error: listen EADDRINUSE: address already in use :::3838", Some(PortInUse)),
+            ("SqliteError: database disk image is malformed", Some(DatabaseCorrupt)),
+            ("SQLITE_NOTADB: file is not a database", Some(DatabaseCorrupt)),
+            (
+                "FATAL ERROR: Reached heap limit Allocation failed - JavaScript heap out of memory",
+                Some(OutOfMemory),
+            ),
+            ("Error: dlopen(libonnxruntime.1.14.0.dylib) failed", Some(OnnxLoad)),
+            ("Loaded onnxruntime backend", None),
+            ("REST server listening on port 3838", None),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(classify_failure(line), expected, "{}", line);
+        }
+    }
+
+    #[test]
+    fn parse_version_reads_semver_prefixes() {
+        assert_eq!(parse_version("1.4.2"), Some((1, 4, 2)));
+        assert_eq!(parse_version(" v1.4.2-beta.1 "), Some((1, 4, 2)));
+        assert_eq!(parse_version("2.0.0+build.7"), Some((2, 0, 0)));
+        assert_eq!(parse_version("1.5"), Some((1, 5, 0)));
+        assert_eq!(parse_version("3"), Some((3, 0, 0)));
+        assert_eq!(parse_version(""), None);
+        assert_eq!(parse_version("1.x.0"), None);
+        assert!(parse_version("1.3.9") < parse_version(MIN_SIDECAR_VERSION));
+    }
+}
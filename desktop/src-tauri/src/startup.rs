@@ -0,0 +1,87 @@
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use serde::Serialize;
+
+/// When `main` started; phases are measured from here.
+static ORIGIN: OnceLock<Instant> = OnceLock::new();
+static PHASES: Mutex<Vec<StartupPhase>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupPhase {
+    pub name: String,
+    /// Milliseconds after launch the phase began.
+    pub started_ms: u64,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupTimings {
+    pub phases: Vec<StartupPhase>,
+    /// Milliseconds from launch until the setup hook returned.
+    pub setup_ms: Option<u64>,
+    /// Milliseconds from launch until the first sidecar reported ready.
+    pub sidecar_ready_ms: Option<u64>,
+}
+
+fn origin() -> Instant {
+    *ORIGIN.get_or_init(Instant::now)
+}
+
+/// Start the clock. Called first thing in `main`.
+pub fn begin() {
+    origin();
+}
+
+/// Record a phase that began at `started` and has just finished.
+pub fn record(name: &str, started: Instant) {
+    let phase = StartupPhase {
+        name: name.to_string(),
+        started_ms: started.saturating_duration_since(origin()).as_millis() as u64,
+        duration_ms: started.elapsed().as_millis() as u64,
+    };
+    if let Ok(mut phases) = PHASES.lock() {
+        phases.push(phase);
+    }
+}
+
+/// Record a point in startup, such as the sidecar becoming ready. Only the
+/// first mark with a given name is kept, so restarts don't overwrite it.
+pub fn mark(name: &str) {
+    if let Ok(mut phases) = PHASES.lock() {
+        if phases.iter().any(|phase| phase.name == name) {
+            return;
+        }
+        phases.push(StartupPhase {
+            name: name.to_string(),
+            started_ms: origin().elapsed().as_millis() as u64,
+            duration_ms: 0,
+        });
+    }
+}
+
+/// Time `f` as the phase `name`.
+pub fn timed<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let result = f();
+    record(name, started);
+    result
+}
+
+pub fn timings() -> StartupTimings {
+    let mut phases = PHASES.lock().map(|p| p.clone()).unwrap_or_default();
+    phases.sort_by_key(|phase| phase.started_ms);
+    let at = |name: &str| {
+        phases
+            .iter()
+            .find(|phase| phase.name == name)
+            .map(|phase| phase.started_ms + phase.duration_ms)
+    };
+    StartupTimings {
+        setup_ms: at("setup"),
+        sidecar_ready_ms: at("sidecar-ready"),
+        phases,
+    }
+}
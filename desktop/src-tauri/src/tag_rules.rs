@@ -0,0 +1,208 @@
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A user-defined rule that adds tags to matching memories, e.g. content
+/// matching `kubectl|k8s` gets `devops`. Every condition that is set must
+/// match; a rule with no conditions never matches.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TagRule {
+    pub name: String,
+    pub enabled: bool,
+    /// Case-insensitive regular expression tested against the content.
+    pub content_pattern: String,
+    /// Case-insensitive substring of the memory's source (e.g. "cursor").
+    pub source: String,
+    /// Substring of the file or project path the memory was captured from.
+    pub path_contains: String,
+    pub tags: Vec<String>,
+}
+
+impl Default for TagRule {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            enabled: true,
+            content_pattern: String::new(),
+            source: String::new(),
+            path_contains: String::new(),
+            tags: Vec::new(),
+        }
+    }
+}
+
+/// What a rule can see about a memory being captured or re-tagged.
+pub struct TagContext<'a> {
+    pub content: &'a str,
+    pub source: &'a str,
+    /// Unknown for memories that were stored without one; rules that need a
+    /// path then don't match.
+    pub path: Option<&'a str>,
+}
+
+/// Memories the retroactive job looks at.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TagRuleScope {
+    All,
+    /// Only memories without any tags yet.
+    Untagged,
+    Namespace(String),
+}
+
+impl TagRuleScope {
+    /// Parse "all", "untagged", or "namespace:<name>".
+    pub fn parse(scope: &str) -> Result<Self, String> {
+        match scope.trim() {
+            "" | "all" => Ok(TagRuleScope::All),
+            "untagged" => Ok(TagRuleScope::Untagged),
+            other => match other.strip_prefix("namespace:") {
+                Some(name) if !name.trim().is_empty() => {
+                    Ok(TagRuleScope::Namespace(name.trim().to_string()))
+                }
+                _ => Err(format!(
+                    "Unknown scope \"{}\"; use all, untagged, or namespace:<name>",
+                    other
+                )),
+            },
+        }
+    }
+
+    /// The namespace filter to pass to `/api/memories`, if any.
+    pub fn namespace(&self) -> Option<&str> {
+        match self {
+            TagRuleScope::Namespace(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    pub fn includes(&self, existing_tags: &[String]) -> bool {
+        match self {
+            TagRuleScope::Untagged => existing_tags.is_empty(),
+            _ => true,
+        }
+    }
+}
+
+/// Rules with their content patterns compiled once.
+pub struct CompiledRules<'a> {
+    rules: Vec<(&'a TagRule, Option<Regex>)>,
+}
+
+impl<'a> CompiledRules<'a> {
+    /// Compile the enabled rules, skipping (and logging) any that no longer
+    /// compile so one bad rule doesn't block captures.
+    pub fn new(rules: &'a [TagRule]) -> Self {
+        let rules = rules
+            .iter()
+            .filter(|rule| rule.enabled)
+            .filter_map(|rule| match compile_pattern(&rule.content_pattern) {
+                Ok(pattern) => Some((rule, pattern)),
+                Err(e) => {
+                    engram_log!("Skipping tag rule \"{}\": {}", rule.name, e);
+                    None
+                }
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Tags from every matching rule, in rule order and without duplicates.
+    pub fn tags_for(&self, ctx: &TagContext) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        for (rule, pattern) in &self.rules {
+            if matches(rule, pattern.as_ref(), ctx) {
+                for tag in rule.tags.iter().filter(|tag| !tag.trim().is_empty()) {
+                    if !tags.contains(tag) {
+                        tags.push(tag.clone());
+                    }
+                }
+            }
+        }
+        tags
+    }
+
+    /// Merge matching tags into a memory body's `tags` array. Returns whether
+    /// any tag was added.
+    pub fn apply_to_body(&self, body: &mut Value, source: &str, path: Option<&str>) -> bool {
+        let content = body.get("content").and_then(Value::as_str).unwrap_or_default();
+        let added = self.tags_for(&TagContext {
+            content,
+            source,
+            path,
+        });
+        if added.is_empty() {
+            return false;
+        }
+        let Some(fields) = body.as_object_mut() else {
+            return false;
+        };
+        let mut tags: Vec<String> = fields
+            .get("tags")
+            .and_then(|t| serde_json::from_value(t.clone()).ok())
+            .unwrap_or_default();
+        let before = tags.len();
+        for tag in added {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        let changed = tags.len() > before;
+        fields.insert("tags".to_string(), serde_json::json!(tags));
+        changed
+    }
+}
+
+fn compile_pattern(pattern: &str) -> Result<Option<Regex>, String> {
+    if pattern.trim().is_empty() {
+        return Ok(None);
+    }
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .size_limit(1 << 20)
+        .build()
+        .map(Some)
+        .map_err(|e| format!("Invalid pattern /{}/: {}", pattern, e))
+}
+
+fn matches(rule: &TagRule, pattern: Option<&Regex>, ctx: &TagContext) -> bool {
+    let source = rule.source.trim();
+    let path = rule.path_contains.trim();
+    if pattern.is_none() && source.is_empty() && path.is_empty() {
+        return false;
+    }
+    if let Some(pattern) = pattern {
+        if !pattern.is_match(ctx.content) {
+            return false;
+        }
+    }
+    if !source.is_empty() && !ctx.source.to_lowercase().contains(&source.to_lowercase()) {
+        return false;
+    }
+    if !path.is_empty() && !ctx.path.is_some_and(|p| p.contains(path)) {
+        return false;
+    }
+    true
+}
+
+/// Check rules before they are saved.
+pub fn validate(rules: &[TagRule]) -> Result<(), String> {
+    for rule in rules {
+        let label = if rule.name.trim().is_empty() {
+            "Unnamed tag rule".to_string()
+        } else {
+            format!("Tag rule \"{}\"", rule.name.trim())
+        };
+        if rule.tags.iter().all(|tag| tag.trim().is_empty()) {
+            return Err(format!("{} adds no tags", label));
+        }
+        if rule.content_pattern.trim().is_empty()
+            && rule.source.trim().is_empty()
+            && rule.path_contains.trim().is_empty()
+        {
+            return Err(format!("{} needs at least one condition", label));
+        }
+        compile_pattern(&rule.content_pattern).map_err(|e| format!("{}: {}", label, e))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use chrono::{DateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// Resolve the timezone used for user-facing timestamps.
+/// An empty or invalid preference falls back to the system timezone, then UTC.
+pub fn preferred_timezone(preference: &str) -> Tz {
+    let preference = preference.trim();
+    if !preference.is_empty() {
+        match preference.parse::<Tz>() {
+            Ok(tz) => return tz,
+            Err(_) => engram_log!("Unknown timezone \"{}\", using system timezone", preference),
+        }
+    }
+
+    iana_time_zone::get_timezone()
+        .ok()
+        .and_then(|name| name.parse::<Tz>().ok())
+        .unwrap_or(Tz::UTC)
+}
+
+/// Current time in the given timezone.
+pub fn now(tz: &Tz) -> DateTime<Tz> {
+    Utc::now().with_timezone(tz)
+}
+
+/// Format a Unix timestamp (seconds) as RFC 3339 with the timezone's offset.
+pub fn format_epoch(epoch_secs: i64, tz: &Tz) -> String {
+    match Utc.timestamp_opt(epoch_secs, 0).single() {
+        Some(utc) => utc.with_timezone(tz).to_rfc3339(),
+        None => epoch_secs.to_string(),
+    }
+}
+
+/// Readable time for names offered in save dialogs, with month names in
+/// the system locale, e.g. `14 October 2026 15.30.42`. Avoids characters
+/// that aren't allowed in file names.
+pub fn filename_label(tz: &Tz) -> String {
+    let locale = sys_locale::get_locale()
+        .and_then(|name| chrono::Locale::try_from(name.replace('-', "_").as_str()).ok())
+        .unwrap_or(chrono::Locale::en_US);
+    now(tz)
+        .format_localized("%-d %B %Y %H.%M.%S", locale)
+        .to_string()
+}
+
+/// Timestamp suitable for file names, e.g. `2026-10-14_153042`.
+pub fn filename_timestamp(tz: &Tz) -> String {
+    now(tz).format("%Y-%m-%d_%H%M%S").to_string()
+}
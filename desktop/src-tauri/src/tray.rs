@@ -1,12 +1,119 @@
+use std::sync::Mutex;
+
 use tauri::{
     image::Image,
-    menu::{MenuBuilder, MenuItemBuilder, PredefinedMenuItem, SubmenuBuilder},
+    menu::{Menu, MenuBuilder, MenuItemBuilder, PredefinedMenuItem, SubmenuBuilder},
     tray::TrayIconBuilder,
-    AppHandle, Emitter, Manager,
+    AppHandle, Emitter, Manager, Wry,
 };
 
+use crate::commands::DesktopPreferences;
+
+const TRAY_ID: &str = "main";
+
+/// Which agents have an Engram entry, by menu id. `None` until the first
+/// detection finishes, so building the menu never reads agent configs.
+static AGENTS_CONNECTED: Mutex<Option<Vec<(&'static str, bool)>>> = Mutex::new(None);
+
 pub fn create_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    // Build the tray menu
+    let prefs = crate::commands::read_preferences().unwrap_or_default();
+    let menu = build_menu(app, &prefs)?;
+
+    // Load tray icon (embedded at compile time so it works in bundled apps)
+    let icon = Image::from_bytes(include_bytes!("../icons/tray-icon.png")).unwrap_or_else(|_| {
+        // Fallback: create a minimal 1x1 RGBA pixel if icon not found
+        Image::new_owned(vec![0, 0, 0, 255], 1, 1)
+    });
+
+    let _tray = TrayIconBuilder::with_id(TRAY_ID)
+        .icon(icon)
+        .icon_as_template(false)
+        .menu(&menu)
+        .tooltip("Engram - AI Memory")
+        .on_menu_event(move |app, event| {
+            handle_menu_event(app, event.id().as_ref());
+        })
+        .on_tray_icon_event(|tray_icon, event| {
+            if let tauri::tray::TrayIconEvent::DoubleClick {
+                button: tauri::tray::MouseButton::Left,
+                ..
+            } = event
+            {
+                // Double-click: toggle main window
+                let app = tray_icon.app_handle();
+                if let Some(window) = app.get_webview_window("main") {
+                    if window.is_visible().unwrap_or(false) {
+                        let _ = window.hide();
+                    } else {
+                        let _ = window.show();
+                        let _ = window.set_focus();
+                    }
+                }
+            }
+        })
+        .build(app)?;
+
+    Ok(())
+}
+
+/// Rebuild the tray menu after the tray layout or shortcuts change.
+pub fn apply_layout(app: &AppHandle, prefs: &DesktopPreferences) -> Result<(), String> {
+    let menu = build_menu(app, prefs).map_err(|e| e.to_string())?;
+    let tray = app.tray_by_id(TRAY_ID).ok_or("Tray icon not found")?;
+    tray.set_menu(Some(menu)).map_err(|e| e.to_string())
+}
+
+/// Menu ids and names of the agents listed in the tray.
+const AGENTS: [(&str, &str); 5] = [
+    ("agent-claude-desktop", "Claude Desktop"),
+    ("agent-claude-code", "Claude Code"),
+    ("agent-cursor", "Cursor"),
+    ("agent-windsurf", "Windsurf"),
+    ("agent-chatgpt", "ChatGPT"),
+];
+
+/// Check which agents are connected off the main thread, then rebuild the
+/// menu with the result. Called at startup and after connecting an agent.
+pub fn refresh_agents(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let started = std::time::Instant::now();
+        let home = dirs::home_dir().unwrap_or_default();
+        let connected = AGENTS
+            .iter()
+            .map(|(id, _)| {
+                let config_path = match *id {
+                    "agent-claude-desktop" if cfg!(target_os = "macos") => {
+                        home.join("Library/Application Support/Claude/claude_desktop_config.json")
+                    }
+                    "agent-claude-desktop" => home.join(".config/Claude/claude_desktop_config.json"),
+                    "agent-claude-code" => home.join(".claude/mcp.json"),
+                    "agent-cursor" => home.join(".cursor/mcp.json"),
+                    "agent-windsurf" => home.join(".windsurf/mcp.json"),
+                    // ChatGPT uses in-app config, can't detect
+                    _ => return (*id, false),
+                };
+                (*id, is_agent_connected(&config_path))
+            })
+            .collect();
+        if let Ok(mut agents) = AGENTS_CONNECTED.lock() {
+            *agents = Some(connected);
+        }
+        crate::startup::record("agent-detection", started);
+
+        let prefs = crate::commands::read_preferences().unwrap_or_default();
+        if let Err(e) = apply_layout(&app, &prefs) {
+            engram_log!("Failed to update tray agents: {}", e);
+        }
+    });
+}
+
+/// The tray menu, with optional sections shown per `prefs.tray`.
+fn build_menu(
+    app: &AppHandle,
+    prefs: &DesktopPreferences,
+) -> Result<Menu<Wry>, Box<dyn std::error::Error>> {
+    let layout = &prefs.tray;
     let status_item = MenuItemBuilder::with_id("status", "Status: Running")
         .enabled(false)
         .build(app)?;
@@ -15,6 +122,27 @@ pub fn create_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         .enabled(false)
         .build(app)?;
 
+    // Profile submenu, once there is more than the default profile
+    let active_profile = crate::profiles::active();
+    let profiles = crate::profiles::list().unwrap_or_default();
+    let mut profile_submenu_builder = SubmenuBuilder::with_id(
+        app,
+        "profiles",
+        crate::labels::tray_label(&format!("Profile: {}", active_profile)),
+    );
+    for profile in &profiles {
+        let name = crate::labels::truncate_end(&profile.name, crate::labels::TRAY_LABEL_MAX - 2);
+        let label = if profile.active {
+            format!("{} \u{2713}", name)
+        } else {
+            name
+        };
+        profile_submenu_builder = profile_submenu_builder.item(
+            &MenuItemBuilder::with_id(format!("profile-{}", profile.name), label).build(app)?,
+        );
+    }
+    let profile_submenu = profile_submenu_builder.build()?;
+
     let separator1 = PredefinedMenuItem::separator(app)?;
 
     let open_dashboard = MenuItemBuilder::with_id("open-dashboard", "Open Dashboard")
@@ -22,37 +150,38 @@ pub fn create_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         .build(app)?;
 
     let quick_add = MenuItemBuilder::with_id("quick-add", "Quick Add Memory")
-        .accelerator("CmdOrCtrl+Shift+M")
+        .accelerator(&prefs.shortcuts.quick_add)
         .build(app)?;
 
-    let separator2 = PredefinedMenuItem::separator(app)?;
+    let present_mode = crate::windows::present_mode_active();
+    let present = MenuItemBuilder::with_id(
+        "present-mode",
+        if present_mode { "End Present Mode" } else { "Present Mode" },
+    )
+    .build(app)?;
 
-    // Connected Agents submenu — detect status at build time
-    let home = dirs::home_dir().unwrap_or_default();
-    let agents_info: Vec<(&str, &str, std::path::PathBuf)> = vec![
-        ("agent-claude-desktop", "Claude Desktop",
-            if cfg!(target_os = "macos") {
-                home.join("Library/Application Support/Claude/claude_desktop_config.json")
-            } else {
-                home.join(".config/Claude/claude_desktop_config.json")
-            }),
-        ("agent-claude-code", "Claude Code", home.join(".claude/mcp.json")),
-        ("agent-cursor", "Cursor", home.join(".cursor/mcp.json")),
-        ("agent-windsurf", "Windsurf", home.join(".windsurf/mcp.json")),
-        ("agent-chatgpt", "ChatGPT", std::path::PathBuf::from("/Applications/ChatGPT.app")),
-    ];
+    let resurfaced = match crate::resurfacing::current() {
+        Some(memory) => Some(
+            MenuItemBuilder::with_id("resurfaced", crate::resurfacing::tray_label(&memory))
+                .build(app)?,
+        ),
+        None => None,
+    };
+
+    let separator2 = PredefinedMenuItem::separator(app)?;
 
+    // Connected Agents submenu, from the last detection
+    let detected = AGENTS_CONNECTED.lock().ok().and_then(|agents| agents.clone());
     let mut agents_submenu_builder = SubmenuBuilder::with_id(app, "agents", "Connected Agents");
-    for (id, name, config_path) in &agents_info {
-        let connected = if *id == "agent-chatgpt" {
-            false // ChatGPT uses in-app config, can't detect
-        } else {
-            is_agent_connected(config_path)
-        };
-        let label = if connected {
-            format!("{} \u{2713}", name) // ✓ checkmark
-        } else {
-            format!("{} — Click to connect", name)
+    for (id, name) in AGENTS {
+        let connected = detected
+            .as_ref()
+            .and_then(|agents| agents.iter().find(|(agent, _)| agent == id))
+            .map(|(_, connected)| *connected);
+        let label = match connected {
+            Some(true) => format!("{} \u{2713}", name), // ✓ checkmark
+            Some(false) => format!("{} — Click to connect", name),
+            None => name.to_string(),
         };
         agents_submenu_builder = agents_submenu_builder.item(
             &MenuItemBuilder::with_id(*id, label).build(app)?,
@@ -66,8 +195,18 @@ pub fn create_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         .item(&manage_agents)
         .build()?;
 
+    let copy_connection_info =
+        MenuItemBuilder::with_id("copy-connection-info", "Copy Connection Info").build(app)?;
+
     let separator3 = PredefinedMenuItem::separator(app)?;
 
+    let check_update =
+        MenuItemBuilder::with_id("check-sidecar-update", "Check for Sidecar Update...")
+            .build(app)?;
+
+    let data_folder =
+        MenuItemBuilder::with_id("open-data-dir", "Show Data Folder").build(app)?;
+
     let preferences = MenuItemBuilder::with_id("preferences", "Preferences")
         .accelerator("CmdOrCtrl+,")
         .build(app)?;
@@ -76,54 +215,39 @@ pub fn create_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         .accelerator("CmdOrCtrl+Q")
         .build(app)?;
 
-    let menu = MenuBuilder::new(app)
-        .item(&status_item)
-        .item(&memory_count_item)
-        .item(&separator1)
-        .item(&open_dashboard)
-        .item(&quick_add)
-        .item(&separator2)
-        .item(&agents_submenu)
+    let mut menu = MenuBuilder::new(app).item(&status_item);
+    if layout.show_memory_count {
+        menu = menu.item(&memory_count_item);
+    }
+    if profiles.len() > 1 {
+        menu = menu.item(&profile_submenu);
+    }
+    menu = menu.item(&separator1).item(&open_dashboard);
+    if !present_mode {
+        menu = menu.item(&quick_add);
+    }
+    menu = menu.item(&present);
+    if let Some(resurfaced) = &resurfaced {
+        menu = menu.item(resurfaced);
+    }
+    if layout.show_agents || layout.show_copy_connection_info {
+        menu = menu.item(&separator2);
+    }
+    if layout.show_agents {
+        menu = menu.item(&agents_submenu);
+    }
+    if layout.show_copy_connection_info {
+        menu = menu.item(&copy_connection_info);
+    }
+    let menu = menu
         .item(&separator3)
+        .item(&check_update)
+        .item(&data_folder)
         .item(&preferences)
         .item(&quit)
         .build()?;
 
-    // Load tray icon (embedded at compile time so it works in bundled apps)
-    let icon = Image::from_bytes(include_bytes!("../icons/tray-icon.png")).unwrap_or_else(|_| {
-        // Fallback: create a minimal 1x1 RGBA pixel if icon not found
-        Image::new_owned(vec![0, 0, 0, 255], 1, 1)
-    });
-
-    let _tray = TrayIconBuilder::new()
-        .icon(icon)
-        .icon_as_template(false)
-        .menu(&menu)
-        .tooltip("Engram - AI Memory")
-        .on_menu_event(move |app, event| {
-            handle_menu_event(app, event.id().as_ref());
-        })
-        .on_tray_icon_event(|tray_icon, event| {
-            if let tauri::tray::TrayIconEvent::DoubleClick {
-                button: tauri::tray::MouseButton::Left,
-                ..
-            } = event
-            {
-                // Double-click: toggle main window
-                let app = tray_icon.app_handle();
-                if let Some(window) = app.get_webview_window("main") {
-                    if window.is_visible().unwrap_or(false) {
-                        let _ = window.hide();
-                    } else {
-                        let _ = window.show();
-                        let _ = window.set_focus();
-                    }
-                }
-            }
-        })
-        .build(app)?;
-
-    Ok(())
+    Ok(menu)
 }
 
 fn handle_menu_event(app: &AppHandle, event_id: &str) {
@@ -135,11 +259,23 @@ fn handle_menu_event(app: &AppHandle, event_id: &str) {
                 let _ = window.eval("window.location.hash = '#/'");
             }
         }
-        "quick-add" => {
+        "resurfaced" => {
             if let Some(window) = app.get_webview_window("main") {
                 let _ = window.show();
                 let _ = window.set_focus();
-                let _ = app.emit("open-quick-add", ());
+                let _ = window.eval("window.location.hash = '#/memories'");
+            }
+            crate::resurfacing::dismiss(app);
+        }
+        "present-mode" => {
+            let enabled = !crate::windows::present_mode_active();
+            if let Err(e) = crate::windows::set_present_mode(app, enabled) {
+                engram_log!("Failed to toggle present mode: {}", e);
+            }
+        }
+        "quick-add" => {
+            if let Err(e) = crate::windows::show_quick_add(app) {
+                engram_log!("Failed to open quick add: {}", e);
             }
         }
         "preferences" => {
@@ -149,6 +285,22 @@ fn handle_menu_event(app: &AppHandle, event_id: &str) {
                 let _ = window.eval("window.location.hash = '#/preferences'");
             }
         }
+        "check-sidecar-update" => {
+            // The Advanced tab runs the check and offers the update
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+                let _ = window.eval("window.location.hash = '#/preferences?tab=advanced&check=update'");
+            }
+        }
+        "open-data-dir" => {
+            let app_handle = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = crate::commands::open_data_dir(app_handle).await {
+                    engram_log!("Failed to open data folder: {}", e);
+                }
+            });
+        }
         "manage-agents" => {
             if let Some(window) = app.get_webview_window("main") {
                 let _ = window.show();
@@ -156,14 +308,41 @@ fn handle_menu_event(app: &AppHandle, event_id: &str) {
                 let _ = window.eval("window.location.hash = '#/preferences?tab=agents'");
             }
         }
+        id if id.starts_with("profile-") => {
+            let name = id.strip_prefix("profile-").unwrap_or(id).to_string();
+            let app_handle = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = crate::profiles::switch(&app_handle, &name).await {
+                    engram_log!("Failed to switch profile: {}", e);
+                }
+            });
+        }
         id if id.starts_with("agent-") => {
             let agent_name = id.strip_prefix("agent-").unwrap_or(id);
-            let _app_handle = app.clone();
+            let app_handle = app.clone();
             let name = agent_name.to_string();
             tauri::async_runtime::spawn(async move {
-                match crate::commands::configure_agent(name).await {
-                    Ok(msg) => eprintln!("[engram] {}", msg),
-                    Err(e) => eprintln!("[engram] Failed to configure agent: {}", e),
+                match crate::commands::configure_agent(app_handle, name).await {
+                    Ok(msg) => engram_log!("{}", msg),
+                    Err(e) => engram_log!("Failed to configure agent: {}", e),
+                }
+            });
+        }
+        "copy-connection-info" => {
+            let app_handle = app.clone();
+            tauri::async_runtime::spawn(async move {
+                use tauri_plugin_clipboard_manager::ClipboardExt;
+
+                let port = *app_handle
+                    .state::<crate::sidecar::SidecarState>()
+                    .port
+                    .lock()
+                    .await;
+                let info = crate::commands::connection_info(port);
+                let text = serde_json::to_string_pretty(&info).unwrap_or_default();
+                match app_handle.clipboard().write_text(text) {
+                    Ok(()) => engram_log!("Connection info copied to clipboard"),
+                    Err(e) => engram_log!("Failed to copy connection info: {}", e),
                 }
             });
         }
@@ -171,7 +350,7 @@ fn handle_menu_event(app: &AppHandle, event_id: &str) {
             // Stop sidecar before quitting
             let app_handle = app.clone();
             tauri::async_runtime::spawn(async move {
-                let _ = crate::sidecar::stop_sidecar(&app_handle).await;
+                let _ = crate::sidecar::stop(&app_handle).await;
                 app_handle.exit(0);
             });
         }
@@ -0,0 +1,334 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{
+    AppHandle, LogicalSize, Manager, Monitor, PhysicalPosition, PhysicalSize, WebviewUrl,
+    WebviewWindow, WebviewWindowBuilder, WindowEvent,
+};
+
+pub const QUICK_ADD_LABEL: &str = "quick-add";
+pub const API_EXPLORER_LABEL: &str = "api-explorer";
+pub const PRESENT_LABEL: &str = "present";
+
+/// Used until the panel reports its content size or the user resizes it.
+const QUICK_ADD_DEFAULT: (f64, f64) = (560.0, 240.0);
+const QUICK_ADD_MIN: (f64, f64) = (360.0, 160.0);
+/// Space around the content card, in logical pixels.
+const QUICK_ADD_MARGIN: f64 = 24.0;
+/// Largest share of the monitor the panel may cover.
+const MAX_MONITOR_FRACTION: f64 = 0.8;
+
+/// Logical (scale-independent) size of a window.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SavedSize {
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct WindowStateFile {
+    /// Set once the user resizes the quick-add panel; content sizing stops then.
+    quick_add: Option<SavedSize>,
+}
+
+/// Whether the read-only present window is standing in for the dashboard.
+static PRESENT_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Physical size we last applied ourselves, so the resulting `Resized`
+/// event isn't mistaken for a user resize.
+static PENDING_RESIZE: Mutex<Option<PhysicalSize<u32>>> = Mutex::new(None);
+
+fn state_path() -> Result<PathBuf, String> {
+    Ok(crate::profiles::root_dir()?.join("window-state.json"))
+}
+
+fn read_state() -> WindowStateFile {
+    state_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_state(state: &WindowStateFile) {
+    let result = state_path().and_then(|path| {
+        let content = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+        fs::write(path, content).map_err(|e| e.to_string())
+    });
+    if let Err(e) = result {
+        engram_log!("Failed to save window state: {}", e);
+    }
+}
+
+/// The monitor under the mouse cursor, falling back to the primary monitor.
+fn target_monitor(app: &AppHandle) -> Option<Monitor> {
+    app.cursor_position()
+        .ok()
+        .and_then(|p| app.monitor_from_point(p.x, p.y).ok().flatten())
+        .or_else(|| app.primary_monitor().ok().flatten())
+}
+
+/// Convert a logical size to physical pixels on `monitor`, clamped to fit on it.
+fn fit_to_monitor(size: SavedSize, monitor: &Monitor) -> PhysicalSize<u32> {
+    let scale = monitor.scale_factor();
+    let bounds = monitor.size();
+    let max_width = bounds.width as f64 * MAX_MONITOR_FRACTION;
+    let max_height = bounds.height as f64 * MAX_MONITOR_FRACTION;
+    PhysicalSize::new(
+        (size.width.max(QUICK_ADD_MIN.0) * scale).min(max_width).round() as u32,
+        (size.height.max(QUICK_ADD_MIN.1) * scale).min(max_height).round() as u32,
+    )
+}
+
+/// Place the window horizontally centred in the upper third of `monitor`.
+fn position_on_monitor(window: &WebviewWindow, size: PhysicalSize<u32>, monitor: &Monitor) {
+    let origin = monitor.position();
+    let bounds = monitor.size();
+    let x = origin.x + (bounds.width.saturating_sub(size.width) / 2) as i32;
+    let y = origin.y + (bounds.height / 5) as i32;
+    let _ = window.set_position(PhysicalPosition::new(x, y));
+}
+
+fn apply_size(window: &WebviewWindow, size: PhysicalSize<u32>) {
+    if let Ok(mut pending) = PENDING_RESIZE.lock() {
+        *pending = Some(size);
+    }
+    let _ = window.set_size(size);
+}
+
+/// Show the quick-add panel on the monitor under the cursor, creating it on first use.
+pub fn show_quick_add(app: &AppHandle) -> Result<(), String> {
+    if crate::commands::read_preferences()
+        .unwrap_or_default()
+        .capture_frontmost_app
+    {
+        crate::frontmost::record();
+    }
+    // Nothing can be added while presenting
+    if present_mode_active() {
+        return Ok(());
+    }
+    let window = match app.get_webview_window(QUICK_ADD_LABEL) {
+        Some(window) => window,
+        None => create_quick_add(app)?,
+    };
+
+    let saved = read_state().quick_add.unwrap_or(SavedSize {
+        width: QUICK_ADD_DEFAULT.0,
+        height: QUICK_ADD_DEFAULT.1,
+    });
+    if let Some(monitor) = target_monitor(app) {
+        let size = fit_to_monitor(saved, &monitor);
+        apply_size(&window, size);
+        position_on_monitor(&window, size, &monitor);
+    }
+
+    window.show().map_err(|e| e.to_string())?;
+    window.set_focus().map_err(|e| e.to_string())
+}
+
+/// Register `accelerator` as the global quick-add shortcut, replacing `previous`.
+/// If another application holds the new shortcut, the previous one stays active.
+pub fn register_quick_add_shortcut(
+    app: &AppHandle,
+    accelerator: &str,
+    previous: Option<&str>,
+) -> Result<(), String> {
+    use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+    if previous == Some(accelerator) {
+        return Ok(());
+    }
+    let app_handle = app.clone();
+    app.global_shortcut()
+        .on_shortcut(accelerator, move |_app, _shortcut, event| {
+            if event.state == ShortcutState::Pressed {
+                if let Err(e) = show_quick_add(&app_handle) {
+                    engram_log!("Failed to open quick add: {}", e);
+                }
+            }
+        })
+        .map_err(|e| {
+            format!(
+                "Could not register {}; it may be in use by another application ({})",
+                accelerator, e
+            )
+        })?;
+
+    if let Some(previous) = previous {
+        let _ = app.global_shortcut().unregister(previous);
+    }
+    engram_log!("Quick add shortcut set to {}", accelerator);
+    Ok(())
+}
+
+pub fn hide_quick_add(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(QUICK_ADD_LABEL) {
+        let _ = window.hide();
+    }
+}
+
+/// Resize the panel to its rendered content (logical CSS pixels), unless the
+/// user has chosen a size themselves.
+pub fn fit_quick_add(app: &AppHandle, width: f64, height: f64) {
+    if read_state().quick_add.is_some() {
+        return;
+    }
+    let Some(window) = app.get_webview_window(QUICK_ADD_LABEL) else {
+        return;
+    };
+    let monitor = window.current_monitor().ok().flatten().or_else(|| target_monitor(app));
+    if let Some(monitor) = monitor {
+        let content = SavedSize {
+            width: width + QUICK_ADD_MARGIN * 2.0,
+            height: height + QUICK_ADD_MARGIN * 2.0,
+        };
+        apply_size(&window, fit_to_monitor(content, &monitor));
+    }
+}
+
+fn create_quick_add(app: &AppHandle) -> Result<WebviewWindow, String> {
+    let window = WebviewWindowBuilder::new(
+        app,
+        QUICK_ADD_LABEL,
+        WebviewUrl::App("index.html?window=quick-add".into()),
+    )
+    .title("Quick Add")
+    .inner_size(QUICK_ADD_DEFAULT.0, QUICK_ADD_DEFAULT.1)
+    .min_inner_size(QUICK_ADD_MIN.0, QUICK_ADD_MIN.1)
+    .resizable(true)
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .visible(false)
+    .build()
+    .map_err(|e| format!("Failed to create quick-add window: {}", e))?;
+
+    let handle = window.clone();
+    window.on_window_event(move |event| match event {
+        WindowEvent::Resized(size) => remember_resize(&handle, *size),
+        WindowEvent::Focused(false) => {
+            let _ = handle.hide();
+        }
+        _ => {}
+    });
+
+    Ok(window)
+}
+
+/// Persist a user resize as a logical size, so it carries across monitors
+/// with different scale factors.
+fn remember_resize(window: &WebviewWindow, size: PhysicalSize<u32>) {
+    if size.width == 0 || size.height == 0 {
+        return;
+    }
+    if let Ok(mut pending) = PENDING_RESIZE.lock() {
+        if *pending == Some(size) {
+            *pending = None;
+            return;
+        }
+    }
+
+    let scale = window.scale_factor().unwrap_or(1.0);
+    let logical: LogicalSize<f64> = size.to_logical(scale);
+    let mut state = read_state();
+    state.quick_add = Some(SavedSize {
+        width: logical.width,
+        height: logical.height,
+    });
+    write_state(&state);
+}
+
+/// Show the API explorer window, creating it on first use.
+pub fn show_api_explorer(app: &AppHandle) -> Result<(), String> {
+    let window = match app.get_webview_window(API_EXPLORER_LABEL) {
+        Some(window) => window,
+        None => WebviewWindowBuilder::new(
+            app,
+            API_EXPLORER_LABEL,
+            WebviewUrl::App("index.html?window=api-explorer".into()),
+        )
+        .title("Engram API Explorer")
+        .inner_size(1000.0, 700.0)
+        .min_inner_size(640.0, 400.0)
+        .build()
+        .map_err(|e| format!("Failed to create API explorer window: {}", e))?,
+    };
+    window.show().map_err(|e| e.to_string())?;
+    window.set_focus().map_err(|e| e.to_string())
+}
+
+pub fn present_mode_active() -> bool {
+    PRESENT_MODE.load(Ordering::SeqCst)
+}
+
+/// Guard for commands that change data or settings, or return memories
+/// unmasked: none of them run while present mode is on.
+pub fn ensure_not_presenting() -> Result<(), String> {
+    if present_mode_active() {
+        return Err("Not available in present mode".to_string());
+    }
+    Ok(())
+}
+
+/// Enter or leave present mode for screen sharing. Entering hides the
+/// dashboard and quick-add panel behind a read-only window; leaving closes
+/// it and brings the dashboard back.
+pub fn set_present_mode(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    PRESENT_MODE.store(enabled, Ordering::SeqCst);
+    if enabled {
+        hide_quick_add(app);
+        if let Some(main) = app.get_webview_window("main") {
+            let _ = main.hide();
+        }
+        let window = match app.get_webview_window(PRESENT_LABEL) {
+            Some(window) => window,
+            None => create_present(app)?,
+        };
+        window.show().map_err(|e| e.to_string())?;
+        window.set_focus().map_err(|e| e.to_string())?;
+    } else {
+        if let Some(window) = app.get_webview_window(PRESENT_LABEL) {
+            let _ = window.destroy();
+        }
+        if let Some(main) = app.get_webview_window("main") {
+            let _ = main.show();
+            let _ = main.set_focus();
+        }
+    }
+    let prefs = crate::commands::read_preferences().unwrap_or_default();
+    crate::tray::apply_layout(app, &prefs)
+}
+
+fn create_present(app: &AppHandle) -> Result<WebviewWindow, String> {
+    let window = WebviewWindowBuilder::new(
+        app,
+        PRESENT_LABEL,
+        WebviewUrl::App("index.html?window=present".into()),
+    )
+    .title("Engram — Present Mode")
+    .inner_size(900.0, 700.0)
+    .min_inner_size(480.0, 400.0)
+    .build()
+    .map_err(|e| format!("Failed to create present window: {}", e))?;
+
+    // Closing the window ends present mode rather than leaving it half on
+    let app_handle = app.clone();
+    window.on_window_event(move |event| {
+        if let WindowEvent::CloseRequested { api, .. } = event {
+            api.prevent_close();
+            // Destroyed outside its own event handler
+            let app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = set_present_mode(&app_handle, false) {
+                    engram_log!("Failed to leave present mode: {}", e);
+                }
+            });
+        }
+    });
+    Ok(window)
+}
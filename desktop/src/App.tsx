@@ -14,6 +14,8 @@ import Contradictions from "./pages/Contradictions";
 import Import from "./pages/Import";
 import Onboarding from "./pages/Onboarding";
 import Preferences from "./pages/Preferences";
+import ApiExplorer from "./pages/ApiExplorer";
+import PresentMode from "./pages/PresentMode";
 import QuickAddModal from "./components/QuickAddModal";
 
 function LoadingScreen() {
@@ -29,6 +31,29 @@ function LoadingScreen() {
   );
 }
 
+// The quick-add panel, the API explorer, and present mode are separate
+// windows running this app with ?window=quick-add, ?window=api-explorer,
+// or ?window=present
+const windowKind = new URLSearchParams(window.location.search).get("window");
+const isQuickAddWindow = windowKind === "quick-add";
+const isApiExplorerWindow = windowKind === "api-explorer";
+const isPresentWindow = windowKind === "present";
+
+function QuickAddWindow() {
+  useEffect(() => {
+    const card = document.querySelector("[data-quick-add-card]");
+    if (!card) return;
+    const observer = new ResizeObserver(() => {
+      const rect = card.getBoundingClientRect();
+      invoke("fit_quick_add_window", { width: rect.width, height: rect.height }).catch(() => {});
+    });
+    observer.observe(card);
+    return () => observer.disconnect();
+  }, []);
+
+  return <QuickAddModal onClose={() => invoke("hide_quick_add_window").catch(() => {})} />;
+}
+
 function AppLayout({ children }: { children: React.ReactNode }) {
   return (
     <div className="flex h-screen overflow-hidden">
@@ -86,10 +111,22 @@ export default function App() {
     };
   }, [navigate]);
 
+  if (isQuickAddWindow) {
+    return <QuickAddWindow />;
+  }
+
+  if (isApiExplorerWindow) {
+    return <ApiExplorer />;
+  }
+
   if (loading) {
     return <LoadingScreen />;
   }
 
+  if (isPresentWindow) {
+    return <PresentMode />;
+  }
+
   // Onboarding gets its own full-screen layout (no sidebar)
   if (location.pathname === "/onboarding") {
     return (
@@ -0,0 +1,42 @@
+import React from "react";
+import { reportFrontendError } from "../lib/errors";
+
+interface State {
+  error: Error | null;
+}
+
+/** Reports render errors and shows a way out instead of a blank window. */
+export default class ErrorBoundary extends React.Component<{ children: React.ReactNode }, State> {
+  state: State = { error: null };
+
+  static getDerivedStateFromError(error: Error): State {
+    return { error };
+  }
+
+  componentDidCatch(error: Error, info: React.ErrorInfo) {
+    reportFrontendError("render", error, info.componentStack ?? undefined);
+  }
+
+  render() {
+    if (!this.state.error) return this.props.children;
+    return (
+      <div className="flex items-center justify-center h-screen bg-surface">
+        <div className="text-center max-w-md px-6">
+          <p className="text-sm font-medium">Something went wrong</p>
+          <p className="mt-2 text-xs font-mono" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+            {this.state.error.message}
+          </p>
+          <p className="mt-2 text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+            The error was saved to the Engram log and will be included in support bundles.
+          </p>
+          <button
+            onClick={() => window.location.reload()}
+            className="mt-4 px-4 py-2 text-sm font-medium rounded-lg bg-indigo-600 text-white hover:bg-indigo-700"
+          >
+            Reload
+          </button>
+        </div>
+      </div>
+    );
+  }
+}
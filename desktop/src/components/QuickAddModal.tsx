@@ -1,6 +1,6 @@
 import React, { useState, useRef, useEffect } from "react";
 import { motion, AnimatePresence } from "framer-motion";
-import { getApiBase } from "../lib/api";
+import { invoke } from "@tauri-apps/api/core";
 
 interface QuickAddModalProps {
   onClose: () => void;
@@ -43,28 +43,15 @@ export default function QuickAddModal({ onClose }: QuickAddModalProps) {
     setError(null);
 
     try {
-      const body: Record<string, string> = {
+      await invoke<string>("add_memory", {
         content: content.trim(),
         category,
-      };
-      if (entity.trim()) {
-        body.entity = entity.trim();
-      }
-
-      const res = await fetch(`${getApiBase()}/memories`, {
-        method: "POST",
-        headers: { "Content-Type": "application/json" },
-        body: JSON.stringify(body),
+        entity: entity.trim() || null,
       });
 
-      if (!res.ok) {
-        const data = await res.json().catch(() => ({}));
-        throw new Error(data.error || "Failed to save memory");
-      }
-
       onClose();
     } catch (err) {
-      setError(err instanceof Error ? err.message : "Failed to save memory");
+      setError(String(err));
     } finally {
       setSaving(false);
     }
@@ -94,6 +81,7 @@ export default function QuickAddModal({ onClose }: QuickAddModalProps) {
           animate={{ opacity: 1, y: 0, scale: 1 }}
           exit={{ opacity: 0, y: -20, scale: 0.95 }}
           transition={{ duration: 0.15 }}
+          data-quick-add-card
           className="w-full max-w-lg rounded-xl shadow-2xl overflow-hidden"
           style={{ backgroundColor: "rgba(var(--surface-raised), 1)" }}
         >
@@ -15,6 +15,15 @@ export async function initApiPort(): Promise<void> {
   }
 }
 
+/** Follow a `restPort` change saved in preferences. */
+export function setApiPort(next: number): void {
+  if (next > 0) port = next;
+}
+
+export function getApiPort(): number {
+  return port;
+}
+
 export function getApiBase(): string {
   return `http://localhost:${port}/api`;
 }
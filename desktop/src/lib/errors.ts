@@ -0,0 +1,38 @@
+import { invoke } from "@tauri-apps/api/core";
+
+/** Reports sent per page load, so an error in a render loop can't flood the log. */
+const MAX_REPORTS = 10;
+
+let sent = 0;
+
+/** Forward a JavaScript error to the desktop log and diagnostics reports. */
+export function reportFrontendError(
+  kind: "error" | "unhandledrejection" | "render",
+  error: unknown,
+  componentStack?: string
+): void {
+  if (sent >= MAX_REPORTS) return;
+  sent += 1;
+  const err = error instanceof Error ? error : null;
+  invoke("report_frontend_error", {
+    error: {
+      kind,
+      message: err ? `${err.name}: ${err.message}` : String(error),
+      stack: err?.stack ?? null,
+      componentStack: componentStack ?? null,
+      route: window.location.hash || "#/",
+    },
+  }).catch(() => {
+    // Tauri not available (e.g. dev mode in browser)
+  });
+}
+
+/** Report uncaught errors and unhandled promise rejections. Call once at startup. */
+export function installErrorReporting(): void {
+  window.addEventListener("error", (event) => {
+    reportFrontendError("error", event.error ?? event.message);
+  });
+  window.addEventListener("unhandledrejection", (event) => {
+    reportFrontendError("unhandledrejection", event.reason);
+  });
+}
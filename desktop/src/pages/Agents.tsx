@@ -1,6 +1,6 @@
 import { useState, useEffect } from "react";
 import { motion } from "framer-motion";
-import { api, Memory } from "../lib/api";
+import { api, getApiBase, Memory } from "../lib/api";
 
 const SOURCE_COLORS: Record<string, string> = {
   cli: "bg-blue-100 text-blue-800 dark:bg-blue-900/50 dark:text-blue-300",
@@ -229,7 +229,7 @@ export default function Agents() {
               Use the REST API from any programming language. Create memories with custom namespaces for each agent.
             </p>
             <code className="block mt-1 p-2 bg-gray-100 dark:bg-gray-800 rounded text-xs font-mono">
-              POST http://localhost:3838/api/memories {`{"namespace": "my-agent"}`}
+              POST {getApiBase()}/memories {`{"namespace": "my-agent"}`}
             </code>
           </div>
           <div>
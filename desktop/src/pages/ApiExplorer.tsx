@@ -0,0 +1,351 @@
+import { useEffect, useRef, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+interface Route {
+  method: string;
+  url: string;
+}
+
+interface ApiResponse {
+  status: number;
+  durationMs: number;
+  body: unknown;
+}
+
+interface AnalyticsResult {
+  columns: string[];
+  rows: unknown[][];
+  truncated: boolean;
+  refreshedAt: number | null;
+}
+
+interface AppEvent {
+  id: number;
+  at: string;
+  name: string;
+  payload: unknown;
+}
+
+/** Events the desktop app emits; Tauri has no wildcard listener. */
+const APP_EVENTS = [
+  "sidecar-status",
+  "sidecar-ready",
+  "sidecar-crashed",
+  "sidecar-failure",
+  "sidecar-start-failed",
+  "sidecar-incompatible",
+  "sidecar-log-dropped",
+  "tray-status-update",
+  "profile-changed",
+  "system-wake",
+  "notification",
+  "memory-added",
+  "export-progress",
+  "import-progress",
+  "maintenance-complete",
+  "maintenance-failed",
+  "database-restored",
+  "read-only-changed",
+  "memory-resurfaced",
+  "notion-sync-complete",
+  "notion-sync-failed",
+  "github-sync-complete",
+  "github-sync-failed",
+  "issue-enrichment-complete",
+  "issue-enrichment-failed",
+];
+
+const EXAMPLE_QUERY = `SELECT source, date_trunc('month', created_at) AS month, count(*) AS memories
+FROM memories
+GROUP BY ALL
+ORDER BY month DESC, memories DESC`;
+
+/** Events kept in the stream view. */
+const MAX_EVENTS = 200;
+
+const METHOD_COLORS: Record<string, string> = {
+  GET: "text-green-600 dark:text-green-400",
+  POST: "text-blue-600 dark:text-blue-400",
+  PUT: "text-yellow-600 dark:text-yellow-400",
+  PATCH: "text-yellow-600 dark:text-yellow-400",
+  DELETE: "text-red-600 dark:text-red-400",
+};
+
+export default function ApiExplorer() {
+  const [routes, setRoutes] = useState<Route[]>([]);
+  const [method, setMethod] = useState("GET");
+  const [path, setPath] = useState("/api/status");
+  const [body, setBody] = useState("");
+  const [response, setResponse] = useState<ApiResponse | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [sending, setSending] = useState(false);
+  const [events, setEvents] = useState<AppEvent[]>([]);
+  const [paused, setPaused] = useState(false);
+  const [mode, setMode] = useState<"rest" | "analytics">("rest");
+  const [sql, setSql] = useState(EXAMPLE_QUERY);
+  const [analytics, setAnalytics] = useState<AnalyticsResult | null>(null);
+  const nextEventId = useRef(0);
+
+  useEffect(() => {
+    invoke<ApiResponse>("api_request", { method: "GET", path: "/api/routes" })
+      .then((res) => {
+        const listed = (res.body as { routes?: Route[] })?.routes;
+        setRoutes(listed ?? []);
+      })
+      .catch((err) => setError(String(err)));
+  }, []);
+
+  useEffect(() => {
+    if (paused) return;
+    const unlisten: Array<() => void> = [];
+    for (const name of APP_EVENTS) {
+      listen(name, (event) => {
+        const entry = {
+          id: nextEventId.current++,
+          at: new Date().toLocaleTimeString(),
+          name,
+          payload: event.payload,
+        };
+        setEvents((prev) => [entry, ...prev].slice(0, MAX_EVENTS));
+      }).then((fn) => unlisten.push(fn));
+    }
+    return () => {
+      unlisten.forEach((fn) => fn());
+    };
+  }, [paused]);
+
+  function selectRoute(route: Route) {
+    setMethod(route.method);
+    setPath(route.url);
+    setBody(route.method === "GET" || route.method === "DELETE" ? "" : "{\n  \n}");
+  }
+
+  async function handleSend() {
+    let parsed: unknown = null;
+    if (body.trim()) {
+      try {
+        parsed = JSON.parse(body);
+      } catch (err) {
+        setError(`Body is not valid JSON: ${err}`);
+        return;
+      }
+    }
+
+    setSending(true);
+    setError(null);
+    try {
+      setResponse(await invoke<ApiResponse>("api_request", { method, path, body: parsed }));
+    } catch (err) {
+      setResponse(null);
+      setError(String(err));
+    } finally {
+      setSending(false);
+    }
+  }
+
+  async function handleRunQuery() {
+    setSending(true);
+    setError(null);
+    try {
+      setAnalytics(await invoke<AnalyticsResult>("run_analytics_query", { sql }));
+    } catch (err) {
+      setAnalytics(null);
+      setError(String(err));
+    } finally {
+      setSending(false);
+    }
+  }
+
+  return (
+    <div className="flex h-screen overflow-hidden text-sm">
+      <div className="w-72 border-r border-gray-200 dark:border-gray-700 overflow-y-auto p-3 space-y-0.5">
+        <p
+          className="text-xs font-medium uppercase tracking-wider px-2 mb-2"
+          style={{ color: "rgba(var(--text-secondary), 1)" }}
+        >
+          Endpoints
+        </p>
+        {routes.map((route) => (
+          <button
+            key={`${route.method} ${route.url}`}
+            onClick={() => selectRoute(route)}
+            className="flex w-full gap-2 px-2 py-1 rounded text-left font-mono text-xs hover:bg-gray-100 dark:hover:bg-gray-800"
+          >
+            <span className={`w-12 shrink-0 ${METHOD_COLORS[route.method] ?? ""}`}>
+              {route.method}
+            </span>
+            <span className="truncate">{route.url}</span>
+          </button>
+        ))}
+      </div>
+
+      <div className="flex-1 flex flex-col min-w-0">
+        <div className="flex gap-4 px-4 pt-3 text-xs font-medium">
+          {(["rest", "analytics"] as const).map((m) => (
+            <button
+              key={m}
+              onClick={() => {
+                setMode(m);
+                setError(null);
+              }}
+              className={mode === m ? "text-indigo-600 dark:text-indigo-400" : "hover:underline"}
+            >
+              {m === "rest" ? "REST" : "Analytics (DuckDB)"}
+            </button>
+          ))}
+        </div>
+
+        {mode === "analytics" ? (
+          <>
+            <div className="p-4 space-y-3 border-b border-gray-200 dark:border-gray-700">
+              <textarea
+                value={sql}
+                onChange={(e) => setSql(e.target.value)}
+                onKeyDown={(e) => (e.metaKey || e.ctrlKey) && e.key === "Enter" && handleRunQuery()}
+                rows={6}
+                className="w-full px-3 py-2 rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent font-mono text-xs"
+              />
+              <div className="flex items-center justify-between">
+                <p className="text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                  Tables: memories, memory_tags (memory_id, tag). Copied hourly from the live database.
+                </p>
+                <button
+                  onClick={handleRunQuery}
+                  disabled={sending}
+                  className="px-4 py-1.5 font-medium rounded-lg bg-indigo-600 text-white hover:bg-indigo-700 disabled:opacity-50"
+                >
+                  {sending ? "Running..." : "Run"}
+                </button>
+              </div>
+              {error && <p className="text-xs text-red-600 dark:text-red-400">{error}</p>}
+            </div>
+
+            <div className="flex-1 overflow-auto p-4">
+              {analytics && (
+                <>
+                  <p className="text-xs mb-2" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                    {analytics.rows.length}
+                    {analytics.truncated ? "+" : ""} rows
+                    {analytics.refreshedAt !== null &&
+                      ` · copy from ${new Date(analytics.refreshedAt).toLocaleString()}`}
+                  </p>
+                  <table className="text-xs font-mono">
+                    <thead>
+                      <tr>
+                        {analytics.columns.map((column) => (
+                          <th key={column} className="text-left pr-4 pb-1 font-medium">
+                            {column}
+                          </th>
+                        ))}
+                      </tr>
+                    </thead>
+                    <tbody>
+                      {analytics.rows.map((row, i) => (
+                        <tr key={i}>
+                          {row.map((value, j) => (
+                            <td key={j} className="pr-4 align-top whitespace-nowrap">
+                              {typeof value === "string" ? value : JSON.stringify(value)}
+                            </td>
+                          ))}
+                        </tr>
+                      ))}
+                    </tbody>
+                  </table>
+                </>
+              )}
+            </div>
+          </>
+        ) : (
+          <>
+            <div className="p-4 space-y-3 border-b border-gray-200 dark:border-gray-700">
+              <div className="flex gap-2">
+                <select
+                  value={method}
+                  onChange={(e) => setMethod(e.target.value)}
+                  className="px-2 py-1.5 rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent font-mono"
+                >
+                  {["GET", "POST", "PUT", "PATCH", "DELETE"].map((m) => (
+                    <option key={m} value={m}>
+                      {m}
+                    </option>
+                  ))}
+                </select>
+                <input
+                  type="text"
+                  value={path}
+                  onChange={(e) => setPath(e.target.value)}
+                  onKeyDown={(e) => e.key === "Enter" && handleSend()}
+                  className="flex-1 px-3 py-1.5 rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent font-mono"
+                />
+                <button
+                  onClick={handleSend}
+                  disabled={sending}
+                  className="px-4 py-1.5 font-medium rounded-lg bg-indigo-600 text-white hover:bg-indigo-700 disabled:opacity-50"
+                >
+                  {sending ? "Sending..." : "Send"}
+                </button>
+              </div>
+              {method !== "GET" && (
+                <textarea
+                  value={body}
+                  onChange={(e) => setBody(e.target.value)}
+                  placeholder="JSON body"
+                  rows={5}
+                  className="w-full px-3 py-2 rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent font-mono text-xs"
+                />
+              )}
+              {error && <p className="text-xs text-red-600 dark:text-red-400">{error}</p>}
+            </div>
+
+            <div className="flex-1 overflow-auto p-4">
+              {response && (
+                <>
+                  <p className="text-xs mb-2" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                    <span className={response.status < 400 ? "text-green-600" : "text-red-600"}>
+                      {response.status}
+                    </span>{" "}
+                    · {response.durationMs} ms
+                  </p>
+                  <pre className="text-xs font-mono whitespace-pre-wrap break-all">
+                    {typeof response.body === "string"
+                      ? response.body
+                      : JSON.stringify(response.body, null, 2)}
+                  </pre>
+                </>
+              )}
+            </div>
+          </>
+        )}
+
+        <div className="h-64 border-t border-gray-200 dark:border-gray-700 flex flex-col">
+          <div className="flex items-center justify-between px-4 py-2">
+            <p
+              className="text-xs font-medium uppercase tracking-wider"
+              style={{ color: "rgba(var(--text-secondary), 1)" }}
+            >
+              App events
+            </p>
+            <div className="flex gap-3 text-xs">
+              <button onClick={() => setPaused(!paused)} className="hover:underline">
+                {paused ? "Resume" : "Pause"}
+              </button>
+              <button onClick={() => setEvents([])} className="hover:underline">
+                Clear
+              </button>
+            </div>
+          </div>
+          <div className="flex-1 overflow-y-auto px-4 pb-2 font-mono text-xs space-y-0.5">
+            {events.map((event) => (
+              <div key={event.id} className="flex gap-2">
+                <span style={{ color: "rgba(var(--text-secondary), 1)" }}>{event.at}</span>
+                <span className="text-indigo-600 dark:text-indigo-400">{event.name}</span>
+                <span className="truncate">{JSON.stringify(event.payload)}</span>
+              </div>
+            ))}
+          </div>
+        </div>
+      </div>
+    </div>
+  );
+}
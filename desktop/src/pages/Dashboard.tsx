@@ -1,6 +1,6 @@
 import React, { useState, useEffect, useCallback } from "react";
 import { useNavigate } from "react-router-dom";
-import { getApiBase, getHealthUrl } from "../lib/api";
+import { getApiBase, getApiPort, getHealthUrl } from "../lib/api";
 
 interface Memory {
   id: string;
@@ -421,7 +421,7 @@ export default function Dashboard() {
               }`}
             />
             <span className="text-sm">
-              REST API (port 3838):{" "}
+              REST API (port {getApiPort()}):{" "}
               {sidecarHealthy ? "Running" : "Not running"}
             </span>
           </div>
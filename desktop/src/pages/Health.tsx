@@ -1,5 +1,6 @@
 import { useState, useEffect } from "react";
 import { motion } from "framer-motion";
+import { invoke } from "@tauri-apps/api/core";
 import { api, type AnalyticsOverview, type StaleData, type NeverRecalledData, type DuplicatesData, type TrendsData } from "../lib/api";
 import HealthGauge from "../components/HealthGauge";
 import TrendsChart from "../components/TrendsChart";
@@ -13,6 +14,12 @@ const CATEGORY_COLORS: Record<string, string> = {
   outcome: "bg-red-100 text-red-800 dark:bg-red-900 dark:text-red-300",
 };
 
+interface CleanupBatch {
+  reason: string;
+  label: string;
+  memories: Array<{ id: string; preview: string; quality: number }>;
+}
+
 export default function Health() {
   const [overview, setOverview] = useState<AnalyticsOverview | null>(null);
   const [stale, setStale] = useState<StaleData | null>(null);
@@ -27,6 +34,8 @@ export default function Health() {
   const [confirmMerge, setConfirmMerge] = useState(false);
   const [confirmNeverRecalled, setConfirmNeverRecalled] = useState(false);
   const [deletingId, setDeletingId] = useState<string | null>(null);
+  const [cleanupBatches, setCleanupBatches] = useState<CleanupBatch[]>([]);
+  const [archivingReason, setArchivingReason] = useState<string | null>(null);
 
   async function loadData() {
     setLoading(true);
@@ -51,10 +60,37 @@ export default function Health() {
     }
   }
 
+  async function loadCleanupSuggestions() {
+    try {
+      const result = await invoke<{ batches: CleanupBatch[] }>("get_cleanup_suggestions");
+      setCleanupBatches(result.batches);
+    } catch {
+      // Only available in the desktop app
+      setCleanupBatches([]);
+    }
+  }
+
   useEffect(() => {
     loadData();
+    loadCleanupSuggestions();
   }, []);
 
+  async function handleArchiveBatch(batch: CleanupBatch) {
+    setArchivingReason(batch.reason);
+    try {
+      const report = await invoke<{ archived: number; path: string }>("archive_memories", {
+        ids: batch.memories.map((m) => m.id),
+      });
+      setCleanResult(`Archived ${report.archived} memories to ${report.path}`);
+      loadData();
+      loadCleanupSuggestions();
+    } catch (err) {
+      setCleanResult(`Error: ${err}`);
+    } finally {
+      setArchivingReason(null);
+    }
+  }
+
   function handleCleanStale() {
     if (!stale || stale.items.length === 0) return;
     if (!confirmStale) {
@@ -267,6 +303,36 @@ export default function Health() {
         )}
       </div>
 
+      {/* Suggested cleanup batches (desktop app only) */}
+      {cleanupBatches.length > 0 && (
+        <div className="glass rounded-[10px] p-6 border border-gray-200/50 dark:border-gray-700/50">
+          <h3 className="text-sm font-medium mb-4" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+            Suggested Cleanup
+          </h3>
+          <div className="space-y-2">
+            {cleanupBatches.map((batch) => (
+              <div key={batch.reason} className="flex items-center justify-between gap-4">
+                <div className="min-w-0">
+                  <p className="text-sm font-medium">
+                    {batch.label} ({batch.memories.length})
+                  </p>
+                  <p className="text-xs truncate" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                    {batch.memories.slice(0, 3).map((m) => m.preview).join(" · ")}
+                  </p>
+                </div>
+                <button
+                  onClick={() => handleArchiveBatch(batch)}
+                  disabled={archivingReason !== null}
+                  className="shrink-0 px-3 py-1.5 text-xs font-medium rounded-[10px] border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 disabled:opacity-50 transition-colors"
+                >
+                  {archivingReason === batch.reason ? "Archiving..." : "Archive"}
+                </button>
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+
       {/* Stale memories detail */}
       {stale && stale.items.length > 0 && (
         <MemoryTable
@@ -1,5 +1,6 @@
 import { useState, useEffect, useCallback } from "react";
 import { motion } from "framer-motion";
+import { invoke } from "@tauri-apps/api/core";
 import { api, Memory } from "../lib/api";
 import CreateMemoryModal from "../components/CreateMemoryModal";
 
@@ -14,6 +15,15 @@ const CATEGORY_COLORS: Record<string, string> = {
   outcome: "bg-red-100 text-red-800 dark:bg-red-900/50 dark:text-red-300",
 };
 
+interface PackMetadata {
+  title: string;
+  description: string;
+  author: string;
+  license: string;
+}
+
+const EMPTY_PACK: PackMetadata = { title: "", description: "", author: "", license: "" };
+
 function formatDate(timestamp: number): string {
   return new Date(timestamp).toLocaleDateString(undefined, {
     month: "short",
@@ -31,6 +41,9 @@ export default function Memories() {
   const [namespace, setNamespace] = useState("");
   const [page, setPage] = useState(0);
   const [totalMemories, setTotalMemories] = useState(0);
+  const [selected, setSelected] = useState<Set<string>>(new Set());
+  const [pack, setPack] = useState<PackMetadata | null>(null);
+  const [packStatus, setPackStatus] = useState<string | null>(null);
 
   const totalPages = Math.max(1, Math.ceil(totalMemories / PAGE_SIZE));
 
@@ -69,22 +82,105 @@ export default function Memories() {
     }
   }
 
+  function toggleSelected(id: string) {
+    setSelected((prev) => {
+      const next = new Set(prev);
+      if (next.has(id)) next.delete(id);
+      else next.add(id);
+      return next;
+    });
+  }
+
+  async function handleCreatePack() {
+    if (!pack) return;
+    setPackStatus(null);
+    try {
+      const path = await invoke<string | null>("create_memory_pack", {
+        ids: [...selected],
+        metadata: {
+          title: pack.title.trim(),
+          description: pack.description.trim() || null,
+          author: pack.author.trim() || null,
+          license: pack.license.trim() || null,
+        },
+      });
+      if (path) {
+        setPackStatus(`Saved pack of ${selected.size} memories to ${path}`);
+        setSelected(new Set());
+        setPack(null);
+      }
+    } catch (err) {
+      setPackStatus(`Failed to create pack: ${err}`);
+    }
+  }
+
   return (
     <div className="space-y-5 animate-fade-up">
       {/* Header */}
       <div className="flex items-center justify-between">
         <h2 className="text-xl font-semibold">Memories</h2>
-        <button
-          onClick={() => setShowCreateModal(true)}
-          className="inline-flex items-center gap-1.5 px-3 py-2 text-sm font-medium text-white rounded-[10px] bg-blue-600 hover:bg-blue-700 transition-colors"
-        >
-          <svg className="h-4 w-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
-            <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M12 4v16m8-8H4" />
-          </svg>
-          New Memory
-        </button>
+        <div className="flex items-center gap-2">
+          {selected.size > 0 && (
+            <button
+              onClick={() => setPack(pack ? null : EMPTY_PACK)}
+              className="px-3 py-2 text-sm font-medium rounded-[10px] border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors"
+            >
+              Share {selected.size} as Pack
+            </button>
+          )}
+          <button
+            onClick={() => setShowCreateModal(true)}
+            className="inline-flex items-center gap-1.5 px-3 py-2 text-sm font-medium text-white rounded-[10px] bg-blue-600 hover:bg-blue-700 transition-colors"
+          >
+            <svg className="h-4 w-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+              <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M12 4v16m8-8H4" />
+            </svg>
+            New Memory
+          </button>
+        </div>
       </div>
 
+      {/* Memory pack */}
+      {pack && (
+        <div className="glass rounded-[10px] p-4 border border-gray-200/50 dark:border-gray-700/50 space-y-2">
+          <p className="text-sm font-medium">Memory pack</p>
+          <p className="text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+            A signed file with the {selected.size} selected memories that teammates can preview
+            and import.
+          </p>
+          {(["title", "description", "author", "license"] as const).map((field) => (
+            <input
+              key={field}
+              type="text"
+              value={pack[field]}
+              onChange={(e) => setPack({ ...pack, [field]: e.target.value })}
+              placeholder={
+                {
+                  title: "Title, e.g. Backend onboarding",
+                  description: "Description (optional)",
+                  author: "Author (optional)",
+                  license: "License, e.g. Internal or CC-BY-4.0 (optional)",
+                }[field]
+              }
+              className="w-full rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-blue-500 focus:border-transparent"
+              style={{ color: "rgba(var(--text-primary), 1)" }}
+            />
+          ))}
+          <button
+            onClick={handleCreatePack}
+            disabled={!pack.title.trim()}
+            className="px-3 py-2 text-sm font-medium text-white rounded-[10px] bg-blue-600 hover:bg-blue-700 disabled:opacity-50 transition-colors"
+          >
+            Save Pack
+          </button>
+        </div>
+      )}
+      {packStatus && (
+        <p className="text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+          {packStatus}
+        </p>
+      )}
+
       {/* Filters */}
       <div className="glass rounded-[10px] p-4 border border-gray-200/50 dark:border-gray-700/50 space-y-3">
         {/* Category tabs */}
@@ -159,6 +255,13 @@ export default function Memories() {
               className="glass rounded-[10px] p-4 border border-gray-200/50 dark:border-gray-700/50 group"
             >
               <div className="flex items-start justify-between gap-3">
+                <input
+                  type="checkbox"
+                  checked={selected.has(memory.id)}
+                  onChange={() => toggleSelected(memory.id)}
+                  className="mt-1 flex-shrink-0"
+                  title="Select for a memory pack"
+                />
                 <div className="flex-1 min-w-0">
                   <div className="flex items-center gap-2 mb-1.5">
                     <span
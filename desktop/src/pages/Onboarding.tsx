@@ -9,6 +9,8 @@ interface DetectedAgent {
   configPath: string;
   connected: boolean;
   available: boolean;
+  /** Missing from the placeholder list shown outside the desktop app. */
+  icon?: string;
 }
 
 const STEPS = ["welcome", "agents", "seed", "complete"] as const;
@@ -173,6 +175,9 @@ export default function Onboarding() {
                           onChange={() => toggleAgent(agent.id)}
                           className="h-4 w-4 text-indigo-600 rounded border-gray-300 focus:ring-indigo-500"
                         />
+                        {agent.icon && (
+                          <img src={agent.icon} alt="" className="ml-3 h-8 w-8 rounded-lg" />
+                        )}
                         <div className="ml-3 flex-1">
                           <div className="flex items-center gap-2">
                             <span className="font-medium">{agent.name}</span>
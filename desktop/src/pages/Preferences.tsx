@@ -1,7 +1,9 @@
 import React, { useState, useEffect, useRef } from "react";
 import { useNavigate } from "react-router-dom";
 import { invoke } from "@tauri-apps/api/core";
-import { getApiBase } from "../lib/api";
+import { listen } from "@tauri-apps/api/event";
+import { exit } from "@tauri-apps/plugin-process";
+import { setApiPort } from "../lib/api";
 
 type Tab = "general" | "agents" | "shortcuts" | "storage" | "advanced";
 
@@ -11,6 +13,10 @@ interface DetectedAgent {
   configPath: string;
   connected: boolean;
   available: boolean;
+  installed: boolean;
+  configDirExists: boolean;
+  orphaned: boolean;
+  icon: string;
 }
 
 interface Prefs {
@@ -19,6 +25,282 @@ interface Prefs {
   restPort: string;
   enableRestApi: boolean;
   logLevel: string;
+  developer?: DeveloperSettings;
+  tagRules?: TagRule[];
+  normalizeIngest?: boolean;
+  runAsService?: boolean;
+  notion?: NotionSyncSettings;
+  issueTracker?: IssueTrackerSettings;
+  github?: GitHubSettings;
+  captureEndpoint?: CaptureEndpointSettings;
+  calendarContext?: boolean;
+  captureFrontmostApp?: boolean;
+  lowDiskThresholdMb?: number;
+  resurfacing?: ResurfacingSettings;
+  developerMode?: boolean;
+  notifications?: { quietHours?: QuietHours; [event: string]: unknown };
+}
+
+interface StartupTimings {
+  phases: { name: string; startedMs: number; durationMs: number }[];
+  setupMs: number | null;
+  sidecarReadyMs: number | null;
+}
+
+interface Diagnosis {
+  agentId: string;
+  agentName: string;
+  checks: {
+    id: string;
+    label: string;
+    outcome: "passed" | "failed" | "warning" | "skipped";
+    detail: string;
+  }[];
+  causes: {
+    priority: number;
+    title: string;
+    detail: string;
+    fix: "connect-agent" | "restart-sidecar" | null;
+    fixLabel: string | null;
+  }[];
+}
+
+const CHECK_ICONS: Record<string, string> = {
+  passed: "✓",
+  failed: "✗",
+  warning: "!",
+  skipped: "–",
+};
+
+interface LicenseState {
+  status: "none" | "valid" | "expired" | "invalid";
+  edition: "community" | "pro" | "team";
+  licensee: string | null;
+  expiresAt: string | null;
+  features: string[];
+  message: string | null;
+}
+
+interface SidecarReleaseInfo {
+  currentVersion: string;
+  latestVersion: string;
+  newer: boolean;
+  changelog: {
+    version: string;
+    publishedAt: string | null;
+    highlights: string[];
+    more: number;
+  }[];
+  update: {
+    available: boolean;
+    latestVersion: string;
+    requiresAppVersion: string | null;
+  } | null;
+}
+
+interface PackPreview {
+  metadata: {
+    title: string;
+    description: string | null;
+    author: string | null;
+    license: string | null;
+  };
+  createdAt: string;
+  signer: string;
+  signedByYou: boolean;
+  memories: { originalId: string; content: string; category: string | null }[];
+}
+
+interface FactoryResetReport {
+  removed: string[];
+  failed: string[];
+}
+
+/** Must match FACTORY_RESET_CONFIRMATION in commands.rs. */
+const FACTORY_RESET_CONFIRMATION = "DELETE ENGRAM";
+
+interface QuietHours {
+  enabled: boolean;
+  ranges: { start: string; end: string }[];
+  weekends: boolean;
+}
+
+const DEFAULT_QUIET_HOURS: QuietHours = {
+  enabled: false,
+  ranges: [{ start: "22:00", end: "08:00" }],
+  weekends: false,
+};
+
+interface ResurfacingSettings {
+  enabled: boolean;
+  intervalHours: number;
+  anniversaryDays: number[];
+  includeNeverRecalled: boolean;
+  quietStartHour: number;
+  quietEndHour: number;
+}
+
+const DEFAULT_RESURFACING: ResurfacingSettings = {
+  enabled: false,
+  intervalHours: 24,
+  anniversaryDays: [7, 30, 365],
+  includeNeverRecalled: true,
+  quietStartHour: 20,
+  quietEndHour: 9,
+};
+
+interface GitHubSettings {
+  enabled: boolean;
+  intervalMinutes: number;
+  repos: string[];
+  captureReviews: boolean;
+}
+
+interface CaptureEndpointSettings {
+  enabled: boolean;
+  port: number;
+}
+
+interface CaptureEndpointInfo {
+  enabled: boolean;
+  url: string;
+  token: string | null;
+}
+
+const DEFAULT_CAPTURE_ENDPOINT: CaptureEndpointSettings = { enabled: false, port: 3839 };
+
+const DEFAULT_GITHUB: GitHubSettings = {
+  enabled: false,
+  intervalMinutes: 60,
+  repos: [],
+  captureReviews: true,
+};
+
+interface IssueTrackerSettings {
+  enabled: boolean;
+  provider: "linear" | "jira";
+  jiraUrl: string;
+  jiraEmail: string;
+  projectKeys: string[];
+  intervalMinutes: number;
+}
+
+const DEFAULT_ISSUE_TRACKER: IssueTrackerSettings = {
+  enabled: false,
+  provider: "linear",
+  jiraUrl: "",
+  jiraEmail: "",
+  projectKeys: [],
+  intervalMinutes: 60,
+};
+
+interface NotionSyncSettings {
+  enabled: boolean;
+  databaseId: string;
+  intervalMinutes: number;
+  pushPinned: boolean;
+}
+
+interface ImportSummary {
+  found: number;
+  created: number;
+  duplicates: number;
+}
+
+interface NotionSyncReport {
+  pulled: { found: number; created: number };
+  pushed: number;
+}
+
+const DEFAULT_NOTION: NotionSyncSettings = {
+  enabled: false,
+  databaseId: "",
+  intervalMinutes: 30,
+  pushPinned: false,
+};
+
+interface TagRule {
+  name: string;
+  enabled: boolean;
+  contentPattern: string;
+  source: string;
+  pathContains: string;
+  tags: string[];
+}
+
+interface TagRulesReport {
+  scanned: number;
+  updated: number;
+  failed: number;
+}
+
+interface TagCount {
+  name: string;
+  count: number;
+}
+
+interface TagEditReport {
+  updated: number;
+}
+
+const EMPTY_TAG_RULE: TagRule = {
+  name: "",
+  enabled: true,
+  contentPattern: "",
+  source: "",
+  pathContains: "",
+  tags: [],
+};
+
+interface DeveloperSettings {
+  enabled: boolean;
+  nodePath: string;
+  entryScript: string;
+}
+
+interface SidecarResolution {
+  source: string;
+  node: string;
+  script: string;
+  reason: string;
+  skipped: string[];
+}
+
+interface Preset {
+  name: string;
+}
+
+interface PresetImport {
+  preset: Preset;
+  conflicts: string[];
+  warnings: string[];
+}
+
+interface DiskUsage {
+  currentBytes: number;
+  bytesPerDay: number | null;
+  projection: { targetBytes: number; days: number } | null;
+}
+
+interface Backup {
+  fileName: string;
+  name: string;
+  path: string;
+  sizeBytes: number;
+  createdAt: string;
+}
+
+function formatBytes(bytes: number): string {
+  if (bytes >= 1 << 30) return `${(bytes / (1 << 30)).toFixed(1).replace(/\.0$/, "")} GB`;
+  if (bytes >= 1 << 20) return `${Math.round(bytes / (1 << 20))} MB`;
+  return `${Math.round(bytes / 1024)} KB`;
+}
+
+function formatDuration(days: number): string {
+  if (days < 14) return `${Math.max(1, Math.round(days))} days`;
+  if (days < 60) return `${Math.round(days / 7)} weeks`;
+  if (days < 730) return `${Math.round(days / 30)} months`;
+  return `${Math.round(days / 365)} years`;
 }
 
 const TABS: { id: Tab; label: string }[] = [
@@ -49,13 +331,76 @@ export default function Preferences() {
   const [saving, setSaving] = useState(false);
   const [exportPath, setExportPath] = useState<string | null>(null);
   const [resetConfirm, setResetConfirm] = useState(false);
+  const [resetToken, setResetToken] = useState<string | null>(null);
+  const [exportFormat, setExportFormat] = useState<"json" | "jsonl" | "csv" | "markdown" | "sqlite" | "parquet">("json");
+  const [exportTags, setExportTags] = useState("");
+  const [exportSince, setExportSince] = useState("");
+  const [exportUntil, setExportUntil] = useState("");
+  const [exportQuery, setExportQuery] = useState("");
+  const [exportPassword, setExportPassword] = useState("");
+  const [exportEmbeddings, setExportEmbeddings] = useState(false);
+  const [importPath, setImportPath] = useState<string | null>(null);
+  const [mergePath, setMergePath] = useState("");
+  const [mergeStatus, setMergeStatus] = useState<string | null>(null);
+  const [packPath, setPackPath] = useState("");
+  const [packPreview, setPackPreview] = useState<PackPreview | null>(null);
+  const [packStatus, setPackStatus] = useState<string | null>(null);
+  const [importMode, setImportMode] = useState<"merge" | "replace">("merge");
+  const [importConfirm, setImportConfirm] = useState(false);
   const [actionStatus, setActionStatus] = useState<string | null>(null);
+  const [calendarStatus, setCalendarStatus] = useState<string | null>(null);
+  const [explorerError, setExplorerError] = useState<string | null>(null);
+  const [connectionStatus, setConnectionStatus] = useState<string | null>(null);
+  const [prefsImportPath, setPrefsImportPath] = useState("");
+  const [prefsTransferStatus, setPrefsTransferStatus] = useState<string | null>(null);
+  const [license, setLicense] = useState<LicenseState | null>(null);
+  const [licenseKey, setLicenseKey] = useState("");
+  const [licenseError, setLicenseError] = useState<string | null>(null);
+  const [startupTimings, setStartupTimings] = useState<StartupTimings | null>(null);
+  const [sidecarRelease, setSidecarRelease] = useState<SidecarReleaseInfo | null>(null);
+  const [sidecarReleaseStatus, setSidecarReleaseStatus] = useState<string | null>(null);
+  const [checkingSidecar, setCheckingSidecar] = useState(false);
+  const [factoryResetText, setFactoryResetText] = useState("");
+  const [factoryResetReport, setFactoryResetReport] = useState<FactoryResetReport | null>(null);
+  const [factoryResetError, setFactoryResetError] = useState<string | null>(null);
   const [agents, setAgents] = useState<DetectedAgent[]>([]);
   const [agentsLoading, setAgentsLoading] = useState(false);
   const [connectingAgent, setConnectingAgent] = useState<string | null>(null);
+  const [diagnoseAgent, setDiagnoseAgent] = useState("claude-desktop");
+  const [diagnosis, setDiagnosis] = useState<Diagnosis | null>(null);
+  const [diagnosing, setDiagnosing] = useState(false);
+  const [diagnoseError, setDiagnoseError] = useState<string | null>(null);
+  const [diskUsage, setDiskUsage] = useState<DiskUsage | null>(null);
+  const [vacuumStatus, setVacuumStatus] = useState<string | null>(null);
+  const [backups, setBackups] = useState<Backup[]>([]);
+  const [backupName, setBackupName] = useState("");
+  const [restoreConfirm, setRestoreConfirm] = useState<string | null>(null);
+  const [backupStatus, setBackupStatus] = useState<string | null>(null);
+  const [resolution, setResolution] = useState<SidecarResolution | null>(null);
+  const [presets, setPresets] = useState<Preset[]>([]);
+  const [presetName, setPresetName] = useState("");
+  const [presetStatus, setPresetStatus] = useState<string | null>(null);
+  const presetInputRef = useRef<HTMLInputElement>(null);
+  const [newRule, setNewRule] = useState<TagRule>(EMPTY_TAG_RULE);
+  const [newRuleTags, setNewRuleTags] = useState("");
+  const [tagRuleScope, setTagRuleScope] = useState("all");
+  const [tagRuleStatus, setTagRuleStatus] = useState<string | null>(null);
+  const [tags, setTags] = useState<TagCount[] | null>(null);
+  const [renaming, setRenaming] = useState<{ from: string; to: string } | null>(null);
+  const [mergeSources, setMergeSources] = useState("");
+  const [mergeTarget, setMergeTarget] = useState("");
+  const [tagStatus, setTagStatus] = useState<string | null>(null);
+  const [confirmRemoveTag, setConfirmRemoveTag] = useState<string | null>(null);
+  const [notionDraft, setNotionDraft] = useState<NotionSyncSettings | null>(null);
+  const [notionToken, setNotionToken] = useState("");
+  const [trackerDraft, setTrackerDraft] = useState<IssueTrackerSettings | null>(null);
+  const [trackerToken, setTrackerToken] = useState("");
+  const [githubDraft, setGithubDraft] = useState<GitHubSettings | null>(null);
+  const [captureDraft, setCaptureDraft] = useState<CaptureEndpointSettings | null>(null);
+  const [captureInfo, setCaptureInfo] = useState<CaptureEndpointInfo | null>(null);
+  const [githubToken, setGithubToken] = useState("");
   const navigate = useNavigate();
   const saveTimer = useRef<ReturnType<typeof setTimeout> | null>(null);
-  const fileInputRef = useRef<HTMLInputElement>(null);
 
   useEffect(() => {
     loadPreferences();
@@ -67,12 +412,53 @@ export default function Preferences() {
     }
   }, []);
 
+  useEffect(() => {
+    // The tray's "Check for Sidecar Update" lands here, possibly while open
+    function checkFromHash() {
+      if (/[?&]check=update/.test(window.location.hash)) {
+        setActiveTab("advanced");
+        handleCheckSidecarRelease();
+      }
+    }
+    checkFromHash();
+    window.addEventListener("hashchange", checkFromHash);
+    return () => window.removeEventListener("hashchange", checkFromHash);
+  }, []);
+
   useEffect(() => {
     if (activeTab === "agents") {
       loadAgents();
     }
+    if (activeTab === "advanced") {
+      invoke<SidecarResolution>("get_sidecar_resolution")
+        .then(setResolution)
+        .catch(() => setResolution(null));
+      invoke<LicenseState>("get_license_state")
+        .then(setLicense)
+        .catch(() => setLicense(null));
+      invoke<StartupTimings>("get_startup_timings")
+        .then(setStartupTimings)
+        .catch(() => setStartupTimings(null));
+    }
+    if (activeTab === "shortcuts") {
+      loadPresets();
+    }
+    if (activeTab === "storage") {
+      invoke<DiskUsage>("get_disk_usage")
+        .then(setDiskUsage)
+        .catch(() => setDiskUsage(null));
+      loadBackups();
+    }
   }, [activeTab]);
 
+  async function loadBackups() {
+    try {
+      setBackups(await invoke<Backup[]>("list_backups"));
+    } catch {
+      setBackups([]);
+    }
+  }
+
   async function loadPreferences() {
     try {
       const loaded = await invoke<Prefs>("get_preferences");
@@ -101,6 +487,7 @@ export default function Preferences() {
     setSaving(true);
     try {
       await invoke("save_preferences", { prefs: prefsToSave });
+      setApiPort(parseInt(prefsToSave.restPort, 10));
     } catch {
       // Silently fail if Tauri not available
     } finally {
@@ -108,6 +495,63 @@ export default function Preferences() {
     }
   }
 
+  async function handleOpenApiExplorer() {
+    // Save a just-flipped toggle now rather than after the debounce
+    if (saveTimer.current) clearTimeout(saveTimer.current);
+    await persistPreferences(prefs);
+    try {
+      await invoke("open_api_explorer");
+      setExplorerError(null);
+    } catch (err) {
+      setExplorerError(`Failed to open API explorer: ${err}`);
+    }
+  }
+
+  async function handleActivateLicense() {
+    setLicenseError(null);
+    try {
+      setLicense(await invoke<LicenseState>("activate_license", { key: licenseKey }));
+      setLicenseKey("");
+    } catch (err) {
+      setLicenseError(String(err));
+    }
+  }
+
+  async function handleCheckSidecarRelease() {
+    setCheckingSidecar(true);
+    setSidecarReleaseStatus(null);
+    try {
+      setSidecarRelease(await invoke<SidecarReleaseInfo>("check_sidecar_release"));
+    } catch (err) {
+      setSidecarReleaseStatus(String(err));
+    } finally {
+      setCheckingSidecar(false);
+    }
+  }
+
+  async function handleApplySidecarUpdate() {
+    setSidecarReleaseStatus("Updating sidecar...");
+    try {
+      const version = await invoke<string>("apply_sidecar_update");
+      setSidecarReleaseStatus(`Now running sidecar ${version}`);
+      setSidecarRelease(null);
+    } catch (err) {
+      setSidecarReleaseStatus(String(err));
+    }
+  }
+
+  async function handleFactoryReset() {
+    setFactoryResetError(null);
+    try {
+      setFactoryResetReport(
+        await invoke<FactoryResetReport>("factory_reset", { confirmation: factoryResetText })
+      );
+      setFactoryResetText("");
+    } catch (err) {
+      setFactoryResetError(String(err));
+    }
+  }
+
   async function handleStartAtLogin(enabled: boolean) {
     updatePref("startAtLogin", enabled);
     try {
@@ -118,6 +562,28 @@ export default function Preferences() {
     }
   }
 
+  async function handleCalendarContext(enabled: boolean) {
+    setCalendarStatus(null);
+    if (!enabled) {
+      updatePref("calendarContext", false);
+      return;
+    }
+    try {
+      const access = await invoke<string>("request_calendar_access");
+      if (access === "granted") {
+        updatePref("calendarContext", true);
+      } else if (access === "unavailable") {
+        setCalendarStatus("Calendar context is only available on macOS");
+      } else {
+        setCalendarStatus(
+          "Engram doesn't have calendar access. Allow it in System Settings > Privacy & Security > Calendars."
+        );
+      }
+    } catch (err) {
+      setCalendarStatus(`Failed to request calendar access: ${err}`);
+    }
+  }
+
   async function loadAgents() {
     setAgentsLoading(true);
     try {
@@ -145,162 +611,885 @@ export default function Preferences() {
     }
   }
 
-  async function handleExport() {
-    setActionStatus("Exporting...");
+  async function handleDiagnose() {
+    setDiagnosing(true);
+    setDiagnoseError(null);
+    setDiagnosis(null);
     try {
-      const path = await invoke<string>("export_data");
-      setExportPath(path);
-      setActionStatus(`Exported to ${path}`);
+      setDiagnosis(await invoke<Diagnosis>("diagnose_agent", { agentId: diagnoseAgent }));
     } catch (err) {
-      setActionStatus(`Export failed: ${err}`);
+      setDiagnoseError(String(err));
+    } finally {
+      setDiagnosing(false);
     }
   }
 
-  async function handleImport(e: React.ChangeEvent<HTMLInputElement>) {
-    const file = e.target.files?.[0];
-    if (!file) return;
-
-    setActionStatus("Importing...");
+  async function handleDiagnosisFix(fix: "connect-agent" | "restart-sidecar") {
     try {
-      const text = await file.text();
-      const data = JSON.parse(text);
-      const memories: Array<{ content: string; category?: string; entity?: string; confidence?: number }> =
-        data.memories || data;
-
-      let imported = 0;
-      for (const mem of memories) {
-        try {
-          await fetch(`${getApiBase()}/memories`, {
-            method: "POST",
-            headers: { "Content-Type": "application/json" },
-            body: JSON.stringify({
-              content: mem.content,
-              category: mem.category || "fact",
-              entity: mem.entity || null,
-              confidence: mem.confidence || 0.8,
-            }),
-          });
-          imported++;
-        } catch {
-          // Skip individual failures
-        }
+      if (fix === "connect-agent") {
+        await invoke("configure_agent", { agentName: diagnoseAgent });
+        await loadAgents();
+      } else {
+        await invoke("restart_sidecar");
       }
-      setActionStatus(`Imported ${imported} memories`);
-    } catch {
-      setActionStatus("Import failed: invalid JSON file");
+      await handleDiagnose();
+    } catch (err) {
+      setDiagnoseError(String(err));
     }
+  }
 
-    // Reset file input
-    if (fileInputRef.current) fileInputRef.current.value = "";
+  async function handleRemoveOrphans() {
+    try {
+      const results = await invoke<string[]>("remove_orphaned_agent_entries");
+      setActionStatus(results.join("\n"));
+      await loadAgents();
+    } catch (err) {
+      setActionStatus(`Cleanup failed: ${err}`);
+    }
   }
 
-  async function handleReset() {
-    if (!resetConfirm) {
-      setResetConfirm(true);
-      return;
+  async function handleShellHook(shell: string, install: boolean) {
+    try {
+      const result = await invoke<string>(
+        install ? "install_shell_hook" : "uninstall_shell_hook",
+        { shell }
+      );
+      setActionStatus(result);
+    } catch (err) {
+      setActionStatus(`Failed to update the ${shell} hook: ${err}`);
     }
+  }
 
-    setActionStatus("Resetting database...");
-    setResetConfirm(false);
+  async function handleSaveNotion(settings: NotionSyncSettings) {
     try {
-      await invoke("reset_database");
-      setActionStatus("Database reset successfully. Engram has been restarted.");
+      const title = await invoke<string | null>("configure_notion_sync", {
+        settings,
+        token: notionToken || null,
+      });
+      setPrefs((prev) => ({ ...prev, notion: settings }));
+      setNotionDraft(null);
+      setNotionToken("");
+      setActionStatus(
+        settings.enabled ? `Syncing with Notion database "${title}"` : "Notion sync disabled"
+      );
     } catch (err) {
-      setActionStatus(`Reset failed: ${err}`);
+      setActionStatus(`Failed to configure Notion sync: ${err}`);
     }
   }
 
-  return (
-    <div className="min-h-screen flex">
-      {/* Sidebar */}
-      <div className="w-48 border-r border-gray-200 dark:border-gray-700 p-4 space-y-1">
-        <button
-          onClick={() => navigate("/")}
-          className="flex items-center gap-2 w-full px-3 py-2 text-sm rounded-lg hover:bg-gray-100 dark:hover:bg-gray-800 mb-4 transition-colors"
-          style={{ color: "rgba(var(--text-secondary), 1)" }}
-        >
-          <svg className="h-4 w-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
-            <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M15 19l-7-7 7-7" />
-          </svg>
-          Back
-        </button>
+  async function handleNotionSyncNow() {
+    try {
+      const report = await invoke<NotionSyncReport>("run_notion_sync");
+      setActionStatus(
+        `Pulled ${report.pulled.created} of ${report.pulled.found} new pages, pushed ${report.pushed} pinned memories`
+      );
+    } catch (err) {
+      setActionStatus(`Notion sync failed: ${err}`);
+    }
+  }
 
-        <p
-          className="text-xs font-medium uppercase tracking-wider px-3 mb-2"
-          style={{ color: "rgba(var(--text-secondary), 1)" }}
-        >
-          Preferences
-        </p>
+  async function handleSaveIssueTracker(settings: IssueTrackerSettings) {
+    try {
+      const account = await invoke<string | null>("configure_issue_tracker", {
+        settings,
+        token: trackerToken || null,
+      });
+      setPrefs((prev) => ({ ...prev, issueTracker: settings }));
+      setTrackerDraft(null);
+      setTrackerToken("");
+      setActionStatus(
+        settings.enabled ? `Looking up issues as ${account}` : "Issue lookups disabled"
+      );
+    } catch (err) {
+      setActionStatus(`Failed to configure the issue tracker: ${err}`);
+    }
+  }
 
-        {TABS.map((tab) => (
-          <button
-            key={tab.id}
-            onClick={() => setActiveTab(tab.id)}
-            className={`w-full text-left px-3 py-2 text-sm rounded-lg transition-colors ${
-              activeTab === tab.id
-                ? "bg-indigo-50 text-indigo-700 dark:bg-indigo-900/50 dark:text-indigo-300"
-                : "hover:bg-gray-100 dark:hover:bg-gray-800"
-            }`}
-          >
-            {tab.label}
-          </button>
-        ))}
+  async function handleIssueEnrichmentNow() {
+    try {
+      const report = await invoke<{ memories: number; issues: number; missing: number }>(
+        "run_issue_enrichment"
+      );
+      setActionStatus(
+        `Updated ${report.issues} issues mentioned in ${report.memories} memories` +
+          (report.missing > 0 ? ` (${report.missing} keys not found)` : "")
+      );
+    } catch (err) {
+      setActionStatus(`Issue lookup failed: ${err}`);
+    }
+  }
 
-        {saving && (
-          <p className="px-3 pt-4 text-xs text-indigo-500">Saving...</p>
-        )}
-      </div>
+  async function handleSaveGitHub(settings: GitHubSettings) {
+    try {
+      const login = await invoke<string | null>("configure_github_sync", {
+        settings,
+        token: githubToken || null,
+      });
+      setPrefs((prev) => ({ ...prev, github: settings }));
+      setGithubDraft(null);
+      setGithubToken("");
+      setActionStatus(settings.enabled ? `Capturing GitHub activity for ${login}` : "GitHub capture disabled");
+    } catch (err) {
+      setActionStatus(`Failed to configure GitHub: ${err}`);
+    }
+  }
 
-      {/* Content */}
-      <div className="flex-1 p-8 max-w-2xl">
-        {activeTab === "general" && (
-          <div className="space-y-6">
-            <h2 className="text-xl font-bold">General</h2>
+  async function handleSaveCaptureEndpoint(settings: CaptureEndpointSettings, rotateToken = false) {
+    try {
+      const info = await invoke<CaptureEndpointInfo>("configure_capture_endpoint", {
+        settings,
+        rotateToken,
+      });
+      setPrefs((prev) => ({ ...prev, captureEndpoint: settings }));
+      setCaptureDraft(null);
+      setCaptureInfo(info);
+      setActionStatus(settings.enabled ? `Accepting captures at ${info.url}` : "Capture endpoint off");
+    } catch (err) {
+      setActionStatus(`Failed to configure capture endpoint: ${err}`);
+    }
+  }
 
-            <div className="space-y-4">
-              <label className="flex items-center justify-between p-4 rounded-lg border border-gray-200 dark:border-gray-700">
-                <div>
-                  <p className="text-sm font-medium">Start at login</p>
-                  <p
-                    className="text-xs mt-0.5"
-                    style={{ color: "rgba(var(--text-secondary), 1)" }}
-                  >
-                    Launch Engram automatically when you log in
-                  </p>
-                </div>
-                <button
-                  role="switch"
-                  aria-checked={prefs.startAtLogin}
-                  onClick={() => handleStartAtLogin(!prefs.startAtLogin)}
-                  className={`relative inline-flex h-6 w-11 items-center rounded-full transition-colors ${
-                    prefs.startAtLogin ? "bg-indigo-600" : "bg-gray-300 dark:bg-gray-600"
-                  }`}
-                >
-                  <span
-                    className={`inline-block h-4 w-4 transform rounded-full bg-white transition-transform ${
-                      prefs.startAtLogin ? "translate-x-6" : "translate-x-1"
-                    }`}
-                  />
-                </button>
-              </label>
+  async function handleGitHubSyncNow() {
+    try {
+      const summary = await invoke<{ found: number; created: number }>("run_github_sync");
+      setActionStatus(`Captured ${summary.created} of ${summary.found} new GitHub items`);
+    } catch (err) {
+      setActionStatus(`GitHub sync failed: ${err}`);
+    }
+  }
 
-              <label className="flex items-center justify-between p-4 rounded-lg border border-gray-200 dark:border-gray-700">
-                <div>
-                  <p className="text-sm font-medium">Sound on save</p>
-                  <p
-                    className="text-xs mt-0.5"
-                    style={{ color: "rgba(var(--text-secondary), 1)" }}
-                  >
-                    Play a subtle sound when a memory is saved
-                  </p>
-                </div>
-                <button
-                  role="switch"
-                  aria-checked={prefs.soundOnSave}
-                  onClick={() => updatePref("soundOnSave", !prefs.soundOnSave)}
-                  className={`relative inline-flex h-6 w-11 items-center rounded-full transition-colors ${
-                    prefs.soundOnSave ? "bg-indigo-600" : "bg-gray-300 dark:bg-gray-600"
-                  }`}
+  async function loadPresets() {
+    try {
+      setPresets(await invoke<Preset[]>("list_presets"));
+    } catch {
+      setPresets([]);
+    }
+  }
+
+  async function handleSavePreset() {
+    if (!presetName.trim()) return;
+    try {
+      await invoke("save_preset", { name: presetName });
+      setPresetStatus(`Saved preset "${presetName.trim()}"`);
+      setPresetName("");
+      await loadPresets();
+    } catch (err) {
+      setPresetStatus(`Failed to save preset: ${err}`);
+    }
+  }
+
+  async function handlePresetAction(command: string, name: string) {
+    try {
+      const result = await invoke<string | null>(command, { name });
+      if (command === "apply_preset") setPresetStatus(`Applied preset "${name}"`);
+      if (command === "export_preset") setPresetStatus(`Exported to ${result}`);
+      if (command === "delete_preset") setPresetStatus(`Deleted preset "${name}"`);
+      await loadPresets();
+    } catch (err) {
+      setPresetStatus(`${err}`);
+    }
+  }
+
+  async function handleImportPreset(e: React.ChangeEvent<HTMLInputElement>) {
+    const file = e.target.files?.[0];
+    if (!file) return;
+    try {
+      const report = await invoke<PresetImport>("import_preset", { contents: await file.text() });
+      const notes = [...report.warnings];
+      if (report.conflicts.length > 0) {
+        notes.push(`Resolve before applying: ${report.conflicts.join("; ")}`);
+      }
+      setPresetStatus(
+        `Imported preset "${report.preset.name}"` + (notes.length ? `. ${notes.join(". ")}` : "")
+      );
+      await loadPresets();
+    } catch (err) {
+      setPresetStatus(`Import failed: ${err}`);
+    }
+    if (presetInputRef.current) presetInputRef.current.value = "";
+  }
+
+  async function saveTagRules(tagRules: TagRule[]) {
+    const next = { ...prefs, tagRules };
+    await invoke("save_preferences", { prefs: next });
+    setPrefs(next);
+  }
+
+  async function handleAddTagRule() {
+    const rule = {
+      ...newRule,
+      name: newRule.name.trim() || newRuleTags.trim(),
+      tags: newRuleTags.split(",").map((t) => t.trim()).filter(Boolean),
+    };
+    try {
+      await saveTagRules([...(prefs.tagRules ?? []), rule]);
+      setNewRule(EMPTY_TAG_RULE);
+      setNewRuleTags("");
+      setTagRuleStatus(null);
+    } catch (err) {
+      setTagRuleStatus(`${err}`);
+    }
+  }
+
+  async function handleRemoveTagRule(index: number) {
+    try {
+      await saveTagRules((prefs.tagRules ?? []).filter((_, i) => i !== index));
+    } catch (err) {
+      setTagRuleStatus(`${err}`);
+    }
+  }
+
+  async function handleApplyTagRules() {
+    setTagRuleStatus("Applying rules...");
+    try {
+      const report = await invoke<TagRulesReport>("apply_tag_rules", { scope: tagRuleScope });
+      setTagRuleStatus(
+        `Tagged ${report.updated} of ${report.scanned} memories` +
+          (report.failed ? ` (${report.failed} failed)` : "")
+      );
+    } catch (err) {
+      setTagRuleStatus(`Failed to apply rules: ${err}`);
+    }
+  }
+
+  async function loadTags() {
+    try {
+      setTags(await invoke<TagCount[]>("list_tags"));
+    } catch (err) {
+      setTagStatus(`Failed to load tags: ${err}`);
+    }
+  }
+
+  async function runTagEdit(label: string, command: string, args: Record<string, unknown>) {
+    setTagStatus(`${label}...`);
+    try {
+      const report = await invoke<TagEditReport>(command, args);
+      setTagStatus(`Updated ${report.updated} memories`);
+      await loadTags();
+    } catch (err) {
+      setTagStatus(`${label} failed: ${err}`);
+    }
+  }
+
+  async function handleRenameTag() {
+    if (!renaming || !renaming.to.trim()) return;
+    await runTagEdit("Renaming", "rename_tag", { from: renaming.from, to: renaming.to.trim() });
+    setRenaming(null);
+  }
+
+  async function handleDeleteTag(tag: string) {
+    if (confirmRemoveTag !== tag) {
+      setConfirmRemoveTag(tag);
+      return;
+    }
+    setConfirmRemoveTag(null);
+    await runTagEdit("Removing", "delete_tag", { tag });
+  }
+
+  async function handleMergeTags() {
+    const sources = mergeSources.split(",").map((t) => t.trim()).filter(Boolean);
+    await runTagEdit("Merging", "merge_tags", { sources, target: mergeTarget.trim() });
+    setMergeSources("");
+    setMergeTarget("");
+  }
+
+  function exportFilter() {
+    // Dates are local days; `until` includes the whole day
+    const dayStart = (date: string) => new Date(`${date}T00:00:00`).getTime();
+    return {
+      tags: exportTags.split(",").map((t) => t.trim()).filter(Boolean),
+      since: exportSince ? dayStart(exportSince) : null,
+      until: exportUntil ? dayStart(exportUntil) + 24 * 60 * 60 * 1000 : null,
+      query: exportQuery.trim() || null,
+    };
+  }
+
+  async function handleExport() {
+    setActionStatus("Exporting...");
+    const unlisten = await listen<{ exported: number; total: number }>(
+      "export-progress",
+      (event) => {
+        setActionStatus(`Exporting ${event.payload.exported} of ${event.payload.total} memories...`);
+      }
+    );
+    try {
+      const path =
+        exportFormat === "sqlite"
+          ? await invoke<string | null>("export_sqlite", { includeEmbeddings: exportEmbeddings })
+          : exportFormat === "parquet"
+          ? await invoke<string | null>("export_parquet", { filter: exportFilter() })
+          : await invoke<string | null>("export_data", {
+              format: exportFormat,
+              filter: exportFilter(),
+              encrypt: exportPassword || null,
+            });
+      if (path === null) {
+        setActionStatus(null);
+        return;
+      }
+      setExportPath(path);
+      setActionStatus(`Exported to ${path}`);
+    } catch (err) {
+      setActionStatus(`Export failed: ${err}`);
+    } finally {
+      unlisten();
+    }
+  }
+
+  async function handleObsidianExport() {
+    setActionStatus("Exporting to Obsidian...");
+    const unlisten = await listen<{ exported: number; total: number }>(
+      "export-progress",
+      (event) => {
+        setActionStatus(`Writing ${event.payload.exported} of ${event.payload.total} notes...`);
+      }
+    );
+    try {
+      const folder = await invoke<string | null>("export_obsidian_vault", {
+        filter: exportFilter(),
+      });
+      setActionStatus(folder === null ? null : `Notes written to ${folder}`);
+    } catch (err) {
+      setActionStatus(`Export failed: ${err}`);
+    } finally {
+      unlisten();
+    }
+  }
+
+  async function handleImport() {
+    if (!importPath?.trim()) return;
+    if (importMode === "replace" && !importConfirm) {
+      setImportConfirm(true);
+      return;
+    }
+
+    setImportConfirm(false);
+    setActionStatus("Importing...");
+    const unlisten = await listen<{ processed: number; total: number }>(
+      "import-progress",
+      (event) => {
+        setActionStatus(`Importing ${event.payload.processed} of ${event.payload.total} memories...`);
+      }
+    );
+    try {
+      const summary = await invoke<ImportSummary>("import_data", {
+        path: importPath.trim(),
+        mode: importMode,
+      });
+      setActionStatus(
+        `Imported ${summary.created} of ${summary.found} memories (${summary.duplicates} duplicates skipped)`
+      );
+      setImportPath(null);
+    } catch (err) {
+      setActionStatus(`Import failed: ${err}`);
+    } finally {
+      unlisten();
+    }
+  }
+
+  async function handleMerge() {
+    setMergeStatus("Merging...");
+    try {
+      const report = await invoke<{
+        found: number;
+        inserted: number;
+        skippedIds: number;
+        skippedDuplicates: number;
+      }>("merge_databases", { path: mergePath.trim() });
+      setMergeStatus(
+        `Added ${report.inserted} of ${report.found} memories (${
+          report.skippedIds + report.skippedDuplicates
+        } already here). Engram has been restarted.`
+      );
+      setMergePath("");
+      loadBackups();
+    } catch (err) {
+      setMergeStatus(`Merge failed: ${err}`);
+    }
+  }
+
+  async function handlePreviewPack() {
+    setPackStatus(null);
+    setPackPreview(null);
+    try {
+      setPackPreview(await invoke<PackPreview>("preview_memory_pack", { path: packPath.trim() }));
+    } catch (err) {
+      setPackStatus(`Can't open pack: ${err}`);
+    }
+  }
+
+  async function handleImportPack() {
+    setPackStatus("Importing pack...");
+    try {
+      const summary = await invoke<ImportSummary>("import_memory_pack", { path: packPath.trim() });
+      setPackStatus(
+        `Imported ${summary.created} of ${summary.found} memories (${summary.duplicates} duplicates skipped)`
+      );
+      setPackPreview(null);
+      setPackPath("");
+    } catch (err) {
+      setPackStatus(`Import failed: ${err}`);
+    }
+  }
+
+  async function handleVacuum() {
+    setVacuumStatus("Compacting database...");
+    try {
+      const report = await invoke<{ reclaimedBytes: number }>("vacuum_database");
+      setVacuumStatus(`Reclaimed ${formatBytes(report.reclaimedBytes)}. Engram has been restarted.`);
+      setDiskUsage(await invoke<DiskUsage>("get_disk_usage"));
+    } catch (err) {
+      setVacuumStatus(`Compaction failed: ${err}`);
+    }
+  }
+
+  async function handleExportPreferences() {
+    try {
+      const path = await invoke<string | null>("export_preferences");
+      if (path !== null) setPrefsTransferStatus(`Exported to ${path}`);
+    } catch (err) {
+      setPrefsTransferStatus(`Export failed: ${err}`);
+    }
+  }
+
+  async function handleImportPreferences() {
+    try {
+      const report = await invoke<{ warnings: string[] }>("import_preferences", {
+        path: prefsImportPath.trim(),
+      });
+      setPrefsTransferStatus(
+        "Preferences imported" + (report.warnings.length ? `. ${report.warnings.join(". ")}` : "")
+      );
+      setPrefsImportPath("");
+      await loadPreferences();
+    } catch (err) {
+      setPrefsTransferStatus(`Import failed: ${err}`);
+    }
+  }
+
+  async function handleCopyConnectionInfo() {
+    try {
+      const text = await invoke<string>("copy_connection_info");
+      setConnectionStatus(`Copied ${text}`);
+    } catch (err) {
+      setConnectionStatus(`${err}`);
+    }
+  }
+
+  async function handleOpenDataDir() {
+    try {
+      await invoke("open_data_dir");
+    } catch (err) {
+      setBackupStatus(`Couldn't open the data folder: ${err}`);
+    }
+  }
+
+  async function handleBackup() {
+    setBackupStatus("Creating snapshot...");
+    try {
+      const backup = await invoke<Backup>("backup_database", { name: backupName.trim() });
+      setBackupStatus(`Snapshot saved to ${backup.path}`);
+      setBackupName("");
+      loadBackups();
+    } catch (err) {
+      setBackupStatus(`Snapshot failed: ${err}`);
+    }
+  }
+
+  async function handleRestore(fileName: string) {
+    if (restoreConfirm !== fileName) {
+      setRestoreConfirm(fileName);
+      return;
+    }
+
+    setRestoreConfirm(null);
+    setBackupStatus("Restoring snapshot...");
+    try {
+      await invoke("restore_backup", { fileName });
+      setBackupStatus(`Restored ${fileName}. Engram has been restarted.`);
+      loadBackups();
+    } catch (err) {
+      setBackupStatus(`Restore failed: ${err}`);
+    }
+  }
+
+  async function handleReset() {
+    if (!resetConfirm) {
+      try {
+        setResetToken(await invoke<string>("request_reset"));
+        setResetConfirm(true);
+      } catch (err) {
+        setActionStatus(`Reset failed: ${err}`);
+      }
+      return;
+    }
+
+    setActionStatus("Resetting database...");
+    setResetConfirm(false);
+    try {
+      await invoke("reset_database", { token: resetToken });
+      setActionStatus(
+        "Database reset successfully. Engram has been restarted. The old database is kept as a pre-reset snapshot."
+      );
+      loadBackups();
+    } catch (err) {
+      setActionStatus(`Reset failed: ${err}`);
+    }
+  }
+
+  return (
+    <div className="min-h-screen flex">
+      {/* Sidebar */}
+      <div className="w-48 border-r border-gray-200 dark:border-gray-700 p-4 space-y-1">
+        <button
+          onClick={() => navigate("/")}
+          className="flex items-center gap-2 w-full px-3 py-2 text-sm rounded-lg hover:bg-gray-100 dark:hover:bg-gray-800 mb-4 transition-colors"
+          style={{ color: "rgba(var(--text-secondary), 1)" }}
+        >
+          <svg className="h-4 w-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+            <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M15 19l-7-7 7-7" />
+          </svg>
+          Back
+        </button>
+
+        <p
+          className="text-xs font-medium uppercase tracking-wider px-3 mb-2"
+          style={{ color: "rgba(var(--text-secondary), 1)" }}
+        >
+          Preferences
+        </p>
+
+        {TABS.map((tab) => (
+          <button
+            key={tab.id}
+            onClick={() => setActiveTab(tab.id)}
+            className={`w-full text-left px-3 py-2 text-sm rounded-lg transition-colors ${
+              activeTab === tab.id
+                ? "bg-indigo-50 text-indigo-700 dark:bg-indigo-900/50 dark:text-indigo-300"
+                : "hover:bg-gray-100 dark:hover:bg-gray-800"
+            }`}
+          >
+            {tab.label}
+          </button>
+        ))}
+
+        {saving && (
+          <p className="px-3 pt-4 text-xs text-indigo-500">Saving...</p>
+        )}
+      </div>
+
+      {/* Content */}
+      <div className="flex-1 p-8 max-w-2xl">
+        {activeTab === "general" && (
+          <div className="space-y-6">
+            <h2 className="text-xl font-bold">General</h2>
+
+            <div className="space-y-4">
+              <label className="flex items-center justify-between p-4 rounded-lg border border-gray-200 dark:border-gray-700">
+                <div>
+                  <p className="text-sm font-medium">Start at login</p>
+                  <p
+                    className="text-xs mt-0.5"
+                    style={{ color: "rgba(var(--text-secondary), 1)" }}
+                  >
+                    Launch Engram automatically when you log in
+                  </p>
+                </div>
+                <button
+                  role="switch"
+                  aria-checked={prefs.startAtLogin}
+                  onClick={() => handleStartAtLogin(!prefs.startAtLogin)}
+                  className={`relative inline-flex h-6 w-11 items-center rounded-full transition-colors ${
+                    prefs.startAtLogin ? "bg-indigo-600" : "bg-gray-300 dark:bg-gray-600"
+                  }`}
+                >
+                  <span
+                    className={`inline-block h-4 w-4 transform rounded-full bg-white transition-transform ${
+                      prefs.startAtLogin ? "translate-x-6" : "translate-x-1"
+                    }`}
+                  />
+                </button>
+              </label>
+
+              <label className="flex items-center justify-between p-4 rounded-lg border border-gray-200 dark:border-gray-700">
+                <div>
+                  <p className="text-sm font-medium">Run Engram service independently</p>
+                  <p
+                    className="text-xs mt-0.5"
+                    style={{ color: "rgba(var(--text-secondary), 1)" }}
+                  >
+                    Keep capturing memories when the menu bar app isn't running (macOS)
+                  </p>
+                </div>
+                <button
+                  role="switch"
+                  aria-checked={prefs.runAsService ?? false}
+                  onClick={() => updatePref("runAsService", !prefs.runAsService)}
+                  className={`relative inline-flex h-6 w-11 items-center rounded-full transition-colors ${
+                    prefs.runAsService ? "bg-indigo-600" : "bg-gray-300 dark:bg-gray-600"
+                  }`}
+                >
+                  <span
+                    className={`inline-block h-4 w-4 transform rounded-full bg-white transition-transform ${
+                      prefs.runAsService ? "translate-x-6" : "translate-x-1"
+                    }`}
+                  />
+                </button>
+              </label>
+
+              <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700">
+                <label className="flex items-center justify-between">
+                  <div>
+                    <p className="text-sm font-medium">Calendar context</p>
+                    <p
+                      className="text-xs mt-0.5"
+                      style={{ color: "rgba(var(--text-secondary), 1)" }}
+                    >
+                      Tag quick-added memories with the meeting in progress (macOS, read-only)
+                    </p>
+                  </div>
+                  <button
+                    role="switch"
+                    aria-checked={prefs.calendarContext ?? false}
+                    onClick={() => handleCalendarContext(!prefs.calendarContext)}
+                    className={`relative inline-flex h-6 w-11 items-center rounded-full transition-colors ${
+                      prefs.calendarContext ? "bg-indigo-600" : "bg-gray-300 dark:bg-gray-600"
+                    }`}
+                  >
+                    <span
+                      className={`inline-block h-4 w-4 transform rounded-full bg-white transition-transform ${
+                        prefs.calendarContext ? "translate-x-6" : "translate-x-1"
+                      }`}
+                    />
+                  </button>
+                </label>
+                {calendarStatus && (
+                  <p className="text-xs mt-2 text-amber-600 dark:text-amber-400">{calendarStatus}</p>
+                )}
+              </div>
+
+              <label className="flex items-center justify-between p-4 rounded-lg border border-gray-200 dark:border-gray-700">
+                <div>
+                  <p className="text-sm font-medium">Tag captures with the current app</p>
+                  <p
+                    className="text-xs mt-0.5"
+                    style={{ color: "rgba(var(--text-secondary), 1)" }}
+                  >
+                    Record which app was in front when you opened quick add, e.g. app:Xcode (macOS)
+                  </p>
+                </div>
+                <button
+                  role="switch"
+                  aria-checked={prefs.captureFrontmostApp ?? false}
+                  onClick={() => updatePref("captureFrontmostApp", !prefs.captureFrontmostApp)}
+                  className={`relative inline-flex h-6 w-11 items-center rounded-full transition-colors ${
+                    prefs.captureFrontmostApp ? "bg-indigo-600" : "bg-gray-300 dark:bg-gray-600"
+                  }`}
+                >
+                  <span
+                    className={`inline-block h-4 w-4 transform rounded-full bg-white transition-transform ${
+                      prefs.captureFrontmostApp ? "translate-x-6" : "translate-x-1"
+                    }`}
+                  />
+                </button>
+              </label>
+
+              {(() => {
+                const resurfacing = prefs.resurfacing ?? DEFAULT_RESURFACING;
+                const editResurfacing = (patch: Partial<ResurfacingSettings>) =>
+                  updatePref("resurfacing", { ...resurfacing, ...patch });
+                const hourInput = (value: number, onChange: (hour: number) => void) => (
+                  <input
+                    type="number"
+                    min={0}
+                    max={23}
+                    value={value}
+                    onChange={(e) => onChange(Math.min(23, Math.max(0, Number(e.target.value) || 0)))}
+                    className="w-16 px-2 py-1 text-sm rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent"
+                  />
+                );
+                return (
+                  <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700 space-y-3">
+                    <label className="flex items-center justify-between">
+                      <div>
+                        <p className="text-sm font-medium">Remember this?</p>
+                        <p
+                          className="text-xs mt-0.5"
+                          style={{ color: "rgba(var(--text-secondary), 1)" }}
+                        >
+                          Now and then, bring back a memory from a week, a month, or a year ago today
+                        </p>
+                      </div>
+                      <button
+                        role="switch"
+                        aria-checked={resurfacing.enabled}
+                        onClick={() => editResurfacing({ enabled: !resurfacing.enabled })}
+                        className={`relative inline-flex h-6 w-11 items-center rounded-full transition-colors ${
+                          resurfacing.enabled ? "bg-indigo-600" : "bg-gray-300 dark:bg-gray-600"
+                        }`}
+                      >
+                        <span
+                          className={`inline-block h-4 w-4 transform rounded-full bg-white transition-transform ${
+                            resurfacing.enabled ? "translate-x-6" : "translate-x-1"
+                          }`}
+                        />
+                      </button>
+                    </label>
+                    {resurfacing.enabled && (
+                      <div
+                        className="space-y-2 text-xs"
+                        style={{ color: "rgba(var(--text-secondary), 1)" }}
+                      >
+                        <div className="flex items-center gap-2">
+                          <span>At most every</span>
+                          <select
+                            value={resurfacing.intervalHours}
+                            onChange={(e) => editResurfacing({ intervalHours: Number(e.target.value) })}
+                            className="px-2 py-1 text-sm rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent"
+                          >
+                            <option value={4}>4 hours</option>
+                            <option value={12}>12 hours</option>
+                            <option value={24}>day</option>
+                            <option value={168}>week</option>
+                          </select>
+                        </div>
+                        <div className="flex items-center gap-2">
+                          <span>Quiet from</span>
+                          {hourInput(resurfacing.quietStartHour, (hour) =>
+                            editResurfacing({ quietStartHour: hour })
+                          )}
+                          <span>to</span>
+                          {hourInput(resurfacing.quietEndHour, (hour) =>
+                            editResurfacing({ quietEndHour: hour })
+                          )}
+                          <span>o'clock</span>
+                        </div>
+                        <label className="flex items-center gap-2">
+                          <input
+                            type="checkbox"
+                            checked={resurfacing.includeNeverRecalled}
+                            onChange={(e) =>
+                              editResurfacing({ includeNeverRecalled: e.target.checked })
+                            }
+                          />
+                          <span>Otherwise, pick a memory that has never been recalled</span>
+                        </label>
+                      </div>
+                    )}
+                  </div>
+                );
+              })()}
+
+              {(() => {
+                const quiet = prefs.notifications?.quietHours ?? DEFAULT_QUIET_HOURS;
+                const editQuiet = (patch: Partial<QuietHours>) =>
+                  updatePref("notifications", {
+                    ...prefs.notifications,
+                    quietHours: { ...quiet, ...patch },
+                  });
+                const editRange = (index: number, patch: { start?: string; end?: string }) =>
+                  editQuiet({
+                    ranges: quiet.ranges.map((range, i) => (i === index ? { ...range, ...patch } : range)),
+                  });
+                const timeClass =
+                  "px-2 py-1 text-sm rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent";
+                return (
+                  <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700 space-y-3">
+                    <label className="flex items-center justify-between">
+                      <div>
+                        <p className="text-sm font-medium">Quiet hours</p>
+                        <p
+                          className="text-xs mt-0.5"
+                          style={{ color: "rgba(var(--text-secondary), 1)" }}
+                        >
+                          Hold back routine notifications and deliver them when quiet hours end.
+                          Crash alerts always come through.
+                        </p>
+                      </div>
+                      <button
+                        role="switch"
+                        aria-checked={quiet.enabled}
+                        onClick={() => editQuiet({ enabled: !quiet.enabled })}
+                        className={`relative inline-flex h-6 w-11 items-center rounded-full transition-colors ${
+                          quiet.enabled ? "bg-indigo-600" : "bg-gray-300 dark:bg-gray-600"
+                        }`}
+                      >
+                        <span
+                          className={`inline-block h-4 w-4 transform rounded-full bg-white transition-transform ${
+                            quiet.enabled ? "translate-x-6" : "translate-x-1"
+                          }`}
+                        />
+                      </button>
+                    </label>
+                    {quiet.enabled && (
+                      <div
+                        className="space-y-2 text-xs"
+                        style={{ color: "rgba(var(--text-secondary), 1)" }}
+                      >
+                        {quiet.ranges.map((range, index) => (
+                          <div key={index} className="flex items-center gap-2">
+                            <input
+                              type="time"
+                              value={range.start}
+                              onChange={(e) => editRange(index, { start: e.target.value })}
+                              className={timeClass}
+                            />
+                            <span>to</span>
+                            <input
+                              type="time"
+                              value={range.end}
+                              onChange={(e) => editRange(index, { end: e.target.value })}
+                              className={timeClass}
+                            />
+                            <button
+                              onClick={() =>
+                                editQuiet({ ranges: quiet.ranges.filter((_, i) => i !== index) })
+                              }
+                              className="text-red-600 dark:text-red-400 hover:underline"
+                            >
+                              Remove
+                            </button>
+                          </div>
+                        ))}
+                        <button
+                          onClick={() =>
+                            editQuiet({ ranges: [...quiet.ranges, { start: "12:00", end: "13:00" }] })
+                          }
+                          className="text-indigo-600 dark:text-indigo-400 hover:underline"
+                        >
+                          Add time range
+                        </button>
+                        <label className="flex items-center gap-2">
+                          <input
+                            type="checkbox"
+                            checked={quiet.weekends}
+                            onChange={(e) => editQuiet({ weekends: e.target.checked })}
+                          />
+                          <span>Quiet all weekend</span>
+                        </label>
+                      </div>
+                    )}
+                  </div>
+                );
+              })()}
+
+              <label className="flex items-center justify-between p-4 rounded-lg border border-gray-200 dark:border-gray-700">
+                <div>
+                  <p className="text-sm font-medium">Sound on save</p>
+                  <p
+                    className="text-xs mt-0.5"
+                    style={{ color: "rgba(var(--text-secondary), 1)" }}
+                  >
+                    Play a subtle sound when a memory is saved
+                  </p>
+                </div>
+                <button
+                  role="switch"
+                  aria-checked={prefs.soundOnSave}
+                  onClick={() => updatePref("soundOnSave", !prefs.soundOnSave)}
+                  className={`relative inline-flex h-6 w-11 items-center rounded-full transition-colors ${
+                    prefs.soundOnSave ? "bg-indigo-600" : "bg-gray-300 dark:bg-gray-600"
+                  }`}
                 >
                   <span
                     className={`inline-block h-4 w-4 transform rounded-full bg-white transition-transform ${
@@ -336,15 +1525,21 @@ export default function Preferences() {
                     key={agent.id}
                     className="flex items-center justify-between p-4 rounded-lg border border-gray-200 dark:border-gray-700"
                   >
+                    <img src={agent.icon} alt="" className="h-8 w-8 rounded-lg mr-3 shrink-0" />
                     <div className="flex-1 min-w-0">
                       <div className="flex items-center gap-2">
                         <span className="text-sm font-medium">{agent.name}</span>
-                        {agent.connected && (
+                        {agent.connected && !agent.orphaned && (
                           <span className="text-xs px-2 py-0.5 rounded-full bg-green-100 text-green-700 dark:bg-green-900 dark:text-green-300">
                             Connected
                           </span>
                         )}
-                        {!agent.available && !agent.connected && (
+                        {agent.orphaned && (
+                          <span className="text-xs px-2 py-0.5 rounded-full bg-yellow-100 text-yellow-700 dark:bg-yellow-900 dark:text-yellow-300">
+                            Uninstalled, entry left behind
+                          </span>
+                        )}
+                        {!agent.installed && !agent.connected && (
                           <span className="text-xs px-2 py-0.5 rounded-full bg-gray-100 text-gray-500 dark:bg-gray-800 dark:text-gray-400">
                             Not installed
                           </span>
@@ -378,9 +1573,471 @@ export default function Preferences() {
                     </button>
                   </div>
                 ))}
+                {agents.some((agent) => agent.orphaned) && (
+                  <div className="flex items-center justify-between p-4 rounded-lg border border-yellow-200 dark:border-yellow-800">
+                    <p className="text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                      Some uninstalled agents still list Engram in their config. Their configs
+                      are backed up before the entry is removed.
+                    </p>
+                    <button
+                      onClick={handleRemoveOrphans}
+                      className="ml-4 px-4 py-1.5 text-xs font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors shrink-0"
+                    >
+                      Clean Up
+                    </button>
+                  </div>
+                )}
               </div>
             )}
 
+            <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700 space-y-3">
+              <div>
+                <p className="text-sm font-medium">Memories not showing up?</p>
+                <p
+                  className="text-xs mt-0.5"
+                  style={{ color: "rgba(var(--text-secondary), 1)" }}
+                >
+                  Checks the agent's config, whether it needs a restart, the Engram server, an
+                  MCP handshake, and recent recalls.
+                </p>
+              </div>
+              <div className="flex gap-2">
+                <select
+                  value={diagnoseAgent}
+                  onChange={(e) => {
+                    setDiagnoseAgent(e.target.value);
+                    setDiagnosis(null);
+                  }}
+                  className="flex-1 rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent px-3 py-1.5 text-sm"
+                  style={{ color: "rgba(var(--text-primary), 1)" }}
+                >
+                  {agents
+                    .filter((agent) => agent.id !== "chatgpt")
+                    .map((agent) => (
+                      <option key={agent.id} value={agent.id}>
+                        {agent.name}
+                      </option>
+                    ))}
+                </select>
+                <button
+                  onClick={handleDiagnose}
+                  disabled={diagnosing}
+                  className="px-3 py-1 text-xs font-medium rounded-lg bg-indigo-600 text-white hover:bg-indigo-700 disabled:opacity-50"
+                >
+                  {diagnosing ? "Checking..." : "Diagnose"}
+                </button>
+              </div>
+              {diagnosis && (
+                <>
+                  <ul className="space-y-1">
+                    {diagnosis.checks.map((check) => (
+                      <li key={check.id} className="flex gap-2 text-xs">
+                        <span
+                          className={`w-3 shrink-0 ${
+                            check.outcome === "passed"
+                              ? "text-green-600 dark:text-green-400"
+                              : check.outcome === "failed"
+                              ? "text-red-600 dark:text-red-400"
+                              : check.outcome === "warning"
+                              ? "text-yellow-600 dark:text-yellow-400"
+                              : ""
+                          }`}
+                        >
+                          {CHECK_ICONS[check.outcome]}
+                        </span>
+                        <span className="font-medium">{check.label}</span>
+                        <span style={{ color: "rgba(var(--text-secondary), 1)" }}>{check.detail}</span>
+                      </li>
+                    ))}
+                  </ul>
+                  {diagnosis.causes.length === 0 ? (
+                    <p className="text-xs text-green-600 dark:text-green-400">
+                      Everything checks out. Ask {diagnosis.agentName} to recall something from Engram.
+                    </p>
+                  ) : (
+                    <div className="space-y-2">
+                      {diagnosis.causes.map((cause, i) => (
+                        <div
+                          key={i}
+                          className="flex items-start justify-between gap-3 p-3 rounded-lg bg-gray-50 dark:bg-gray-800/50"
+                        >
+                          <div>
+                            <p className="text-sm font-medium">{cause.title}</p>
+                            <p
+                              className="text-xs mt-0.5 whitespace-pre-line"
+                              style={{ color: "rgba(var(--text-secondary), 1)" }}
+                            >
+                              {cause.detail}
+                            </p>
+                          </div>
+                          {cause.fix && (
+                            <button
+                              onClick={() => handleDiagnosisFix(cause.fix!)}
+                              className="px-3 py-1 text-xs font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 shrink-0"
+                            >
+                              {cause.fixLabel}
+                            </button>
+                          )}
+                        </div>
+                      ))}
+                    </div>
+                  )}
+                </>
+              )}
+              {diagnoseError && (
+                <p className="text-xs text-red-600 dark:text-red-400">{diagnoseError}</p>
+              )}
+            </div>
+
+            <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700 space-y-3">
+              <div>
+                <p className="text-sm font-medium">Terminal</p>
+                <p
+                  className="text-xs mt-0.5"
+                  style={{ color: "rgba(var(--text-secondary), 1)" }}
+                >
+                  Adds <code>engram-note</code> to your shell: pass it text or pipe output into
+                  it, or press Ctrl+X Ctrl+N to save the current command line.
+                </p>
+              </div>
+              {["zsh", "bash", "fish"].map((shell) => (
+                <div key={shell} className="flex items-center justify-between">
+                  <span className="text-sm font-mono">{shell}</span>
+                  <div className="flex gap-2">
+                    <button
+                      onClick={() => handleShellHook(shell, true)}
+                      className="px-3 py-1 text-xs font-medium rounded-lg bg-indigo-600 text-white hover:bg-indigo-700"
+                    >
+                      Install
+                    </button>
+                    <button
+                      onClick={() => handleShellHook(shell, false)}
+                      className="px-3 py-1 text-xs font-medium rounded-lg bg-gray-100 text-gray-600 hover:bg-gray-200 dark:bg-gray-800 dark:text-gray-300"
+                    >
+                      Remove
+                    </button>
+                  </div>
+                </div>
+              ))}
+            </div>
+
+            {(() => {
+              const notion = notionDraft ?? prefs.notion ?? DEFAULT_NOTION;
+              const editNotion = (patch: Partial<NotionSyncSettings>) =>
+                setNotionDraft({ ...notion, ...patch });
+              return (
+                <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700 space-y-3">
+                  <div>
+                    <p className="text-sm font-medium">Notion</p>
+                    <p
+                      className="text-xs mt-0.5"
+                      style={{ color: "rgba(var(--text-secondary), 1)" }}
+                    >
+                      Pulls new pages from a Notion database as memories, and can push memories
+                      tagged <code>pinned</code> back as pages. Share the database with your
+                      integration first; the token is stored in the system keychain.
+                    </p>
+                  </div>
+                  <input
+                    type="password"
+                    value={notionToken}
+                    onChange={(e) => setNotionToken(e.target.value)}
+                    placeholder="Integration token (leave empty to keep the saved one)"
+                    className="w-full px-3 py-1.5 text-sm rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent"
+                  />
+                  <input
+                    type="text"
+                    value={notion.databaseId}
+                    onChange={(e) => editNotion({ databaseId: e.target.value })}
+                    placeholder="Database URL or id"
+                    className="w-full px-3 py-1.5 text-sm rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent"
+                  />
+                  <div className="flex items-center gap-4 text-sm">
+                    <label className="flex items-center gap-2">
+                      <input
+                        type="checkbox"
+                        checked={notion.enabled}
+                        onChange={(e) => editNotion({ enabled: e.target.checked })}
+                      />
+                      Sync every
+                      <input
+                        type="number"
+                        min={5}
+                        value={notion.intervalMinutes}
+                        onChange={(e) => editNotion({ intervalMinutes: Number(e.target.value) })}
+                        className="w-16 px-2 py-0.5 rounded border border-gray-200 dark:border-gray-700 bg-transparent"
+                      />
+                      minutes
+                    </label>
+                    <label className="flex items-center gap-2">
+                      <input
+                        type="checkbox"
+                        checked={notion.pushPinned}
+                        onChange={(e) => editNotion({ pushPinned: e.target.checked })}
+                      />
+                      Push pinned memories
+                    </label>
+                  </div>
+                  <div className="flex gap-2">
+                    <button
+                      onClick={() => handleSaveNotion(notion)}
+                      className="px-3 py-1 text-xs font-medium rounded-lg bg-indigo-600 text-white hover:bg-indigo-700"
+                    >
+                      Save
+                    </button>
+                    <button
+                      onClick={handleNotionSyncNow}
+                      disabled={!prefs.notion?.enabled}
+                      className="px-3 py-1 text-xs font-medium rounded-lg bg-gray-100 text-gray-600 hover:bg-gray-200 dark:bg-gray-800 dark:text-gray-300 disabled:opacity-50"
+                    >
+                      Sync Now
+                    </button>
+                  </div>
+                </div>
+              );
+            })()}
+
+            {(() => {
+              const tracker = trackerDraft ?? prefs.issueTracker ?? DEFAULT_ISSUE_TRACKER;
+              const editTracker = (patch: Partial<IssueTrackerSettings>) =>
+                setTrackerDraft({ ...tracker, ...patch });
+              const inputClass =
+                "w-full px-3 py-1.5 text-sm rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent";
+              return (
+                <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700 space-y-3">
+                  <div>
+                    <p className="text-sm font-medium">Issue Tracker</p>
+                    <p
+                      className="text-xs mt-0.5"
+                      style={{ color: "rgba(var(--text-secondary), 1)" }}
+                    >
+                      Memories that mention issue keys like <code>ENG-123</code> are recalled
+                      with the issue's current title and status.
+                    </p>
+                  </div>
+                  <select
+                    value={tracker.provider}
+                    onChange={(e) =>
+                      editTracker({ provider: e.target.value as IssueTrackerSettings["provider"] })
+                    }
+                    className={inputClass}
+                  >
+                    <option value="linear">Linear</option>
+                    <option value="jira">Jira</option>
+                  </select>
+                  <input
+                    type="password"
+                    value={trackerToken}
+                    onChange={(e) => setTrackerToken(e.target.value)}
+                    placeholder="API token (leave empty to keep the saved one)"
+                    className={inputClass}
+                  />
+                  {tracker.provider === "jira" && (
+                    <>
+                      <input
+                        type="text"
+                        value={tracker.jiraUrl}
+                        onChange={(e) => editTracker({ jiraUrl: e.target.value })}
+                        placeholder="https://your-site.atlassian.net"
+                        className={inputClass}
+                      />
+                      <input
+                        type="text"
+                        value={tracker.jiraEmail}
+                        onChange={(e) => editTracker({ jiraEmail: e.target.value })}
+                        placeholder="Account email"
+                        className={inputClass}
+                      />
+                    </>
+                  )}
+                  <input
+                    type="text"
+                    value={tracker.projectKeys.join(", ")}
+                    onChange={(e) =>
+                      editTracker({ projectKeys: e.target.value.split(",").map((k) => k.trim()) })
+                    }
+                    placeholder="Project keys, e.g. ENG, OPS (empty for all)"
+                    className={inputClass}
+                  />
+                  <label className="flex items-center gap-2 text-sm">
+                    <input
+                      type="checkbox"
+                      checked={tracker.enabled}
+                      onChange={(e) => editTracker({ enabled: e.target.checked })}
+                    />
+                    Refresh every
+                    <input
+                      type="number"
+                      min={5}
+                      value={tracker.intervalMinutes}
+                      onChange={(e) => editTracker({ intervalMinutes: Number(e.target.value) })}
+                      className="w-16 px-2 py-0.5 rounded border border-gray-200 dark:border-gray-700 bg-transparent"
+                    />
+                    minutes
+                  </label>
+                  <div className="flex gap-2">
+                    <button
+                      onClick={() => handleSaveIssueTracker(tracker)}
+                      className="px-3 py-1 text-xs font-medium rounded-lg bg-indigo-600 text-white hover:bg-indigo-700"
+                    >
+                      Save
+                    </button>
+                    <button
+                      onClick={handleIssueEnrichmentNow}
+                      disabled={!prefs.issueTracker?.enabled}
+                      className="px-3 py-1 text-xs font-medium rounded-lg bg-gray-100 text-gray-600 hover:bg-gray-200 dark:bg-gray-800 dark:text-gray-300 disabled:opacity-50"
+                    >
+                      Refresh Now
+                    </button>
+                  </div>
+                </div>
+              );
+            })()}
+
+            {(() => {
+              const github = githubDraft ?? prefs.github ?? DEFAULT_GITHUB;
+              const editGithub = (patch: Partial<GitHubSettings>) =>
+                setGithubDraft({ ...github, ...patch });
+              const inputClass =
+                "w-full px-3 py-1.5 text-sm rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent";
+              return (
+                <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700 space-y-3">
+                  <div>
+                    <p className="text-sm font-medium">GitHub</p>
+                    <p
+                      className="text-xs mt-0.5"
+                      style={{ color: "rgba(var(--text-secondary), 1)" }}
+                    >
+                      Saves your merged pull requests and review comments as memories tagged
+                      with their repository, so agents know what you have been working on.
+                    </p>
+                  </div>
+                  <input
+                    type="password"
+                    value={githubToken}
+                    onChange={(e) => setGithubToken(e.target.value)}
+                    placeholder="Personal access token (leave empty to keep the saved one)"
+                    className={inputClass}
+                  />
+                  <input
+                    type="text"
+                    value={github.repos.join(", ")}
+                    onChange={(e) =>
+                      editGithub({ repos: e.target.value.split(",").map((r) => r.trim()) })
+                    }
+                    placeholder="Repositories, e.g. owner/name (empty for all)"
+                    className={inputClass}
+                  />
+                  <div className="flex items-center gap-4 text-sm">
+                    <label className="flex items-center gap-2">
+                      <input
+                        type="checkbox"
+                        checked={github.enabled}
+                        onChange={(e) => editGithub({ enabled: e.target.checked })}
+                      />
+                      Capture every
+                      <input
+                        type="number"
+                        min={15}
+                        value={github.intervalMinutes}
+                        onChange={(e) => editGithub({ intervalMinutes: Number(e.target.value) })}
+                        className="w-16 px-2 py-0.5 rounded border border-gray-200 dark:border-gray-700 bg-transparent"
+                      />
+                      minutes
+                    </label>
+                    <label className="flex items-center gap-2">
+                      <input
+                        type="checkbox"
+                        checked={github.captureReviews}
+                        onChange={(e) => editGithub({ captureReviews: e.target.checked })}
+                      />
+                      Include review comments
+                    </label>
+                  </div>
+                  <div className="flex gap-2">
+                    <button
+                      onClick={() => handleSaveGitHub(github)}
+                      className="px-3 py-1 text-xs font-medium rounded-lg bg-indigo-600 text-white hover:bg-indigo-700"
+                    >
+                      Save
+                    </button>
+                    <button
+                      onClick={handleGitHubSyncNow}
+                      disabled={!prefs.github?.enabled}
+                      className="px-3 py-1 text-xs font-medium rounded-lg bg-gray-100 text-gray-600 hover:bg-gray-200 dark:bg-gray-800 dark:text-gray-300 disabled:opacity-50"
+                    >
+                      Sync Now
+                    </button>
+                  </div>
+                </div>
+              );
+            })()}
+
+            {(() => {
+              const capture = captureDraft ?? prefs.captureEndpoint ?? DEFAULT_CAPTURE_ENDPOINT;
+              return (
+                <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700 space-y-3">
+                  <div>
+                    <p className="text-sm font-medium">Capture endpoint</p>
+                    <p
+                      className="text-xs mt-0.5"
+                      style={{ color: "rgba(var(--text-secondary), 1)" }}
+                    >
+                      Lets scripts and other apps on this computer save memories with
+                      POST /capture and a bearer token. Only reachable from this computer.
+                    </p>
+                  </div>
+                  <div className="flex items-center gap-4 text-sm">
+                    <label className="flex items-center gap-2">
+                      <input
+                        type="checkbox"
+                        checked={capture.enabled}
+                        onChange={(e) => setCaptureDraft({ ...capture, enabled: e.target.checked })}
+                      />
+                      Listen on port
+                      <input
+                        type="number"
+                        min={1024}
+                        max={65535}
+                        value={capture.port}
+                        onChange={(e) => setCaptureDraft({ ...capture, port: Number(e.target.value) })}
+                        className="w-20 px-2 py-0.5 rounded border border-gray-200 dark:border-gray-700 bg-transparent"
+                      />
+                    </label>
+                  </div>
+                  {captureInfo?.enabled && captureInfo.token && (
+                    <pre className="text-xs font-mono whitespace-pre-wrap break-all p-2 rounded bg-gray-50 dark:bg-gray-800">
+                      {`curl -X POST ${captureInfo.url} \\\n  -H "Authorization: Bearer ${captureInfo.token}" \\\n  -d "Something to remember"`}
+                    </pre>
+                  )}
+                  <div className="flex gap-2">
+                    <button
+                      onClick={() => handleSaveCaptureEndpoint(capture)}
+                      className="px-3 py-1 text-xs font-medium rounded-lg bg-indigo-600 text-white hover:bg-indigo-700"
+                    >
+                      Save
+                    </button>
+                    <button
+                      onClick={async () =>
+                        setCaptureInfo(await invoke<CaptureEndpointInfo>("get_capture_endpoint"))
+                      }
+                      disabled={!prefs.captureEndpoint?.enabled}
+                      className="px-3 py-1 text-xs font-medium rounded-lg bg-gray-100 text-gray-600 hover:bg-gray-200 dark:bg-gray-800 dark:text-gray-300 disabled:opacity-50"
+                    >
+                      Show Token
+                    </button>
+                    <button
+                      onClick={() => handleSaveCaptureEndpoint(capture, true)}
+                      disabled={!prefs.captureEndpoint?.enabled}
+                      className="px-3 py-1 text-xs font-medium rounded-lg bg-gray-100 text-gray-600 hover:bg-gray-200 dark:bg-gray-800 dark:text-gray-300 disabled:opacity-50"
+                    >
+                      New Token
+                    </button>
+                  </div>
+                </div>
+              );
+            })()}
+
             {actionStatus && (
               <p
                 className="text-xs p-3 rounded-lg bg-gray-50 dark:bg-gray-800"
@@ -423,6 +2080,72 @@ export default function Preferences() {
                 </div>
               ))}
             </div>
+
+            <div className="space-y-3">
+              <h3 className="text-sm font-medium">Presets</h3>
+              <p className="text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                Save shortcuts, tray layout, and notification settings as a preset to share.
+              </p>
+              {presets.map((preset) => (
+                <div
+                  key={preset.name}
+                  className="flex items-center justify-between p-3 rounded-lg border border-gray-200 dark:border-gray-700"
+                >
+                  <span className="text-sm">{preset.name}</span>
+                  <div className="flex gap-2">
+                    {[
+                      ["apply_preset", "Apply"],
+                      ["export_preset", "Export"],
+                      ["delete_preset", "Delete"],
+                    ].map(([command, label]) => (
+                      <button
+                        key={command}
+                        onClick={() => handlePresetAction(command, preset.name)}
+                        className="px-3 py-1 text-xs font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors"
+                      >
+                        {label}
+                      </button>
+                    ))}
+                  </div>
+                </div>
+              ))}
+              <div className="flex gap-2">
+                <input
+                  type="text"
+                  value={presetName}
+                  onChange={(e) => setPresetName(e.target.value)}
+                  placeholder="Preset name"
+                  className="flex-1 px-3 py-2 text-sm rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent"
+                />
+                <button
+                  onClick={handleSavePreset}
+                  className="px-4 py-2 text-sm font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors"
+                >
+                  Save Current
+                </button>
+                <button
+                  onClick={() => presetInputRef.current?.click()}
+                  className="px-4 py-2 text-sm font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors"
+                >
+                  Import
+                </button>
+                <input
+                  ref={presetInputRef}
+                  type="file"
+                  accept=".json"
+                  onChange={handleImportPreset}
+                  className="hidden"
+                />
+              </div>
+              {presetStatus && (
+                <p
+                  className="text-xs p-3 rounded-lg bg-gray-50 dark:bg-gray-800"
+                  style={{ color: "rgba(var(--text-secondary), 1)" }}
+                >
+                  {presetStatus}
+                </p>
+              )}
+            </div>
           </div>
         )}
 
@@ -439,9 +2162,79 @@ export default function Preferences() {
                 >
                   ~/.engram/memory.db
                 </p>
+                {diskUsage && (
+                  <p
+                    className="text-xs mt-2"
+                    style={{ color: "rgba(var(--text-secondary), 1)" }}
+                  >
+                    {formatBytes(diskUsage.currentBytes)}
+                    {diskUsage.projection
+                      ? ` — at current rate, memory.db will reach ${formatBytes(
+                          diskUsage.projection.targetBytes
+                        )} in ~${formatDuration(diskUsage.projection.days)}`
+                      : diskUsage.bytesPerDay === null
+                        ? " — growth estimate available after a few days of use"
+                        : " — not growing"}
+                  </p>
+                )}
+                <div className="flex items-center gap-3 mt-3">
+                  <button
+                    onClick={handleVacuum}
+                    className="px-3 py-1 text-xs font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800"
+                  >
+                    Compact Database
+                  </button>
+                  <span
+                    className="text-xs"
+                    style={{ color: "rgba(var(--text-secondary), 1)" }}
+                  >
+                    {vacuumStatus ?? "Releases space left by deleted memories. Restarts Engram."}
+                  </span>
+                </div>
+              </div>
+
+              <div className="flex items-center justify-between p-4 rounded-lg border border-gray-200 dark:border-gray-700">
+                <div>
+                  <p className="text-sm font-medium">Low disk protection</p>
+                  <p
+                    className="text-xs mt-0.5"
+                    style={{ color: "rgba(var(--text-secondary), 1)" }}
+                  >
+                    Stop saving memories while free space is below this, so a full disk can't
+                    corrupt the database. 0 turns it off.
+                  </p>
+                </div>
+                <div className="flex items-center gap-2 shrink-0 ml-4">
+                  <input
+                    type="number"
+                    min={0}
+                    step={100}
+                    value={prefs.lowDiskThresholdMb ?? 500}
+                    onChange={(e) =>
+                      updatePref("lowDiskThresholdMb", Math.max(0, Number(e.target.value) || 0))
+                    }
+                    className="w-20 px-2 py-1 text-sm rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent"
+                  />
+                  <span className="text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                    MB
+                  </span>
+                </div>
               </div>
 
               <div className="flex gap-3">
+                <select
+                  value={exportFormat}
+                  onChange={(e) => setExportFormat(e.target.value as typeof exportFormat)}
+                  aria-label="Export format"
+                  className="px-3 py-2 text-sm rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent"
+                >
+                  <option value="json">JSON</option>
+                  <option value="jsonl">JSONL</option>
+                  <option value="csv">CSV</option>
+                  <option value="markdown">Markdown</option>
+                  <option value="sqlite">SQLite</option>
+                  <option value="parquet">Parquet</option>
+                </select>
                 <button
                   onClick={handleExport}
                   className="flex-1 px-4 py-2 text-sm font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors"
@@ -449,18 +2242,17 @@ export default function Preferences() {
                   Export Data
                 </button>
                 <button
-                  onClick={() => fileInputRef.current?.click()}
+                  onClick={handleObsidianExport}
                   className="flex-1 px-4 py-2 text-sm font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors"
                 >
-                  Import Data
+                  Export to Obsidian
+                </button>
+                <button
+                  onClick={() => setImportPath(importPath === null ? exportPath ?? "" : null)}
+                  className="flex-1 px-4 py-2 text-sm font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors"
+                >
+                  Import Data
                 </button>
-                <input
-                  ref={fileInputRef}
-                  type="file"
-                  accept=".json"
-                  onChange={handleImport}
-                  className="hidden"
-                />
                 <button
                   onClick={handleReset}
                   className={`flex-1 px-4 py-2 text-sm font-medium rounded-lg border transition-colors ${
@@ -482,6 +2274,192 @@ export default function Preferences() {
                 </p>
               )}
 
+              <div className="grid grid-cols-2 gap-2">
+                <input
+                  type="text"
+                  value={exportTags}
+                  onChange={(e) => setExportTags(e.target.value)}
+                  placeholder="Export only tags (comma-separated)"
+                  className="px-3 py-1.5 text-sm rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent"
+                />
+                <input
+                  type="text"
+                  value={exportQuery}
+                  onChange={(e) => setExportQuery(e.target.value)}
+                  placeholder="Containing words"
+                  className="px-3 py-1.5 text-sm rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent"
+                />
+                <label className="flex items-center gap-2 text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                  From
+                  <input
+                    type="date"
+                    value={exportSince}
+                    onChange={(e) => setExportSince(e.target.value)}
+                    className="flex-1 px-2 py-1 text-sm rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent"
+                  />
+                </label>
+                <label className="flex items-center gap-2 text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                  To
+                  <input
+                    type="date"
+                    value={exportUntil}
+                    onChange={(e) => setExportUntil(e.target.value)}
+                    className="flex-1 px-2 py-1 text-sm rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent"
+                  />
+                </label>
+                <input
+                  type="password"
+                  value={exportPassword}
+                  onChange={(e) => setExportPassword(e.target.value)}
+                  placeholder="Encrypt with password (optional)"
+                  autoComplete="new-password"
+                  className="col-span-2 px-3 py-1.5 text-sm rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent"
+                />
+                {exportFormat === "sqlite" && (
+                  <label className="col-span-2 flex items-center gap-2 text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                    <input
+                      type="checkbox"
+                      checked={exportEmbeddings}
+                      onChange={(e) => setExportEmbeddings(e.target.checked)}
+                    />
+                    Include embeddings (SQLite exports contain every memory; filters and password don't apply)
+                  </label>
+                )}
+              </div>
+
+              {importPath !== null && (
+                <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700 space-y-3">
+                  <input
+                    type="text"
+                    value={importPath}
+                    onChange={(e) => setImportPath(e.target.value)}
+                    placeholder="Path to an Engram export (.json)"
+                    className="w-full px-3 py-1.5 text-sm font-mono rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent"
+                  />
+                  <div className="flex gap-3">
+                    <select
+                      value={importMode}
+                      onChange={(e) => {
+                        setImportMode(e.target.value as "merge" | "replace");
+                        setImportConfirm(false);
+                      }}
+                      className="flex-1 px-3 py-1.5 text-sm rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent"
+                    >
+                      <option value="merge">Merge with existing memories</option>
+                      <option value="replace">Replace all existing memories</option>
+                    </select>
+                    <button
+                      onClick={handleImport}
+                      disabled={!importPath.trim()}
+                      className={`px-4 py-1.5 text-sm font-medium rounded-lg border transition-colors disabled:opacity-50 ${
+                        importConfirm
+                          ? "text-white bg-red-600 border-red-600 hover:bg-red-700"
+                          : "border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800"
+                      }`}
+                    >
+                      {importConfirm ? "Confirm Replace" : "Import"}
+                    </button>
+                  </div>
+                </div>
+              )}
+
+              <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700 space-y-3">
+                <div>
+                  <p className="text-sm font-medium">Merge another database</p>
+                  <p
+                    className="text-xs mt-0.5"
+                    style={{ color: "rgba(var(--text-secondary), 1)" }}
+                  >
+                    Add the memories from another memory.db, e.g. one from an old computer.
+                    Memories already here are skipped. The current database is kept as a
+                    pre-merge snapshot.
+                  </p>
+                </div>
+                <div className="flex gap-2">
+                  <input
+                    type="text"
+                    value={mergePath}
+                    onChange={(e) => setMergePath(e.target.value)}
+                    placeholder="Path to memory.db"
+                    className="flex-1 px-3 py-1.5 text-sm font-mono rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent"
+                  />
+                  <button
+                    onClick={handleMerge}
+                    disabled={!mergePath.trim()}
+                    className="px-4 py-1.5 text-sm font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors disabled:opacity-50"
+                  >
+                    Merge
+                  </button>
+                </div>
+                {mergeStatus && (
+                  <p className="text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                    {mergeStatus}
+                  </p>
+                )}
+              </div>
+
+              <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700 space-y-3">
+                <div>
+                  <p className="text-sm font-medium">Memory packs</p>
+                  <p
+                    className="text-xs mt-0.5"
+                    style={{ color: "rgba(var(--text-secondary), 1)" }}
+                  >
+                    Import a signed .engrampack shared by a teammate. Create one by selecting
+                    memories on the Memories page.
+                  </p>
+                </div>
+                <div className="flex gap-2">
+                  <input
+                    type="text"
+                    value={packPath}
+                    onChange={(e) => {
+                      setPackPath(e.target.value);
+                      setPackPreview(null);
+                    }}
+                    placeholder="Path to a memory pack (.engrampack)"
+                    className="flex-1 px-3 py-1.5 text-sm font-mono rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent"
+                  />
+                  <button
+                    onClick={packPreview ? handleImportPack : handlePreviewPack}
+                    disabled={!packPath.trim()}
+                    className="px-4 py-1.5 text-sm font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors disabled:opacity-50"
+                  >
+                    {packPreview ? `Import ${packPreview.memories.length} Memories` : "Preview"}
+                  </button>
+                </div>
+                {packPreview && (
+                  <div className="text-xs space-y-1" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                    <p className="text-sm font-medium" style={{ color: "rgba(var(--text-primary), 1)" }}>
+                      {packPreview.metadata.title}
+                    </p>
+                    {packPreview.metadata.description && <p>{packPreview.metadata.description}</p>}
+                    <p>
+                      {packPreview.metadata.author ? `By ${packPreview.metadata.author}, ` : ""}
+                      {new Date(packPreview.createdAt).toLocaleDateString()}
+                      {packPreview.metadata.license ? ` · ${packPreview.metadata.license}` : ""}
+                    </p>
+                    <p className="font-mono">
+                      Signed by {packPreview.signer}
+                      {packPreview.signedByYou ? " (you)" : ""}
+                    </p>
+                    <ul className="max-h-40 overflow-y-auto space-y-0.5 pt-1">
+                      {packPreview.memories.map((memory) => (
+                        <li key={memory.originalId} className="truncate">
+                          {memory.category && <span className="font-medium">{memory.category}: </span>}
+                          {memory.content}
+                        </li>
+                      ))}
+                    </ul>
+                  </div>
+                )}
+                {packStatus && (
+                  <p className="text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                    {packStatus}
+                  </p>
+                )}
+              </div>
+
               {exportPath && (
                 <p
                   className="text-xs font-mono"
@@ -490,6 +2468,277 @@ export default function Preferences() {
                   Last export: {exportPath}
                 </p>
               )}
+
+              <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700 space-y-3">
+                <div>
+                  <p className="text-sm font-medium">Snapshots</p>
+                  <p
+                    className="text-xs mt-0.5"
+                    style={{ color: "rgba(var(--text-secondary), 1)" }}
+                  >
+                    Copies of the whole database in ~/.engram/backups. Restoring one restarts
+                    Engram and keeps the current database as a pre-restore snapshot.
+                  </p>
+                </div>
+                {backups.map((backup) => (
+                  <div key={backup.fileName} className="flex items-center justify-between text-xs">
+                    <span style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                      <span className="font-mono">{backup.name}</span> ·{" "}
+                      {new Date(backup.createdAt).toLocaleString()} · {formatBytes(backup.sizeBytes)}
+                    </span>
+                    <button
+                      onClick={() => handleRestore(backup.fileName)}
+                      className="text-red-600 dark:text-red-400 hover:underline"
+                    >
+                      {restoreConfirm === backup.fileName ? "Confirm Restore" : "Restore"}
+                    </button>
+                  </div>
+                ))}
+                <div className="flex gap-3">
+                  <input
+                    type="text"
+                    value={backupName}
+                    onChange={(e) => setBackupName(e.target.value)}
+                    placeholder="Snapshot name, e.g. before-import"
+                    className="flex-1 px-3 py-1.5 text-sm rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent"
+                  />
+                  <button
+                    onClick={handleBackup}
+                    className="px-4 py-1.5 text-sm font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors"
+                  >
+                    Create Snapshot
+                  </button>
+                  <button
+                    onClick={handleOpenDataDir}
+                    className="px-4 py-1.5 text-sm font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors"
+                  >
+                    Show Data Folder
+                  </button>
+                </div>
+                {backupStatus && (
+                  <p className="text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                    {backupStatus}
+                  </p>
+                )}
+              </div>
+
+              <label className="flex items-center justify-between p-4 rounded-lg border border-gray-200 dark:border-gray-700">
+                <div>
+                  <p className="text-sm font-medium">Clean up captured text</p>
+                  <p
+                    className="text-xs mt-0.5"
+                    style={{ color: "rgba(var(--text-secondary), 1)" }}
+                  >
+                    Strip terminal colors and progress noise, normalize Unicode, and tag
+                    non-English memories with their language
+                  </p>
+                </div>
+                <button
+                  role="switch"
+                  aria-checked={prefs.normalizeIngest ?? false}
+                  onClick={() => updatePref("normalizeIngest", !prefs.normalizeIngest)}
+                  className={`relative inline-flex h-6 w-11 items-center rounded-full transition-colors ${
+                    prefs.normalizeIngest ? "bg-indigo-600" : "bg-gray-300 dark:bg-gray-600"
+                  }`}
+                >
+                  <span
+                    className={`inline-block h-4 w-4 transform rounded-full bg-white transition-transform ${
+                      prefs.normalizeIngest ? "translate-x-6" : "translate-x-1"
+                    }`}
+                  />
+                </button>
+              </label>
+
+              <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700 space-y-3">
+                <div>
+                  <p className="text-sm font-medium">Tagging rules</p>
+                  <p
+                    className="text-xs mt-0.5"
+                    style={{ color: "rgba(var(--text-secondary), 1)" }}
+                  >
+                    Add tags to quick-add and imported memories that match. Patterns are
+                    case-insensitive regular expressions.
+                  </p>
+                </div>
+                {(prefs.tagRules ?? []).map((rule, index) => (
+                  <div key={index} className="flex items-center justify-between text-xs">
+                    <span className="font-mono" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                      {[
+                        rule.contentPattern && `/${rule.contentPattern}/`,
+                        rule.source && `source ~ ${rule.source}`,
+                        rule.pathContains && `path ~ ${rule.pathContains}`,
+                      ]
+                        .filter(Boolean)
+                        .join(" and ")}{" "}
+                      → {rule.tags.join(", ")}
+                    </span>
+                    <button
+                      onClick={() => handleRemoveTagRule(index)}
+                      className="text-red-600 dark:text-red-400 hover:underline"
+                    >
+                      Remove
+                    </button>
+                  </div>
+                ))}
+                <div className="grid grid-cols-2 gap-2">
+                  {(
+                    [
+                      ["contentPattern", "Content matches, e.g. kubectl|k8s"],
+                      ["source", "Source contains, e.g. cursor"],
+                      ["pathContains", "Path contains, e.g. repoX"],
+                    ] as const
+                  ).map(([key, placeholder]) => (
+                    <input
+                      key={key}
+                      type="text"
+                      value={newRule[key]}
+                      placeholder={placeholder}
+                      onChange={(e) => setNewRule({ ...newRule, [key]: e.target.value })}
+                      className="rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent px-3 py-2 text-sm font-mono focus:outline-none focus:ring-2 focus:ring-indigo-500"
+                      style={{ color: "rgba(var(--text-primary), 1)" }}
+                    />
+                  ))}
+                  <input
+                    type="text"
+                    value={newRuleTags}
+                    placeholder="Add tags, e.g. devops"
+                    onChange={(e) => setNewRuleTags(e.target.value)}
+                    className="rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-indigo-500"
+                    style={{ color: "rgba(var(--text-primary), 1)" }}
+                  />
+                </div>
+                <div className="flex gap-3">
+                  <button
+                    onClick={handleAddTagRule}
+                    disabled={!newRuleTags.trim()}
+                    className="px-4 py-2 text-sm font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors disabled:opacity-50"
+                  >
+                    Add Rule
+                  </button>
+                  <select
+                    value={tagRuleScope}
+                    onChange={(e) => setTagRuleScope(e.target.value)}
+                    className="rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-indigo-500"
+                    style={{ color: "rgba(var(--text-primary), 1)" }}
+                  >
+                    <option value="all">All memories</option>
+                    <option value="untagged">Untagged memories</option>
+                  </select>
+                  <button
+                    onClick={handleApplyTagRules}
+                    disabled={!prefs.tagRules?.length}
+                    className="flex-1 px-4 py-2 text-sm font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors disabled:opacity-50"
+                  >
+                    Apply to Existing Memories
+                  </button>
+                </div>
+                {tagRuleStatus && (
+                  <p className="text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                    {tagRuleStatus}
+                  </p>
+                )}
+              </div>
+
+              <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700 space-y-3">
+                <div className="flex items-center justify-between">
+                  <div>
+                    <p className="text-sm font-medium">Tags</p>
+                    <p
+                      className="text-xs mt-0.5"
+                      style={{ color: "rgba(var(--text-secondary), 1)" }}
+                    >
+                      Rename, merge, or remove tags across all memories.
+                    </p>
+                  </div>
+                  <button
+                    onClick={loadTags}
+                    className="px-3 py-1.5 text-xs font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors"
+                  >
+                    {tags ? "Refresh" : "Load Tags"}
+                  </button>
+                </div>
+                {tags && tags.length === 0 && (
+                  <p className="text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                    No memories are tagged yet.
+                  </p>
+                )}
+                {tags && tags.length > 0 && (
+                  <div className="max-h-64 overflow-y-auto space-y-1">
+                    {tags.map((tag) => (
+                      <div key={tag.name} className="flex items-center justify-between gap-2 text-xs">
+                        {renaming?.from === tag.name ? (
+                          <input
+                            type="text"
+                            value={renaming.to}
+                            autoFocus
+                            onChange={(e) => setRenaming({ from: tag.name, to: e.target.value })}
+                            onKeyDown={(e) => {
+                              if (e.key === "Enter") handleRenameTag();
+                              if (e.key === "Escape") setRenaming(null);
+                            }}
+                            className="flex-1 rounded border border-gray-200 dark:border-gray-700 bg-transparent px-2 py-1 font-mono focus:outline-none focus:ring-2 focus:ring-indigo-500"
+                            style={{ color: "rgba(var(--text-primary), 1)" }}
+                          />
+                        ) : (
+                          <span className="font-mono" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                            {tag.name} ({tag.count})
+                          </span>
+                        )}
+                        <div className="flex gap-3">
+                          {renaming?.from === tag.name ? (
+                            <button onClick={handleRenameTag} className="text-indigo-500 hover:underline">
+                              Save
+                            </button>
+                          ) : (
+                            <button
+                              onClick={() => setRenaming({ from: tag.name, to: tag.name })}
+                              className="text-indigo-500 hover:underline"
+                            >
+                              Rename
+                            </button>
+                          )}
+                          <button
+                            onClick={() => handleDeleteTag(tag.name)}
+                            className="text-red-500 hover:underline"
+                          >
+                            {confirmRemoveTag === tag.name ? "Click to confirm" : "Remove"}
+                          </button>
+                        </div>
+                      </div>
+                    ))}
+                  </div>
+                )}
+                <div className="flex gap-3">
+                  <input
+                    type="text"
+                    value={mergeSources}
+                    placeholder="Merge tags, e.g. js, javascript"
+                    onChange={(e) => setMergeSources(e.target.value)}
+                    className="flex-1 rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-indigo-500"
+                    style={{ color: "rgba(var(--text-primary), 1)" }}
+                  />
+                  <input
+                    type="text"
+                    value={mergeTarget}
+                    placeholder="Into"
+                    onChange={(e) => setMergeTarget(e.target.value)}
+                    className="w-32 rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-indigo-500"
+                    style={{ color: "rgba(var(--text-primary), 1)" }}
+                  />
+                  <button
+                    onClick={handleMergeTags}
+                    disabled={!mergeSources.trim() || !mergeTarget.trim()}
+                    className="px-4 py-2 text-sm font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors disabled:opacity-50"
+                  >
+                    Merge
+                  </button>
+                </div>
+                {tagStatus && (
+                  <p className="text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                    {tagStatus}
+                  </p>
+                )}
+              </div>
             </div>
           </div>
         )}
@@ -513,11 +2762,245 @@ export default function Preferences() {
                     className="text-xs mt-1"
                     style={{ color: "rgba(var(--text-secondary), 1)" }}
                   >
-                    Requires restart to take effect
+                    The sidecar restarts on the new port when this changes
+                  </p>
+                </div>
+                <div className="flex items-center gap-3">
+                  <button
+                    onClick={handleCopyConnectionInfo}
+                    className="px-4 py-1.5 text-sm font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors"
+                  >
+                    Copy Base URL
+                  </button>
+                  {connectionStatus && (
+                    <span className="text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                      {connectionStatus}
+                    </span>
+                  )}
+                </div>
+              </div>
+
+              <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700 space-y-3">
+                <div>
+                  <p className="text-sm font-medium">Move preferences</p>
+                  <p
+                    className="text-xs mt-0.5"
+                    style={{ color: "rgba(var(--text-secondary), 1)" }}
+                  >
+                    Export shortcuts, ports, schedules, and other desktop settings to set up
+                    another computer the same way. Tokens stay in the keychain and aren't included.
+                  </p>
+                </div>
+                <div className="flex gap-2">
+                  <input
+                    type="text"
+                    value={prefsImportPath}
+                    onChange={(e) => setPrefsImportPath(e.target.value)}
+                    placeholder="Path to Engram preferences.json"
+                    className="flex-1 px-3 py-1.5 text-sm font-mono rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent"
+                  />
+                  <button
+                    onClick={handleImportPreferences}
+                    disabled={!prefsImportPath.trim()}
+                    className="px-4 py-1.5 text-sm font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors disabled:opacity-50"
+                  >
+                    Import
+                  </button>
+                  <button
+                    onClick={handleExportPreferences}
+                    className="px-4 py-1.5 text-sm font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 transition-colors"
+                  >
+                    Export
+                  </button>
+                </div>
+                {prefsTransferStatus && (
+                  <p className="text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                    {prefsTransferStatus}
+                  </p>
+                )}
+              </div>
+
+              <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700 space-y-3">
+                <div>
+                  <p className="text-sm font-medium">License</p>
+                  <p
+                    className="text-xs mt-0.5"
+                    style={{ color: "rgba(var(--text-secondary), 1)" }}
+                  >
+                    {license?.status === "valid"
+                      ? `${license.edition === "team" ? "Team" : "Pro"} edition, licensed to ${license.licensee}${
+                          license.expiresAt
+                            ? ` until ${new Date(license.expiresAt).toLocaleDateString()}`
+                            : ""
+                        }`
+                      : "Community edition"}
+                  </p>
+                  {license?.message && (
+                    <p className="text-xs mt-1 text-yellow-600 dark:text-yellow-400">
+                      {license.message}
+                    </p>
+                  )}
+                </div>
+                <div className="flex gap-2">
+                  <input
+                    type="text"
+                    value={licenseKey}
+                    onChange={(e) => setLicenseKey(e.target.value)}
+                    placeholder="License key"
+                    className="flex-1 rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent px-3 py-2 text-sm font-mono focus:outline-none focus:ring-2 focus:ring-indigo-500"
+                    style={{ color: "rgba(var(--text-primary), 1)" }}
+                  />
+                  <button
+                    onClick={handleActivateLicense}
+                    disabled={!licenseKey.trim()}
+                    className="px-3 py-1 text-xs font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 disabled:opacity-50"
+                  >
+                    Activate
+                  </button>
+                </div>
+                {licenseError && (
+                  <p className="text-xs text-red-600 dark:text-red-400">{licenseError}</p>
+                )}
+              </div>
+
+              <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700 space-y-3">
+                <div className="flex items-center justify-between">
+                  <div>
+                    <p className="text-sm font-medium">Sidecar updates</p>
+                    <p
+                      className="text-xs mt-0.5"
+                      style={{ color: "rgba(var(--text-secondary), 1)" }}
+                    >
+                      {sidecarRelease
+                        ? sidecarRelease.newer
+                          ? `Sidecar ${sidecarRelease.currentVersion}, ${sidecarRelease.latestVersion} is available on npm`
+                          : `Sidecar ${sidecarRelease.currentVersion} is the latest release`
+                        : "Compare the bundled sidecar with the latest release on npm"}
+                    </p>
+                  </div>
+                  <button
+                    onClick={handleCheckSidecarRelease}
+                    disabled={checkingSidecar}
+                    className="px-3 py-1 text-xs font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800 disabled:opacity-50"
+                  >
+                    {checkingSidecar ? "Checking..." : "Check Now"}
+                  </button>
+                </div>
+                {sidecarRelease && sidecarRelease.changelog.length > 0 && (
+                  <div className="space-y-2 max-h-48 overflow-y-auto">
+                    {sidecarRelease.changelog.map((release) => (
+                      <div key={release.version}>
+                        <p className="text-xs font-medium">
+                          {release.version}
+                          {release.publishedAt && (
+                            <span
+                              className="font-normal ml-2"
+                              style={{ color: "rgba(var(--text-secondary), 1)" }}
+                            >
+                              {new Date(release.publishedAt).toLocaleDateString()}
+                            </span>
+                          )}
+                        </p>
+                        <ul
+                          className="text-xs list-disc ml-4"
+                          style={{ color: "rgba(var(--text-secondary), 1)" }}
+                        >
+                          {release.highlights.map((line, i) => (
+                            <li key={i}>{line}</li>
+                          ))}
+                          {release.more > 0 && <li>...and {release.more} more</li>}
+                        </ul>
+                      </div>
+                    ))}
+                  </div>
+                )}
+                {sidecarRelease?.update?.available && (
+                  <button
+                    onClick={handleApplySidecarUpdate}
+                    className="px-3 py-1 text-xs font-medium rounded-lg bg-indigo-600 text-white hover:bg-indigo-700"
+                  >
+                    Update Sidecar to {sidecarRelease.update.latestVersion}
+                  </button>
+                )}
+                {sidecarRelease?.newer && !sidecarRelease.update?.available && (
+                  <p className="text-xs text-yellow-600 dark:text-yellow-400">
+                    {sidecarRelease.update?.requiresAppVersion
+                      ? `This release needs Engram ${sidecarRelease.update.requiresAppVersion} or later`
+                      : "No bundle update is published for this release yet"}
+                  </p>
+                )}
+                {sidecarReleaseStatus && (
+                  <p className="text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                    {sidecarReleaseStatus}
+                  </p>
+                )}
+              </div>
+
+              <div className="flex items-center justify-between p-4 rounded-lg border border-gray-200 dark:border-gray-700">
+                <div>
+                  <p className="text-sm font-medium">Developer mode</p>
+                  <p
+                    className="text-xs mt-0.5"
+                    style={{ color: "rgba(var(--text-secondary), 1)" }}
+                  >
+                    An API explorer for trying sidecar endpoints and watching app events
                   </p>
+                  {explorerError && (
+                    <p className="text-xs mt-1 text-red-600 dark:text-red-400">{explorerError}</p>
+                  )}
+                </div>
+                <div className="flex items-center gap-3">
+                  {prefs.developerMode && (
+                    <button
+                      onClick={handleOpenApiExplorer}
+                      className="px-3 py-1 text-xs font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800"
+                    >
+                      Open API Explorer
+                    </button>
+                  )}
+                  <button
+                    role="switch"
+                    aria-checked={prefs.developerMode ?? false}
+                    onClick={() => updatePref("developerMode", !prefs.developerMode)}
+                    className={`relative inline-flex h-6 w-11 items-center rounded-full transition-colors ${
+                      prefs.developerMode ? "bg-indigo-600" : "bg-gray-300 dark:bg-gray-600"
+                    }`}
+                  >
+                    <span
+                      className={`inline-block h-4 w-4 transform rounded-full bg-white transition-transform ${
+                        prefs.developerMode ? "translate-x-6" : "translate-x-1"
+                      }`}
+                    />
+                  </button>
                 </div>
               </div>
 
+              {prefs.developerMode && startupTimings && (
+                <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700 space-y-2">
+                  <div>
+                    <p className="text-sm font-medium">Startup timings</p>
+                    <p
+                      className="text-xs mt-0.5"
+                      style={{ color: "rgba(var(--text-secondary), 1)" }}
+                    >
+                      Setup finished after {startupTimings.setupMs ?? "?"} ms
+                      {startupTimings.sidecarReadyMs !== null &&
+                        `, sidecar ready after ${startupTimings.sidecarReadyMs} ms`}
+                    </p>
+                  </div>
+                  <ul className="text-xs font-mono space-y-0.5">
+                    {startupTimings.phases.map((phase) => (
+                      <li key={phase.name} className="flex justify-between">
+                        <span>{phase.name}</span>
+                        <span style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                          +{phase.startedMs} ms{phase.durationMs > 0 && ` · ${phase.durationMs} ms`}
+                        </span>
+                      </li>
+                    ))}
+                  </ul>
+                </div>
+              )}
+
               <label className="flex items-center justify-between p-4 rounded-lg border border-gray-200 dark:border-gray-700">
                 <div>
                   <p className="text-sm font-medium">Enable REST API</p>
@@ -558,6 +3041,130 @@ export default function Preferences() {
                   <option value="debug">Debug</option>
                 </select>
               </div>
+
+              <div className="p-4 rounded-lg border border-gray-200 dark:border-gray-700 space-y-3">
+                <label className="flex items-center justify-between">
+                  <div>
+                    <p className="text-sm font-medium">Developer mode</p>
+                    <p
+                      className="text-xs mt-0.5"
+                      style={{ color: "rgba(var(--text-secondary), 1)" }}
+                    >
+                      Run the sidecar with your own node binary and entry script. Takes effect on restart.
+                    </p>
+                  </div>
+                  <input
+                    type="checkbox"
+                    checked={prefs.developer?.enabled ?? false}
+                    onChange={(e) =>
+                      updatePref("developer", {
+                        nodePath: "",
+                        entryScript: "",
+                        ...prefs.developer,
+                        enabled: e.target.checked,
+                      })
+                    }
+                  />
+                </label>
+                {prefs.developer?.enabled &&
+                  (
+                    [
+                      ["nodePath", "Node binary", "/usr/local/bin/node"],
+                      ["entryScript", "Entry script", "~/src/engram/bin/engram.js"],
+                    ] as const
+                  ).map(([key, label, placeholder]) => (
+                    <div key={key}>
+                      <label className="text-sm font-medium">{label}</label>
+                      <input
+                        type="text"
+                        value={prefs.developer?.[key] ?? ""}
+                        placeholder={`${placeholder} (auto-detect if empty)`}
+                        onChange={(e) =>
+                          updatePref("developer", {
+                            enabled: true,
+                            nodePath: "",
+                            entryScript: "",
+                            ...prefs.developer,
+                            [key]: e.target.value,
+                          })
+                        }
+                        className="mt-1 w-full rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent px-3 py-2 text-sm font-mono focus:outline-none focus:ring-2 focus:ring-indigo-500"
+                        style={{ color: "rgba(var(--text-primary), 1)" }}
+                      />
+                    </div>
+                  ))}
+                {resolution && (
+                  <div
+                    className="text-xs font-mono space-y-0.5"
+                    style={{ color: "rgba(var(--text-secondary), 1)" }}
+                  >
+                    <p>Source: {resolution.source} — {resolution.reason}</p>
+                    <p>Node: {resolution.node}</p>
+                    <p>Script: {resolution.script}</p>
+                    {resolution.skipped.map((reason) => (
+                      <p key={reason}>Skipped: {reason}</p>
+                    ))}
+                  </div>
+                )}
+              </div>
+
+              <div className="p-4 rounded-lg border border-red-200 dark:border-red-800 space-y-3">
+                <div>
+                  <p className="text-sm font-medium text-red-600 dark:text-red-400">Factory reset</p>
+                  <p
+                    className="text-xs mt-0.5"
+                    style={{ color: "rgba(var(--text-secondary), 1)" }}
+                  >
+                    Deletes every profile's memories, settings, and snapshots, and removes Engram
+                    from agent configs, shell hooks, and login items. This can't be undone. Type{" "}
+                    <span className="font-mono">{FACTORY_RESET_CONFIRMATION}</span> to confirm.
+                  </p>
+                </div>
+                {factoryResetReport ? (
+                  <div className="space-y-2">
+                    <div
+                      className="text-xs space-y-0.5"
+                      style={{ color: "rgba(var(--text-secondary), 1)" }}
+                    >
+                      {factoryResetReport.removed.map((item) => (
+                        <p key={item}>Removed {item}</p>
+                      ))}
+                      {factoryResetReport.failed.map((item) => (
+                        <p key={item} className="text-red-600 dark:text-red-400">
+                          Not removed: {item}
+                        </p>
+                      ))}
+                    </div>
+                    <button
+                      onClick={() => exit(0)}
+                      className="px-3 py-1 text-xs font-medium rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-800"
+                    >
+                      Quit Engram
+                    </button>
+                  </div>
+                ) : (
+                  <div className="flex gap-2">
+                    <input
+                      type="text"
+                      value={factoryResetText}
+                      onChange={(e) => setFactoryResetText(e.target.value)}
+                      placeholder={FACTORY_RESET_CONFIRMATION}
+                      className="flex-1 rounded-lg border border-gray-200 dark:border-gray-700 bg-transparent px-3 py-2 text-sm font-mono focus:outline-none focus:ring-2 focus:ring-red-500"
+                      style={{ color: "rgba(var(--text-primary), 1)" }}
+                    />
+                    <button
+                      onClick={handleFactoryReset}
+                      disabled={factoryResetText.trim() !== FACTORY_RESET_CONFIRMATION}
+                      className="px-3 py-1 text-xs font-medium rounded-lg text-white bg-red-600 hover:bg-red-700 disabled:opacity-50"
+                    >
+                      Reset Everything
+                    </button>
+                  </div>
+                )}
+                {factoryResetError && (
+                  <p className="text-xs text-red-600 dark:text-red-400">{factoryResetError}</p>
+                )}
+              </div>
             </div>
           </div>
         )}
@@ -0,0 +1,140 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+
+/** A memory as the backend lets present mode show it; masked ones have no content. */
+interface PresentedMemory {
+  id: string;
+  category: string;
+  entity: string | null;
+  content: string | null;
+  masked: boolean;
+}
+
+const CATEGORY_COLORS: Record<string, string> = {
+  preference: "bg-purple-100 text-purple-800 dark:bg-purple-900/50 dark:text-purple-300",
+  fact: "bg-blue-100 text-blue-800 dark:bg-blue-900/50 dark:text-blue-300",
+  pattern: "bg-green-100 text-green-800 dark:bg-green-900/50 dark:text-green-300",
+  decision: "bg-yellow-100 text-yellow-800 dark:bg-yellow-900/50 dark:text-yellow-300",
+  outcome: "bg-red-100 text-red-800 dark:bg-red-900/50 dark:text-red-300",
+};
+
+/**
+ * Read-only view for screen sharing: search and read, nothing that adds or
+ * deletes, and redaction-flagged memories shown as placeholders.
+ */
+export default function PresentMode() {
+  const [query, setQuery] = useState("");
+  const [memories, setMemories] = useState<PresentedMemory[]>([]);
+  const [expanded, setExpanded] = useState<string | null>(null);
+  const [loading, setLoading] = useState(true);
+  const [error, setError] = useState<string | null>(null);
+
+  // Recent memories for an empty query; masking happens in the backend
+  async function load(search: string) {
+    try {
+      setLoading(true);
+      setMemories(await invoke<PresentedMemory[]>("get_present_memories", { query: search }));
+      setError(null);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setLoading(false);
+    }
+  }
+
+  useEffect(() => {
+    load("");
+  }, []);
+
+  function handleSearch(e: React.FormEvent) {
+    e.preventDefault();
+    load(query);
+  }
+
+  return (
+    <div className="flex flex-col h-screen overflow-hidden">
+      <div className="flex items-center justify-between px-6 py-3 border-b border-gray-200 dark:border-gray-700 bg-amber-50 dark:bg-amber-900/20">
+        <p className="text-sm font-medium text-amber-800 dark:text-amber-300">
+          Present mode · read only, private memories hidden
+        </p>
+        <button
+          onClick={() => invoke("set_present_mode", { enabled: false }).catch(() => {})}
+          className="px-3 py-1 text-xs font-medium rounded-lg border border-amber-300 dark:border-amber-700 text-amber-800 dark:text-amber-300 hover:bg-amber-100 dark:hover:bg-amber-900/40"
+        >
+          End Present Mode
+        </button>
+      </div>
+
+      <main className="flex-1 overflow-y-auto p-6 space-y-4">
+        <form onSubmit={handleSearch}>
+          <input
+            type="text"
+            value={query}
+            onChange={(e) => setQuery(e.target.value)}
+            placeholder="Search memories..."
+            className="w-full rounded-[10px] border border-gray-200 dark:border-gray-700 bg-transparent p-3 text-sm focus:outline-none focus:ring-2 focus:ring-blue-500 focus:border-transparent"
+            style={{ color: "rgba(var(--text-primary), 1)" }}
+          />
+        </form>
+
+        {error && <p className="text-sm text-red-600 dark:text-red-400">{error}</p>}
+
+        {loading ? (
+          <div className="flex justify-center items-center h-48">
+            <div className="animate-spin rounded-full h-10 w-10 border-2 border-blue-500 border-t-transparent" />
+          </div>
+        ) : (
+          <div className="space-y-2">
+            {memories.map((memory) => {
+              const masked = memory.masked;
+              return (
+                <button
+                  key={memory.id}
+                  onClick={() => !masked && setExpanded(expanded === memory.id ? null : memory.id)}
+                  className="w-full text-left glass rounded-[10px] p-4 border border-gray-200/50 dark:border-gray-700/50"
+                >
+                  <div className="flex items-center gap-2 mb-1.5">
+                    <span
+                      className={`inline-flex items-center px-2 py-0.5 rounded-full text-xs font-medium ${
+                        CATEGORY_COLORS[memory.category] ||
+                        "bg-gray-100 text-gray-800 dark:bg-gray-700 dark:text-gray-300"
+                      }`}
+                    >
+                      {memory.category}
+                    </span>
+                    {!masked && memory.entity && (
+                      <span
+                        className="text-xs font-mono"
+                        style={{ color: "rgba(var(--text-secondary), 1)" }}
+                      >
+                        {memory.entity}
+                      </span>
+                    )}
+                  </div>
+                  {masked ? (
+                    <p className="text-sm italic" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                      Hidden while presenting
+                    </p>
+                  ) : (
+                    <p
+                      className={`text-sm leading-relaxed ${
+                        expanded === memory.id ? "whitespace-pre-wrap" : "line-clamp-2"
+                      }`}
+                    >
+                      {memory.content}
+                    </p>
+                  )}
+                </button>
+              );
+            })}
+            {memories.length === 0 && (
+              <p className="text-sm text-center py-16" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                No memories found.
+              </p>
+            )}
+          </div>
+        )}
+      </main>
+    </div>
+  );
+}
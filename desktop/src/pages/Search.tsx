@@ -1,6 +1,10 @@
 import { useState } from "react";
 import { motion } from "framer-motion";
-import { api, Memory } from "../lib/api";
+import { invoke } from "@tauri-apps/api/core";
+import { Memory } from "../lib/api";
+
+/** What the `search_memories` command returns for each hit. */
+type SearchResult = Omit<Memory, "source" | "updatedAt">;
 
 const CATEGORIES_COLORS: Record<string, string> = {
   preference: "bg-purple-100 text-purple-800 dark:bg-purple-900/50 dark:text-purple-300",
@@ -12,7 +16,7 @@ const CATEGORIES_COLORS: Record<string, string> = {
 
 export default function Search() {
   const [query, setQuery] = useState("");
-  const [results, setResults] = useState<Memory[]>([]);
+  const [results, setResults] = useState<SearchResult[]>([]);
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
   const [options, setOptions] = useState({ limit: 5, threshold: 0.3 });
@@ -23,10 +27,9 @@ export default function Search() {
     try {
       setLoading(true);
       setError(null);
-      const data = await api.searchMemories(query, options);
-      setResults(data.memories || []);
+      setResults(await invoke<SearchResult[]>("search_memories", { query, ...options }));
     } catch (err) {
-      setError(err instanceof Error ? err.message : "Search failed");
+      setError(String(err));
     } finally {
       setLoading(false);
     }
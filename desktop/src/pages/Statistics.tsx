@@ -1,6 +1,8 @@
 import { useState, useEffect } from "react";
 import { motion } from "framer-motion";
+import { invoke } from "@tauri-apps/api/core";
 import { api } from "../lib/api";
+import TrendsChart from "../components/TrendsChart";
 
 interface StatusData {
   memory: {
@@ -11,6 +13,26 @@ interface StatusData {
   };
 }
 
+interface CountEntry {
+  name: string;
+  count: number;
+}
+
+interface MemoryStats {
+  total: number;
+  byTag: CountEntry[];
+  bySource: CountEntry[];
+  byWeek: { weekStart: string; added: number; total: number }[];
+}
+
+interface DayCount {
+  date: string;
+  created: number;
+}
+
+/** Tags listed before the rest are summed into "Other". */
+const TOP_TAGS = 10;
+
 interface Conflict {
   conflictId: string;
   memories: Array<{ id: string; content: string; confidence: number }>;
@@ -19,6 +41,8 @@ interface Conflict {
 export default function Statistics() {
   const [status, setStatus] = useState<StatusData | null>(null);
   const [conflicts, setConflicts] = useState<Conflict[]>([]);
+  const [stats, setStats] = useState<MemoryStats | null>(null);
+  const [timeline, setTimeline] = useState<DayCount[] | null>(null);
   const [loading, setLoading] = useState(true);
   const [consolidating, setConsolidating] = useState(false);
   const [error, setError] = useState<string | null>(null);
@@ -36,6 +60,13 @@ export default function Statistics() {
       ]);
       setStatus(statusData);
       setConflicts(conflictsData.conflicts || []);
+      // Read from the database directly; the page works without it
+      invoke<MemoryStats>("get_stats")
+        .then(setStats)
+        .catch(() => setStats(null));
+      invoke<DayCount[]>("get_memory_timeline")
+        .then(setTimeline)
+        .catch(() => setTimeline(null));
       setError(null);
     } catch (err) {
       setError(err instanceof Error ? err.message : "Failed to load data");
@@ -177,6 +208,74 @@ export default function Statistics() {
         )}
       </div>
 
+      {timeline && (
+        <div className="glass rounded-[10px] p-6 border border-gray-200/50 dark:border-gray-700/50">
+          <h3 className="text-base font-semibold mb-4">Memories Added per Day</h3>
+          <TrendsChart data={timeline} />
+        </div>
+      )}
+
+      {stats && (
+        <>
+          {/* Growth */}
+          <div className="glass rounded-[10px] p-6 border border-gray-200/50 dark:border-gray-700/50">
+            <h3 className="text-base font-semibold mb-4">Memories Added per Week</h3>
+            <TrendsChart
+              data={stats.byWeek.map((week) => ({ date: week.weekStart, created: week.added }))}
+            />
+          </div>
+
+          <div className="grid grid-cols-2 gap-4">
+            {[
+              {
+                title: "Memories by Tag",
+                entries:
+                  stats.byTag.length > TOP_TAGS
+                    ? [
+                        ...stats.byTag.slice(0, TOP_TAGS),
+                        {
+                          name: "Other",
+                          count: stats.byTag.slice(TOP_TAGS).reduce((sum, e) => sum + e.count, 0),
+                        },
+                      ]
+                    : stats.byTag,
+                color: "bg-purple-500",
+              },
+              { title: "Memories by Source", entries: stats.bySource, color: "bg-amber-500" },
+            ].map((section) => (
+              <div
+                key={section.title}
+                className="glass rounded-[10px] p-6 border border-gray-200/50 dark:border-gray-700/50"
+              >
+                <h3 className="text-base font-semibold mb-4">{section.title}</h3>
+                {section.entries.length === 0 ? (
+                  <p className="text-sm" style={{ color: "rgba(var(--text-secondary), 1)" }}>No memories yet</p>
+                ) : (
+                  <div className="space-y-3">
+                    {section.entries.map((entry) => {
+                      const pct = stats.total > 0 ? ((entry.count / stats.total) * 100).toFixed(1) : "0.0";
+                      return (
+                        <div key={entry.name}>
+                          <div className="flex justify-between items-center mb-1">
+                            <span className="text-sm font-medium truncate">{entry.name}</span>
+                            <span className="text-xs" style={{ color: "rgba(var(--text-secondary), 1)" }}>
+                              {entry.count}
+                            </span>
+                          </div>
+                          <div className="w-full bg-gray-200 dark:bg-gray-700 rounded-full h-1.5">
+                            <div className={`h-full rounded-full ${section.color}`} style={{ width: `${pct}%` }} />
+                          </div>
+                        </div>
+                      );
+                    })}
+                  </div>
+                )}
+              </div>
+            ))}
+          </div>
+        </>
+      )}
+
       {/* Conflicts */}
       <div className="glass rounded-[10px] p-6 border border-gray-200/50 dark:border-gray-700/50">
         <h3 className="text-base font-semibold mb-4">Detected Conflicts</h3>
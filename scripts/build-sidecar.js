@@ -1,5 +1,5 @@
 #!/usr/bin/env node
-import { existsSync, mkdirSync, rmSync, copyFileSync, cpSync, chmodSync, readFileSync } from 'fs';
+import { existsSync, mkdirSync, rmSync, copyFileSync, cpSync, chmodSync, readFileSync, readdirSync } from 'fs';
 import { join, dirname } from 'path';
 import { fileURLToPath } from 'url';
 import { exec } from 'child_process';
@@ -14,14 +14,22 @@ const pkgVersion = JSON.parse(readFileSync(join(projectRoot, 'package.json'), 'u
 const resourcesDir = join(projectRoot, 'desktop/src-tauri/resources');
 const nodeModulesDir = join(projectRoot, 'node_modules');
 
-// Platform guard — sidecar build currently targets macOS only
-if (process.platform !== 'darwin') {
-  console.error(`Error: build-sidecar.js currently only supports macOS (darwin). Detected: ${process.platform}`);
+// Platform guard — sidecar build targets macOS, Windows, and Linux
+const TARGET_TRIPLES = {
+  darwin: { arm64: 'aarch64-apple-darwin', x64: 'x86_64-apple-darwin' },
+  win32: { arm64: 'aarch64-pc-windows-msvc', x64: 'x86_64-pc-windows-msvc' },
+  linux: { arm64: 'aarch64-unknown-linux-gnu', x64: 'x86_64-unknown-linux-gnu' },
+};
+if (!TARGET_TRIPLES[process.platform]) {
+  console.error(`Error: build-sidecar.js supports macOS (darwin), Windows (win32), and Linux. Detected: ${process.platform}`);
   process.exit(1);
 }
+const isWindows = process.platform === 'win32';
 
-// Architecture mapping
-const archSuffix = process.arch === 'arm64' ? 'aarch64-apple-darwin' : 'x86_64-apple-darwin';
+// Architecture mapping (must match arch_suffix/ort_platform in desktop/src-tauri/src/sidecar.rs)
+const archSuffix = TARGET_TRIPLES[process.platform][process.arch === 'arm64' ? 'arm64' : 'x64'];
+const exeSuffix = isWindows ? '.exe' : '';
+const ortPlatform = process.platform;
 const ortArch = process.arch === 'arm64' ? 'arm64' : 'x64';
 
 function copyFile(src, dest) {
@@ -40,7 +48,7 @@ async function buildSidecar() {
     join(resourcesDir, 'engram-sidecar-x86_64-apple-darwin'),
     join(resourcesDir, '.bundle'),
     join(resourcesDir, 'engram-bundle.cjs'),
-    join(resourcesDir, `node-${archSuffix}`),
+    join(resourcesDir, `node-${archSuffix}${exeSuffix}`),
     join(resourcesDir, 'node_modules'),
     join(resourcesDir, 'models'),
   ];
@@ -57,8 +65,9 @@ async function buildSidecar() {
 
   // Step 1: esbuild bundle
   // Bundle @xenova/transformers INTO the output (pure JS).
-  // Keep native addon packages as external.
-  // Alias onnxruntime-web and sharp to stubs (not needed at runtime).
+  // Keep native addon packages and onnxruntime-web (WASM fallback) as external.
+  // Route onnxruntime-node through a selector that honours ENGRAM_EMBEDDING_BACKEND.
+  // Alias sharp to a stub (not needed at runtime).
   console.log('\nStep 1: Building esbuild bundle...');
   const stubsDir = join(projectRoot, 'scripts/stubs');
   const esbuildCmd = [
@@ -69,14 +78,15 @@ async function buildSidecar() {
     '--format=cjs',
     `--outfile="${join(resourcesDir, 'engram-bundle.cjs')}"`,
     '--external:better-sqlite3',
-    '--external:onnxruntime-node',
+    '--external:onnxruntime-web',
     '--external:onnxruntime-common',
     '--external:bindings',
     '--external:file-uri-to-path',
-    `--alias:onnxruntime-web=${join(stubsDir, 'onnxruntime-web.cjs')}`,
+    `--alias:onnxruntime-node=${join(stubsDir, 'onnxruntime-node.cjs')}`,
     `--alias:sharp=${join(stubsDir, 'sharp.cjs')}`,
     '--define:import.meta.url=__import_meta_url',
     `--define:process.env.ENGRAM_VERSION='"${pkgVersion}"'`,
+    `--define:process.env.ENGRAM_SIDECAR_BUNDLE='"1"'`,
     `--banner:js='var __import_meta_url = require("url").pathToFileURL(__filename).href;'`,
     '--log-level=warning',
   ].join(' ');
@@ -92,10 +102,10 @@ async function buildSidecar() {
 
   // Step 2: Copy Node.js binary
   console.log('\nStep 2: Copying Node.js binary...');
-  const nodeBinaryName = `node-${archSuffix}`;
+  const nodeBinaryName = `node-${archSuffix}${exeSuffix}`;
   const nodeBinaryDest = join(resourcesDir, nodeBinaryName);
   copyFileSync(process.execPath, nodeBinaryDest);
-  chmodSync(nodeBinaryDest, 0o755);
+  if (!isWindows) chmodSync(nodeBinaryDest, 0o755);
   console.log(`  Copied: ${process.execPath} → ${nodeBinaryName}`);
 
   // Step 3: Copy native module packages
@@ -128,23 +138,34 @@ async function buildSidecar() {
   copyFile(join(fSrc, 'index.js'), join(fDest, 'index.js'));
   console.log('  Copied: file-uri-to-path');
 
-  // 3d: onnxruntime-node (native .node + .dylib for current arch)
+  // 3d: onnxruntime-node (native .node + .dylib/.dll for current arch)
   const ortNodeSrc = join(nodeModulesDir, 'onnxruntime-node');
   const ortNodeDest = join(destModules, 'onnxruntime-node');
   copyFile(join(ortNodeSrc, 'package.json'), join(ortNodeDest, 'package.json'));
   for (const f of ['index.js', 'backend.js', 'binding.js']) {
     copyFile(join(ortNodeSrc, 'dist', f), join(ortNodeDest, 'dist', f));
   }
-  const ortNativeDir = join(ortNodeSrc, 'bin/napi-v3/darwin', ortArch);
-  const ortNativeDest = join(ortNodeDest, 'bin/napi-v3/darwin', ortArch);
+  const ortNativeDir = join(ortNodeSrc, 'bin/napi-v3', ortPlatform, ortArch);
+  const ortNativeDest = join(ortNodeDest, 'bin/napi-v3', ortPlatform, ortArch);
   if (existsSync(ortNativeDir)) {
     cpSync(ortNativeDir, ortNativeDest, { recursive: true });
-    console.log(`  Copied: onnxruntime-node (dist/ + bin/napi-v3/darwin/${ortArch}/)`);
+    console.log(`  Copied: onnxruntime-node (dist/ + bin/napi-v3/${ortPlatform}/${ortArch}/)`);
   } else {
     console.warn(`  WARNING: onnxruntime-node native dir not found: ${ortNativeDir}`);
   }
 
-  // 3e: onnxruntime-common (pure JS, required by onnxruntime-node)
+  // 3e: onnxruntime-web (Node build + .wasm binaries, used by the WASM embedding backend)
+  const ortWebSrc = join(nodeModulesDir, 'onnxruntime-web');
+  const ortWebDest = join(destModules, 'onnxruntime-web');
+  copyFile(join(ortWebSrc, 'package.json'), join(ortWebDest, 'package.json'));
+  for (const f of readdirSync(join(ortWebSrc, 'dist'))) {
+    if (f === 'ort-web.node.js' || f.endsWith('.wasm')) {
+      copyFile(join(ortWebSrc, 'dist', f), join(ortWebDest, 'dist', f));
+    }
+  }
+  console.log('  Copied: onnxruntime-web (dist/ort-web.node.js + .wasm)');
+
+  // 3f: onnxruntime-common (pure JS, required by onnxruntime-node and onnxruntime-web)
   const ortCommonSrc = join(nodeModulesDir, 'onnxruntime-common');
   const ortCommonDest = join(destModules, 'onnxruntime-common');
   copyFile(join(ortCommonSrc, 'package.json'), join(ortCommonDest, 'package.json'));
@@ -174,7 +195,7 @@ async function buildSidecar() {
   console.log(`  engram-bundle.cjs`);
   console.log(`  ${nodeBinaryName}`);
   console.log(`  models/       (Xenova/all-MiniLM-L6-v2 embedding model)`);
-  console.log(`  node_modules/  (better-sqlite3, bindings, file-uri-to-path, onnxruntime-node, onnxruntime-common)`);
+  console.log(`  node_modules/  (better-sqlite3, bindings, file-uri-to-path, onnxruntime-node, onnxruntime-web, onnxruntime-common)`);
   console.log(`\nTest manually:`);
   console.log(`  NODE_PATH=${join(resourcesDir, 'node_modules')} \\`);
   console.log(`    ${nodeBinaryDest} \\`);
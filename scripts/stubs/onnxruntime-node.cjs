@@ -0,0 +1,7 @@
+// Not a stub: picks the ONNX runtime for the sidecar bundle. transformers.js
+// imports onnxruntime-node unconditionally, so the WASM embedding backend
+// (ENGRAM_EMBEDDING_BACKEND=wasm) swaps in onnxruntime-web here without the
+// native library ever loading. createRequire keeps both packages external.
+const load = require('module').createRequire(__filename);
+const wasm = (process.env.ENGRAM_EMBEDDING_BACKEND || '').trim().toLowerCase() === 'wasm';
+module.exports = load(wasm ? 'onnxruntime-web' : 'onnxruntime-node');
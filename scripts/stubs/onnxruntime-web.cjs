@@ -1,3 +0,0 @@
-// Stub: @xenova/transformers imports both onnxruntime-node and onnxruntime-web
-// but only uses onnxruntime-node in Node.js. This stub satisfies the import.
-module.exports = {};
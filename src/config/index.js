@@ -32,6 +32,11 @@ const DEFAULT_CONFIG = {
     duplicateThreshold: 0.92,
     decayEnabled: true
   },
+  summarization: {
+    enabled: true,
+    thresholdChars: 4000,
+    maxSentences: 5
+  },
   security: {
     secretDetection: true,
     auditLog: false
@@ -148,3 +153,30 @@ export function getDatabasePath(config) {
 export function getModelsPath(config) {
   return path.join(config.dataDir, 'models');
 }
+
+/**
+ * Get the path of the read-only flag file from config.
+ * The desktop app writes it when free disk space runs low.
+ * @param {Object} config - Configuration object
+ * @returns {string} Path to the flag file
+ */
+export function getReadOnlyFlagPath(config) {
+  return path.join(config.dataDir, 'read-only.json');
+}
+
+/**
+ * Why Engram is read-only, if it is.
+ * Checked on each write so the REST and MCP servers agree without a restart.
+ * @param {Object} config - Configuration object
+ * @returns {string|null} Reason, or null when writes are allowed
+ */
+export function getReadOnlyReason(config) {
+  const flagPath = getReadOnlyFlagPath(config);
+  if (!fs.existsSync(flagPath)) return null;
+  try {
+    const flag = JSON.parse(fs.readFileSync(flagPath, 'utf-8'));
+    return flag.reason || 'Engram is read-only';
+  } catch {
+    return 'Engram is read-only';
+  }
+}
@@ -0,0 +1,98 @@
+import { register, createRequire } from 'node:module';
+import path from 'path';
+import * as logger from '../utils/logger.js';
+
+/**
+ * Environment variable selecting the embedding backend. The desktop app sets
+ * it to `wasm` after the native onnxruntime library fails to load.
+ */
+export const EMBEDDING_BACKEND_ENV = 'ENGRAM_EMBEDDING_BACKEND';
+
+/**
+ * ONNX runtime package behind each embedding backend
+ */
+export const ONNX_RUNTIMES = {
+  native: 'onnxruntime-node',
+  wasm: 'onnxruntime-web'
+};
+
+/**
+ * Loaded @xenova/transformers module
+ */
+let transformers = null;
+
+/**
+ * Backend the loaded module runs on
+ */
+let activeBackend = null;
+
+/**
+ * Read the embedding backend from the environment
+ * @param {Object} [env=process.env]
+ * @returns {'native'|'wasm'} Backend name, `native` when unset or unknown
+ */
+export function resolveEmbeddingBackend(env = process.env) {
+  const value = env[EMBEDDING_BACKEND_ENV]?.trim().toLowerCase();
+  if (!value) {
+    return 'native';
+  }
+  if (Object.hasOwn(ONNX_RUNTIMES, value)) {
+    return value;
+  }
+  logger.warn('Unknown embedding backend, using native', { value });
+  return 'native';
+}
+
+/**
+ * Get the backend the embedding pipeline runs on
+ * @returns {string|null} Backend name, or null before the model is first loaded
+ */
+export function getEmbeddingBackend() {
+  return activeBackend;
+}
+
+/**
+ * Load @xenova/transformers on the selected backend.
+ * transformers.js imports onnxruntime-node unconditionally, so the WASM
+ * backend swaps in onnxruntime-web before the first import: through a
+ * resolve hook when running from source, and through
+ * scripts/stubs/onnxruntime-node.cjs in the sidecar bundle.
+ * @param {string} [backend] - Backend name (default: from the environment)
+ * @returns {Promise<Object>} The transformers module
+ */
+export async function loadTransformers(backend = resolveEmbeddingBackend()) {
+  if (transformers) {
+    return transformers;
+  }
+
+  if (backend === 'wasm' && !process.env.ENGRAM_SIDECAR_BUNDLE) {
+    register('./onnx-hooks.js', import.meta.url);
+  }
+
+  let loaded;
+  try {
+    loaded = await import('@xenova/transformers');
+  } catch (error) {
+    if (backend === 'native') {
+      // A failed import stays in the module cache, so WASM can't be tried
+      // in-process; exit instead and let the desktop app restart the
+      // sidecar with ENGRAM_EMBEDDING_BACKEND=wasm
+      logger.error('Failed to load onnxruntime native backend', { error: error.message });
+      process.exit(1);
+    }
+    throw error;
+  }
+
+  if (backend === 'wasm') {
+    // Load the .wasm binaries from the installed package rather than a CDN,
+    // single-threaded since Node has no browser workers to spread across
+    const runtimeDir = path.dirname(createRequire(import.meta.url).resolve(ONNX_RUNTIMES.wasm));
+    loaded.env.backends.onnx.wasm.wasmPaths = runtimeDir + path.sep;
+    loaded.env.backends.onnx.wasm.numThreads = 1;
+  }
+
+  logger.info('Embedding backend selected', { backend, runtime: ONNX_RUNTIMES[backend] });
+  transformers = loaded;
+  activeBackend = backend;
+  return transformers;
+}
@@ -1,8 +1,8 @@
-import { pipeline } from '@xenova/transformers';
 import fs from 'fs';
 import path from 'path';
 import { fileURLToPath } from 'url';
 import * as logger from '../utils/logger.js';
+import { loadTransformers, getEmbeddingBackend } from './backend.js';
 
 const __filename = fileURLToPath(import.meta.url);
 const __dirname = path.dirname(__filename);
@@ -142,6 +142,7 @@ async function _doInitializePipeline(modelsPath) {
     logger.info('Loading embedding model (this may take a moment on first run)...');
 
     // Create pipeline
+    const { pipeline } = await loadTransformers();
     cachedPipeline = await pipeline(
       MODEL_CONFIG.task,
       MODEL_CONFIG.name,
@@ -339,6 +340,7 @@ export function getModelInfo(modelsPath) {
   return {
     name: MODEL_CONFIG.name,
     task: MODEL_CONFIG.task,
+    backend: getEmbeddingBackend(),
     available,
     loading: pipelineLoading,
     cached: pipelineLoaded,
@@ -0,0 +1,11 @@
+/**
+ * Module resolution hooks registered for the WASM embedding backend.
+ * Resolves transformers.js's onnxruntime-node import to onnxruntime-web, so
+ * the native library is never loaded.
+ */
+export async function resolve(specifier, context, nextResolve) {
+  if (specifier === 'onnxruntime-node') {
+    return nextResolve('onnxruntime-web', context);
+  }
+  return nextResolve(specifier, context);
+}
@@ -0,0 +1,76 @@
+/**
+ * Words too common to say anything about what a text is about
+ */
+const STOPWORDS = new Set([
+  'a', 'an', 'and', 'are', 'as', 'at', 'be', 'been', 'but', 'by', 'can', 'do',
+  'does', 'for', 'from', 'has', 'have', 'he', 'her', 'his', 'how', 'i', 'if',
+  'in', 'into', 'is', 'it', 'its', 'me', 'my', 'no', 'not', 'of', 'on', 'or',
+  'our', 'she', 'so', 'that', 'the', 'their', 'them', 'then', 'there', 'these',
+  'they', 'this', 'to', 'too', 'up', 'us', 'was', 'we', 'were', 'what', 'when',
+  'which', 'who', 'will', 'with', 'would', 'you', 'your'
+]);
+
+/**
+ * Split text into sentences, treating line breaks as boundaries too
+ * @param {string} text - Text to split
+ * @returns {string[]} Trimmed, non-empty sentences
+ */
+export function splitSentences(text) {
+  return text
+    .split(/\n+|(?<=[.!?])\s+/)
+    .map(s => s.trim())
+    .filter(s => s.length > 0);
+}
+
+function words(sentence) {
+  return (sentence.toLowerCase().match(/[\p{L}\p{N}][\p{L}\p{N}'_-]*/gu) || [])
+    .filter(w => w.length > 1 && !STOPWORDS.has(w));
+}
+
+/**
+ * Extractive summary: pick the sentences whose words occur most often across
+ * all texts, and return them in their original order
+ * @param {string[]} texts - Texts to summarize together
+ * @param {Object} [options]
+ * @param {number} [options.maxSentences=5] - Sentences to keep
+ * @returns {{summary: string, sentences: number}} Summary and how many sentences it has
+ */
+export function summarize(texts, options = {}) {
+  const maxSentences = Math.max(1, options.maxSentences || 5);
+  const sentences = texts.flatMap(t => splitSentences(String(t || '')));
+  if (sentences.length <= maxSentences) {
+    return { summary: sentences.join(' '), sentences: sentences.length };
+  }
+
+  const frequency = new Map();
+  for (const sentence of sentences) {
+    for (const word of words(sentence)) {
+      frequency.set(word, (frequency.get(word) || 0) + 1);
+    }
+  }
+
+  const seen = new Set();
+  const ranked = sentences
+    .map((sentence, index) => {
+      const ws = words(sentence);
+      const score = ws.length === 0
+        ? 0
+        : ws.reduce((sum, w) => sum + frequency.get(w), 0) / Math.sqrt(ws.length);
+      return { sentence, index, score };
+    })
+    // Repeated sentences (e.g. the same note captured twice) count once
+    .filter(({ sentence }) => {
+      const key = sentence.toLowerCase();
+      if (seen.has(key)) return false;
+      seen.add(key);
+      return true;
+    })
+    .sort((a, b) => b.score - a.score || a.index - b.index)
+    .slice(0, maxSentences)
+    .sort((a, b) => a.index - b.index);
+
+  return {
+    summary: ranked.map(r => r.sentence).join(' '),
+    sentences: ranked.length
+  };
+}
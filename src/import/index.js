@@ -9,7 +9,9 @@ const PARSERS = {
   ssh: () => import('./parsers/ssh.js'),
   shell: () => import('./parsers/shell.js'),
   obsidian: () => import('./parsers/obsidian.js'),
-  env: () => import('./parsers/env.js')
+  env: () => import('./parsers/env.js'),
+  chatgpt: () => import('./parsers/chatgpt.js'),
+  'claude-export': () => import('./parsers/claude-export.js')
 };
 
 /**
@@ -139,7 +141,9 @@ export async function commitMemories(db, memories, options = {}) {
     namespace,
     createMemoryFn,
     generateEmbeddingFn,
-    validateContentFn
+    validateContentFn,
+    longMemoryThreshold,
+    onLongMemory
   } = options;
 
   const startTime = Date.now();
@@ -218,6 +222,9 @@ export async function commitMemories(db, memories, options = {}) {
       switch (commitResult.status) {
         case 'created':
           results.created++;
+          if (onLongMemory && longMemoryThreshold && memoryData.content.length >= longMemoryThreshold) {
+            onLongMemory(commitResult.id);
+          }
           break;
         case 'duplicate':
           results.duplicates++;
@@ -0,0 +1,273 @@
+import fs from 'fs';
+import path from 'path';
+import os from 'os';
+import { validateContent } from '../../extract/secrets.js';
+import { extractMemories } from '../../extract/rules.js';
+
+/**
+ * Where unzipped OpenAI data exports usually end up
+ */
+const EXPORT_SEARCH_DIRS = [
+  path.join(os.homedir(), 'Downloads'),
+  path.join(os.homedir(), 'Documents')
+];
+
+/**
+ * Phrases that mark the wrap-up of an assistant answer
+ */
+const CONCLUSION_MARKERS = /\b(in summary|to summarize|in conclusion|tl;?dr|bottom line|overall,|key takeaways?)\b/i;
+
+/**
+ * Whether a directory looks like an OpenAI export: conversations.json next to
+ * chat.html, which distinguishes it from other exports using the same file name
+ */
+function isChatGPTExport(dir) {
+  return fs.existsSync(path.join(dir, 'conversations.json')) &&
+    fs.existsSync(path.join(dir, 'chat.html'));
+}
+
+/**
+ * Detect unzipped ChatGPT data exports
+ * @param {Object} [options] - Detection options
+ * @param {string[]} [options.paths] - Additional directories to scan for exports
+ * @returns {{ found: boolean, path: string|null, paths: string[] }}
+ */
+export function detect(options = {}) {
+  const searchDirs = [...EXPORT_SEARCH_DIRS];
+  if (options.paths && Array.isArray(options.paths)) {
+    for (const dir of options.paths) {
+      const resolved = path.resolve(dir);
+      if (!searchDirs.includes(resolved)) {
+        searchDirs.push(resolved);
+      }
+    }
+  }
+
+  const foundPaths = [];
+  const seen = new Set();
+
+  const add = (dir) => {
+    const file = path.join(dir, 'conversations.json');
+    if (!seen.has(file)) {
+      seen.add(file);
+      foundPaths.push(file);
+    }
+  };
+
+  for (const dir of searchDirs) {
+    if (!fs.existsSync(dir)) continue;
+    if (isChatGPTExport(dir)) add(dir);
+
+    // Exports unzip into a folder of their own
+    try {
+      const entries = fs.readdirSync(dir, { withFileTypes: true });
+      for (const entry of entries) {
+        if (!entry.isDirectory() || entry.name.startsWith('.')) continue;
+        const subdir = path.join(dir, entry.name);
+        if (isChatGPTExport(subdir)) add(subdir);
+      }
+    } catch {
+      // Permission denied or other error, skip
+    }
+  }
+
+  return { found: foundPaths.length > 0, path: foundPaths[0] || null, paths: foundPaths };
+}
+
+/**
+ * Parse ChatGPT conversations.json into memory candidates
+ * @param {Object} [options] - Parse options
+ * @param {string} [options.filePath] - Explicit conversations.json to parse
+ * @param {boolean} [options.includeUser=true] - Extract statements from user messages
+ * @param {boolean} [options.includeConclusions=true] - Extract the assistant's closing summary
+ * @param {number} [options.maxConversations=100] - Most recent conversations to read
+ * @param {number} [options.maxPerConversation=10] - Cap on memories per conversation
+ * @param {string|number} [options.since] - Skip conversations created before this date
+ */
+export async function parse(options = {}) {
+  const result = { source: 'chatgpt', memories: [], skipped: [], warnings: [] };
+
+  const files = options.filePath ? [options.filePath] : detect(options).paths;
+  if (files.length === 0) {
+    result.warnings.push('No ChatGPT export found (unzip it and look for conversations.json)');
+    return result;
+  }
+
+  for (const filePath of files) {
+    parseExportFile(filePath, options, result);
+  }
+
+  return result;
+}
+
+/**
+ * Parse one conversations.json and accumulate results
+ */
+function parseExportFile(filePath, options, result) {
+  if (!fs.existsSync(filePath)) {
+    result.warnings.push(`File not found: ${filePath}`);
+    return;
+  }
+
+  let conversations;
+  try {
+    conversations = JSON.parse(fs.readFileSync(filePath, 'utf-8'));
+  } catch (error) {
+    result.warnings.push(`Failed to read ${path.basename(filePath)}: ${error.message}`);
+    return;
+  }
+
+  if (!Array.isArray(conversations)) {
+    result.warnings.push(`${path.basename(filePath)} is not a ChatGPT conversations export`);
+    return;
+  }
+
+  const maxConversations = options.maxConversations || 100;
+  const since = options.since ? new Date(options.since).getTime() / 1000 : null;
+
+  const selected = conversations
+    .filter(c => c && c.mapping)
+    .filter(c => since === null || (c.create_time || 0) >= since)
+    .sort((a, b) => (b.update_time || b.create_time || 0) - (a.update_time || a.create_time || 0))
+    .slice(0, maxConversations);
+
+  for (const conversation of selected) {
+    processConversation(conversation, options, result);
+  }
+
+  if (conversations.length > selected.length && !since) {
+    result.warnings.push(
+      `Read the ${selected.length} most recent of ${conversations.length} conversations`
+    );
+  }
+}
+
+/**
+ * Walk the conversation tree from its current node back to the root, giving
+ * the branch the user actually ended up on (edits create sibling branches)
+ */
+function mainThread(conversation) {
+  const messages = [];
+  const mapping = conversation.mapping;
+  let nodeId = conversation.current_node;
+  const visited = new Set();
+
+  while (nodeId && mapping[nodeId] && !visited.has(nodeId)) {
+    visited.add(nodeId);
+    const node = mapping[nodeId];
+    if (node.message) messages.push(node.message);
+    nodeId = node.parent;
+  }
+
+  return messages.reverse();
+}
+
+/**
+ * Plain text of a message, ignoring images, code-interpreter output, etc.
+ */
+function messageText(message) {
+  const content = message.content || {};
+  if (content.content_type !== 'text' || !Array.isArray(content.parts)) return '';
+  return content.parts.filter(p => typeof p === 'string').join('\n').trim();
+}
+
+/**
+ * The paragraph of an assistant answer that wraps it up, if any
+ */
+function findConclusion(text) {
+  const paragraphs = text.split(/\n\s*\n/).map(p => p.trim()).filter(Boolean);
+  for (let i = paragraphs.length - 1; i >= 0; i--) {
+    if (CONCLUSION_MARKERS.test(paragraphs[i])) {
+      return paragraphs[i]
+        .replace(/^#+\s+/gm, '')
+        .replace(/[*_`]/g, '')
+        .replace(/\s+/g, ' ')
+        .trim();
+    }
+  }
+  return null;
+}
+
+/**
+ * Turn a conversation title into a tag
+ */
+function titleTag(title) {
+  return title
+    .toLowerCase()
+    .replace(/[^a-z0-9]+/g, '-')
+    .replace(/^-+|-+$/g, '')
+    .substring(0, 40);
+}
+
+/**
+ * Process a single conversation into memory candidates
+ */
+function processConversation(conversation, options, result) {
+  const includeUser = options.includeUser !== false;
+  const includeConclusions = options.includeConclusions !== false;
+  const maxPerConversation = options.maxPerConversation || 10;
+
+  const title = (conversation.title || 'Untitled').trim();
+  const date = conversation.create_time
+    ? new Date(conversation.create_time * 1000).toISOString().substring(0, 10)
+    : null;
+  const tags = ['chatgpt', 'conversation', titleTag(title), date].filter(Boolean);
+  const prefix = date
+    ? `From ChatGPT conversation "${title}" (${date})`
+    : `From ChatGPT conversation "${title}"`;
+
+  const candidates = [];
+  const messages = mainThread(conversation);
+
+  if (includeUser) {
+    for (const message of messages) {
+      if (message.author?.role !== 'user') continue;
+      for (const extracted of extractMemories(messageText(message))) {
+        candidates.push({
+          content: extracted.content,
+          category: extracted.category,
+          entity: extracted.entity,
+          confidence: Math.min(extracted.confidence, 0.85)
+        });
+      }
+    }
+  }
+
+  if (includeConclusions) {
+    const lastAnswer = [...messages].reverse().find(m => m.author?.role === 'assistant');
+    const conclusion = lastAnswer ? findConclusion(messageText(lastAnswer)) : null;
+    if (conclusion && conclusion.length >= 20) {
+      candidates.push({
+        content: conclusion.substring(0, 500),
+        category: 'outcome',
+        entity: null,
+        confidence: 0.7
+      });
+    }
+  }
+
+  for (const candidate of candidates.slice(0, maxPerConversation)) {
+    const validation = validateContent(candidate.content, { autoRedact: false });
+    if (!validation.valid) {
+      result.skipped.push({ content: candidate.content.substring(0, 50), reason: 'Contains secrets' });
+      continue;
+    }
+
+    result.memories.push({
+      content: `${prefix}: ${candidate.content}`,
+      category: candidate.category,
+      entity: candidate.entity,
+      confidence: candidate.confidence,
+      tags,
+      source: 'import:chatgpt'
+    });
+  }
+}
+
+export const meta = {
+  name: 'chatgpt',
+  label: 'ChatGPT data export',
+  description: 'Statements and conclusions from an OpenAI account export (conversations.json)',
+  category: 'fact',
+  locations: ['~/Downloads/*/conversations.json']
+};
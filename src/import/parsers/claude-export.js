@@ -0,0 +1,264 @@
+import fs from 'fs';
+import path from 'path';
+import os from 'os';
+import { validateContent } from '../../extract/secrets.js';
+import { extractMemories } from '../../extract/rules.js';
+
+/**
+ * Where unzipped Claude data exports usually end up
+ */
+const EXPORT_SEARCH_DIRS = [
+  path.join(os.homedir(), 'Downloads'),
+  path.join(os.homedir(), 'Documents')
+];
+
+/**
+ * Whether a directory looks like a Claude export: users.json alongside
+ * conversations.json and/or projects.json
+ */
+function isClaudeExport(dir) {
+  return fs.existsSync(path.join(dir, 'users.json')) &&
+    (fs.existsSync(path.join(dir, 'conversations.json')) ||
+      fs.existsSync(path.join(dir, 'projects.json')));
+}
+
+/**
+ * Detect unzipped Claude data exports
+ * @param {Object} [options] - Detection options
+ * @param {string[]} [options.paths] - Additional directories to scan for exports
+ * @returns {{ found: boolean, path: string|null, paths: string[] }}
+ */
+export function detect(options = {}) {
+  const searchDirs = [...EXPORT_SEARCH_DIRS];
+  if (options.paths && Array.isArray(options.paths)) {
+    for (const dir of options.paths) {
+      const resolved = path.resolve(dir);
+      if (!searchDirs.includes(resolved)) {
+        searchDirs.push(resolved);
+      }
+    }
+  }
+
+  const foundPaths = [];
+  const seen = new Set();
+
+  const add = (dir) => {
+    if (!seen.has(dir)) {
+      seen.add(dir);
+      foundPaths.push(dir);
+    }
+  };
+
+  for (const dir of searchDirs) {
+    if (!fs.existsSync(dir)) continue;
+    if (isClaudeExport(dir)) add(dir);
+
+    // Exports unzip into a folder of their own
+    try {
+      const entries = fs.readdirSync(dir, { withFileTypes: true });
+      for (const entry of entries) {
+        if (!entry.isDirectory() || entry.name.startsWith('.')) continue;
+        const subdir = path.join(dir, entry.name);
+        if (isClaudeExport(subdir)) add(subdir);
+      }
+    } catch {
+      // Permission denied or other error, skip
+    }
+  }
+
+  return { found: foundPaths.length > 0, path: foundPaths[0] || null, paths: foundPaths };
+}
+
+/**
+ * Parse a Claude data export (projects.json and conversations.json) into memory candidates
+ * @param {Object} [options] - Parse options
+ * @param {string} [options.exportPath] - Explicit export directory to parse
+ * @param {boolean} [options.includeKnowledge=true] - Import project knowledge documents
+ * @param {boolean} [options.includeConversations=true] - Extract statements from your messages
+ * @param {number} [options.maxConversations=100] - Most recent conversations to read
+ * @param {number} [options.maxPerItem=20] - Cap on memories per document or conversation
+ */
+export async function parse(options = {}) {
+  const result = { source: 'claude-export', memories: [], skipped: [], warnings: [] };
+
+  const dirs = options.exportPath ? [options.exportPath] : detect(options).paths;
+  if (dirs.length === 0) {
+    result.warnings.push('No Claude export found (unzip it and look for projects.json)');
+    return result;
+  }
+
+  for (const dir of dirs) {
+    if (!fs.existsSync(dir)) {
+      result.warnings.push(`Export directory not found: ${dir}`);
+      continue;
+    }
+
+    const projects = readJsonArray(path.join(dir, 'projects.json'), result);
+    const projectNames = new Map(projects.map(p => [p.uuid, p.name]));
+
+    if (options.includeKnowledge !== false) {
+      for (const project of projects) {
+        processProject(project, options, result);
+      }
+    }
+
+    if (options.includeConversations !== false) {
+      const conversations = readJsonArray(path.join(dir, 'conversations.json'), result)
+        .sort((a, b) => String(b.updated_at || '').localeCompare(String(a.updated_at || '')))
+        .slice(0, options.maxConversations || 100);
+      for (const conversation of conversations) {
+        processConversation(conversation, projectNames, options, result);
+      }
+    }
+  }
+
+  return result;
+}
+
+/**
+ * Read a JSON array file from the export; missing files are not an error
+ * since exports only include what the account has
+ */
+function readJsonArray(filePath, result) {
+  if (!fs.existsSync(filePath)) return [];
+
+  try {
+    const data = JSON.parse(fs.readFileSync(filePath, 'utf-8'));
+    if (Array.isArray(data)) return data.filter(Boolean);
+    result.warnings.push(`${path.basename(filePath)} is not a Claude export file`);
+  } catch (error) {
+    result.warnings.push(`Failed to read ${path.basename(filePath)}: ${error.message}`);
+  }
+  return [];
+}
+
+/**
+ * Turn a project name into a tag
+ */
+function projectTag(name) {
+  return 'project:' + name
+    .toLowerCase()
+    .replace(/[^a-z0-9]+/g, '-')
+    .replace(/^-+|-+$/g, '')
+    .substring(0, 40);
+}
+
+/**
+ * Validate and add a memory candidate to the result
+ */
+function addMemory(result, memory) {
+  const validation = validateContent(memory.content, { autoRedact: false });
+  if (!validation.valid) {
+    result.skipped.push({ content: memory.content.substring(0, 50), reason: 'Contains secrets' });
+    return;
+  }
+  result.memories.push({ ...memory, source: 'import:claude-export' });
+}
+
+/**
+ * Process a project's description, instructions, and knowledge documents
+ */
+function processProject(project, options, result) {
+  const name = (project.name || 'Untitled project').trim();
+  const tags = ['claude', 'project', projectTag(name)];
+  const maxPerItem = options.maxPerItem || 20;
+
+  if (project.description && project.description.trim().length >= 15) {
+    addMemory(result, {
+      content: `Claude project "${name}": ${project.description.trim()}`,
+      category: 'fact',
+      entity: null,
+      confidence: 0.85,
+      tags
+    });
+  }
+
+  if (project.prompt_template && project.prompt_template.trim().length >= 15) {
+    addMemory(result, {
+      content: `Instructions for Claude project "${name}": ${project.prompt_template.trim().substring(0, 500)}`,
+      category: 'preference',
+      entity: null,
+      confidence: 0.9,
+      tags
+    });
+  }
+
+  for (const doc of project.docs || []) {
+    if (!doc.content) continue;
+    let count = 0;
+
+    for (const rawLine of doc.content.split('\n')) {
+      if (count >= maxPerItem) break;
+      const line = rawLine.trim();
+
+      // Skip headings, rules, fences, and very short lines
+      if (!line || /^#{1,6}\s+/.test(line) || line === '---' || line.startsWith('```')) continue;
+
+      const cleaned = line
+        .replace(/^[-*]\s+/, '')
+        .replace(/^\d+\.\s+/, '')
+        .replace(/\[([^\]]+)\]\([^)]+\)/g, '$1')
+        .replace(/[*_`~]/g, '')
+        .trim();
+
+      if (cleaned.length < 15) continue;
+
+      addMemory(result, {
+        content: `From "${doc.filename || 'knowledge'}" in Claude project "${name}": ${cleaned}`,
+        category: 'fact',
+        entity: null,
+        confidence: 0.8,
+        tags: [...tags, 'knowledge']
+      });
+      count++;
+    }
+  }
+}
+
+/**
+ * Plain text of a chat message across export format versions
+ */
+function messageText(message) {
+  if (typeof message.text === 'string' && message.text) return message.text;
+  if (!Array.isArray(message.content)) return '';
+  return message.content
+    .filter(part => part.type === 'text' && typeof part.text === 'string')
+    .map(part => part.text)
+    .join('\n');
+}
+
+/**
+ * Extract statements from your side of a conversation
+ */
+function processConversation(conversation, projectNames, options, result) {
+  const title = (conversation.name || 'Untitled').trim();
+  const date = conversation.created_at ? String(conversation.created_at).substring(0, 10) : null;
+  const project = projectNames.get(conversation.project_uuid);
+  const tags = ['claude', 'conversation', project ? projectTag(project) : null, date].filter(Boolean);
+  const maxPerItem = options.maxPerItem || 20;
+
+  let count = 0;
+  for (const message of conversation.chat_messages || []) {
+    if (message.sender !== 'human') continue;
+
+    for (const extracted of extractMemories(messageText(message))) {
+      if (count >= maxPerItem) return;
+      addMemory(result, {
+        content: `From Claude conversation "${title}"${date ? ` (${date})` : ''}: ${extracted.content}`,
+        category: extracted.category,
+        entity: extracted.entity,
+        confidence: Math.min(extracted.confidence, 0.85),
+        tags
+      });
+      count++;
+    }
+  }
+}
+
+export const meta = {
+  name: 'claude-export',
+  label: 'Claude data export',
+  description: 'Project knowledge, instructions, and your statements from a Claude account export',
+  category: 'fact',
+  locations: ['~/Downloads/*/projects.json', '~/Downloads/*/conversations.json']
+};
@@ -3,6 +3,68 @@ import { searchMemories, getMemoriesWithEmbeddings, updateAccessStats } from './
 import { parseTimeFilter, formatTimestamp } from '../utils/time.js';
 import * as logger from '../utils/logger.js';
 
+/**
+ * Built-in ranking weights; the desktop app overrides them through
+ * ENGRAM_SEARCH_*_WEIGHT at launch and `/api/config/reload` afterwards
+ */
+export const DEFAULT_SEARCH_WEIGHTS = { semantic: 0.45, keyword: 0.1, recency: 0.15 };
+
+const WEIGHT_ENV = {
+  semantic: 'ENGRAM_SEARCH_SEMANTIC_WEIGHT',
+  keyword: 'ENGRAM_SEARCH_KEYWORD_WEIGHT',
+  recency: 'ENGRAM_SEARCH_RECENCY_WEIGHT'
+};
+
+/**
+ * Read ranking weights from the environment, keeping defaults for unset or invalid values
+ * @param {Object} [env=process.env]
+ * @returns {{semantic: number, keyword: number, recency: number}}
+ */
+export function searchWeightsFromEnv(env = process.env) {
+  const weights = { ...DEFAULT_SEARCH_WEIGHTS };
+  for (const [key, name] of Object.entries(WEIGHT_ENV)) {
+    if (env[name] === undefined) continue;
+    const value = Number(env[name]);
+    if (Number.isFinite(value) && value >= 0) {
+      weights[key] = value;
+    } else {
+      logger.warn('Ignoring invalid search weight', { name, value: env[name] });
+    }
+  }
+  return weights;
+}
+
+let searchWeights = searchWeightsFromEnv();
+
+/**
+ * Replace the ranking weights used by later recalls
+ * @param {Object} weights - Any of `semantic`, `keyword`, `recency`; non-negative numbers
+ * @returns {{semantic: number, keyword: number, recency: number}} The weights now in use
+ * @throws {Error} If a weight is not a non-negative number
+ */
+export function setSearchWeights(weights) {
+  searchWeights = resolveWeights(weights);
+  logger.info('Search weights updated', searchWeights);
+  return { ...searchWeights };
+}
+
+export function getSearchWeights() {
+  return { ...searchWeights };
+}
+
+function resolveWeights(overrides = {}) {
+  const resolved = { ...searchWeights };
+  for (const key of Object.keys(DEFAULT_SEARCH_WEIGHTS)) {
+    if (overrides[key] === undefined) continue;
+    const value = overrides[key];
+    if (typeof value !== 'number' || !Number.isFinite(value) || value < 0) {
+      throw new Error(`Search weight ${key} must be a non-negative number`);
+    }
+    resolved[key] = value;
+  }
+  return resolved;
+}
+
 /**
  * Recall memories using hybrid search (embedding similarity + FTS + recency)
  * @param {Database} db - SQLite database instance
@@ -16,6 +78,8 @@ import * as logger from '../utils/logger.js';
  * @param {string} [options.time_filter.after] - Start time (ISO or relative)
  * @param {string} [options.time_filter.before] - End time (ISO or relative)
  * @param {string} [options.time_filter.period] - Period shorthand
+ * @param {Object} [options.weights] - Ranking weights for this recall only (see setSearchWeights)
+ * @param {boolean} [options.track_access=true] - Count the results as recalled
  * @param {string} modelsPath - Path to models directory
  * @returns {Promise<Object[]>} Array of relevant memories with scores
  */
@@ -25,8 +89,11 @@ export async function recallMemories(db, query, options = {}, modelsPath) {
     category,
     namespace,
     threshold = 0.3,
-    time_filter
+    time_filter,
+    weights,
+    track_access = true
   } = options;
+  const ranking = resolveWeights(weights);
 
   // Parse time filter if provided
   const timeRange = parseTimeFilter(time_filter);
@@ -56,7 +123,7 @@ export async function recallMemories(db, query, options = {}, modelsPath) {
 
     // Step 3: Score each candidate
     const scored = candidates.map(memory => {
-      const scores = calculateScores(memory, queryEmbedding, candidates);
+      const scores = calculateScores(memory, queryEmbedding, ranking);
 
       return {
         ...memory,
@@ -81,7 +148,7 @@ export async function recallMemories(db, query, options = {}, modelsPath) {
     const results = filtered.slice(0, limit);
 
     // Step 8: Update access stats for returned memories
-    if (results.length > 0) {
+    if (track_access && results.length > 0) {
       const ids = results.map(m => m.id);
       updateAccessStats(db, ids);
     }
@@ -176,10 +243,10 @@ async function fetchCandidates(db, query, namespace, timeRange) {
  * Calculate all component scores for a memory
  * @param {Object} memory - Memory to score
  * @param {Float32Array} queryEmbedding - Query embedding
- * @param {Object[]} allCandidates - All candidate memories (for FTS boost calculation)
+ * @param {{semantic: number, keyword: number, recency: number}} weights - Ranking weights
  * @returns {Object} Scores object
  */
-function calculateScores(memory, queryEmbedding, allCandidates) {
+function calculateScores(memory, queryEmbedding, weights) {
   // Similarity score (cosine similarity if embedding exists)
   let similarity = 0;
   if (memory.embedding && queryEmbedding) {
@@ -200,11 +267,12 @@ function calculateScores(memory, queryEmbedding, allCandidates) {
   const feedback = (feedbackScore + 1) / 2;
 
   // FTS boost (if memory appeared in FTS results)
-  const ftsBoost = memory.fromFTS ? 0.1 : 0;
+  const ftsBoost = memory.fromFTS ? weights.keyword : 0;
 
   // Final score calculation with feedback component
-  // Adjusted weights: similarity=0.45, recency=0.15, confidence=0.15, access=0.05, feedback=0.10
-  const final = (similarity * 0.45) + (recency * 0.15) + (confidence * 0.15) + (access * 0.05) + (feedback * 0.10) + ftsBoost;
+  // Similarity and recency weights are configurable (defaults 0.45 and 0.15);
+  // confidence=0.15, access=0.05, feedback=0.10 are fixed
+  const final = (similarity * weights.semantic) + (recency * weights.recency) + (confidence * 0.15) + (access * 0.05) + (feedback * 0.10) + ftsBoost;
 
   return {
     similarity,
@@ -343,6 +411,11 @@ export function formatRecallResults(memories) {
 
     lines.push(`[${num}] (${category}, confidence: ${confidence}, id: ${id})${score}`);
     lines.push(memory.content);
+    for (const ref of memory.refs || []) {
+      const title = ref.title ? ` "${ref.title}"` : '';
+      const status = ref.status ? ` (${ref.status})` : '';
+      lines.push(`  Ref: ${ref.key}${title}${status}`);
+    }
     lines.push('');
   });
 
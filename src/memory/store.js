@@ -156,6 +156,41 @@ function runMigrations(db) {
     CREATE INDEX IF NOT EXISTS idx_contradictions_detected_at ON contradictions(detected_at);
   `);
 
+  // Typed links between memories (e.g. a summary and the memory it summarizes)
+  db.exec(`
+    CREATE TABLE IF NOT EXISTS memory_links (
+      id TEXT PRIMARY KEY,
+      source_id TEXT NOT NULL,
+      target_id TEXT NOT NULL,
+      relation TEXT NOT NULL,
+      created_at INTEGER NOT NULL,
+      UNIQUE (source_id, target_id, relation),
+      FOREIGN KEY (source_id) REFERENCES memories(id) ON DELETE CASCADE,
+      FOREIGN KEY (target_id) REFERENCES memories(id) ON DELETE CASCADE
+    );
+  `);
+
+  db.exec(`
+    CREATE INDEX IF NOT EXISTS idx_memory_links_source ON memory_links(source_id);
+    CREATE INDEX IF NOT EXISTS idx_memory_links_target ON memory_links(target_id);
+  `);
+
+  // Issue tracker tickets (and similar external records) a memory mentions,
+  // refreshed by the desktop app so recall shows their current state
+  db.exec(`
+    CREATE TABLE IF NOT EXISTS external_refs (
+      memory_id TEXT NOT NULL,
+      ref_key TEXT NOT NULL,
+      provider TEXT NOT NULL,
+      title TEXT,
+      status TEXT,
+      url TEXT,
+      updated_at INTEGER NOT NULL,
+      PRIMARY KEY (memory_id, ref_key),
+      FOREIGN KEY (memory_id) REFERENCES memories(id) ON DELETE CASCADE
+    );
+  `);
+
   logger.debug('Database migrations completed');
 }
 
@@ -469,12 +504,73 @@ export function deleteMemory(db, id) {
   return false;
 }
 
+/**
+ * Quote each word of a user query so FTS5 treats it literally
+ * @param {string} query - Free text
+ * @returns {string} FTS5 query matching all words
+ */
+function literalFtsQuery(query) {
+  return query
+    .split(/\s+/)
+    .filter(Boolean)
+    .map(word => `"${word.replace(/"/g, '""')}"`)
+    .join(' ');
+}
+
+/**
+ * WHERE clause shared by listMemories and countMemories
+ * @param {Object} options - Filters accepted by listMemories
+ * @returns {{ where: string, params: Array }}
+ */
+function listFilter(options) {
+  const { namespace, category, tags, since, until, query } = options;
+  let where = 'WHERE 1=1';
+  const params = [];
+
+  if (namespace) {
+    where += ' AND namespace = ?';
+    params.push(namespace);
+  }
+
+  if (category) {
+    where += ' AND category = ?';
+    params.push(category);
+  }
+
+  for (const tag of tags || []) {
+    where += ' AND EXISTS (SELECT 1 FROM json_each(memories.tags) WHERE value = ?)';
+    params.push(tag);
+  }
+
+  if (since !== undefined) {
+    where += ' AND created_at >= ?';
+    params.push(since);
+  }
+
+  if (until !== undefined) {
+    where += ' AND created_at < ?';
+    params.push(until);
+  }
+
+  const ftsQuery = query ? literalFtsQuery(query) : '';
+  if (ftsQuery) {
+    where += ' AND rowid IN (SELECT rowid FROM memories_fts WHERE memories_fts MATCH ?)';
+    params.push(ftsQuery);
+  }
+
+  return { where, params };
+}
+
 /**
  * List memories with optional filters
  * @param {Database} db - SQLite database instance
  * @param {Object} [options] - Query options
  * @param {string} [options.namespace] - Filter by namespace
  * @param {string} [options.category] - Filter by category
+ * @param {string[]} [options.tags] - Only memories carrying all of these tags
+ * @param {number} [options.since] - Created at or after this time (ms)
+ * @param {number} [options.until] - Created before this time (ms)
+ * @param {string} [options.query] - Full-text match on content, entity, and tags
  * @param {number} [options.limit=50] - Maximum results
  * @param {number} [options.offset=0] - Offset for pagination
  * @param {string} [options.sort='created_at DESC'] - Sort order
@@ -482,35 +578,29 @@ export function deleteMemory(db, id) {
  */
 export function listMemories(db, options = {}) {
   const {
-    namespace,
-    category,
     limit = 50,
     offset = 0,
     sort = 'created_at DESC'
   } = options;
 
-  let query = 'SELECT * FROM memories WHERE 1=1';
-  const params = [];
-
-  if (namespace) {
-    query += ' AND namespace = ?';
-    params.push(namespace);
-  }
-
-  if (category) {
-    query += ' AND category = ?';
-    params.push(category);
-  }
-
-  query += ` ORDER BY ${sort} LIMIT ? OFFSET ?`;
-  params.push(limit, offset);
-
-  const stmt = db.prepare(query);
-  const rows = stmt.all(...params);
+  const { where, params } = listFilter(options);
+  const stmt = db.prepare(`SELECT * FROM memories ${where} ORDER BY ${sort} LIMIT ? OFFSET ?`);
+  const rows = stmt.all(...params, limit, offset);
 
   return rows.map(deserializeMemory);
 }
 
+/**
+ * Count memories matching listMemories filters
+ * @param {Database} db - SQLite database instance
+ * @param {Object} [options] - Same filters as listMemories
+ * @returns {number} Matching memories
+ */
+export function countMemories(db, options = {}) {
+  const { where, params } = listFilter(options);
+  return db.prepare(`SELECT COUNT(*) as count FROM memories ${where}`).get(...params).count;
+}
+
 /**
  * Search memories using FTS
  * @param {Database} db - SQLite database instance
@@ -960,3 +1050,153 @@ function deserializeContradiction(row) {
     } : null
   };
 }
+
+/**
+ * List every tag in use
+ * @param {Database} db
+ * @returns {Object[]} `{ name, count }` by count, most used first, then name
+ */
+export function listTags(db) {
+  return db.prepare(`
+    SELECT tag.value AS name, COUNT(DISTINCT memories.id) AS count
+    FROM memories, json_each(memories.tags) AS tag
+    GROUP BY tag.value
+    ORDER BY count DESC, name ASC
+  `).all();
+}
+
+/**
+ * Replace tags on every memory carrying them, in one transaction
+ * @param {Database} db
+ * @param {string[]} sources - Tags to replace
+ * @param {string|null} target - Tag to put in their place, or null to remove them
+ * @returns {number} Memories updated
+ */
+export function replaceTags(db, sources, target) {
+  const replaced = new Set(sources.filter(tag => tag !== target));
+  if (replaced.size === 0) return 0;
+
+  const placeholders = [...replaced].map(() => '?').join(', ');
+  const rows = db.prepare(`
+    SELECT id, tags FROM memories
+    WHERE EXISTS (SELECT 1 FROM json_each(memories.tags) WHERE value IN (${placeholders}))
+  `).all(...replaced);
+  const update = db.prepare('UPDATE memories SET tags = ?, updated_at = ? WHERE id = ?');
+
+  const now = Date.now();
+  db.transaction(() => {
+    for (const row of rows) {
+      update.run(JSON.stringify(mergeTagList(JSON.parse(row.tags), replaced, target)), now, row.id);
+    }
+  })();
+
+  logger.debug('Tags replaced', { sources: [...replaced], target, memories: rows.length });
+  return rows.length;
+}
+
+/**
+ * Swap `replaced` tags for `target` (or drop them when it's null), keeping
+ * the original order and dropping the duplicates a merge creates
+ */
+function mergeTagList(tags, replaced, target) {
+  const merged = [];
+  for (const tag of tags) {
+    const next = replaced.has(tag) ? target : tag;
+    if (next !== null && !merged.includes(next)) {
+      merged.push(next);
+    }
+  }
+  return merged;
+}
+
+/**
+ * Record a directed link between two memories
+ * @param {Database} db
+ * @param {Object} link
+ * @param {string} link.sourceId - Memory the link starts at (e.g. the summary)
+ * @param {string} link.targetId - Memory it points to (e.g. the full document)
+ * @param {string} link.relation - Kind of link, e.g. 'summarizes'
+ * @returns {Object} The link record
+ */
+export function createLink(db, { sourceId, targetId, relation }) {
+  const id = generateId();
+  db.prepare(`
+    INSERT OR IGNORE INTO memory_links (id, source_id, target_id, relation, created_at)
+    VALUES (?, ?, ?, ?, ?)
+  `).run(id, sourceId, targetId, relation, Date.now());
+
+  const row = db.prepare(`
+    SELECT * FROM memory_links WHERE source_id = ? AND target_id = ? AND relation = ?
+  `).get(sourceId, targetId, relation);
+  return deserializeLink(row);
+}
+
+/**
+ * Get every link from or to a memory
+ * @param {Database} db
+ * @param {string} memoryId
+ * @returns {Object[]} Links, oldest first
+ */
+export function getLinks(db, memoryId) {
+  return db.prepare(`
+    SELECT * FROM memory_links
+    WHERE source_id = ? OR target_id = ?
+    ORDER BY created_at ASC
+  `).all(memoryId, memoryId).map(deserializeLink);
+}
+
+/**
+ * Replace the external records linked to a memory
+ * @param {Database} db
+ * @param {string} memoryId
+ * @param {Object[]} refs
+ * @param {string} refs[].key - Record key, e.g. 'ENG-123'
+ * @param {string} refs[].provider - Where it lives, e.g. 'linear' or 'jira'
+ * @param {string} [refs[].title]
+ * @param {string} [refs[].status]
+ * @param {string} [refs[].url]
+ * @returns {Object[]} The stored refs
+ */
+export function setExternalRefs(db, memoryId, refs) {
+  const now = Date.now();
+  const insert = db.prepare(`
+    INSERT INTO external_refs (memory_id, ref_key, provider, title, status, url, updated_at)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `);
+  db.transaction(() => {
+    db.prepare('DELETE FROM external_refs WHERE memory_id = ?').run(memoryId);
+    for (const ref of refs) {
+      insert.run(memoryId, ref.key, ref.provider, ref.title ?? null, ref.status ?? null, ref.url ?? null, now);
+    }
+  })();
+  return getExternalRefs(db, memoryId);
+}
+
+/**
+ * Get the external records linked to a memory
+ * @param {Database} db
+ * @param {string} memoryId
+ * @returns {Object[]} Refs, by key
+ */
+export function getExternalRefs(db, memoryId) {
+  return db.prepare(`
+    SELECT * FROM external_refs WHERE memory_id = ? ORDER BY ref_key ASC
+  `).all(memoryId).map(row => ({
+    key: row.ref_key,
+    provider: row.provider,
+    title: row.title,
+    status: row.status,
+    url: row.url,
+    updatedAt: row.updated_at
+  }));
+}
+
+function deserializeLink(row) {
+  return {
+    id: row.id,
+    sourceId: row.source_id,
+    targetId: row.target_id,
+    relation: row.relation,
+    createdAt: row.created_at
+  };
+}
@@ -0,0 +1,72 @@
+import { createMemory, createLink, getMemory } from './store.js';
+import { summarize } from '../extract/summarize.js';
+import * as logger from '../utils/logger.js';
+
+/**
+ * Relation from a summary memory to the memory it summarizes
+ */
+export const SUMMARY_RELATION = 'summarizes';
+
+/**
+ * Store a summary of a long memory, linked to it
+ * @param {Database} db - SQLite database instance
+ * @param {string} memoryId - Memory to summarize
+ * @param {Object} [options]
+ * @param {number} [options.maxSentences=5] - Sentences in the summary
+ * @param {Function} [options.embedFn] - Generates the summary's embedding
+ * @returns {Promise<Object|null>} The summary memory, or null if the memory is gone
+ */
+export async function summarizeMemory(db, memoryId, options = {}) {
+  const memory = getMemory(db, memoryId);
+  if (!memory) return null;
+
+  const { summary } = summarize([memory.content], { maxSentences: options.maxSentences });
+  let embedding = null;
+  if (options.embedFn) {
+    try {
+      embedding = await options.embedFn(summary);
+    } catch {
+      // Continue without embedding
+    }
+  }
+
+  const stored = createMemory(db, {
+    content: summary,
+    entity: memory.entity,
+    category: memory.category,
+    confidence: memory.confidence,
+    namespace: memory.namespace,
+    tags: [...new Set([...(memory.tags || []), 'summary'])],
+    source: 'summary',
+    embedding
+  });
+  createLink(db, { sourceId: stored.id, targetId: memory.id, relation: SUMMARY_RELATION });
+
+  logger.info('Summarized long memory', { id: memory.id, summaryId: stored.id });
+  return stored;
+}
+
+/**
+ * Background queue that summarizes memories one at a time, so imports of
+ * long documents return without waiting for their summaries
+ * @param {Database} db - SQLite database instance
+ * @param {Object} [options] - Passed to summarizeMemory
+ * @returns {{enqueue: Function, idle: Function}} enqueue(memoryId), and idle()
+ *   which resolves once every queued job has finished
+ */
+export function createSummaryQueue(db, options = {}) {
+  let tail = Promise.resolve();
+
+  return {
+    enqueue(memoryId) {
+      tail = tail
+        .then(() => summarizeMemory(db, memoryId, options))
+        .catch(error => {
+          logger.warn('Summarization failed', { id: memoryId, error: error.message });
+        });
+    },
+    idle() {
+      return tail;
+    }
+  };
+}
@@ -5,8 +5,8 @@ import {
   ListToolsRequestSchema
 } from '@modelcontextprotocol/sdk/types.js';
 
-import { loadConfig, getDatabasePath, getModelsPath } from '../config/index.js';
-import { initDatabase, createMemory, createMemoryWithDedup, getMemory, deleteMemory, getStats } from '../memory/store.js';
+import { loadConfig, getDatabasePath, getModelsPath, getReadOnlyReason } from '../config/index.js';
+import { initDatabase, createMemory, createMemoryWithDedup, getMemory, deleteMemory, getStats, getExternalRefs } from '../memory/store.js';
 import { recallMemories, formatRecallResults } from '../memory/recall.js';
 import { recordFeedback, getFeedbackStats } from '../memory/feedback.js';
 import { generateContext } from '../memory/context.js';
@@ -250,6 +250,21 @@ export class EngramMCPServer {
       try {
         const { name, arguments: args } = request.params;
 
+        // Tools that write are refused while low disk space has made Engram read-only
+        const writes = ['engram_remember', 'engram_forget', 'engram_feedback'];
+        const readOnlyReason = writes.includes(name) ? getReadOnlyReason(this.config) : null;
+        if (readOnlyReason) {
+          return {
+            content: [
+              {
+                type: 'text',
+                text: `Engram is read-only right now: ${readOnlyReason}. Recall still works.`
+              }
+            ],
+            isError: true
+          };
+        }
+
         switch (name) {
           case 'engram_remember':
             return await this.handleRemember(args);
@@ -401,6 +416,11 @@ export class EngramMCPServer {
       modelsPath
     );
 
+    // Current ticket context for memories that mention issues
+    for (const memory of memories) {
+      memory.refs = getExternalRefs(db, memory.id);
+    }
+
     // Format results
     const formattedResults = formatRecallResults(memories);
 
@@ -3,14 +3,16 @@ import fastifyStatic from '@fastify/static';
 import fs from 'fs';
 import path from 'path';
 import { fileURLToPath } from 'url';
-import { loadConfig, getDatabasePath, getModelsPath } from '../config/index.js';
-import { initDatabase, createMemory, getMemory, deleteMemory, listMemories, getStats, listContradictions, resolveContradiction, countUnresolvedContradictions, migrateTagConflicts } from '../memory/store.js';
-import { recallMemories } from '../memory/recall.js';
+import { loadConfig, getDatabasePath, getModelsPath, getReadOnlyReason } from '../config/index.js';
+import { initDatabase, createMemory, getMemory, updateMemory, deleteMemory, listMemories, countMemories, getStats, listContradictions, resolveContradiction, countUnresolvedContradictions, migrateTagConflicts, getLinks, getExternalRefs, setExternalRefs, listTags, replaceTags } from '../memory/store.js';
+import { createSummaryQueue } from '../memory/summaries.js';
+import { recallMemories, setSearchWeights } from '../memory/recall.js';
 import { consolidate, getConflicts, detectContradictionsForMemory } from '../memory/consolidate.js';
 import { getOverview, getStaleMemories, getNeverRecalled, getDuplicateClusters, getTrends } from '../memory/analytics.js';
 import { calculateHealthScore } from '../memory/health.js';
 import { validateContent } from '../extract/secrets.js';
 import { extractMemory } from '../extract/rules.js';
+import { summarize } from '../extract/summarize.js';
 import { exportToStatic } from '../export/static.js';
 import * as logger from '../utils/logger.js';
 
@@ -79,10 +81,31 @@ export function createRESTServer(config) {
     logger.warn('Tag conflict migration failed', { error: error.message });
   }
 
+  // Long imported documents get a linked summary memory in the background
+  const summarization = config.summarization || {};
+  const summaryQueue = createSummaryQueue(db, {
+    maxSentences: summarization.maxSentences,
+    embedFn: async (content) => {
+      const { generateEmbedding } = await import('../embed/index.js');
+      return generateEmbedding(content, modelsPath);
+    }
+  });
+
+  // Every route as it is registered, for GET /api/routes
+  const routes = [];
+  fastify.addHook('onRoute', (route) => {
+    const methods = Array.isArray(route.method) ? route.method : [route.method];
+    for (const method of methods) {
+      if (method !== 'OPTIONS' && method !== 'HEAD') {
+        routes.push({ method, url: route.url });
+      }
+    }
+  });
+
   // CORS support
   fastify.addHook('onRequest', async (request, reply) => {
     reply.header('Access-Control-Allow-Origin', '*');
-    reply.header('Access-Control-Allow-Methods', 'GET, POST, DELETE, OPTIONS');
+    reply.header('Access-Control-Allow-Methods', 'GET, POST, PUT, PATCH, DELETE, OPTIONS');
     reply.header('Access-Control-Allow-Headers', 'Content-Type');
   });
 
@@ -91,6 +114,19 @@ export function createRESTServer(config) {
     reply.code(204).send();
   });
 
+  // Refuse writes while the desktop app has flagged low disk space.
+  // These POST routes only read (search bumps access counts, which is tiny).
+  const readOnlySafeRoutes = new Set(['/api/memories/search', '/api/config/reload', '/api/import/scan']);
+  fastify.addHook('preHandler', async (request, reply) => {
+    if (['GET', 'HEAD', 'OPTIONS'].includes(request.method)) return;
+    if (readOnlySafeRoutes.has(request.routeOptions.url)) return;
+    const reason = getReadOnlyReason(config);
+    if (reason) {
+      reply.code(503);
+      return reply.send({ error: reason, readOnly: true });
+    }
+  });
+
   const serverVersion = getServerVersion();
 
   // Health check endpoint
@@ -103,6 +139,12 @@ export function createRESTServer(config) {
     };
   });
 
+  // Route listing, used by the desktop app's API explorer
+  fastify.get('/api/routes', async (request, reply) => {
+    const sorted = [...routes].sort((a, b) => a.url.localeCompare(b.url) || a.method.localeCompare(b.method));
+    return { routes: sorted };
+  });
+
   // System status endpoint
   fastify.get('/api/status', async (request, reply) => {
     try {
@@ -128,6 +170,7 @@ export function createRESTServer(config) {
       return {
         status: 'ok',
         version: serverVersion,
+        readOnly: getReadOnlyReason(config),
         memory: {
           total: stats.total,
           withEmbeddings: stats.withEmbeddings,
@@ -156,10 +199,43 @@ export function createRESTServer(config) {
     }
   });
 
+  // Apply runtime-adjustable settings without restarting the server
+  fastify.post('/api/config/reload', async (request, reply) => {
+    const { logLevel, search } = request.body || {};
+    const applied = [];
+
+    if (logLevel !== undefined) {
+      const level = logger.LOG_LEVELS[String(logLevel).toUpperCase()];
+      if (level === undefined) {
+        reply.code(400);
+        return { error: `Unknown log level: ${logLevel}` };
+      }
+      logger.setLogLevel(level);
+      applied.push('logLevel');
+    }
+
+    if (search !== undefined) {
+      try {
+        setSearchWeights({
+          semantic: search?.semanticWeight,
+          keyword: search?.keywordWeight,
+          recency: search?.recencyWeight
+        });
+      } catch (error) {
+        reply.code(400);
+        return { error: error.message };
+      }
+      applied.push('search');
+    }
+
+    logger.info('Configuration reloaded', { applied });
+    return { success: true, applied };
+  });
+
   // Create memory endpoint
   fastify.post('/api/memories', async (request, reply) => {
     try {
-      const { content, category, entity, confidence, namespace, tags } = request.body;
+      const { content, category, entity, confidence, namespace, tags, source } = request.body;
 
       if (!content) {
         reply.code(400);
@@ -188,7 +264,7 @@ export function createRESTServer(config) {
         confidence: confidence !== undefined ? confidence : 0.8,
         namespace: namespace || 'default',
         tags: tags || [],
-        source: 'api'
+        source: source || 'api'
       };
 
       if (!entity || !category) {
@@ -250,21 +326,26 @@ export function createRESTServer(config) {
   // List memories endpoint
   fastify.get('/api/memories', async (request, reply) => {
     try {
-      const { limit = 50, offset = 0, category, namespace } = request.query;
+      const { limit = 50, offset = 0, category, namespace, tags, since, until, q } = request.query;
+
+      // tags is comma-separated; since/until are millisecond timestamps
+      const filters = {
+        category,
+        namespace,
+        tags: tags ? tags.split(',').map(t => t.trim()).filter(Boolean) : undefined,
+        since: since !== undefined ? parseInt(since) : undefined,
+        until: until !== undefined ? parseInt(until) : undefined,
+        query: q
+      };
 
       const memories = listMemories(db, {
+        ...filters,
         limit: parseInt(limit),
-        offset: parseInt(offset),
-        category,
-        namespace
+        offset: parseInt(offset)
       });
 
       // Get total count for pagination
-      let countQuery = 'SELECT COUNT(*) as count FROM memories WHERE 1=1';
-      const countParams = [];
-      if (namespace) { countQuery += ' AND namespace = ?'; countParams.push(namespace); }
-      if (category) { countQuery += ' AND category = ?'; countParams.push(category); }
-      const totalCount = db.prepare(countQuery).get(...countParams).count;
+      const totalCount = countMemories(db, filters);
 
       return {
         success: true,
@@ -276,6 +357,7 @@ export function createRESTServer(config) {
           confidence: m.confidence,
           namespace: m.namespace,
           tags: m.tags,
+          source: m.source,
           accessCount: m.access_count,
           createdAt: m.created_at,
           lastAccessed: m.last_accessed
@@ -296,7 +378,8 @@ export function createRESTServer(config) {
   // Search/recall memories endpoint
   fastify.post('/api/memories/search', async (request, reply) => {
     try {
-      const { query, limit = 5, category, namespace, threshold = 0.3 } = request.body;
+      // `weights` ranks this search only; `preview` leaves access stats untouched
+      const { query, limit = 5, category, namespace, threshold = 0.3, weights, preview = false } = request.body;
 
       if (!query) {
         reply.code(400);
@@ -306,7 +389,18 @@ export function createRESTServer(config) {
       const memories = await recallMemories(
         db,
         query,
-        { limit, category, namespace, threshold },
+        {
+          limit,
+          category,
+          namespace,
+          threshold,
+          track_access: !preview,
+          weights: weights && {
+            semantic: weights.semanticWeight,
+            keyword: weights.keywordWeight,
+            recency: weights.recencyWeight
+          }
+        },
         modelsPath
       );
 
@@ -325,7 +419,8 @@ export function createRESTServer(config) {
           scoreBreakdown: m.scoreBreakdown,
           accessCount: m.access_count,
           createdAt: m.created_at,
-          lastAccessed: m.last_accessed
+          lastAccessed: m.last_accessed,
+          refs: getExternalRefs(db, m.id)
         }))
       };
     } catch (error) {
@@ -356,12 +451,14 @@ export function createRESTServer(config) {
           confidence: memory.confidence,
           namespace: memory.namespace,
           tags: memory.tags,
+          source: memory.source,
           accessCount: memory.access_count,
           decayRate: memory.decay_rate,
           createdAt: memory.created_at,
           updatedAt: memory.updated_at,
           lastAccessed: memory.last_accessed,
-          hasEmbedding: !!memory.embedding
+          hasEmbedding: !!memory.embedding,
+          refs: getExternalRefs(db, memory.id)
         }
       };
     } catch (error) {
@@ -371,6 +468,126 @@ export function createRESTServer(config) {
     }
   });
 
+  // Links from and to a memory (e.g. its summary), for graph views
+  fastify.get('/api/memories/:id/links', async (request, reply) => {
+    try {
+      const { id } = request.params;
+      if (!getMemory(db, id)) {
+        reply.code(404);
+        return { error: 'Memory not found' };
+      }
+      return { success: true, links: getLinks(db, id) };
+    } catch (error) {
+      logger.error('Get memory links error', { error: error.message });
+      reply.code(500);
+      return { error: error.message };
+    }
+  });
+
+  // Replace the issue tracker records linked to a memory
+  fastify.put('/api/memories/:id/refs', async (request, reply) => {
+    try {
+      const { id } = request.params;
+      const { refs } = request.body || {};
+
+      const valid = Array.isArray(refs) && refs.every(ref =>
+        ref && typeof ref.key === 'string' && typeof ref.provider === 'string'
+      );
+      if (!valid) {
+        reply.code(400);
+        return { error: 'refs must be an array of { key, provider, title?, status?, url? }' };
+      }
+      if (!getMemory(db, id)) {
+        reply.code(404);
+        return { error: 'Memory not found' };
+      }
+
+      return { success: true, refs: setExternalRefs(db, id, refs) };
+    } catch (error) {
+      logger.error('Set memory refs error', { error: error.message });
+      reply.code(500);
+      return { error: error.message };
+    }
+  });
+
+  // Update memory endpoint. The id, creation time and recall history are kept;
+  // new content is checked for secrets and re-embedded like a created memory
+  fastify.patch('/api/memories/:id', async (request, reply) => {
+    try {
+      const { id } = request.params;
+      const { content, category, entity, confidence, namespace, tags } = request.body || {};
+
+      if (tags !== undefined && (!Array.isArray(tags) || tags.some(t => typeof t !== 'string'))) {
+        reply.code(400);
+        return { error: 'tags must be an array of strings' };
+      }
+
+      if (content !== undefined && (typeof content !== 'string' || !content.trim())) {
+        reply.code(400);
+        return { error: 'content must be a non-empty string' };
+      }
+
+      const updates = {};
+      if (content !== undefined) {
+        if (!getMemory(db, id)) {
+          reply.code(404);
+          return { error: 'Memory not found' };
+        }
+
+        const validation = validateContent(content, {
+          autoRedact: config.security?.secretDetection !== false
+        });
+        if (!validation.valid) {
+          reply.code(400);
+          return {
+            error: 'Cannot store memory',
+            details: validation.errors,
+            warnings: validation.warnings
+          };
+        }
+        updates.content = validation.content;
+
+        try {
+          const { generateEmbedding } = await import('../embed/index.js');
+          updates.embedding = await generateEmbedding(validation.content, modelsPath);
+        } catch (error) {
+          logger.warn('Failed to generate embedding', { error: error.message });
+        }
+      }
+      if (category !== undefined) updates.category = category;
+      if (entity !== undefined) updates.entity = entity;
+      if (confidence !== undefined) updates.confidence = confidence;
+      if (namespace !== undefined) updates.namespace = namespace;
+      if (tags !== undefined) updates.tags = tags;
+
+      const memory = updateMemory(db, id, updates);
+      if (!memory) {
+        reply.code(404);
+        return { error: 'Memory not found' };
+      }
+
+      logger.info('Memory updated via API', { id, fields: Object.keys(updates) });
+
+      return {
+        success: true,
+        memory: {
+          id: memory.id,
+          content: memory.content,
+          category: memory.category,
+          entity: memory.entity,
+          confidence: memory.confidence,
+          namespace: memory.namespace,
+          tags: memory.tags,
+          updatedAt: memory.updated_at
+        }
+      };
+    } catch (error) {
+      logger.error('Update memory error', { error: error.message });
+      reply.code(500);
+      return { error: error.message };
+    }
+  });
+
   // Delete memory endpoint
   fastify.delete('/api/memories/:id', async (request, reply) => {
     try {
@@ -445,6 +662,34 @@ export function createRESTServer(config) {
     }
   });
 
+  // Summarize memories (by id) and/or raw texts into a few key sentences
+  fastify.post('/api/summarize', async (request, reply) => {
+    try {
+      const { memoryIds = [], contents = [], maxSentences } = request.body || {};
+      if (!Array.isArray(memoryIds) || !Array.isArray(contents)) {
+        reply.code(400);
+        return { error: 'memoryIds and contents must be arrays' };
+      }
+
+      const texts = [...contents.map(String)];
+      for (const id of memoryIds) {
+        const memory = getMemory(db, id);
+        if (memory) texts.push(memory.content);
+      }
+      if (texts.length === 0) {
+        reply.code(400);
+        return { error: 'Nothing to summarize' };
+      }
+
+      const result = summarize(texts, { maxSentences });
+      return { success: true, summary: result.summary, sentences: result.sentences, sources: texts.length };
+    } catch (error) {
+      logger.error('Summarize error', { error: error.message });
+      reply.code(500);
+      return { error: error.message };
+    }
+  });
+
   // Get conflicts endpoint
   fastify.get('/api/conflicts', async (request, reply) => {
     try {
@@ -623,6 +868,76 @@ export function createRESTServer(config) {
     }
   });
 
+  // Tag endpoints: list tags, and rename, merge or remove them across all memories
+  const tagName = (value) => (typeof value === 'string' ? value.trim() : '');
+
+  fastify.get('/api/tags', async (request, reply) => {
+    try {
+      return { success: true, tags: listTags(db) };
+    } catch (error) {
+      logger.error('List tags error', { error: error.message });
+      reply.code(500);
+      return { error: error.message };
+    }
+  });
+
+  fastify.post('/api/tags/rename', async (request, reply) => {
+    try {
+      const from = tagName(request.body?.from);
+      const to = tagName(request.body?.to);
+      if (!from || !to) {
+        reply.code(400);
+        return { error: 'from and to are required' };
+      }
+
+      const updated = replaceTags(db, [from], to);
+      logger.info('Tag renamed via API', { from, to, updated });
+      return { success: true, updated };
+    } catch (error) {
+      logger.error('Rename tag error', { error: error.message });
+      reply.code(500);
+      return { error: error.message };
+    }
+  });
+
+  fastify.post('/api/tags/merge', async (request, reply) => {
+    try {
+      const { sources } = request.body || {};
+      const target = tagName(request.body?.target);
+      const names = Array.isArray(sources) ? sources.map(tagName).filter(Boolean) : [];
+      if (names.length === 0 || !target) {
+        reply.code(400);
+        return { error: 'sources array and target are required' };
+      }
+
+      const updated = replaceTags(db, names, target);
+      logger.info('Tags merged via API', { sources: names, target, updated });
+      return { success: true, updated };
+    } catch (error) {
+      logger.error('Merge tags error', { error: error.message });
+      reply.code(500);
+      return { error: error.message };
+    }
+  });
+
+  fastify.post('/api/tags/delete', async (request, reply) => {
+    try {
+      const tag = tagName(request.body?.tag);
+      if (!tag) {
+        reply.code(400);
+        return { error: 'tag is required' };
+      }
+
+      const updated = replaceTags(db, [tag], null);
+      logger.info('Tag removed via API', { tag, updated });
+      return { success: true, updated };
+    } catch (error) {
+      logger.error('Delete tag error', { error: error.message });
+      reply.code(500);
+      return { error: error.message };
+    }
+  });
+
   // Get installation info endpoint
   fastify.get('/api/installation-info', async (request, reply) => {
     try {
@@ -783,7 +1098,13 @@ export function createRESTServer(config) {
       }
 
       const { commitMemories } = await import('../import/index.js');
-      const result = await commitMemories(db, memories, { namespace });
+      const result = await commitMemories(db, memories, {
+        namespace,
+        ...(summarization.enabled !== false && {
+          longMemoryThreshold: summarization.thresholdChars || 4000,
+          onLongMemory: (id) => summaryQueue.enqueue(id)
+        })
+      });
 
       return {
         success: true,
@@ -9,9 +9,9 @@ export const LOG_LEVELS = {
 };
 
 /**
- * Current log level (defaults to INFO)
+ * Current log level (defaults to INFO, or ENGRAM_LOG_LEVEL when set)
  */
-let currentLevel = LOG_LEVELS.INFO;
+let currentLevel = LOG_LEVELS[process.env.ENGRAM_LOG_LEVEL?.toUpperCase()] ?? LOG_LEVELS.INFO;
 
 /**
  * Set the log level
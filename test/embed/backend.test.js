@@ -0,0 +1,45 @@
+import { describe, it, expect, vi } from 'vitest';
+import {
+  EMBEDDING_BACKEND_ENV,
+  ONNX_RUNTIMES,
+  resolveEmbeddingBackend
+} from '../../src/embed/backend.js';
+import { resolve } from '../../src/embed/onnx-hooks.js';
+
+describe('Embedding backend', () => {
+  describe('resolveEmbeddingBackend', () => {
+    it('should default to the native backend', () => {
+      expect(resolveEmbeddingBackend({})).toBe('native');
+      expect(resolveEmbeddingBackend({ [EMBEDDING_BACKEND_ENV]: '' })).toBe('native');
+    });
+
+    it('should select the WASM backend', () => {
+      expect(resolveEmbeddingBackend({ [EMBEDDING_BACKEND_ENV]: 'wasm' })).toBe('wasm');
+      expect(resolveEmbeddingBackend({ [EMBEDDING_BACKEND_ENV]: ' WASM ' })).toBe('wasm');
+    });
+
+    it('should fall back to native for unknown backends', () => {
+      expect(resolveEmbeddingBackend({ [EMBEDDING_BACKEND_ENV]: 'gpu' })).toBe('native');
+      expect(resolveEmbeddingBackend({ [EMBEDDING_BACKEND_ENV]: 'constructor' })).toBe('native');
+    });
+  });
+
+  describe('onnx resolve hook', () => {
+    it('should resolve onnxruntime-node to onnxruntime-web', async () => {
+      const nextResolve = vi.fn(async (specifier) => ({ url: `resolved:${specifier}` }));
+
+      const result = await resolve('onnxruntime-node', {}, nextResolve);
+
+      expect(nextResolve).toHaveBeenCalledWith(ONNX_RUNTIMES.wasm, {});
+      expect(result.url).toBe('resolved:onnxruntime-web');
+    });
+
+    it('should leave other modules alone', async () => {
+      const nextResolve = vi.fn(async (specifier) => ({ url: `resolved:${specifier}` }));
+
+      await resolve('better-sqlite3', {}, nextResolve);
+
+      expect(nextResolve).toHaveBeenCalledWith('better-sqlite3', {});
+    });
+  });
+});
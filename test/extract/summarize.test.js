@@ -0,0 +1,44 @@
+import { describe, it, expect } from 'vitest';
+import { splitSentences, summarize } from '../../src/extract/summarize.js';
+
+describe('Summarization', () => {
+  describe('splitSentences', () => {
+    it('should split on sentence punctuation and line breaks', () => {
+      expect(splitSentences('One. Two! Three?\nFour')).toEqual(['One.', 'Two!', 'Three?', 'Four']);
+    });
+
+    it('should drop empty lines', () => {
+      expect(splitSentences('First\n\n\nSecond')).toEqual(['First', 'Second']);
+    });
+  });
+
+  describe('summarize', () => {
+    it('should return short input unchanged', () => {
+      const result = summarize(['Short note.'], { maxSentences: 3 });
+      expect(result.summary).toBe('Short note.');
+      expect(result.sentences).toBe(1);
+    });
+
+    it('should keep the sentences about the recurring topic, in order', () => {
+      const result = summarize([
+        'Docker builds are slow. We cached Docker layers to fix the slow builds. Lunch was good.',
+        'The Docker cache cut build time in half. Weather is nice.'
+      ], { maxSentences: 2 });
+
+      expect(result.sentences).toBe(2);
+      expect(result.summary).toContain('Docker');
+      expect(result.summary).not.toContain('Lunch');
+      expect(result.summary).not.toContain('Weather');
+      expect(result.summary.indexOf('slow.')).toBeLessThan(result.summary.indexOf('cached'));
+    });
+
+    it('should not repeat duplicate sentences', () => {
+      const result = summarize([
+        'The API uses Fastify. The API uses Fastify.',
+        'Fastify handles the API routes. Unrelated remark here.'
+      ], { maxSentences: 2 });
+
+      expect(result.summary.match(/The API uses Fastify\./g)).toHaveLength(1);
+    });
+  });
+});
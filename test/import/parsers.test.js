@@ -185,6 +185,101 @@ describe('Import Parsers', () => {
       expect(result.warnings.some(w => w.includes('NAMES'))).toBe(true);
     });
   });
+
+  describe('chatgpt parser', () => {
+    it('should extract user statements and conclusions from the main branch', async () => {
+      const { parse } = await import('../../src/import/parsers/chatgpt.js');
+      const result = await parse({ filePath: path.join(FIXTURES, 'sample.chatgpt-conversations.json') });
+
+      expect(result.source).toBe('chatgpt');
+      expect(result.memories.length).toBeGreaterThan(0);
+
+      const decision = result.memories.find(m => m.content.includes('pgbouncer in transaction mode'));
+      expect(decision).toBeTruthy();
+      expect(decision.category).toBe('decision');
+      expect(decision.content).toContain('"Postgres connection pooling" (2024-05-01)');
+      expect(decision.tags).toContain('postgres-connection-pooling');
+      expect(decision.tags).toContain('2024-05-01');
+      expect(decision.source).toBe('import:chatgpt');
+
+      const conclusion = result.memories.find(m => m.content.includes('In summary'));
+      expect(conclusion).toBeTruthy();
+      expect(conclusion.category).toBe('outcome');
+
+      // Regenerated branches are not part of the conversation
+      const allContent = result.memories.map(m => m.content).join(' ');
+      expect(allContent).not.toContain('regenerated');
+      expect(allContent).not.toContain('Quick question');
+    });
+
+    it('should respect extraction rules', async () => {
+      const { parse } = await import('../../src/import/parsers/chatgpt.js');
+      const result = await parse({
+        filePath: path.join(FIXTURES, 'sample.chatgpt-conversations.json'),
+        includeUser: false
+      });
+
+      expect(result.memories.length).toBe(1);
+      expect(result.memories[0].category).toBe('outcome');
+    });
+
+    it('should handle missing file gracefully', async () => {
+      const { parse } = await import('../../src/import/parsers/chatgpt.js');
+      const result = await parse({ filePath: '/nonexistent/conversations.json' });
+
+      expect(result.memories).toEqual([]);
+      expect(result.warnings.length).toBeGreaterThan(0);
+    });
+  });
+
+  describe('claude-export parser', () => {
+    it('should import project knowledge tagged with the project name', async () => {
+      const { parse } = await import('../../src/import/parsers/claude-export.js');
+      const result = await parse({ exportPath: path.join(FIXTURES, 'claude-export') });
+
+      expect(result.source).toBe('claude-export');
+      expect(result.warnings).toEqual([]);
+
+      const description = result.memories.find(m => m.content.includes('Stripe-based billing'));
+      expect(description).toBeTruthy();
+      expect(description.tags).toContain('project:billing-service');
+
+      const instructions = result.memories.find(m => m.content.startsWith('Instructions for'));
+      expect(instructions).toBeTruthy();
+      expect(instructions.category).toBe('preference');
+
+      const knowledge = result.memories.filter(m => m.tags.includes('knowledge'));
+      expect(knowledge.length).toBe(2);
+      expect(knowledge[0].content).toContain('architecture.md');
+    });
+
+    it('should extract statements from conversations and link their project', async () => {
+      const { parse } = await import('../../src/import/parsers/claude-export.js');
+      const result = await parse({
+        exportPath: path.join(FIXTURES, 'claude-export'),
+        includeKnowledge: false
+      });
+
+      expect(result.memories.length).toBeGreaterThan(0);
+      const decision = result.memories.find(m => m.content.includes('exponential backoff'));
+      expect(decision).toBeTruthy();
+      expect(decision.category).toBe('decision');
+      expect(decision.tags).toContain('project:billing-service');
+      expect(decision.tags).toContain('2024-04-11');
+
+      // Assistant replies are not imported
+      const allContent = result.memories.map(m => m.content).join(' ');
+      expect(allContent).not.toContain('one hour');
+    });
+
+    it('should handle missing export gracefully', async () => {
+      const { parse } = await import('../../src/import/parsers/claude-export.js');
+      const result = await parse({ exportPath: '/nonexistent/claude-export' });
+
+      expect(result.memories).toEqual([]);
+      expect(result.warnings.length).toBeGreaterThan(0);
+    });
+  });
 });
 
 describe('Multi-Path Scanning', () => {
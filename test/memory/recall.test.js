@@ -6,7 +6,14 @@ import {
   initDatabase,
   createMemory
 } from '../../src/memory/store.js';
-import { recallMemories, formatRecallResults } from '../../src/memory/recall.js';
+import {
+  recallMemories,
+  formatRecallResults,
+  DEFAULT_SEARCH_WEIGHTS,
+  searchWeightsFromEnv,
+  setSearchWeights,
+  getSearchWeights
+} from '../../src/memory/recall.js';
 
 describe('Memory Recall', () => {
   let db;
@@ -203,4 +210,36 @@ describe('Memory Recall', () => {
       expect(formatted).toContain('Second memory');
     });
   });
+
+  describe('search weights', () => {
+    afterEach(() => {
+      setSearchWeights(DEFAULT_SEARCH_WEIGHTS);
+    });
+
+    it('should read weights from the environment', () => {
+      const weights = searchWeightsFromEnv({
+        ENGRAM_SEARCH_SEMANTIC_WEIGHT: '0.6',
+        ENGRAM_SEARCH_RECENCY_WEIGHT: '0'
+      });
+
+      expect(weights).toEqual({ semantic: 0.6, keyword: 0.1, recency: 0 });
+    });
+
+    it('should keep defaults for invalid environment values', () => {
+      const weights = searchWeightsFromEnv({ ENGRAM_SEARCH_KEYWORD_WEIGHT: 'heavy' });
+
+      expect(weights).toEqual(DEFAULT_SEARCH_WEIGHTS);
+    });
+
+    it('should update only the given weights', () => {
+      setSearchWeights({ keyword: 0.3 });
+
+      expect(getSearchWeights()).toEqual({ ...DEFAULT_SEARCH_WEIGHTS, keyword: 0.3 });
+    });
+
+    it('should reject negative weights', () => {
+      expect(() => setSearchWeights({ semantic: -1 })).toThrow();
+      expect(getSearchWeights()).toEqual(DEFAULT_SEARCH_WEIGHTS);
+    });
+  });
 });
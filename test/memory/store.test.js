@@ -9,10 +9,15 @@ import {
   updateMemory,
   deleteMemory,
   listMemories,
+  countMemories,
   searchMemories,
   getMemoriesWithEmbeddings,
   updateAccessStats,
-  getStats
+  getStats,
+  setExternalRefs,
+  getExternalRefs,
+  listTags,
+  replaceTags
 } from '../../src/memory/store.js';
 
 describe('Memory Store', () => {
@@ -219,6 +224,18 @@ describe('Memory Store', () => {
       expect(page1.length).toBe(2);
       expect(page2.length).toBe(1);
     });
+
+    it('should filter by tags, creation time, and text', () => {
+      const tagged = createMemory(db, { content: 'Sprint retro notes', tags: ['work', 'retro'] });
+      createMemory(db, { content: 'Weekend hike plan', tags: ['personal'] });
+
+      expect(listMemories(db, { tags: ['work'] }).map(m => m.id)).toEqual([tagged.id]);
+      expect(listMemories(db, { tags: ['work', 'personal'] })).toHaveLength(0);
+      expect(listMemories(db, { since: tagged.created_at }).map(m => m.id)).toContain(tagged.id);
+      expect(listMemories(db, { until: tagged.created_at }).map(m => m.id)).not.toContain(tagged.id);
+      expect(listMemories(db, { query: 'retro "notes' }).map(m => m.id)).toEqual([tagged.id]);
+      expect(countMemories(db, { namespace: 'ns1' })).toBe(2);
+    });
   });
 
   describe('searchMemories', () => {
@@ -314,4 +331,67 @@ describe('Memory Store', () => {
       expect(stats.withEmbeddings).toBe(1);
     });
   });
+
+  describe('external refs', () => {
+    it('should replace the refs of a memory', () => {
+      const memory = createMemory(db, { content: 'Fixing ENG-12 and ENG-7' });
+
+      setExternalRefs(db, memory.id, [
+        { key: 'ENG-7', provider: 'linear', title: 'Old title', status: 'Todo' }
+      ]);
+      const refs = setExternalRefs(db, memory.id, [
+        { key: 'ENG-12', provider: 'linear', title: 'Crash on start', status: 'In Progress', url: 'https://linear.app/x/issue/ENG-12' },
+        { key: 'ENG-7', provider: 'linear', title: 'Login bug', status: 'Done' }
+      ]);
+
+      expect(refs.map(r => r.key)).toEqual(['ENG-12', 'ENG-7']);
+      expect(getExternalRefs(db, memory.id)[1]).toMatchObject({ title: 'Login bug', status: 'Done', url: null });
+    });
+
+    it('should drop refs when the memory is deleted', () => {
+      const memory = createMemory(db, { content: 'See PROJ-1' });
+      setExternalRefs(db, memory.id, [{ key: 'PROJ-1', provider: 'jira' }]);
+
+      deleteMemory(db, memory.id);
+      expect(getExternalRefs(db, memory.id)).toEqual([]);
+    });
+  });
+
+  describe('tags', () => {
+    beforeEach(() => {
+      createMemory(db, { content: 'Uses React hooks', tags: ['js', 'react'] });
+      createMemory(db, { content: 'Prefers arrow functions', tags: ['javascript', 'style'] });
+      createMemory(db, { content: 'Node 20 in CI', tags: ['js', 'javascript'] });
+      createMemory(db, { content: 'No tags here' });
+    });
+
+    it('should count memories per tag, most used first', () => {
+      expect(listTags(db)).toEqual([
+        { name: 'javascript', count: 2 },
+        { name: 'js', count: 2 },
+        { name: 'react', count: 1 },
+        { name: 'style', count: 1 }
+      ]);
+    });
+
+    it('should merge tags without duplicating the target', () => {
+      expect(replaceTags(db, ['js'], 'javascript')).toBe(2);
+
+      const tags = listMemories(db, { limit: 10 }).map(m => m.tags);
+      expect(tags).toContainEqual(['javascript', 'react']);
+      expect(tags).toContainEqual(['javascript']);
+      expect(listTags(db)[0]).toEqual({ name: 'javascript', count: 3 });
+    });
+
+    it('should remove a tag and keep the memories', () => {
+      expect(replaceTags(db, ['javascript'], null)).toBe(2);
+
+      expect(countMemories(db)).toBe(4);
+      expect(listTags(db).map(t => t.name)).not.toContain('javascript');
+    });
+
+    it('should ignore a merge into the same tag', () => {
+      expect(replaceTags(db, ['js'], 'js')).toBe(0);
+    });
+  });
 });
@@ -0,0 +1,83 @@
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import fs from 'fs';
+import path from 'path';
+import os from 'os';
+import { initDatabase, createMemory, deleteMemory, getLinks } from '../../src/memory/store.js';
+import { summarizeMemory, createSummaryQueue, SUMMARY_RELATION } from '../../src/memory/summaries.js';
+
+const LONG_DOCUMENT = [
+  'The deploy pipeline builds Docker images on every merge.',
+  'Docker layer caching keeps the pipeline under five minutes.',
+  'The team had pizza on Friday.',
+  'Staging deploys run the same Docker images as production.',
+  'Someone mentioned the weather.',
+  'Rollbacks redeploy the previous Docker image tag.',
+  'The office plants need water.'
+].join(' ');
+
+describe('Memory Summaries', () => {
+  let db;
+  let tmpDir;
+
+  beforeEach(() => {
+    tmpDir = path.join(os.tmpdir(), 'engram-summaries-test-' + Date.now());
+    fs.mkdirSync(tmpDir, { recursive: true });
+    db = initDatabase(path.join(tmpDir, 'test.db'));
+  });
+
+  afterEach(() => {
+    if (db) db.close();
+    if (tmpDir && fs.existsSync(tmpDir)) {
+      fs.rmSync(tmpDir, { recursive: true });
+    }
+  });
+
+  it('should store a summary linked to the original memory', async () => {
+    const original = createMemory(db, {
+      content: LONG_DOCUMENT,
+      namespace: 'work',
+      tags: ['deploy']
+    });
+
+    const summary = await summarizeMemory(db, original.id, { maxSentences: 3 });
+
+    expect(summary.content.length).toBeLessThan(original.content.length);
+    expect(summary.content).toContain('Docker');
+    expect(summary.namespace).toBe('work');
+    expect(summary.tags).toEqual(['deploy', 'summary']);
+    expect(summary.source).toBe('summary');
+
+    const links = getLinks(db, original.id);
+    expect(links).toHaveLength(1);
+    expect(links[0].sourceId).toBe(summary.id);
+    expect(links[0].targetId).toBe(original.id);
+    expect(links[0].relation).toBe(SUMMARY_RELATION);
+    expect(getLinks(db, summary.id)).toEqual(links);
+  });
+
+  it('should return null for a missing memory', async () => {
+    expect(await summarizeMemory(db, 'missing')).toBeNull();
+  });
+
+  it('should drop links when a linked memory is deleted', async () => {
+    const original = createMemory(db, { content: LONG_DOCUMENT });
+    const summary = await summarizeMemory(db, original.id);
+
+    deleteMemory(db, original.id);
+    expect(getLinks(db, summary.id)).toHaveLength(0);
+  });
+
+  it('should process queued memories in the background', async () => {
+    const first = createMemory(db, { content: LONG_DOCUMENT });
+    const second = createMemory(db, { content: LONG_DOCUMENT + ' Extra sentence here.' });
+    const queue = createSummaryQueue(db, { maxSentences: 2 });
+
+    queue.enqueue(first.id);
+    queue.enqueue('missing');
+    queue.enqueue(second.id);
+    await queue.idle();
+
+    expect(getLinks(db, first.id)).toHaveLength(1);
+    expect(getLinks(db, second.id)).toHaveLength(1);
+  });
+});
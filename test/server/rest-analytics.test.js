@@ -4,6 +4,7 @@ import path from 'path';
 import os from 'os';
 import { initDatabase, createMemory } from '../../src/memory/store.js';
 import { createRESTServer } from '../../src/server/rest.js';
+import { DEFAULT_SEARCH_WEIGHTS, getSearchWeights, setSearchWeights } from '../../src/memory/recall.js';
 
 describe('REST Analytics Endpoints', () => {
   let fastify;
@@ -103,6 +104,160 @@ describe('REST Analytics Endpoints', () => {
     }
   });
 
+  it('PATCH /api/memories/:id should update tags', async () => {
+    const createRes = await fetch(`${baseUrl}/api/memories`, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ content: 'kubectl rollout restart', category: 'fact' })
+    });
+    const { memory } = await createRes.json();
+
+    const res = await fetch(`${baseUrl}/api/memories/${memory.id}`, {
+      method: 'PATCH',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ tags: ['devops'] })
+    });
+    expect(res.ok).toBe(true);
+
+    const data = await res.json();
+    expect(data.memory.tags).toEqual(['devops']);
+    expect(data.memory.content).toBe('kubectl rollout restart');
+  });
+
+  it('PATCH /api/memories/:id should update content in place', async () => {
+    const createRes = await fetch(`${baseUrl}/api/memories`, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ content: 'Deploys run on Fridays', category: 'fact' })
+    });
+    const { memory } = await createRes.json();
+
+    const res = await fetch(`${baseUrl}/api/memories/${memory.id}`, {
+      method: 'PATCH',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ content: 'Deploys run on Fridays\nExcept during freezes' })
+    });
+    expect(res.ok).toBe(true);
+
+    const data = await res.json();
+    expect(data.memory.id).toBe(memory.id);
+    expect(data.memory.content).toBe('Deploys run on Fridays\nExcept during freezes');
+  });
+
+  it('PATCH /api/memories/:id should reject empty content', async () => {
+    const listRes = await fetch(`${baseUrl}/api/memories?limit=1`);
+    const { memories } = await listRes.json();
+
+    const res = await fetch(`${baseUrl}/api/memories/${memories[0].id}`, {
+      method: 'PATCH',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ content: '  ' })
+    });
+    expect(res.status).toBe(400);
+  });
+
+  it('PATCH /api/memories/:id should reject non-string tags', async () => {
+    const listRes = await fetch(`${baseUrl}/api/memories?limit=1`);
+    const { memories } = await listRes.json();
+
+    const res = await fetch(`${baseUrl}/api/memories/${memories[0].id}`, {
+      method: 'PATCH',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ tags: 'devops' })
+    });
+    expect(res.status).toBe(400);
+  });
+
+  it('PATCH /api/memories/:id should 404 for unknown ids', async () => {
+    const res = await fetch(`${baseUrl}/api/memories/does-not-exist`, {
+      method: 'PATCH',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ tags: ['devops'] })
+    });
+    expect(res.status).toBe(404);
+  });
+
+  it('POST /api/tags/rename should retag every memory', async () => {
+    await fetch(`${baseUrl}/api/memories`, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ content: 'Terraform state lives in S3', tags: ['infra'] })
+    });
+
+    const res = await fetch(`${baseUrl}/api/tags/rename`, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ from: 'infra', to: 'infrastructure' })
+    });
+    expect(res.ok).toBe(true);
+    expect((await res.json()).updated).toBe(1);
+
+    const tagsRes = await fetch(`${baseUrl}/api/tags`);
+    const { tags } = await tagsRes.json();
+    expect(tags).toContainEqual({ name: 'infrastructure', count: 1 });
+    expect(tags.map(t => t.name)).not.toContain('infra');
+  });
+
+  it('POST /api/tags/merge should require a target', async () => {
+    const res = await fetch(`${baseUrl}/api/tags/merge`, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ sources: ['infra'] })
+    });
+    expect(res.status).toBe(400);
+  });
+
+  it('POST /api/config/reload should apply the log level', async () => {
+    const res = await fetch(`${baseUrl}/api/config/reload`, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ logLevel: 'warn' })
+    });
+    expect(res.ok).toBe(true);
+
+    const data = await res.json();
+    expect(data.applied).toEqual(['logLevel']);
+
+    await fetch(`${baseUrl}/api/config/reload`, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ logLevel: 'info' })
+    });
+  });
+
+  it('POST /api/config/reload should reject unknown log levels', async () => {
+    const res = await fetch(`${baseUrl}/api/config/reload`, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ logLevel: 'verbose' })
+    });
+    expect(res.status).toBe(400);
+  });
+
+  it('POST /api/config/reload should apply search weights', async () => {
+    const res = await fetch(`${baseUrl}/api/config/reload`, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ search: { semanticWeight: 0.6, keywordWeight: 0.2, recencyWeight: 0.1 } })
+    });
+    expect(res.ok).toBe(true);
+
+    const data = await res.json();
+    expect(data.applied).toEqual(['search']);
+    expect(getSearchWeights()).toEqual({ semantic: 0.6, keyword: 0.2, recency: 0.1 });
+
+    setSearchWeights(DEFAULT_SEARCH_WEIGHTS);
+  });
+
+  it('POST /api/config/reload should reject invalid search weights', async () => {
+    const res = await fetch(`${baseUrl}/api/config/reload`, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ search: { semanticWeight: 'high' } })
+    });
+    expect(res.status).toBe(400);
+  });
+
   it('POST /api/memories/bulk-delete should delete specified memories', async () => {
     // Create a memory via the API
     const createRes = await fetch(`${baseUrl}/api/memories`, {
@@ -0,0 +1,70 @@
+import { describe, it, expect, beforeAll, afterAll, afterEach } from 'vitest';
+import fs from 'fs';
+import path from 'path';
+import os from 'os';
+import { createRESTServer } from '../../src/server/rest.js';
+import { getReadOnlyFlagPath, getReadOnlyReason } from '../../src/config/index.js';
+
+describe('Read-only mode', () => {
+  let fastify;
+  let baseUrl;
+  let tmpDir;
+  let config;
+
+  beforeAll(async () => {
+    tmpDir = path.join(os.tmpdir(), 'engram-rest-readonly-test-' + Date.now());
+    fs.mkdirSync(path.join(tmpDir, 'models'), { recursive: true });
+    config = { dataDir: tmpDir, defaults: { namespace: 'default' }, security: {} };
+
+    fastify = createRESTServer(config);
+    await fastify.listen({ port: 0, host: '127.0.0.1' });
+    const address = fastify.server.address();
+    baseUrl = `http://127.0.0.1:${address.port}`;
+  });
+
+  afterEach(() => {
+    fs.rmSync(getReadOnlyFlagPath(config), { force: true });
+  });
+
+  afterAll(async () => {
+    if (fastify) await fastify.close();
+    if (tmpDir && fs.existsSync(tmpDir)) {
+      fs.rmSync(tmpDir, { recursive: true });
+    }
+  });
+
+  it('reports no reason without the flag file', () => {
+    expect(getReadOnlyReason(config)).toBeNull();
+  });
+
+  it('reads the reason from the flag file', () => {
+    fs.writeFileSync(getReadOnlyFlagPath(config), JSON.stringify({ reason: 'Low disk space' }));
+    expect(getReadOnlyReason(config)).toBe('Low disk space');
+  });
+
+  it('falls back to a generic reason for an unreadable flag', () => {
+    fs.writeFileSync(getReadOnlyFlagPath(config), 'not json');
+    expect(getReadOnlyReason(config)).toBe('Engram is read-only');
+  });
+
+  it('refuses writes while flagged', async () => {
+    fs.writeFileSync(getReadOnlyFlagPath(config), JSON.stringify({ reason: 'Low disk space' }));
+
+    const res = await fetch(`${baseUrl}/api/memories`, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ content: 'should not be stored' })
+    });
+    expect(res.status).toBe(503);
+    const data = await res.json();
+    expect(data.readOnly).toBe(true);
+    expect(data.error).toBe('Low disk space');
+  });
+
+  it('still serves reads while flagged', async () => {
+    fs.writeFileSync(getReadOnlyFlagPath(config), JSON.stringify({ reason: 'Low disk space' }));
+
+    const res = await fetch(`${baseUrl}/api/memories`);
+    expect(res.ok).toBe(true);
+  });
+});
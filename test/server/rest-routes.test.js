@@ -0,0 +1,39 @@
+import { describe, it, expect, beforeAll, afterAll } from 'vitest';
+import fs from 'fs';
+import path from 'path';
+import os from 'os';
+import { createRESTServer } from '../../src/server/rest.js';
+
+describe('REST Route Listing', () => {
+  let fastify;
+  let baseUrl;
+  let tmpDir;
+
+  beforeAll(async () => {
+    tmpDir = path.join(os.tmpdir(), 'engram-rest-routes-test-' + Date.now());
+    fs.mkdirSync(path.join(tmpDir, 'models'), { recursive: true });
+
+    fastify = createRESTServer({ dataDir: tmpDir });
+    await fastify.listen({ port: 0, host: '127.0.0.1' });
+    const address = fastify.server.address();
+    baseUrl = `http://127.0.0.1:${address.port}`;
+  });
+
+  afterAll(async () => {
+    if (fastify) await fastify.close();
+    if (tmpDir && fs.existsSync(tmpDir)) {
+      fs.rmSync(tmpDir, { recursive: true });
+    }
+  });
+
+  it('GET /api/routes should list registered endpoints', async () => {
+    const res = await fetch(`${baseUrl}/api/routes`);
+    expect(res.ok).toBe(true);
+
+    const data = await res.json();
+    expect(data.routes).toContainEqual({ method: 'GET', url: '/api/memories' });
+    expect(data.routes).toContainEqual({ method: 'POST', url: '/api/memories' });
+    expect(data.routes).toContainEqual({ method: 'GET', url: '/api/routes' });
+    expect(data.routes.some((r) => r.method === 'OPTIONS' || r.method === 'HEAD')).toBe(false);
+  });
+});